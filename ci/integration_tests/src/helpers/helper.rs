@@ -9,7 +9,7 @@ use cw_core::{
 use cw_utils::Duration;
 use voting::{
     deposit::DepositInfo, deposit::DepositToken, threshold::PercentageThreshold,
-    threshold::Threshold,
+    threshold::Threshold, threshold::ThresholdMode,
 };
 
 #[derive(Debug)]
@@ -18,11 +18,74 @@ pub struct DaoState {
     pub state: DumpStateResponse,
 }
 
+/// The proposal module config used by `create_dao`.
+pub fn default_proposal_msg() -> cw_proposal_single::msg::InstantiateMsg {
+    cw_proposal_single::msg::InstantiateMsg {
+        min_voting_period: None,
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(35)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: Duration::Time(432000),
+        allow_revoting: false,
+        only_members_execute: true,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1000000000),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: false,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    }
+}
+
 pub fn create_dao(
     chain: &mut Chain,
     admin: Option<String>,
     op_name: &str,
     user_addr: String,
+) -> Result<DaoState> {
+    create_dao_with_proposal_msg(chain, admin, op_name, user_addr, default_proposal_msg())
+}
+
+/// Creates a DAO like `create_dao` whose proposal module is
+/// instantiated with `proposal_msg`.
+pub fn create_dao_with_proposal_msg(
+    chain: &mut Chain,
+    admin: Option<String>,
+    op_name: &str,
+    user_addr: String,
+    proposal_msg: cw_proposal_single::msg::InstantiateMsg,
 ) -> Result<DaoState> {
     let msg = cw_core::msg::InstantiateMsg {
         admin,
@@ -59,26 +122,12 @@ pub fn create_dao(
         },
         proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
             code_id: chain.orc.contract_map.code_id("cw_proposal_single")?,
-            msg: to_binary(&cw_proposal_single::msg::InstantiateMsg {
-                min_voting_period: None,
-                threshold: Threshold::ThresholdQuorum {
-                    threshold: PercentageThreshold::Majority {},
-                    quorum: PercentageThreshold::Percent(Decimal::percent(35)),
-                },
-                max_voting_period: Duration::Time(432000),
-                allow_revoting: false,
-                only_members_execute: true,
-                deposit_info: Some(DepositInfo {
-                    token: DepositToken::VotingModuleToken {},
-                    deposit: Uint128::new(1000000000),
-                    refund_failed_proposals: true,
-                }),
-                close_proposal_on_execution_failure: false,
-            })?,
+            msg: to_binary(&proposal_msg)?,
             admin: Admin::CoreContract {},
             label: "DAO DAO Proposal Module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     chain
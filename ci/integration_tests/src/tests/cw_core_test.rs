@@ -8,7 +8,10 @@ use cw20_stake::msg::{StakedValueResponse, TotalValueResponse};
 use cw_core::query::{GetItemResponse, PauseInfoResponse};
 use cw_utils::Duration;
 use test_context::test_context;
-use voting::{deposit::CheckedDepositInfo, threshold::PercentageThreshold, threshold::Threshold};
+use voting::{
+    deposit::CheckedDepositInfo, threshold::PercentageThreshold, threshold::Threshold,
+    threshold::ThresholdMode,
+};
 
 // #### ExecuteMsg #####
 
@@ -362,6 +365,7 @@ fn instantiate_with_admin(chain: &mut Chain) {
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(35)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         }
     );
     assert_eq!(
@@ -0,0 +1,168 @@
+use crate::helpers::{
+    chain::Chain,
+    helper::{create_dao_with_proposal_msg, default_proposal_msg},
+};
+use cosmwasm_std::{to_binary, Uint128};
+use cw20_stake::state::Config;
+use cw_proposal_single::msg::{ExecuteMsg, QueryMsg};
+use cw_proposal_single::query::VoteResponse;
+use test_context::test_context;
+use voting::voting::Vote;
+
+/// Proposes twice and votes on both proposals in a DAO whose proposal
+/// module has `cache_voting_power` set to `cache_voting_power`. Each
+/// step's op name is prefixed with `prefix` so that the gas report
+/// shows the cached and uncached paths side by side.
+fn propose_and_vote(chain: &mut Chain, prefix: &str, cache_voting_power: bool) {
+    let dao = create_dao_with_proposal_msg(
+        chain,
+        None,
+        &format!("{prefix}_create_dao"),
+        chain.user.addr.clone(),
+        cw_proposal_single::msg::InstantiateMsg {
+            allow_revoting: true,
+            deposit_info: None,
+            cache_voting_power,
+            ..default_proposal_msg()
+        },
+    )
+    .unwrap();
+
+    chain
+        .orc
+        .contract_map
+        .add_address(
+            "cw20_staked_balance_voting",
+            dao.state.voting_module.as_str(),
+        )
+        .unwrap();
+    chain
+        .orc
+        .contract_map
+        .add_address(
+            "cw_proposal_single",
+            dao.state.proposal_modules[0].address.as_str(),
+        )
+        .unwrap();
+
+    let staking_addr: String = chain
+        .orc
+        .query(
+            "cw20_staked_balance_voting",
+            &format!("{prefix}_q_stake"),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap()
+        .data()
+        .unwrap();
+    chain
+        .orc
+        .contract_map
+        .add_address("cw20_stake", staking_addr.to_string())
+        .unwrap();
+    let config: Config = chain
+        .orc
+        .query(
+            "cw20_stake",
+            &format!("{prefix}_q_cfg"),
+            &cw20_stake::msg::QueryMsg::GetConfig {},
+        )
+        .unwrap()
+        .data()
+        .unwrap();
+    chain
+        .orc
+        .contract_map
+        .add_address("cw20_base", config.token_address.as_str())
+        .unwrap();
+
+    chain
+        .orc
+        .execute(
+            "cw20_base",
+            &format!("{prefix}_stake_tokens"),
+            &cw20_base::msg::ExecuteMsg::Send {
+                contract: staking_addr,
+                amount: Uint128::new(100),
+                msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+            },
+            &chain.user.key,
+        )
+        .unwrap();
+    chain.orc.poll_for_n_blocks(1, 20_000).unwrap();
+
+    for proposal in ["first", "second"] {
+        chain
+            .orc
+            .execute(
+                "cw_proposal_single",
+                &format!("{prefix}_propose_{proposal}"),
+                &ExecuteMsg::Propose {
+                    title: "A simple text proposal".to_string(),
+                    description: "This is a simple text proposal".to_string(),
+                    msgs: vec![],
+                    expiration_override: None,
+                    execution_funds: vec![],
+                    related_proposals: vec![],
+                    threshold_override: None,
+                    metadata: None,
+                },
+                &chain.user.key,
+            )
+            .unwrap();
+    }
+
+    // Revoting is allowed so neither proposal closes early and the
+    // user can vote on both and then revote.
+    for (op_name, proposal_id, vote) in [
+        ("vote_first", 1, Vote::Yes),
+        ("vote_second", 2, Vote::Yes),
+        ("revote_first", 1, Vote::No),
+    ] {
+        chain
+            .orc
+            .execute(
+                "cw_proposal_single",
+                &format!("{prefix}_{op_name}"),
+                &ExecuteMsg::Vote { proposal_id, vote },
+                &chain.user.key,
+            )
+            .unwrap();
+    }
+
+    for proposal_id in [1, 2] {
+        let res: VoteResponse = chain
+            .orc
+            .query(
+                "cw_proposal_single",
+                &format!("{prefix}_q_vote"),
+                &QueryMsg::GetVote {
+                    proposal_id,
+                    voter: chain.user.addr.clone(),
+                },
+            )
+            .unwrap()
+            .data()
+            .unwrap();
+        assert_eq!(res.vote.unwrap().power, Uint128::new(100));
+    }
+}
+
+// #### ExecuteMsg #####
+
+/// Benchmarks proposing and voting without `cache_voting_power`.
+#[test_context(Chain)]
+#[test]
+#[ignore]
+fn execute_vote_uncached(chain: &mut Chain) {
+    propose_and_vote(chain, "uncached", false);
+}
+
+/// Benchmarks proposing and voting with `cache_voting_power`. Compare
+/// the `cached_*` ops in the gas report with the `uncached_*` ops.
+#[test_context(Chain)]
+#[test]
+#[ignore]
+fn execute_vote_cached(chain: &mut Chain) {
+    propose_and_vote(chain, "cached", true);
+}
@@ -1,3 +1,5 @@
 pub mod cw_core_test;
 
 pub mod cw20_stake_test;
+
+pub mod cw_proposal_single_test;
@@ -100,6 +100,7 @@ pub fn test_set_admin() {
             },
         ],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     // multi-test does not support UpdateAdmin yet :(
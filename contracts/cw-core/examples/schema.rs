@@ -6,13 +6,16 @@ use cosmwasm_std::Addr;
 use cw_core::{
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     query::{
-        AdminNominationResponse, Cw20BalanceResponse, DumpStateResponse, GetItemResponse,
-        PauseInfoResponse, SubDao,
+        AdminNominationResponse, CharterHistoryResponse, CharterResponse, Cw20BalanceResponse,
+        DefaultProposalModuleConfigResponse, DumpStateResponse, GetItemResolvedResponse,
+        GetItemResponse, GovernanceFreezeResponse, PauseInfoResponse, SpendAllowanceResponse,
+        SubDao,
     },
     state::{Config, ProposalModule},
 };
-use cw_core_interface::voting::{
-    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+use cw_core_interface::{
+    migration::MigrationInfoResponse,
+    voting::{InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse},
 };
 
 fn main() {
@@ -28,8 +31,15 @@ fn main() {
 
     export_schema(&schema_for!(DumpStateResponse), &out_dir);
     export_schema(&schema_for!(PauseInfoResponse), &out_dir);
+    export_schema(&schema_for!(GovernanceFreezeResponse), &out_dir);
+    export_schema(&schema_for!(CharterResponse), &out_dir);
+    export_schema(&schema_for!(CharterHistoryResponse), &out_dir);
     export_schema(&schema_for!(GetItemResponse), &out_dir);
+    export_schema(&schema_for!(GetItemResolvedResponse), &out_dir);
+    export_schema(&schema_for!(DefaultProposalModuleConfigResponse), &out_dir);
+    export_schema(&schema_for!(SpendAllowanceResponse), &out_dir);
     export_schema(&schema_for!(InfoResponse), &out_dir);
+    export_schema(&schema_for!(MigrationInfoResponse), &out_dir);
     export_schema(&schema_for!(TotalPowerAtHeightResponse), &out_dir);
     export_schema(&schema_for!(VotingPowerAtHeightResponse), &out_dir);
     export_schema(&schema_for!(AdminNominationResponse), &out_dir);
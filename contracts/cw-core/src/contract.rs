@@ -1,28 +1,37 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdError, StdResult, SubMsg,
+    to_binary, to_vec, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    Timestamp, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::Map;
 use cw_utils::{parse_reply_instantiate_data, Duration};
+use serde::Serialize;
 
+use cw_core_interface::migration::{supported_migrations, MigrationInfoResponse};
 use cw_core_interface::voting;
 use cw_paginate::{paginate_map, paginate_map_keys, paginate_map_values};
 
 use crate::error::ContractError;
+use crate::helpers::{instantiated_address_placeholder, substitute_placeholder};
 use crate::msg::{
-    ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, ModuleInstantiateInfo, QueryMsg,
+    AllowanceToken, ExecuteMsg, InitialCw20Transfer, InitialItem, InstantiateMsg, MigrateMsg,
+    ModuleInstantiateInfo, QueryMsg,
 };
 use crate::query::{
-    AdminNominationResponse, Cw20BalanceResponse, DumpStateResponse, GetItemResponse,
-    PauseInfoResponse, SubDao,
+    AdminNominationResponse, CharterHistoryResponse, CharterResponse, Cw20BalanceResponse,
+    DefaultProposalModuleConfigResponse, DumpStateResponse, GetItemResolvedResponse,
+    GetItemResponse, GovernanceFreezeResponse, PauseInfoResponse, SpendAllowanceResponse, SubDao,
 };
 use crate::state::{
-    Config, ProposalModule, ProposalModuleStatus, ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CONFIG,
-    CREATED_TIMESTAMP, CW20_LIST, CW721_LIST, ITEMS, NOMINATED_ADMIN, PAUSED, PROPOSAL_MODULES,
-    SUBDAO_LIST, TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
+    Charter, CheckedAllowanceToken, Config, PendingProposalMsgs, ProposalModule,
+    ProposalModuleStatus, SpendAllowance, ACTIVE_PROPOSAL_MODULE_COUNT, ADMIN, CHARTER_HISTORY,
+    CONFIG, CREATED_TIMESTAMP, CW20_LIST, CW721_LIST, GOVERNANCE_FREEZE, ITEMS,
+    MAX_CHARTER_HISTORY, MAX_GOVERNANCE_FREEZE_SECONDS, NOMINATED_ADMIN, PAUSED,
+    PENDING_PROPOSAL_MSGS, PROPOSAL_MODULES, SPEND_ALLOWANCES, SUBDAO_LIST,
+    TOTAL_PROPOSAL_MODULE_COUNT, VOTING_MODULE,
 };
 
 // version info for migration info
@@ -32,6 +41,7 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const PROPOSAL_MODULE_REPLY_ID: u64 = 0;
 const VOTE_MODULE_INSTANTIATE_REPLY_ID: u64 = 1;
 const VOTE_MODULE_UPDATE_REPLY_ID: u64 = 2;
+const PROPOSAL_INSTANTIATE_REPLY_ID: u64 = 3;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -79,6 +89,29 @@ pub fn instantiate(
         ITEMS.save(deps.storage, key, &value)?;
     }
 
+    // Native funds sent with this message are kept by the contract,
+    // but cw20s must be pulled from the instantiator.
+    let mut cw20_transfer_msgs: Vec<WasmMsg> = vec![];
+    for InitialCw20Transfer { token, amount } in msg.initial_cw20_transfers.unwrap_or_default() {
+        let token = deps.api.addr_validate(&token)?;
+        if amount.is_zero() {
+            return Err(ContractError::ZeroInitialCw20Transfer {});
+        }
+        if CW20_LIST.has(deps.storage, token.clone()) {
+            return Err(ContractError::DuplicateInitialCw20Transfer { token });
+        }
+        CW20_LIST.save(deps.storage, token.clone(), &Empty {})?;
+        cw20_transfer_msgs.push(WasmMsg::Execute {
+            contract_addr: token.into_string(),
+            msg: to_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
+                owner: info.sender.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        });
+    }
+
     // Save total and active proposal module counts
     TOTAL_PROPOSAL_MODULE_COUNT.save(deps.storage, &0)?;
     ACTIVE_PROPOSAL_MODULE_COUNT.save(deps.storage, &0)?;
@@ -86,11 +119,18 @@ pub fn instantiate(
     // Set created timestamp.
     CREATED_TIMESTAMP.save(deps.storage, &env.block.time)?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("sender", info.sender)
+        .add_attribute("sender", info.sender);
+    if !info.funds.is_empty() {
+        let initial_funds: Vec<String> = info.funds.iter().map(|coin| coin.to_string()).collect();
+        response = response.add_attribute("initial_funds", initial_funds.join(","));
+    }
+
+    Ok(response
         .add_submessage(vote_module_msg)
-        .add_submessages(proposal_module_msgs))
+        .add_submessages(proposal_module_msgs)
+        .add_messages(cw20_transfer_msgs))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -111,14 +151,24 @@ pub fn execute(
         ExecuteMsg::ExecuteAdminMsgs { msgs } => {
             execute_admin_msgs(deps.as_ref(), info.sender, msgs)
         }
-        ExecuteMsg::ExecuteProposalHook { msgs } => {
-            execute_proposal_hook(deps.as_ref(), info.sender, msgs)
-        }
+        ExecuteMsg::ExecuteProposalHook { msgs } => execute_proposal_hook(deps, info.sender, msgs),
         ExecuteMsg::Pause { duration } => execute_pause(deps, env, info.sender, duration),
+        ExecuteMsg::SetGovernanceFreeze { until } => {
+            execute_set_governance_freeze(deps, env, info.sender, until)
+        }
+        ExecuteMsg::ClearGovernanceFreeze {} => {
+            execute_clear_governance_freeze(deps, env, info.sender)
+        }
+        ExecuteMsg::SetCharter { uri, hash } => {
+            execute_set_charter(deps, env, info.sender, uri, hash)
+        }
         ExecuteMsg::Receive(_) => execute_receive_cw20(deps, info.sender),
         ExecuteMsg::ReceiveNft(_) => execute_receive_cw721(deps, info.sender),
         ExecuteMsg::RemoveItem { key } => execute_remove_item(deps, env, info.sender, key),
         ExecuteMsg::SetItem { key, addr } => execute_set_item(deps, env, info.sender, key, addr),
+        ExecuteMsg::UpdateItems { to_set, to_remove } => {
+            execute_update_items(deps, env, info.sender, to_set, to_remove)
+        }
         ExecuteMsg::UpdateConfig { config } => {
             execute_update_config(deps, env, info.sender, config)
         }
@@ -144,6 +194,17 @@ pub fn execute(
         ExecuteMsg::UpdateSubDaos { to_add, to_remove } => {
             execute_update_sub_daos_list(deps, env, info.sender, to_add, to_remove)
         }
+        ExecuteMsg::GrantSpendAllowance {
+            grantee,
+            token,
+            amount,
+            period,
+        } => execute_grant_spend_allowance(deps, env, info.sender, grantee, token, amount, period),
+        ExecuteMsg::SpendFromAllowance {
+            token,
+            amount,
+            recipient,
+        } => execute_spend_from_allowance(deps, env, info.sender, token, amount, recipient),
     }
 }
 
@@ -168,6 +229,85 @@ pub fn execute_pause(
         .add_attribute("until", until.to_string()))
 }
 
+pub fn execute_set_governance_freeze(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    until: Timestamp,
+) -> Result<Response, ContractError> {
+    // Only the core contract may call this method.
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if until <= env.block.time {
+        return Err(ContractError::FreezeInPast {});
+    }
+    if until > env.block.time.plus_seconds(MAX_GOVERNANCE_FREEZE_SECONDS) {
+        return Err(ContractError::FreezeTooLong {
+            max_seconds: MAX_GOVERNANCE_FREEZE_SECONDS,
+        });
+    }
+
+    GOVERNANCE_FREEZE.save(deps.storage, &until)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_set_governance_freeze")
+        .add_attribute("sender", sender)
+        .add_attribute("until", until.to_string()))
+}
+
+pub fn execute_clear_governance_freeze(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    // Only the core contract may call this method.
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    GOVERNANCE_FREEZE.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_clear_governance_freeze")
+        .add_attribute("sender", sender))
+}
+
+pub fn execute_set_charter(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    uri: String,
+    hash: Option<String>,
+) -> Result<Response, ContractError> {
+    // Only the core contract may call this method.
+    if sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if uri.is_empty() {
+        return Err(ContractError::EmptyCharterUri {});
+    }
+
+    let mut history = CHARTER_HISTORY.may_load(deps.storage)?.unwrap_or_default();
+    let version = history.last().map_or(1, |charter| charter.version + 1);
+    history.push(Charter {
+        version,
+        uri: uri.clone(),
+        hash,
+        set_at: env.block.time,
+    });
+    if history.len() > MAX_CHARTER_HISTORY {
+        history.drain(..history.len() - MAX_CHARTER_HISTORY);
+    }
+    CHARTER_HISTORY.save(deps.storage, &history)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_set_charter")
+        .add_attribute("sender", sender)
+        .add_attribute("version", version.to_string())
+        .add_attribute("uri", uri))
+}
+
 pub fn execute_admin_msgs(
     deps: Deps,
     sender: Addr,
@@ -186,7 +326,7 @@ pub fn execute_admin_msgs(
 }
 
 pub fn execute_proposal_hook(
-    deps: Deps,
+    deps: DepsMut,
     sender: Addr,
     msgs: Vec<CosmosMsg<Empty>>,
 ) -> Result<Response, ContractError> {
@@ -199,9 +339,48 @@ pub fn execute_proposal_hook(
         return Err(ContractError::ModuleDisabledCannotExecute { address: sender });
     }
 
+    let msgs = dispatch_proposal_msgs(deps.storage, msgs, 0)?;
     Ok(Response::default()
         .add_attribute("action", "execute_proposal_hook")
-        .add_messages(msgs))
+        .add_submessages(msgs))
+}
+
+/// Gets the submessages executing a proposal's messages up to and
+/// including its next instantiation. The messages following the
+/// instantiation may refer to the new contract with an
+/// `instantiated_address_placeholder` and are saved until the
+/// contract's address is known in `reply`. `instantiated` is the
+/// number of contracts the proposal has instantiated so far.
+fn dispatch_proposal_msgs(
+    storage: &mut dyn Storage,
+    mut msgs: Vec<CosmosMsg<Empty>>,
+    instantiated: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let next_instantiate = msgs
+        .iter()
+        .position(|msg| matches!(msg, CosmosMsg::Wasm(WasmMsg::Instantiate { .. })));
+    match next_instantiate {
+        Some(idx) if idx + 1 < msgs.len() => {
+            let pending = msgs.split_off(idx + 1);
+            let mut stack = PENDING_PROPOSAL_MSGS.may_load(storage)?.unwrap_or_default();
+            stack.push(PendingProposalMsgs {
+                msgs: pending,
+                instantiated,
+            });
+            PENDING_PROPOSAL_MSGS.save(storage, &stack)?;
+
+            let instantiate = msgs.pop().unwrap();
+            Ok(msgs
+                .into_iter()
+                .map(SubMsg::new)
+                .chain(std::iter::once(SubMsg::reply_on_success(
+                    instantiate,
+                    PROPOSAL_INSTANTIATE_REPLY_ID,
+                )))
+                .collect())
+        }
+        _ => Ok(msgs.into_iter().map(SubMsg::new).collect()),
+    }
 }
 
 pub fn execute_nominate_admin(
@@ -484,6 +663,37 @@ pub fn execute_remove_item(
     }
 }
 
+pub fn execute_update_items(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    to_set: Vec<(String, String)>,
+    to_remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for (_, addr) in to_set.iter() {
+        deps.api.addr_validate(addr)?;
+    }
+
+    for (key, addr) in to_set.iter() {
+        ITEMS.save(deps.storage, key.clone(), addr)?;
+    }
+    for key in to_remove.iter() {
+        if !ITEMS.has(deps.storage, key.clone()) {
+            return Err(ContractError::KeyMissing {});
+        }
+        ITEMS.remove(deps.storage, key.clone());
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_update_items")
+        .add_attribute("set", to_set.len().to_string())
+        .add_attribute("removed", to_remove.len().to_string()))
+}
+
 pub fn execute_update_sub_daos_list(
     deps: DepsMut,
     env: Env,
@@ -510,6 +720,108 @@ pub fn execute_update_sub_daos_list(
         .add_attribute("sender", sender))
 }
 
+fn check_allowance_token(deps: Deps, token: AllowanceToken) -> StdResult<CheckedAllowanceToken> {
+    Ok(match token {
+        AllowanceToken::Native { denom } => CheckedAllowanceToken::Native { denom },
+        AllowanceToken::Cw20 { address } => CheckedAllowanceToken::Cw20 {
+            address: deps.api.addr_validate(&address)?,
+        },
+    })
+}
+
+pub fn execute_grant_spend_allowance(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    grantee: String,
+    token: AllowanceToken,
+    amount: Uint128,
+    period: Duration,
+) -> Result<Response, ContractError> {
+    if env.contract.address != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let token = check_allowance_token(deps.as_ref(), token)?;
+    let key = (&grantee, token.storage_key());
+    if amount.is_zero() {
+        SPEND_ALLOWANCES.remove(deps.storage, key);
+    } else {
+        let period_start = match period {
+            Duration::Height(0) | Duration::Time(0) => {
+                return Err(ContractError::ZeroAllowancePeriod {})
+            }
+            Duration::Height(_) => env.block.height,
+            Duration::Time(_) => env.block.time.seconds(),
+        };
+        SPEND_ALLOWANCES.save(
+            deps.storage,
+            key,
+            &SpendAllowance {
+                token,
+                amount,
+                period,
+                period_start,
+                spent: Uint128::zero(),
+            },
+        )?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "execute_grant_spend_allowance")
+        .add_attribute("grantee", grantee)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_spend_from_allowance(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    token: AllowanceToken,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token = check_allowance_token(deps.as_ref(), token)?;
+    let key = (&sender, token.storage_key());
+
+    let mut allowance = SPEND_ALLOWANCES
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoSpendAllowance {})?;
+    allowance.update_period(&env.block);
+    let remaining = allowance.remaining();
+    if amount > remaining {
+        return Err(ContractError::SpendAllowanceExceeded { remaining });
+    }
+    allowance.spent += amount;
+    SPEND_ALLOWANCES.save(deps.storage, key, &allowance)?;
+
+    let msg: CosmosMsg = match token {
+        CheckedAllowanceToken::Native { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin { denom, amount }],
+        }
+        .into(),
+        CheckedAllowanceToken::Cw20 { address } => WasmMsg::Execute {
+            contract_addr: address.into_string(),
+            msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("action", "execute_spend_from_allowance")
+        .add_attribute("sender", sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
 pub fn execute_receive_cw20(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if !config.automatically_add_cw20s {
@@ -549,9 +861,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::DumpState {} => query_dump_state(deps, env),
         QueryMsg::GetItem { key } => query_get_item(deps, key),
+        QueryMsg::GetItemResolved { key } => query_get_item_resolved(deps, key),
         QueryMsg::Info {} => query_info(deps),
+        QueryMsg::MigrationInfo {} => query_migration_info(deps),
         QueryMsg::ListItems { start_after, limit } => query_list_items(deps, start_after, limit),
+        QueryMsg::DefaultProposalModuleConfig {} => query_default_proposal_module_config(deps),
         QueryMsg::PauseInfo {} => query_paused(deps, env),
+        QueryMsg::GovernanceFreeze {} => query_governance_freeze(deps, env),
+        QueryMsg::Charter {} => query_charter(deps),
+        QueryMsg::CharterHistory {} => query_charter_history(deps),
         QueryMsg::ProposalModules { start_after, limit } => {
             query_proposal_modules(deps, start_after, limit)
         }
@@ -566,6 +884,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListSubDaos { start_after, limit } => {
             query_list_sub_daos(deps, start_after, limit)
         }
+        QueryMsg::SpendAllowance { grantee, token } => {
+            query_spend_allowance(deps, env, grantee, token)
+        }
     }
 }
 
@@ -618,6 +939,46 @@ pub fn query_proposal_modules(
     )?)
 }
 
+/// The query sent to a proposal module to read its config for the
+/// `DefaultProposalModuleConfig` query.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProposalModuleQuery {
+    Config {},
+}
+
+/// Like `ActiveProposalModules` this visits every module. Prefixes
+/// are derived from the order modules were added in, so the earliest
+/// added module has the shortest, and then alphabetically first,
+/// prefix.
+pub fn query_default_proposal_module_config(deps: Deps) -> StdResult<Binary> {
+    let module = PROPOSAL_MODULES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, module)| module))
+        .collect::<StdResult<Vec<ProposalModule>>>()?
+        .into_iter()
+        .filter(|module| module.status == ProposalModuleStatus::Enabled)
+        .min_by(|a, b| (a.prefix.len(), &a.prefix).cmp(&(b.prefix.len(), &b.prefix)));
+    // Not every proposal module has a config query, so failing to
+    // read one is not an error.
+    let config = match &module {
+        Some(module) => {
+            let request: QueryRequest<Empty> = WasmQuery::Smart {
+                contract_addr: module.address.to_string(),
+                msg: to_binary(&ProposalModuleQuery::Config {})?,
+            }
+            .into();
+            deps.querier
+                .raw_query(&to_vec(&request)?)
+                .into_result()
+                .ok()
+                .and_then(|result| result.into_result().ok())
+        }
+        None => None,
+    };
+    to_binary(&DefaultProposalModuleConfigResponse { module, config })
+}
+
 /// Note: this is not gas efficient as we need to potentially visit all modules in order to
 /// filter out the modules with active status.
 pub fn query_active_proposal_modules(
@@ -663,6 +1024,25 @@ pub fn query_paused(deps: Deps, env: Env) -> StdResult<Binary> {
     to_binary(&get_pause_info(deps, env)?)
 }
 
+pub fn query_governance_freeze(deps: Deps, env: Env) -> StdResult<Binary> {
+    let until = GOVERNANCE_FREEZE
+        .may_load(deps.storage)?
+        .filter(|until| *until > env.block.time);
+    to_binary(&GovernanceFreezeResponse { until })
+}
+
+pub fn query_charter(deps: Deps) -> StdResult<Binary> {
+    let charter = CHARTER_HISTORY
+        .may_load(deps.storage)?
+        .and_then(|mut history| history.pop());
+    to_binary(&CharterResponse { charter })
+}
+
+pub fn query_charter_history(deps: Deps) -> StdResult<Binary> {
+    let charters = CHARTER_HISTORY.may_load(deps.storage)?.unwrap_or_default();
+    to_binary(&CharterHistoryResponse { charters })
+}
+
 pub fn query_dump_state(deps: Deps, env: Env) -> StdResult<Binary> {
     let admin = ADMIN.load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
@@ -715,11 +1095,33 @@ pub fn query_get_item(deps: Deps, item: String) -> StdResult<Binary> {
     to_binary(&GetItemResponse { item })
 }
 
+pub fn query_get_item_resolved(deps: Deps, item: String) -> StdResult<Binary> {
+    let item = ITEMS.may_load(deps.storage, item)?;
+    // Items are arbitrary strings, so failing to resolve a version
+    // here is expected and not an error.
+    let contract_version = item
+        .as_ref()
+        .and_then(|item| deps.api.addr_validate(item).ok())
+        .and_then(|addr| cw2::CONTRACT.query(&deps.querier, addr).ok());
+    to_binary(&GetItemResolvedResponse {
+        item,
+        contract_version,
+    })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cw_core_interface::voting::InfoResponse { info })
 }
 
+pub fn query_migration_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    to_binary(&MigrationInfoResponse {
+        info,
+        supported_migrations: supported_migrations(CONTRACT_VERSION),
+    })
+}
+
 pub fn query_list_items(
     deps: Deps,
     start_after: Option<String>,
@@ -827,6 +1229,29 @@ pub fn query_list_sub_daos(
     to_binary(&subdaos)
 }
 
+pub fn query_spend_allowance(
+    deps: Deps,
+    env: Env,
+    grantee: String,
+    token: AllowanceToken,
+) -> StdResult<Binary> {
+    let grantee = deps.api.addr_validate(&grantee)?;
+    let token = check_allowance_token(deps, token)?;
+    let allowance = SPEND_ALLOWANCES
+        .may_load(deps.storage, (&grantee, token.storage_key()))?
+        .map(|mut allowance| {
+            allowance.update_period(&env.block);
+            allowance
+        });
+    to_binary(&SpendAllowanceResponse {
+        remaining: allowance
+            .as_ref()
+            .map(SpendAllowance::remaining)
+            .unwrap_or_default(),
+        allowance,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -904,6 +1329,24 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
             Ok(Response::default().add_attribute("voting_module", vote_module_addr))
         }
+        PROPOSAL_INSTANTIATE_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let mut stack = PENDING_PROPOSAL_MSGS.load(deps.storage)?;
+            let pending = stack.pop().ok_or(ContractError::UnknownReplyID {})?;
+            PENDING_PROPOSAL_MSGS.save(deps.storage, &stack)?;
+
+            let placeholder = instantiated_address_placeholder(pending.instantiated);
+            let msgs = pending
+                .msgs
+                .into_iter()
+                .map(|msg| substitute_placeholder(msg, &placeholder, &res.contract_address))
+                .collect();
+            let msgs = dispatch_proposal_msgs(deps.storage, msgs, pending.instantiated + 1)?;
+
+            Ok(Response::default()
+                .add_attribute("instantiated", res.contract_address)
+                .add_submessages(msgs))
+        }
         _ => Err(ContractError::UnknownReplyID {}),
     }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, StdError, Uint128};
 use cw_utils::ParseReplyError;
 use thiserror::Error;
 
@@ -13,6 +13,15 @@ pub enum ContractError {
     #[error("The contract is paused.")]
     Paused {},
 
+    #[error("A governance freeze must lift after the current block.")]
+    FreezeInPast {},
+
+    #[error("A governance freeze may last at most ({max_seconds}) seconds.")]
+    FreezeTooLong { max_seconds: u64 },
+
+    #[error("Charter URI may not be empty.")]
+    EmptyCharterUri {},
+
     #[error("No voting module provided.")]
     NoVotingModule {},
 
@@ -50,4 +59,19 @@ pub enum ContractError {
 
     #[error("Proposal module with address is disabled and cannot execute messages.")]
     ModuleDisabledCannotExecute { address: Addr },
+
+    #[error("Initial cw20 transfers may not be zero.")]
+    ZeroInitialCw20Transfer {},
+
+    #[error("The cw20 token ({token}) has more than one initial transfer.")]
+    DuplicateInitialCw20Transfer { token: Addr },
+
+    #[error("Spend allowance periods may not be zero.")]
+    ZeroAllowancePeriod {},
+
+    #[error("No spend allowance for this token.")]
+    NoSpendAllowance {},
+
+    #[error("Spend exceeds the remaining allowance of ({remaining}) for this period.")]
+    SpendAllowanceExceeded { remaining: Uint128 },
 }
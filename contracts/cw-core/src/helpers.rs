@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, WasmMsg};
+use cosmwasm_std::{Addr, BankMsg, Binary, CosmosMsg, Empty, WasmMsg};
 
 use crate::msg::{Admin, ModuleInstantiateInfo};
 
@@ -17,3 +17,83 @@ impl ModuleInstantiateInfo {
         }
     }
 }
+
+/// Gets the placeholder that proposal messages may use in place of
+/// the address of the `index`th (starting at zero) contract
+/// instantiated by the proposal. Before a message following the
+/// instantiation is executed, the placeholder is replaced with the
+/// new contract's address wherever it appears in the message's
+/// addresses and `msg` fields.
+pub fn instantiated_address_placeholder(index: u64) -> String {
+    format!("$instantiated({})", index)
+}
+
+/// Replaces `placeholder` with `address` in the addresses and
+/// serialized messages of `msg`.
+pub(crate) fn substitute_placeholder(
+    msg: CosmosMsg<Empty>,
+    placeholder: &str,
+    address: &str,
+) -> CosmosMsg<Empty> {
+    let replace = |s: String| s.replace(placeholder, address);
+    let replace_binary = |b: Binary| match String::from_utf8(b.to_vec()) {
+        Ok(s) => Binary::from(replace(s).into_bytes()),
+        // Messages that are not valid UTF-8 can not contain the
+        // placeholder.
+        Err(_) => b,
+    };
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) => WasmMsg::Execute {
+            contract_addr: replace(contract_addr),
+            msg: replace_binary(msg),
+            funds,
+        }
+        .into(),
+        CosmosMsg::Wasm(WasmMsg::Instantiate {
+            admin,
+            code_id,
+            msg,
+            funds,
+            label,
+        }) => WasmMsg::Instantiate {
+            admin: admin.map(replace),
+            code_id,
+            msg: replace_binary(msg),
+            funds,
+            label,
+        }
+        .into(),
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr,
+            new_code_id,
+            msg,
+        }) => WasmMsg::Migrate {
+            contract_addr: replace(contract_addr),
+            new_code_id,
+            msg: replace_binary(msg),
+        }
+        .into(),
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+            contract_addr,
+            admin,
+        }) => WasmMsg::UpdateAdmin {
+            contract_addr: replace(contract_addr),
+            admin: replace(admin),
+        }
+        .into(),
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => WasmMsg::ClearAdmin {
+            contract_addr: replace(contract_addr),
+        }
+        .into(),
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => BankMsg::Send {
+            to_address: replace(to_address),
+            amount,
+        }
+        .into(),
+        msg => msg,
+    }
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Binary, CosmosMsg, Empty};
+use cosmwasm_std::{Binary, CosmosMsg, Empty, Timestamp, Uint128};
 use cw_utils::Duration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -43,6 +43,28 @@ pub struct InitialItem {
     pub value: String,
 }
 
+/// A cw20 balance transferred from the instantiator to the DAO when
+/// it is instantiated.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InitialCw20Transfer {
+    /// The address of the cw20 token.
+    pub token: String,
+    /// The amount to transfer. Before instantiating the DAO the
+    /// instantiator must allow the DAO's address to spend at least
+    /// this much of their balance.
+    pub amount: Uint128,
+}
+
+/// A token that may be spent from a spend allowance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowanceToken {
+    /// A native token with the given denom.
+    Native { denom: String },
+    /// The cw20 token at the given address.
+    Cw20 { address: String },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct InstantiateMsg {
     /// Optional Admin with the ability to execute DAO messages
@@ -76,6 +98,15 @@ pub struct InstantiateMsg {
     /// items map. The value is an enum that either uses an existing
     /// address or instantiates a new contract.
     pub initial_items: Option<Vec<InitialItem>>,
+    /// cw20 balances to transfer from the instantiator to the DAO's
+    /// treasury using allowances the instantiator has granted the
+    /// DAO. As an allowance can only be granted to a known address
+    /// this is mostly useful for factory contracts and on chains
+    /// with predictable contract addresses. Each token is added to
+    /// the DAO's cw20 list. Native funds sent along with the
+    /// instantiate message are kept by the DAO as its initial native
+    /// treasury.
+    pub initial_cw20_transfers: Option<Vec<InitialCw20Transfer>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -85,11 +116,32 @@ pub enum ExecuteMsg {
     /// Executes messages in order.
     ExecuteAdminMsgs { msgs: Vec<CosmosMsg<Empty>> },
     /// Callable by proposal modules. The DAO will execute the
-    /// messages in the hook in order.
+    /// messages in the hook in order. Messages may refer to contracts
+    /// instantiated by earlier messages in the hook with
+    /// `helpers::instantiated_address_placeholder`.
     ExecuteProposalHook { msgs: Vec<CosmosMsg<Empty>> },
     /// Pauses the DAO for a set duration.
     /// When paused the DAO is unable to execute proposals
     Pause { duration: Duration },
+    /// Callable by the core contract. Freezes governance until
+    /// `until`, replacing any existing freeze. Unlike a pause the DAO
+    /// keeps working during a freeze, but proposal modules will not
+    /// execute proposals, leaving passed proposals passed until the
+    /// freeze lifts.
+    ///
+    /// As proposals can not be executed during a freeze, a freeze
+    /// may only be cleared early by the DAO's admin with
+    /// `ExecuteAdminMsgs`. A freeze may last at most
+    /// `MAX_GOVERNANCE_FREEZE_SECONDS`, thirty days, so that a DAO
+    /// without an admin can not freeze itself permanently.
+    SetGovernanceFreeze { until: Timestamp },
+    /// Callable by the core contract. Lifts the current governance
+    /// freeze, if any.
+    ClearGovernanceFreeze {},
+    /// Callable by the core contract. Amends the DAO's charter,
+    /// recording `uri` and the optional document `hash` as the next
+    /// charter version.
+    SetCharter { uri: String, hash: Option<String> },
     /// Executed when the contract receives a cw20 token. Depending on
     /// the contract's configuration the contract will automatically
     /// add the token to its treasury.
@@ -104,6 +156,17 @@ pub enum ExecuteMsg {
     /// item already exists the existing value is overriden. If the
     /// item does not exist a new item is added.
     SetItem { key: String, addr: String },
+    /// Callable by the core contract. Sets and removes several items
+    /// in the governance contract's item map at once. Every address
+    /// in `to_set` is validated before any item is changed, and if
+    /// any change fails none are applied. Items in `to_set` are set
+    /// before items in `to_remove` are removed.
+    UpdateItems {
+        /// (key, address) pairs of items to add or override.
+        to_set: Vec<(String, String)>,
+        /// Keys of items to remove. Each must exist.
+        to_remove: Vec<String>,
+    },
     /// Callable by the admin of the contract. If ADMIN is None the
     /// admin is set as the contract itself so that it may be updated
     /// later by vote. If ADMIN is Some a new admin is proposed and
@@ -154,6 +217,28 @@ pub enum ExecuteMsg {
         to_add: Vec<SubDao>,
         to_remove: Vec<String>,
     },
+    /// Callable by the core contract. Allows `grantee` to spend up
+    /// to `amount` of `token` from the DAO's treasury every `period`
+    /// via `SpendFromAllowance`. Periods start when the allowance is
+    /// granted and repeat back to back, with unspent amounts not
+    /// carried over. Granting an allowance replaces any existing
+    /// allowance the grantee has for the token and resets its usage,
+    /// and granting an `amount` of zero removes it.
+    GrantSpendAllowance {
+        grantee: String,
+        token: AllowanceToken,
+        amount: Uint128,
+        period: Duration,
+    },
+    /// Callable by the grantee of a spend allowance. Sends `amount`
+    /// of `token` from the DAO's treasury to `recipient` so long as
+    /// that does not exceed what remains of the sender's allowance
+    /// for the current period.
+    SpendFromAllowance {
+        token: AllowanceToken,
+        amount: Uint128,
+        recipient: String,
+    },
 }
 
 #[voting_query]
@@ -192,6 +277,10 @@ pub enum QueryMsg {
     DumpState {},
     /// Gets the address associated with an item key.
     GetItem { key: String },
+    /// Gets the value associated with an item key and, if that value
+    /// is the address of a contract that sets its version with cw2,
+    /// that contract's version. Returns `GetItemResolvedResponse`.
+    GetItemResolved { key: String },
     /// Lists all of the items associted with the contract. For
     /// example, given the items `{ "group": "foo", "subdao": "bar"}`
     /// this query would return `[("group", "foo"), ("subdao",
@@ -212,16 +301,43 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets the config of the DAO's earliest added proposal module
+    /// that is still enabled, for use as a template when adding
+    /// another proposal module so that the DAO's modules are
+    /// configured consistently. The config is whatever the module
+    /// returns in response to a `{"config": {}}` query. This is a
+    /// best-effort template: it is not validated, modules of a
+    /// different kind may not accept it, and a module's config type
+    /// generally differs from its instantiate message. Returns
+    /// `DefaultProposalModuleConfigResponse`.
+    DefaultProposalModuleConfig {},
     /// Returns information about if the contract is currently paused.
     PauseInfo {},
+    /// Gets the time the DAO's current governance freeze lifts, if
+    /// it is frozen. Returns `GovernanceFreezeResponse`.
+    GovernanceFreeze {},
+    /// Gets the DAO's current charter. Returns `CharterResponse`.
+    Charter {},
+    /// Gets the DAO's most recent charters, oldest first. Returns
+    /// `CharterHistoryResponse`.
+    CharterHistory {},
     /// Gets the contract's voting module. Returns Addr.
     VotingModule {},
+    /// Gets a grantee's spend allowance for a token as of the current
+    /// block. Returns `SpendAllowanceResponse`.
+    SpendAllowance {
+        grantee: String,
+        token: AllowanceToken,
+    },
     /// Returns all SubDAOs with their charters in a vec
     /// start_after is bound exclusive and asks for a string address
     ListSubDaos {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets the contract's cw2 version and the `MigrateMsg` variants
+    /// it accepts. Returns `cw_core_interface::migration::MigrationInfoResponse`.
+    MigrationInfo {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
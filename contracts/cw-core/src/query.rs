@@ -1,10 +1,10 @@
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, Timestamp, Uint128};
 use cw2::ContractVersion;
 use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::{Config, ProposalModule};
+use crate::state::{Charter, Config, ProposalModule, SpendAllowance};
 
 /// Relevant state for the governance module. Returned by the
 /// `DumpState` query.
@@ -38,6 +38,29 @@ pub enum PauseInfoResponse {
     Unpaused {},
 }
 
+/// Returned by the `GovernanceFreeze` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GovernanceFreezeResponse {
+    /// The time the DAO's governance freeze lifts. `None` if the DAO
+    /// is not frozen.
+    pub until: Option<Timestamp>,
+}
+
+/// Returned by the `Charter` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CharterResponse {
+    /// The DAO's current charter. `None` if the DAO has never set a
+    /// charter.
+    pub charter: Option<Charter>,
+}
+
+/// Returned by the `CharterHistory` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CharterHistoryResponse {
+    /// The DAO's most recent charters, oldest first.
+    pub charters: Vec<Charter>,
+}
+
 /// Returned by the `GetItem` query.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct GetItemResponse {
@@ -46,6 +69,18 @@ pub struct GetItemResponse {
     pub item: Option<String>,
 }
 
+/// Returned by the `GetItemResolved` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetItemResolvedResponse {
+    /// `None` if no item with the provided key was found, `Some`
+    /// otherwise.
+    pub item: Option<String>,
+    /// The cw2 contract version of the contract at `item`. `None` if
+    /// the item is not the address of a contract that has set its
+    /// version with cw2.
+    pub contract_version: Option<ContractVersion>,
+}
+
 /// Returned by the `Cw20Balances` query.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Cw20BalanceResponse {
@@ -55,6 +90,17 @@ pub struct Cw20BalanceResponse {
     pub balance: Uint128,
 }
 
+/// Returned by the `DefaultProposalModuleConfig` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DefaultProposalModuleConfigResponse {
+    /// The module the config was read from. `None` if the DAO has no
+    /// enabled proposal modules.
+    pub module: Option<ProposalModule>,
+    /// The module's response to a `{"config": {}}` query. `None` if
+    /// there is no module or it does not support the query.
+    pub config: Option<Binary>,
+}
+
 /// Returned by the `AdminNomination` query.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct AdminNominationResponse {
@@ -70,3 +116,13 @@ pub struct SubDao {
     /// The purpose/constitution for the SubDAO
     pub charter: Option<String>,
 }
+
+/// Returned by the `SpendAllowance` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SpendAllowanceResponse {
+    /// The grantee's allowance, advanced to the current period. `None`
+    /// if the grantee has no allowance for the token.
+    pub allowance: Option<SpendAllowance>,
+    /// The amount the grantee may still spend in the current period.
+    pub remaining: Uint128,
+}
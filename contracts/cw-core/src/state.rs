@@ -1,8 +1,8 @@
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Empty, Timestamp};
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
 /// Top level config type for core module.
@@ -41,6 +41,90 @@ pub enum ProposalModuleStatus {
     Disabled,
 }
 
+/// A validated `AllowanceToken`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckedAllowanceToken {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+
+impl CheckedAllowanceToken {
+    /// The key this token's allowances are stored under in
+    /// `SPEND_ALLOWANCES`.
+    pub fn storage_key(&self) -> String {
+        match self {
+            CheckedAllowanceToken::Native { denom } => format!("native:{}", denom),
+            CheckedAllowanceToken::Cw20 { address } => format!("cw20:{}", address),
+        }
+    }
+}
+
+/// An amount of a token that a grantee may spend from the DAO's
+/// treasury every period.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SpendAllowance {
+    /// The token that may be spent.
+    pub token: CheckedAllowanceToken,
+    /// The amount that may be spent each period.
+    pub amount: Uint128,
+    /// The length of each period.
+    pub period: Duration,
+    /// The block height or time in seconds, depending on `period`,
+    /// that the current period started at.
+    pub period_start: u64,
+    /// The amount spent so far in the current period.
+    pub spent: Uint128,
+}
+
+impl SpendAllowance {
+    /// Advances this allowance to the period containing `block`,
+    /// resetting its usage if a new period has started.
+    pub fn update_period(&mut self, block: &BlockInfo) {
+        let (now, length) = match self.period {
+            Duration::Height(height) => (block.height, height),
+            Duration::Time(time) => (block.time.seconds(), time),
+        };
+        let elapsed = now - self.period_start;
+        if elapsed >= length {
+            self.period_start += elapsed - elapsed % length;
+            self.spent = Uint128::zero();
+        }
+    }
+
+    /// The amount that may still be spent in the current period.
+    pub fn remaining(&self) -> Uint128 {
+        self.amount - self.spent
+    }
+}
+
+/// A version of the DAO's charter.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Charter {
+    /// The version of the charter. The first charter set is version
+    /// one and each amendment increments the version by one.
+    pub version: u64,
+    /// The location of the charter document, e.g. an IPFS URI.
+    pub uri: String,
+    /// An optional hash of the charter document so that readers may
+    /// verify the document at `uri` has not changed.
+    pub hash: Option<String>,
+    /// The time the charter was set.
+    pub set_at: Timestamp,
+}
+
+/// The messages of a proposal that are waiting for a contract
+/// instantiated earlier in the proposal to be given an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingProposalMsgs {
+    /// The messages left to execute.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The number of contracts the proposal has instantiated so
+    /// far. The contract being waited on is given the placeholder
+    /// with this index.
+    pub instantiated: u64,
+}
+
 /// The admin of the contract. Typically a DAO. The contract admin may
 /// unilaterally execute messages on this contract.
 ///
@@ -65,6 +149,23 @@ pub const CONFIG: Item<Config> = Item::new("config");
 /// the DAO has never been paused.
 pub const PAUSED: Item<Expiration> = Item::new("paused");
 
+/// The time the DAO's governance freeze lifts. Not set if the DAO has
+/// never been frozen or its freeze was cleared.
+pub const GOVERNANCE_FREEZE: Item<Timestamp> = Item::new("governance_freeze");
+
+/// The longest a governance freeze may last, in seconds. Thirty
+/// days. A DAO without an admin can not clear its own freeze, so
+/// longer freezes risk leaving it unable to govern.
+pub const MAX_GOVERNANCE_FREEZE_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// The maximum number of charter versions kept in `CHARTER_HISTORY`.
+pub const MAX_CHARTER_HISTORY: usize = 20;
+
+/// The DAO's charters, oldest first. The last entry is the current
+/// charter. Only the most recent `MAX_CHARTER_HISTORY` versions are
+/// kept. Not set if the DAO has never set a charter.
+pub const CHARTER_HISTORY: Item<Vec<Charter>> = Item::new("charter_history");
+
 /// The voting module associated with this contract.
 pub const VOTING_MODULE: Item<Addr> = Item::new("voting_module");
 
@@ -94,3 +195,14 @@ pub const SUBDAO_LIST: Map<&Addr, Option<String>> = Map::new("sub_daos");
 
 /// Timestamp of this DAO's creation. Will only be present for DAOs created v2 and after.
 pub const CREATED_TIMESTAMP: Item<Timestamp> = Item::new("created_timestamp");
+
+/// Spend allowances granted by the DAO, keyed by grantee and
+/// `CheckedAllowanceToken::storage_key`.
+pub const SPEND_ALLOWANCES: Map<(&Addr, String), SpendAllowance> = Map::new("spend_allowances");
+
+/// Proposals whose execution is waiting on a contract to be
+/// instantiated. This is a stack as a proposal's messages may execute
+/// another proposal before the contract being waited on is
+/// instantiated.
+pub const PENDING_PROPOSAL_MSGS: Item<Vec<PendingProposalMsgs>> =
+    Item::new("pending_proposal_msgs");
@@ -1,24 +1,33 @@
 use cosmwasm_std::{
-    from_slice,
+    coins, from_slice,
     testing::{mock_dependencies, mock_env},
-    to_binary, Addr, CosmosMsg, Empty, Storage, Timestamp, Uint128, WasmMsg,
+    to_binary, to_vec, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw2::ContractVersion;
-use cw_core_interface::voting::VotingPowerAtHeightResponse;
-use cw_multi_test::{App, Contract, ContractWrapper, Executor};
-use cw_storage_plus::Map;
+use cw_core_interface::{migration::MigrationInfoResponse, voting::VotingPowerAtHeightResponse};
+use cw_multi_test::{App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
+use cw_storage_plus::{Item, Map};
 use cw_utils::{Duration, Expiration};
+use serde::Deserialize;
 
 use crate::{
-    contract::{derive_proposal_module_prefix, migrate, CONTRACT_NAME, CONTRACT_VERSION},
+    contract::{derive_proposal_module_prefix, migrate, query, CONTRACT_NAME, CONTRACT_VERSION},
+    helpers::instantiated_address_placeholder,
     msg::{
-        Admin, ExecuteMsg, InitialItem, InstantiateMsg, MigrateMsg, ModuleInstantiateInfo, QueryMsg,
+        Admin, AllowanceToken, ExecuteMsg, InitialCw20Transfer, InitialItem, InstantiateMsg,
+        MigrateMsg, ModuleInstantiateInfo, QueryMsg,
     },
     query::{
-        AdminNominationResponse, Cw20BalanceResponse, DumpStateResponse, GetItemResponse,
-        PauseInfoResponse, SubDao,
+        AdminNominationResponse, CharterHistoryResponse, CharterResponse, Cw20BalanceResponse,
+        DefaultProposalModuleConfigResponse, DumpStateResponse, GetItemResolvedResponse,
+        GetItemResponse, GovernanceFreezeResponse, PauseInfoResponse, SpendAllowanceResponse,
+        SubDao,
+    },
+    state::{
+        Charter, Config, ProposalModule, ProposalModuleStatus, MAX_CHARTER_HISTORY,
+        MAX_GOVERNANCE_FREEZE_SECONDS, PROPOSAL_MODULES,
     },
-    state::{Config, ProposalModule, ProposalModuleStatus, PROPOSAL_MODULES},
     ContractError,
 };
 
@@ -72,6 +81,34 @@ fn cw_core_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+/// A proposal module whose config is the string it is instantiated
+/// with.
+fn config_proposal_contract() -> Box<dyn Contract<Empty>> {
+    const CONFIG: Item<String> = Item::new("config");
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum ModuleQueryMsg {
+        Config {},
+    }
+
+    let contract = ContractWrapper::new(
+        |_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty| -> StdResult<Response> {
+            Ok(Response::default())
+        },
+        |deps: DepsMut, _env: Env, _info: MessageInfo, config: String| -> StdResult<Response> {
+            CONFIG.save(deps.storage, &config)?;
+            Ok(Response::default())
+        },
+        |deps: Deps, _env: Env, msg: ModuleQueryMsg| -> StdResult<Binary> {
+            match msg {
+                ModuleQueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+            }
+        },
+    );
+    Box::new(contract)
+}
+
 fn instantiate_gov(app: &mut App, code_id: u64, msg: InstantiateMsg) -> Addr {
     app.instantiate_contract(
         code_id,
@@ -119,6 +156,7 @@ fn test_instantiate_with_n_gov_modules(n: usize) {
             })
             .collect(),
         initial_items: None,
+        initial_cw20_transfers: None,
     };
     let gov_addr = instantiate_gov(&mut app, gov_id, instantiate);
 
@@ -221,6 +259,7 @@ makes wickedness."
         },
         proposal_modules_instantiate_info: governance_modules,
         initial_items: None,
+        initial_cw20_transfers: None,
     };
     instantiate_gov(&mut app, gov_id, instantiate);
 }
@@ -255,6 +294,7 @@ fn test_update_config() {
             label: "voting module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -344,6 +384,7 @@ fn test_swap_governance(swaps: Vec<(u32, u32)>) {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -495,6 +536,7 @@ fn test_removed_modules_can_not_execute() {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -607,6 +649,127 @@ fn test_removed_modules_can_not_execute() {
     .unwrap();
 }
 
+#[test]
+fn test_default_proposal_module_config() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(sudo_proposal_contract());
+    let config_module_id = app.store_code(config_proposal_contract());
+    let gov_id = app.store_code(cw_core_contract());
+
+    let govmod_instantiate = cw_proposal_sudo::msg::InstantiateMsg {
+        root: CREATOR_ADDR.to_string(),
+    };
+    let gov_instantiate = InstantiateMsg {
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: true,
+        automatically_add_cw721s: true,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: govmod_id,
+            msg: to_binary(&govmod_instantiate).unwrap(),
+            admin: Admin::CoreContract {},
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: govmod_id,
+            msg: to_binary(&govmod_instantiate).unwrap(),
+            admin: Admin::CoreContract {},
+            label: "governance module".to_string(),
+        }],
+        initial_items: None,
+        initial_cw20_transfers: None,
+    };
+    let gov_addr = instantiate_gov(&mut app, gov_id, gov_instantiate);
+
+    // The sudo module has no config, so there is no template.
+    let sudo_module = get_active_modules(&app, gov_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap();
+    let res: DefaultProposalModuleConfigResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::DefaultProposalModuleConfig {})
+        .unwrap();
+    assert_eq!(
+        res,
+        DefaultProposalModuleConfigResponse {
+            module: Some(sudo_module.clone()),
+            config: None,
+        }
+    );
+
+    // Add a module with a config and another after it, then disable
+    // the sudo module. The earliest added enabled module is used.
+    let to_add = ["first", "second"]
+        .iter()
+        .map(|config| ModuleInstantiateInfo {
+            code_id: config_module_id,
+            msg: to_binary(config).unwrap(),
+            admin: Admin::CoreContract {},
+            label: format!("{} governance module", config),
+        })
+        .collect();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        sudo_module.address.clone(),
+        &cw_proposal_sudo::msg::ExecuteMsg::Execute {
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: gov_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::UpdateProposalModules {
+                    to_add,
+                    to_disable: vec![sudo_module.address.to_string()],
+                })
+                .unwrap(),
+            }
+            .into()],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res: DefaultProposalModuleConfigResponse = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::DefaultProposalModuleConfig {})
+        .unwrap();
+    let module = res.module.unwrap();
+    assert_eq!(module.prefix, "B");
+    assert_eq!(module.status, ProposalModuleStatus::Enabled);
+    let config: String = from_slice(&res.config.unwrap()).unwrap();
+    assert_eq!(config, "first");
+
+    // A DAO with no enabled proposal modules has no template.
+    let mut deps = mock_dependencies();
+    PROPOSAL_MODULES
+        .save(
+            deps.as_mut().storage,
+            sudo_module.address.clone(),
+            &ProposalModule {
+                status: ProposalModuleStatus::Disabled,
+                ..sudo_module
+            },
+        )
+        .unwrap();
+    let res: DefaultProposalModuleConfigResponse = from_slice(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::DefaultProposalModuleConfig {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        DefaultProposalModuleConfigResponse {
+            module: None,
+            config: None,
+        }
+    );
+}
+
 #[test]
 fn test_module_already_disabled() {
     let mut app = App::default();
@@ -637,6 +800,7 @@ fn test_module_already_disabled() {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -735,6 +899,7 @@ fn test_swap_voting_module() {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -837,6 +1002,7 @@ fn test_permissions() {
         initial_items: None,
         automatically_add_cw20s: true,
         automatically_add_cw721s: true,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -932,6 +1098,7 @@ fn do_standard_instantiate(auto_add: bool, admin: Option<String>) -> (Addr, App)
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -1476,6 +1643,282 @@ fn test_add_remove_get() {
     assert_eq!(a, GetItemResponse { item: None });
 }
 
+#[test]
+fn test_update_items() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+
+    set_item(
+        &mut app,
+        gov_addr.clone(),
+        "fookey".to_string(),
+        "fooaddr".to_string(),
+    );
+    set_item(
+        &mut app,
+        gov_addr.clone(),
+        "barkey".to_string(),
+        "baraddr".to_string(),
+    );
+
+    let update_items =
+        |app: &mut App, sender: &Addr, to_set: Vec<(&str, &str)>, to_remove: Vec<&str>| {
+            app.execute_contract(
+                sender.clone(),
+                gov_addr.clone(),
+                &ExecuteMsg::UpdateItems {
+                    to_set: to_set
+                        .into_iter()
+                        .map(|(key, addr)| (key.to_string(), addr.to_string()))
+                        .collect(),
+                    to_remove: to_remove.into_iter().map(|key| key.to_string()).collect(),
+                },
+                &[],
+            )
+        };
+
+    // Only the core contract may update items.
+    let err: ContractError = update_items(
+        &mut app,
+        &Addr::unchecked(CREATOR_ADDR),
+        vec![("bazkey", "bazaddr")],
+        vec![],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Items are set, overridden, and removed in one call.
+    update_items(
+        &mut app,
+        &gov_addr,
+        vec![("bazkey", "bazaddr"), ("fookey", "newfooaddr")],
+        vec!["barkey"],
+    )
+    .unwrap();
+    let items = list_items(&mut app, gov_addr.clone(), None, None);
+    assert_eq!(
+        items,
+        vec![
+            ("fookey".to_string(), "newfooaddr".to_string()),
+            ("bazkey".to_string(), "bazaddr".to_string()),
+        ]
+    );
+
+    // An invalid address prevents any change from being applied.
+    let err: ContractError = update_items(
+        &mut app,
+        &gov_addr,
+        vec![("loremkey", "loremaddr"), ("ipsumkey", "INVALID")],
+        vec!["fookey"],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    // As does removing an item that does not exist.
+    let err: ContractError = update_items(
+        &mut app,
+        &gov_addr,
+        vec![("loremkey", "loremaddr")],
+        vec!["barkey"],
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert_eq!(err, ContractError::KeyMissing {});
+
+    assert_eq!(list_items(&mut app, gov_addr, None, None), items);
+}
+
+#[test]
+fn test_get_item_resolved() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+
+    // Items that point at contracts resolve to their cw2 version.
+    set_item(
+        &mut app,
+        gov_addr.clone(),
+        "self".to_string(),
+        gov_addr.to_string(),
+    );
+    // Items that don't point at contracts resolve to nothing.
+    set_item(
+        &mut app,
+        gov_addr.clone(),
+        "aaaaakey".to_string(),
+        "aaaaaaddr".to_string(),
+    );
+
+    let resolved: GetItemResolvedResponse = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::GetItemResolved {
+                key: "self".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resolved,
+        GetItemResolvedResponse {
+            item: Some(gov_addr.to_string()),
+            contract_version: Some(ContractVersion {
+                contract: CONTRACT_NAME.to_string(),
+                version: CONTRACT_VERSION.to_string(),
+            }),
+        }
+    );
+
+    let resolved: GetItemResolvedResponse = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::GetItemResolved {
+                key: "aaaaakey".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resolved,
+        GetItemResolvedResponse {
+            item: Some("aaaaaaddr".to_string()),
+            contract_version: None,
+        }
+    );
+
+    let resolved: GetItemResolvedResponse = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::GetItemResolved {
+                key: "missing".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resolved,
+        GetItemResolvedResponse {
+            item: None,
+            contract_version: None,
+        }
+    );
+}
+
+#[test]
+fn test_spend_allowance() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: gov_addr.to_string(),
+        amount: coins(1000, "ujuno"),
+    }))
+    .unwrap();
+    let token = AllowanceToken::Native {
+        denom: "ujuno".to_string(),
+    };
+
+    // Only the DAO may grant allowances.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("grantee"),
+            gov_addr.clone(),
+            &ExecuteMsg::GrantSpendAllowance {
+                grantee: "grantee".to_string(),
+                token: token.clone(),
+                amount: Uint128::new(100),
+                period: Duration::Height(10),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::GrantSpendAllowance {
+            grantee: "grantee".to_string(),
+            token: token.clone(),
+            amount: Uint128::new(100),
+            period: Duration::Height(10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let spend = |app: &mut App, amount: u128| {
+        app.execute_contract(
+            Addr::unchecked("grantee"),
+            gov_addr.clone(),
+            &ExecuteMsg::SpendFromAllowance {
+                token: token.clone(),
+                amount: Uint128::new(amount),
+                recipient: "recipient".to_string(),
+            },
+            &[],
+        )
+    };
+
+    // Spending within the allowance works.
+    spend(&mut app, 60).unwrap();
+    spend(&mut app, 40).unwrap();
+    let balance = app.wrap().query_balance("recipient", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(100));
+
+    // Spending beyond it does not.
+    let err: ContractError = spend(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendAllowanceExceeded {
+            remaining: Uint128::zero()
+        }
+    );
+
+    // The allowance resets once the period is over.
+    app.update_block(|block| block.height += 10);
+    let allowance: SpendAllowanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr.clone(),
+            &QueryMsg::SpendAllowance {
+                grantee: "grantee".to_string(),
+                token: token.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(allowance.remaining, Uint128::new(100));
+
+    spend(&mut app, 30).unwrap();
+    let err: ContractError = spend(&mut app, 71).unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        ContractError::SpendAllowanceExceeded {
+            remaining: Uint128::new(70)
+        }
+    );
+    let balance = app.wrap().query_balance("recipient", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(130));
+
+    // Granting zero removes the allowance.
+    app.execute_contract(
+        gov_addr.clone(),
+        gov_addr.clone(),
+        &ExecuteMsg::GrantSpendAllowance {
+            grantee: "grantee".to_string(),
+            token: token.clone(),
+            amount: Uint128::zero(),
+            period: Duration::Height(10),
+        },
+        &[],
+    )
+    .unwrap();
+    let err: ContractError = spend(&mut app, 1).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NoSpendAllowance {});
+}
+
 #[test]
 #[should_panic(expected = "Key is missing from storage")]
 fn test_remove_missing_key() {
@@ -1529,6 +1972,7 @@ fn test_list_items() {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -1658,6 +2102,7 @@ fn test_instantiate_with_items() {
                 value: "item0_value_override".to_string(),
             },
         ]),
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -1691,39 +2136,214 @@ fn test_instantiate_with_items() {
 }
 
 #[test]
-fn test_cw20_receive_auto_add() {
-    let (gov_addr, mut app) = do_standard_instantiate(true, None);
-
+fn test_instantiate_with_initial_treasury() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(sudo_proposal_contract());
+    let voting_id = app.store_code(cw20_balances_voting());
+    let gov_id = app.store_code(cw_core_contract());
     let cw20_id = app.store_code(cw20_contract());
-    let another_cw20 = app
+
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: coins(100, "ujuno"),
+    }))
+    .unwrap();
+    let token = app
         .instantiate_contract(
             cw20_id,
             Addr::unchecked(CREATOR_ADDR),
             &cw20_base::msg::InstantiateMsg {
-                name: "DAO".to_string(),
-                symbol: "DAO".to_string(),
+                name: "Treasury".to_string(),
+                symbol: "TRS".to_string(),
                 decimals: 6,
-                initial_balances: vec![],
+                initial_balances: vec![cw20::Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::new(50),
+                }],
                 mint: None,
                 marketing: None,
             },
             &[],
-            "another-token",
+            "treasury-token",
             None,
         )
         .unwrap();
 
-    let voting_module: Addr = app
-        .wrap()
-        .query_wasm_smart(gov_addr.clone(), &QueryMsg::VotingModule {})
-        .unwrap();
-    let gov_token: Addr = app
-        .wrap()
-        .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::TokenContract {},
-        )
-        .unwrap();
+    let gov_instantiate = |initial_cw20_transfers| InstantiateMsg {
+        admin: None,
+        name: "DAO DAO".to_string(),
+        description: "A DAO that builds DAOs.".to_string(),
+        image_url: None,
+        automatically_add_cw20s: false,
+        automatically_add_cw721s: false,
+        voting_module_instantiate_info: ModuleInstantiateInfo {
+            code_id: voting_id,
+            msg: to_binary(&cw20_balance_voting::msg::InstantiateMsg {
+                token_info: cw20_balance_voting::msg::TokenInfo::New {
+                    code_id: cw20_id,
+                    label: "DAO DAO voting".to_string(),
+                    name: "DAO DAO".to_string(),
+                    symbol: "DAO".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![cw20::Cw20Coin {
+                        address: CREATOR_ADDR.to_string(),
+                        amount: Uint128::from(2u64),
+                    }],
+                    marketing: None,
+                },
+            })
+            .unwrap(),
+            admin: Admin::CoreContract {},
+            label: "voting module".to_string(),
+        },
+        proposal_modules_instantiate_info: vec![ModuleInstantiateInfo {
+            code_id: govmod_id,
+            msg: to_binary(&cw_proposal_sudo::msg::InstantiateMsg {
+                root: CREATOR_ADDR.to_string(),
+            })
+            .unwrap(),
+            admin: Admin::CoreContract {},
+            label: "governance module".to_string(),
+        }],
+        initial_items: None,
+        initial_cw20_transfers,
+    };
+
+    // The DAO must be allowed to pull the cw20s before it exists, so
+    // use the address the next contract will be instantiated at.
+    let expected_gov_addr = "contract1";
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token.clone(),
+        &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+            spender: expected_gov_addr.to_string(),
+            amount: Uint128::new(50),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let gov_addr = app
+        .instantiate_contract(
+            gov_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &gov_instantiate(Some(vec![InitialCw20Transfer {
+                token: token.to_string(),
+                amount: Uint128::new(50),
+            }])),
+            &coins(100, "ujuno"),
+            "cw-governance",
+            None,
+        )
+        .unwrap();
+    assert_eq!(gov_addr, Addr::unchecked(expected_gov_addr));
+
+    // Native funds are retained as the initial treasury.
+    let balance = app.wrap().query_balance(gov_addr.clone(), "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(100));
+
+    // cw20s are pulled from the instantiator and added to the
+    // treasury even though cw20s are not automatically added.
+    let cw20_balances: Vec<Cw20BalanceResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &QueryMsg::Cw20Balances {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        cw20_balances,
+        vec![Cw20BalanceResponse {
+            addr: token.clone(),
+            balance: Uint128::new(50),
+        }]
+    );
+    let creator_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(creator_balance.balance, Uint128::zero());
+
+    // Transfers must be non-zero and of unique tokens.
+    let err: ContractError = app
+        .instantiate_contract(
+            gov_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &gov_instantiate(Some(vec![InitialCw20Transfer {
+                token: token.to_string(),
+                amount: Uint128::zero(),
+            }])),
+            &[],
+            "cw-governance",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::ZeroInitialCw20Transfer {});
+
+    let transfer = InitialCw20Transfer {
+        token: token.to_string(),
+        amount: Uint128::new(1),
+    };
+    let err: ContractError = app
+        .instantiate_contract(
+            gov_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &gov_instantiate(Some(vec![transfer.clone(), transfer])),
+            &[],
+            "cw-governance",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::DuplicateInitialCw20Transfer { token });
+}
+
+#[test]
+fn test_cw20_receive_auto_add() {
+    let (gov_addr, mut app) = do_standard_instantiate(true, None);
+
+    let cw20_id = app.store_code(cw20_contract());
+    let another_cw20 = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "another-token",
+            None,
+        )
+        .unwrap();
+
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &QueryMsg::VotingModule {})
+        .unwrap();
+    let gov_token: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
 
     // Check that the balances query works with no tokens.
     let cw20_balances: Vec<Cw20BalanceResponse> = app
@@ -2356,6 +2976,244 @@ fn test_pause() {
     );
 }
 
+#[test]
+fn test_governance_freeze() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+
+    let freeze = |app: &App| -> GovernanceFreezeResponse {
+        app.wrap()
+            .query_wasm_smart(core_addr.clone(), &QueryMsg::GovernanceFreeze {})
+            .unwrap()
+    };
+    assert_eq!(freeze(&app), GovernanceFreezeResponse { until: None });
+
+    let until = app.block_info().time.plus_seconds(100);
+
+    // Only the DAO may freeze itself.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            core_addr.clone(),
+            &ExecuteMsg::SetGovernanceFreeze { until },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::SetGovernanceFreeze {
+                until: app.block_info().time,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::FreezeInPast {});
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::SetGovernanceFreeze {
+                until: app
+                    .block_info()
+                    .time
+                    .plus_seconds(MAX_GOVERNANCE_FREEZE_SECONDS + 1),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::FreezeTooLong {
+            max_seconds: MAX_GOVERNANCE_FREEZE_SECONDS
+        }
+    );
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetGovernanceFreeze { until },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        freeze(&app),
+        GovernanceFreezeResponse { until: Some(until) }
+    );
+
+    // The freeze lifts on its own.
+    app.update_block(|block| block.time = until);
+    assert_eq!(freeze(&app), GovernanceFreezeResponse { until: None });
+
+    let until = until.plus_seconds(100);
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetGovernanceFreeze { until },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        freeze(&app),
+        GovernanceFreezeResponse { until: Some(until) }
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            core_addr.clone(),
+            &ExecuteMsg::ClearGovernanceFreeze {},
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::ClearGovernanceFreeze {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(freeze(&app), GovernanceFreezeResponse { until: None });
+}
+
+#[test]
+fn test_charter() {
+    let (core_addr, mut app) = do_standard_instantiate(false, None);
+
+    let charter = |app: &App| -> CharterResponse {
+        app.wrap()
+            .query_wasm_smart(core_addr.clone(), &QueryMsg::Charter {})
+            .unwrap()
+    };
+    let history = |app: &App| -> Vec<Charter> {
+        let res: CharterHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(core_addr.clone(), &QueryMsg::CharterHistory {})
+            .unwrap();
+        res.charters
+    };
+    assert_eq!(charter(&app), CharterResponse { charter: None });
+    assert_eq!(history(&app), vec![]);
+
+    // Only the DAO may set its charter.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            core_addr.clone(),
+            &ExecuteMsg::SetCharter {
+                uri: "ipfs://charter".to_string(),
+                hash: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err: ContractError = app
+        .execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::SetCharter {
+                uri: "".to_string(),
+                hash: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, ContractError::EmptyCharterUri {});
+
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetCharter {
+            uri: "ipfs://charter".to_string(),
+            hash: Some("abc".to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    let first = Charter {
+        version: 1,
+        uri: "ipfs://charter".to_string(),
+        hash: Some("abc".to_string()),
+        set_at: app.block_info().time,
+    };
+    assert_eq!(
+        charter(&app),
+        CharterResponse {
+            charter: Some(first.clone())
+        }
+    );
+
+    // Amend the charter.
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+    app.execute_contract(
+        core_addr.clone(),
+        core_addr.clone(),
+        &ExecuteMsg::SetCharter {
+            uri: "ipfs://amended".to_string(),
+            hash: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let second = Charter {
+        version: 2,
+        uri: "ipfs://amended".to_string(),
+        hash: None,
+        set_at: app.block_info().time,
+    };
+    assert_eq!(
+        charter(&app),
+        CharterResponse {
+            charter: Some(second.clone())
+        }
+    );
+    assert_eq!(history(&app), vec![first, second]);
+
+    // Only the most recent charters are kept.
+    for _ in 0..MAX_CHARTER_HISTORY {
+        app.execute_contract(
+            core_addr.clone(),
+            core_addr.clone(),
+            &ExecuteMsg::SetCharter {
+                uri: "ipfs://amended".to_string(),
+                hash: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let charters = history(&app);
+    assert_eq!(charters.len(), MAX_CHARTER_HISTORY);
+    assert_eq!(charters[0].version, 3);
+    assert_eq!(
+        charters.last().unwrap().version,
+        MAX_CHARTER_HISTORY as u64 + 2
+    );
+    assert_eq!(
+        charter(&app).charter.unwrap().version,
+        MAX_CHARTER_HISTORY as u64 + 2
+    );
+}
+
 #[test]
 fn test_dump_state_proposal_modules() {
     let (core_addr, app) = do_standard_instantiate(false, None);
@@ -2429,6 +3287,7 @@ fn test_migrate_from_compatible() {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -2465,6 +3324,39 @@ fn test_migrate_from_compatible() {
     assert_eq!(new_state, state);
 }
 
+#[test]
+fn test_migration_info() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, CONTRACT_VERSION).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationInfo {}).unwrap();
+    let res: MigrationInfoResponse = from_slice(&res).unwrap();
+    assert_eq!(
+        res.info,
+        ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: CONTRACT_VERSION.to_string(),
+        }
+    );
+
+    // Every `MigrateMsg` variant is listed.
+    let msgs = [MigrateMsg::FromV1 {}, MigrateMsg::FromCompatible {}];
+    assert_eq!(res.supported_migrations.len(), msgs.len());
+    for msg in msgs {
+        let msg = String::from_utf8(to_vec(&msg).unwrap()).unwrap();
+        assert!(res
+            .supported_migrations
+            .iter()
+            .any(|m| msg.starts_with(&format!("{{\"{}\":", m.msg))));
+    }
+
+    // Migrating from the current version is always supported.
+    assert!(res
+        .supported_migrations
+        .iter()
+        .any(|m| m.from_versions.contains(&CONTRACT_VERSION.to_string())));
+}
+
 #[test]
 fn test_migrate_from_beta() {
     let mut app = App::default();
@@ -2520,6 +3412,7 @@ fn test_migrate_from_beta() {
             },
         ],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -2612,6 +3505,101 @@ fn test_execute_stargate_msg() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_proposal_instantiate_placeholder() {
+    let (core_addr, mut app) = do_standard_instantiate(true, None);
+    let proposal_module = get_active_modules(&app, core_addr.clone())
+        .into_iter()
+        .next()
+        .unwrap();
+    let cw20_id = app.store_code(cw20_contract());
+
+    let instantiate_token = |symbol: &str, amount: u64| -> CosmosMsg {
+        WasmMsg::Instantiate {
+            admin: None,
+            code_id: cw20_id,
+            msg: to_binary(&cw20_base::msg::InstantiateMsg {
+                name: symbol.to_string(),
+                symbol: symbol.to_string(),
+                decimals: 6,
+                initial_balances: vec![cw20::Cw20Coin {
+                    address: core_addr.to_string(),
+                    amount: Uint128::from(amount),
+                }],
+                mint: None,
+                marketing: None,
+            })
+            .unwrap(),
+            funds: vec![],
+            label: symbol.to_string(),
+        }
+        .into()
+    };
+
+    // Instantiate two tokens, configuring each after it is
+    // instantiated. The second token is sent to the first.
+    let res = app
+        .execute_contract(
+            proposal_module.address,
+            core_addr.clone(),
+            &ExecuteMsg::ExecuteProposalHook {
+                msgs: vec![
+                    instantiate_token("AAA", 100),
+                    WasmMsg::Execute {
+                        contract_addr: instantiated_address_placeholder(0),
+                        msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                            recipient: CREATOR_ADDR.to_string(),
+                            amount: Uint128::new(10),
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    }
+                    .into(),
+                    instantiate_token("BBB", 50),
+                    WasmMsg::Execute {
+                        contract_addr: instantiated_address_placeholder(1),
+                        msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                            recipient: instantiated_address_placeholder(0),
+                            amount: Uint128::new(5),
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    }
+                    .into(),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+    let instantiated: Vec<String> = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "instantiated")
+        .map(|attr| attr.value.clone())
+        .collect();
+    assert_eq!(instantiated.len(), 2);
+    let (first, second) = (&instantiated[0], &instantiated[1]);
+
+    let balance = |app: &App, token: &str, address: &str| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+    assert_eq!(balance(&app, first, CREATOR_ADDR), Uint128::new(10));
+    assert_eq!(balance(&app, first, core_addr.as_str()), Uint128::new(90));
+    assert_eq!(balance(&app, second, first), Uint128::new(5));
+    assert_eq!(balance(&app, second, core_addr.as_str()), Uint128::new(45));
+}
+
 #[test]
 fn test_module_prefixes() {
     let mut app = App::default();
@@ -2656,6 +3644,7 @@ fn test_module_prefixes() {
             },
         ],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let gov_addr = app
@@ -2868,6 +3857,7 @@ fn test_created_timestamp_set() {
             label: "governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
     let gov_addr = instantiate_gov(&mut app, gov_id, instantiate);
 
@@ -5,7 +5,8 @@ use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, s
 use cosmwasm_std::Addr;
 use cw_controllers::ClaimsResponse;
 use cw_core_interface::voting::{
-    InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    InfoResponse, IsActiveResponse, MembersAbovePowerResponse, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
 };
 use cw_native_staked_balance_voting::msg::{
     ExecuteMsg, InstantiateMsg, ListStakersResponse, MigrateMsg, Owner, QueryMsg,
@@ -30,6 +31,7 @@ fn main() {
     export_schema(&schema_for!(IsActiveResponse), &out_dir);
     export_schema(&schema_for!(ClaimsResponse), &out_dir);
     export_schema(&schema_for!(ListStakersResponse), &out_dir);
+    export_schema(&schema_for!(MembersAbovePowerResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query resonses to the correct name
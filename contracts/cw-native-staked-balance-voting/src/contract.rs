@@ -1,12 +1,15 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    coins, to_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdResult, Uint128,
 };
 use cw2::set_contract_version;
 use cw_controllers::ClaimsResponse;
-use cw_core_interface::voting::{TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_core_interface::voting::{
+    MemberPower, MembersAbovePowerResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cw_storage_plus::Bound;
 use cw_utils::{must_pay, Duration};
 
 use crate::error::ContractError;
@@ -66,6 +69,7 @@ pub fn instantiate(
         manager,
         denom: msg.denom,
         unstaking_duration: msg.unstaking_duration,
+        min_stake: msg.min_stake,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -103,7 +107,8 @@ pub fn execute(
             owner,
             manager,
             duration,
-        } => execute_update_config(deps, info, owner, manager, duration),
+            min_stake,
+        } => execute_update_config(deps, info, owner, manager, duration, min_stake),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
     }
 }
@@ -115,6 +120,11 @@ pub fn execute_stake(
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let amount = must_pay(&info, &config.denom)?;
+    if let Some(min) = config.min_stake {
+        if amount < min {
+            return Err(ContractError::StakeBelowMinimum { min });
+        }
+    }
 
     STAKED_BALANCES.update(
         deps.storage,
@@ -147,10 +157,16 @@ pub fn execute_unstake(
         &info.sender,
         env.block.height,
         |balance| -> Result<Uint128, ContractError> {
-            balance
+            let remaining = balance
                 .unwrap_or_default()
                 .checked_sub(amount)
-                .map_err(|_e| ContractError::InvalidUnstakeAmount {})
+                .map_err(|_e| ContractError::InvalidUnstakeAmount {})?;
+            match config.min_stake {
+                Some(min) if !remaining.is_zero() && remaining < min => {
+                    Err(ContractError::RemainingStakeBelowMinimum { min })
+                }
+                _ => Ok(remaining),
+            }
         },
     )?;
     STAKED_TOTAL.update(
@@ -204,6 +220,7 @@ pub fn execute_update_config(
     new_owner: Option<String>,
     new_manager: Option<String>,
     duration: Option<Duration>,
+    min_stake: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if Some(info.sender.clone()) != config.owner && Some(info.sender.clone()) != config.manager {
@@ -227,6 +244,7 @@ pub fn execute_update_config(
     config.manager = new_manager;
 
     config.unstaking_duration = duration;
+    config.min_stake = min_stake;
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new()
@@ -286,6 +304,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListStakers { start_after, limit } => {
             query_list_stakers(deps, start_after, limit)
         }
+        QueryMsg::MembersAbovePower {
+            min_power,
+            start_after,
+            limit,
+        } => query_members_above_power(deps, min_power, start_after, limit),
     }
 }
 
@@ -357,6 +380,45 @@ pub fn query_list_stakers(
     to_binary(&ListStakersResponse { stakers })
 }
 
+pub fn query_members_above_power(
+    deps: Deps,
+    min_power: Uint128,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let limit = limit.map(|limit| limit as usize).unwrap_or(usize::MAX);
+
+    // The limit bounds the addresses examined rather than those
+    // returned, so that addresses below the minimum can not make the
+    // query cost unbounded gas.
+    let examined = STAKED_BALANCES
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+    let last_examined = examined.last().map(|(address, _)| address.to_string());
+    let members = examined
+        .into_iter()
+        .filter(|(_, power)| !power.is_zero() && *power >= min_power)
+        .map(|(address, power)| MemberPower {
+            address: address.into_string(),
+            power,
+        })
+        .collect();
+
+    to_binary(&MembersAbovePowerResponse {
+        members,
+        last_examined,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
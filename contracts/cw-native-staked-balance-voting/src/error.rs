@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -27,4 +27,10 @@ pub enum ContractError {
 
     #[error("Can only unstake less than or equal to the amount you have staked")]
     InvalidUnstakeAmount {},
+
+    #[error("Must stake at least the minimum stake amount ({min})")]
+    StakeBelowMinimum { min: Uint128 },
+
+    #[error("Unstaking would leave less than the minimum stake amount ({min}) staked. Unstake everything or leave at least the minimum.")]
+    RemainingStakeBelowMinimum { min: Uint128 },
 }
@@ -26,6 +26,9 @@ pub struct InstantiateMsg {
     pub denom: String,
     // How long until the tokens become liquid again
     pub unstaking_duration: Option<Duration>,
+    // The smallest amount that may be staked at once. Partial
+    // unstakes may not leave less than this amount staked.
+    pub min_stake: Option<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
@@ -39,6 +42,7 @@ pub enum ExecuteMsg {
         owner: Option<String>,
         manager: Option<String>,
         duration: Option<Duration>,
+        min_stake: Option<Uint128>,
     },
     Claim {},
 }
@@ -56,6 +60,17 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Lists the addresses with at least `min_power` staked, ordered
+    /// by address. Addresses with nothing staked are never
+    /// listed. At most `limit` addresses are examined, so a page may
+    /// hold fewer members than `limit` while more remain. Continue
+    /// from the response's `last_examined`. Returns
+    /// `cw_core_interface::voting::MembersAbovePowerResponse`.
+    MembersAbovePower {
+        min_power: Uint128,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -11,6 +11,11 @@ pub struct Config {
     pub manager: Option<Addr>,
     pub denom: String,
     pub unstaking_duration: Option<Duration>,
+    /// The smallest amount that may be staked at once. If set, an
+    /// unstake must either remove an address's entire stake or leave
+    /// at least this much staked.
+    #[serde(default)]
+    pub min_stake: Option<Uint128>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
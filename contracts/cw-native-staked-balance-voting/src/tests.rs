@@ -8,7 +8,8 @@ use cosmwasm_std::testing::{mock_dependencies, mock_env};
 use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
 use cw_controllers::ClaimsResponse;
 use cw_core_interface::voting::{
-    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    InfoResponse, MemberPower, MembersAbovePowerResponse, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
 };
 use cw_multi_test::{
     custom_app, next_block, App, AppResponse, Contract, ContractWrapper, Executor,
@@ -142,6 +143,7 @@ fn update_config(
     owner: Option<String>,
     manager: Option<String>,
     duration: Option<Duration>,
+    min_stake: Option<u128>,
 ) -> anyhow::Result<AppResponse> {
     app.execute_contract(
         Addr::unchecked(sender),
@@ -150,6 +152,7 @@ fn update_config(
             owner,
             manager,
             duration,
+            min_stake: min_stake.map(Uint128::new),
         },
         &[],
     )
@@ -208,6 +211,7 @@ fn test_instantiate() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -220,6 +224,7 @@ fn test_instantiate() {
             manager: None,
             denom: DENOM.to_string(),
             unstaking_duration: None,
+            min_stake: None,
         },
     );
 }
@@ -237,6 +242,7 @@ fn test_instantiate_dao_owner() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -259,6 +265,7 @@ fn test_instantiate_invalid_unstaking_duration() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(0)),
+            min_stake: None,
         },
     );
 
@@ -271,6 +278,7 @@ fn test_instantiate_invalid_unstaking_duration() {
             manager: None,
             denom: DENOM.to_string(),
             unstaking_duration: None,
+            min_stake: None,
         },
     );
 }
@@ -288,6 +296,7 @@ fn test_stake_invalid_denom() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -307,6 +316,7 @@ fn test_stake_valid_denom() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -315,6 +325,67 @@ fn test_stake_valid_denom() {
     app.update_block(next_block);
 }
 
+#[test]
+#[should_panic(expected = "Must stake at least the minimum stake amount (50)")]
+fn test_stake_below_minimum() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: Some(Duration::Height(5)),
+            min_stake: Some(Uint128::new(50)),
+        },
+    );
+
+    stake_tokens(&mut app, addr, ADDR1, 49, DENOM).unwrap();
+}
+
+#[test]
+fn test_stake_minimum() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: Some(Duration::Height(5)),
+            min_stake: Some(Uint128::new(50)),
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 50, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR1, 75, DENOM).unwrap();
+    app.update_block(next_block);
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(125));
+
+    // Removing the minimum allows small stakes.
+    update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        Some(DAO_ADDR.to_string()),
+        Some(ADDR1.to_string()),
+        Some(Duration::Height(5)),
+        None,
+    )
+    .unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR1, 1, DENOM).unwrap();
+    app.update_block(next_block);
+
+    let resp = get_voting_power_at_height(&mut app, addr, ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(126));
+}
+
 #[test]
 #[should_panic(expected = "Can only unstake less than or equal to the amount you have staked")]
 fn test_unstake_none_staked() {
@@ -328,6 +399,7 @@ fn test_unstake_none_staked() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -347,6 +419,7 @@ fn test_unstake_invalid_balance() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -370,6 +443,7 @@ fn test_unstake() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -405,6 +479,7 @@ fn test_unstake_no_unstaking_duration() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: None,
+            min_stake: None,
         },
     );
 
@@ -429,6 +504,64 @@ fn test_unstake_no_unstaking_duration() {
     assert_eq!(balance, Uint128::new(10000))
 }
 
+#[test]
+#[should_panic(
+    expected = "Unstaking would leave less than the minimum stake amount (50) staked. Unstake everything or leave at least the minimum."
+)]
+fn test_unstake_below_minimum_remaining() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: Some(Duration::Height(5)),
+            min_stake: Some(Uint128::new(50)),
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    app.update_block(next_block);
+
+    unstake_tokens(&mut app, addr, ADDR1, 51).unwrap();
+}
+
+#[test]
+fn test_unstake_with_minimum() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: Some(Duration::Height(5)),
+            min_stake: Some(Uint128::new(50)),
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    app.update_block(next_block);
+
+    // Leaving exactly the minimum staked is allowed.
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 50).unwrap();
+    app.update_block(next_block);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::new(50));
+
+    // As is unstaking everything, even though less than the minimum
+    // is being unstaked.
+    unstake_tokens(&mut app, addr.clone(), ADDR1, 50).unwrap();
+    app.update_block(next_block);
+    let resp = get_voting_power_at_height(&mut app, addr, ADDR1.to_string(), None);
+    assert_eq!(resp.power, Uint128::zero());
+}
+
 #[test]
 #[should_panic(expected = "Nothing to claim")]
 fn test_claim_no_claims() {
@@ -442,6 +575,7 @@ fn test_claim_no_claims() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -461,6 +595,7 @@ fn test_claim_claim_not_reached() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -488,6 +623,7 @@ fn test_claim() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -539,6 +675,7 @@ fn test_update_config_invalid_sender() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -550,6 +687,7 @@ fn test_update_config_invalid_sender() {
         Some(ADDR1.to_string()),
         Some(DAO_ADDR.to_string()),
         Some(Duration::Height(10)),
+        None,
     )
     .unwrap();
 }
@@ -567,11 +705,21 @@ fn test_update_config_non_owner_changes_owner() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
     // ADDR1 is the manager so cannot change the owner
-    update_config(&mut app, addr, ADDR1, Some(ADDR2.to_string()), None, None).unwrap();
+    update_config(
+        &mut app,
+        addr,
+        ADDR1,
+        Some(ADDR2.to_string()),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
 }
 
 #[test]
@@ -586,6 +734,7 @@ fn test_update_config_as_owner() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -597,6 +746,7 @@ fn test_update_config_as_owner() {
         Some(ADDR1.to_string()),
         Some(DAO_ADDR.to_string()),
         Some(Duration::Height(10)),
+        None,
     )
     .unwrap();
 
@@ -607,6 +757,7 @@ fn test_update_config_as_owner() {
             manager: Some(Addr::unchecked(DAO_ADDR)),
             unstaking_duration: Some(Duration::Height(10)),
             denom: DENOM.to_string(),
+            min_stake: None,
         },
         config
     );
@@ -624,6 +775,7 @@ fn test_update_config_as_manager() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -635,6 +787,7 @@ fn test_update_config_as_manager() {
         Some(DAO_ADDR.to_string()),
         Some(ADDR2.to_string()),
         Some(Duration::Height(10)),
+        None,
     )
     .unwrap();
 
@@ -645,6 +798,7 @@ fn test_update_config_as_manager() {
             manager: Some(Addr::unchecked(ADDR2)),
             unstaking_duration: Some(Duration::Height(10)),
             denom: DENOM.to_string(),
+            min_stake: None,
         },
         config
     );
@@ -663,6 +817,7 @@ fn test_update_config_invalid_duration() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -674,6 +829,7 @@ fn test_update_config_invalid_duration() {
         Some(DAO_ADDR.to_string()),
         Some(ADDR2.to_string()),
         Some(Duration::Height(0)),
+        None,
     )
     .unwrap();
 }
@@ -690,6 +846,7 @@ fn test_query_dao() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -710,6 +867,7 @@ fn test_query_info() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -733,6 +891,7 @@ fn test_query_claims() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -769,6 +928,7 @@ fn test_query_get_config() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -780,6 +940,7 @@ fn test_query_get_config() {
             manager: Some(Addr::unchecked(ADDR1)),
             unstaking_duration: Some(Duration::Height(5)),
             denom: DENOM.to_string(),
+            min_stake: None,
         }
     )
 }
@@ -796,6 +957,7 @@ fn test_voting_power_queries() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -902,6 +1064,7 @@ fn test_query_list_stakers() {
             manager: Some(ADDR1.to_string()),
             denom: DENOM.to_string(),
             unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
         },
     );
 
@@ -974,6 +1137,79 @@ fn test_query_list_stakers() {
     assert_eq!(stakers, ListStakersResponse { stakers: vec![] });
 }
 
+#[test]
+fn test_query_members_above_power() {
+    let mut app = mock_app();
+    let staking_id = app.store_code(staking_contract());
+    let addr = instantiate_staking(
+        &mut app,
+        staking_id,
+        InstantiateMsg {
+            owner: Some(Owner::Addr(DAO_ADDR.to_string())),
+            manager: Some(ADDR1.to_string()),
+            denom: DENOM.to_string(),
+            unstaking_duration: Some(Duration::Height(5)),
+            min_stake: None,
+        },
+    );
+
+    stake_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    stake_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+    // The DAO unstakes everything it staked.
+    stake_tokens(&mut app, addr.clone(), DAO_ADDR, 10, DENOM).unwrap();
+    unstake_tokens(&mut app, addr.clone(), DAO_ADDR, 10).unwrap();
+
+    let members_above_power = |min_power: u128, start_after: Option<&str>, limit: Option<u32>| {
+        let res: MembersAbovePowerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::MembersAbovePower {
+                    min_power: Uint128::new(min_power),
+                    start_after: start_after.map(|addr| addr.to_string()),
+                    limit,
+                },
+            )
+            .unwrap();
+        (res.members, res.last_examined)
+    };
+    let member = |address: &str, power: u128| MemberPower {
+        address: address.to_string(),
+        power: Uint128::new(power),
+    };
+    let last = |address: &str| Some(address.to_string());
+
+    assert_eq!(
+        members_above_power(50, None, None),
+        (vec![member(ADDR1, 100), member(ADDR2, 50)], last(DAO_ADDR))
+    );
+    assert_eq!(
+        members_above_power(60, None, None),
+        (vec![member(ADDR1, 100)], last(DAO_ADDR))
+    );
+
+    // Addresses with nothing staked are not listed, though they are
+    // still examined.
+    assert_eq!(
+        members_above_power(0, None, None),
+        (vec![member(ADDR1, 100), member(ADDR2, 50)], last(DAO_ADDR))
+    );
+    assert_eq!(
+        members_above_power(0, Some(ADDR2), None),
+        (vec![], last(DAO_ADDR))
+    );
+
+    assert_eq!(
+        members_above_power(0, None, Some(1)),
+        (vec![member(ADDR1, 100)], last(ADDR1))
+    );
+    assert_eq!(
+        members_above_power(60, Some(ADDR1), Some(1)),
+        (vec![], last(ADDR2))
+    );
+    assert_eq!(members_above_power(0, Some(DAO_ADDR), None), (vec![], None));
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
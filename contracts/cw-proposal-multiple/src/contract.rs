@@ -1,9 +1,10 @@
 use cosmwasm_std::{
-    entry_point, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
-    StdResult, Storage, SubMsg, WasmMsg,
+    entry_point, to_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Reply, Response, StdResult, Storage, SubMsg, Timestamp, WasmMsg,
 };
 
 use cw2::set_contract_version;
+use cw_core::query::GovernanceFreezeResponse;
 use cw_core_interface::voting::IsActiveResponse;
 use cw_storage_plus::Bound;
 use cw_utils::Duration;
@@ -14,7 +15,7 @@ use vote_hooks::new_vote_hooks;
 use voting::{
     deposit::{get_deposit_msg, get_return_deposit_msg, DepositInfo},
     proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
-    reply::{mask_proposal_execution_proposal_id, TaggedReplyId},
+    reply::{error::TagError, mask_proposal_execution_proposal_id, TaggedReplyId},
     status::Status,
     voting::{
         get_total_power, get_voting_power, validate_voting_period, MultipleChoiceVote,
@@ -27,7 +28,7 @@ use crate::{
     proposal::{MultipleChoiceProposal, VoteResult},
     query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
     state::{Config, MultipleChoiceOptions, CONFIG, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
-    voting_strategy::VotingStrategy,
+    voting_strategy::{TieBreak, VotingStrategy},
     ContractError,
 };
 
@@ -65,6 +66,7 @@ pub fn instantiate(
         dao,
         deposit_info,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        tie_break: msg.tie_break,
     };
 
     // Initialize proposal count to zero.
@@ -101,6 +103,7 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            tie_break,
         } => execute_update_config(
             deps,
             info,
@@ -112,6 +115,7 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            tie_break,
         ),
         ExecuteMsg::AddProposalHook { address } => {
             execute_add_proposal_hook(deps, env, info, address)
@@ -162,6 +166,13 @@ pub fn execute_propose(
 
     // Validate options.
     let checked_multiple_choice_options = options.into_checked()?.options;
+    if let VotingStrategy::TopK { k, .. } = config.voting_strategy {
+        // Excluding the "None of the above" option, which can not be
+        // elected.
+        if checked_multiple_choice_options.len() - 1 <= k as usize {
+            return Err(ContractError::TooFewChoicesForTopK { k });
+        }
+    }
 
     let expiration = config.max_voting_period.after(&env.block);
     let total_power = get_total_power(deps.as_ref(), config.dao, None)?;
@@ -184,6 +195,7 @@ pub fn execute_propose(
             choices: checked_multiple_choice_options,
             created: env.block.time,
             last_updated: env.block.time,
+            tie_break: config.tie_break,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -350,6 +362,9 @@ pub fn execute_execute(
     if prop.status != Status::Passed {
         return Err(ContractError::NotPassed {});
     }
+    if let Some(until) = get_governance_freeze(deps.as_ref(), &config.dao) {
+        return Err(ContractError::GovernanceFrozen { until });
+    }
 
     prop.status = Status::Executed;
     // Update proposal's last updated timestamp.
@@ -362,55 +377,63 @@ pub fn execute_execute(
         None => vec![],
     };
 
-    let vote_result = prop.calculate_vote_result()?;
-    match vote_result {
-        VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
-        VoteResult::SingleWinner(winning_choice) => {
-            let response = match winning_choice.msgs {
-                Some(msgs) => {
-                    if !msgs.is_empty() {
-                        let execute_message = WasmMsg::Execute {
-                            contract_addr: config.dao.to_string(),
-                            msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
-                                msgs,
-                            })?,
-                            funds: vec![],
-                        };
-                        match config.close_proposal_on_execution_failure {
-                            true => {
-                                let masked_proposal_id =
-                                    mask_proposal_execution_proposal_id(proposal_id);
-                                Response::default().add_submessage(SubMsg::reply_on_error(
-                                    execute_message,
-                                    masked_proposal_id,
-                                ))
-                            }
-                            false => Response::default().add_message(execute_message),
-                        }
-                    } else {
-                        Response::default()
-                    }
-                }
-                None => Response::default(),
-            };
-
-            let hooks = proposal_status_changed_hooks(
-                PROPOSAL_HOOKS,
-                deps.storage,
-                proposal_id,
-                old_status.to_string(),
-                prop.status.to_string(),
-            )?;
-
-            Ok(response
-                .add_messages(refund_message)
-                .add_submessages(hooks)
-                .add_attribute("action", "execute")
-                .add_attribute("sender", info.sender)
-                .add_attribute("proposal_id", proposal_id.to_string())
-                .add_attribute("dao", config.dao))
+    let vote_result = prop.resolve_vote_result(&env.block)?;
+    let msgs: Vec<CosmosMsg> = match vote_result {
+        VoteResult::Tie => return Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
+        VoteResult::SingleWinner(winning_choice) => winning_choice.msgs.unwrap_or_default(),
+        // Every elected option's messages are executed together so
+        // that either all or none of them are.
+        VoteResult::MultipleWinners(winning_choices) => winning_choices
+            .into_iter()
+            .flat_map(|choice| choice.msgs.unwrap_or_default())
+            .collect(),
+    };
+    let response = if !msgs.is_empty() {
+        let execute_message = WasmMsg::Execute {
+            contract_addr: config.dao.to_string(),
+            msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook { msgs })?,
+            funds: vec![],
+        };
+        match config.close_proposal_on_execution_failure {
+            true => {
+                let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+                Response::default()
+                    .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+            }
+            false => Response::default().add_message(execute_message),
         }
-    }
+    } else {
+        Response::default()
+    };
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(response
+        .add_messages(refund_message)
+        .add_submessages(hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", config.dao))
+}
+
+/// Gets the time the DAO's governance freeze lifts if it is frozen.
+fn get_governance_freeze(deps: Deps, dao: &Addr) -> Option<Timestamp> {
+    // Core contracts that predate governance freezes do not support
+    // the query, and are never frozen.
+    deps.querier
+        .query_wasm_smart::<GovernanceFreezeResponse>(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::GovernanceFreeze {},
+        )
+        .ok()
+        .and_then(|response| response.until)
 }
 
 pub fn execute_close(
@@ -475,6 +498,7 @@ pub fn execute_update_config(
     dao: String,
     deposit_info: Option<DepositInfo>,
     close_proposal_on_execution_failure: bool,
+    tie_break: Option<TieBreak>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -504,6 +528,7 @@ pub fn execute_update_config(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            tie_break,
         },
     )?;
 
@@ -514,7 +539,7 @@ pub fn execute_update_config(
 
 pub fn execute_add_proposal_hook(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     address: String,
 ) -> Result<Response, ContractError> {
@@ -526,7 +551,7 @@ pub fn execute_add_proposal_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    add_hook(PROPOSAL_HOOKS, deps.storage, &env.block, validated_address)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
@@ -556,7 +581,7 @@ pub fn execute_remove_proposal_hook(
 
 pub fn execute_add_vote_hook(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     address: String,
 ) -> Result<Response, ContractError> {
@@ -568,7 +593,7 @@ pub fn execute_add_vote_hook(
 
     let validated_address = deps.api.addr_validate(&address)?;
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(VOTE_HOOKS, deps.storage, &env.block, validated_address)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
@@ -599,10 +624,11 @@ pub fn execute_remove_vote_hook(
 pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
+    block: &BlockInfo,
     validated_address: Addr,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook(storage, block, validated_address)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -771,6 +797,11 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
             let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
             Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
         }
+        // This module does not send execution callbacks or catch
+        // failed deposit refunds.
+        TaggedReplyId::FailedExecutionCallback(_) | TaggedReplyId::FailedDepositRefund(_) => {
+            Err(TagError::UnknownReplyId { id: msg.id }.into())
+        }
     }
 }
 
@@ -778,5 +809,9 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // Hooks registered before registration heights were recorded are
+    // reported as registered at height zero.
+    PROPOSAL_HOOKS.backfill_registrations(deps.storage)?;
+    VOTE_HOOKS.backfill_registrations(deps.storage)?;
     Ok(Response::default())
 }
@@ -1,6 +1,6 @@
 use std::u64;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp};
 use indexable_hooks::HookError;
 use thiserror::Error;
 use voting::{reply::error::TagError, threshold::ThresholdError};
@@ -49,6 +49,9 @@ pub enum ContractError {
     #[error("Proposal must be in 'passed' state to be executed.")]
     NotPassed {},
 
+    #[error("Proposals may not be executed until the DAO's governance freeze lifts at ({until})")]
+    GovernanceFrozen { until: Timestamp },
+
     #[error("Proposal is in a tie: two or more options have the same number of votes.")]
     Tie {},
 
@@ -76,6 +79,12 @@ pub enum ContractError {
     #[error("Must have voting power to propose.")]
     MustHaveVotingPower {},
 
+    #[error("k must be between 1 and ({max}).")]
+    InvalidTopK { max: u32 },
+
+    #[error("Proposals electing the top ({k}) options must have more than ({k}) choices.")]
+    TooFewChoicesForTopK { k: u32 },
+
     #[error("{0}")]
     Tag(#[from] TagError),
 }
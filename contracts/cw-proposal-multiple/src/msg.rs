@@ -3,7 +3,10 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use voting::{deposit::DepositInfo, voting::MultipleChoiceVote};
 
-use crate::{state::MultipleChoiceOptions, voting_strategy::VotingStrategy};
+use crate::{
+    state::MultipleChoiceOptions,
+    voting_strategy::{TieBreak, VotingStrategy},
+};
 use cw_core_macros::govmod_query;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -39,6 +42,10 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// How a tie between the most voted options of a proposal is
+    /// resolved. None if a tie rejects the proposal. This will only
+    /// apply to proposals created after this is set.
+    pub tie_break: Option<TieBreak>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -112,6 +119,10 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// How a tie between the most voted options of a proposal is
+        /// resolved. None if a tie rejects the proposal. This will only
+        /// apply to proposals created after this is set.
+        tie_break: Option<TieBreak>,
     },
     AddProposalHook {
         address: String,
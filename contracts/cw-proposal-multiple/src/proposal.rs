@@ -12,7 +12,7 @@ use voting::{
 use crate::{
     query::ProposalResponse,
     state::{CheckedMultipleChoiceOption, MultipleChoiceOptionType},
-    voting_strategy::VotingStrategy,
+    voting_strategy::{TieBreak, VotingStrategy},
 };
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -42,10 +42,17 @@ pub struct MultipleChoiceProposal {
     /// a proposal expires and passes upon expiration, this field will not be updated because it can only update
     /// upon actions on the contract.
     pub last_updated: Timestamp,
+    /// How a tie between the most voted options is resolved. None if
+    /// a tie rejects the proposal.
+    #[serde(default)]
+    pub tie_break: Option<TieBreak>,
 }
 
 pub enum VoteResult {
     SingleWinner(CheckedMultipleChoiceOption),
+    /// The options elected by a `TopK` proposal in the order they
+    /// were proposed. Empty if no option was elected.
+    MultipleWinners(Vec<CheckedMultipleChoiceOption>),
     Tie,
 }
 
@@ -120,6 +127,9 @@ impl MultipleChoiceProposal {
                 return Ok(false);
             }
         }
+        if let VotingStrategy::TopK { .. } = self.voting_strategy {
+            return self.is_top_k_passed(block);
+        }
 
         // Proposal can only pass if quorum has been met.
         if does_vote_count_pass(
@@ -127,10 +137,11 @@ impl MultipleChoiceProposal {
             self.total_power,
             self.voting_strategy.get_quorum(),
         ) {
-            let vote_result = self.calculate_vote_result()?;
+            let vote_result = self.resolve_vote_result(block)?;
             match vote_result {
-                // Proposal is not passed if there is a tie.
-                VoteResult::Tie => return Ok(false),
+                // Proposal is not passed if there is a tie. Only top-k
+                // proposals, handled above, have multiple winners.
+                VoteResult::Tie | VoteResult::MultipleWinners(_) => return Ok(false),
                 VoteResult::SingleWinner(winning_choice) => {
                     // Proposal is not passed if winning choice is None.
                     if winning_choice.option_type != MultipleChoiceOptionType::None {
@@ -155,16 +166,24 @@ impl MultipleChoiceProposal {
         if self.allow_revoting && !self.expiration.is_expired(block) {
             return Ok(false);
         }
+        if let VotingStrategy::TopK { .. } = self.voting_strategy {
+            return self.is_top_k_rejected(block);
+        }
 
-        let vote_result = self.calculate_vote_result()?;
+        let vote_result = self.resolve_vote_result(block)?;
         match vote_result {
             // Proposal is rejected if there is a tie, and either the proposal is expired or
-            // there is no voting power left.
+            // there is no voting power left. Ties that will be broken
+            // are resolved once the proposal expires.
             VoteResult::Tie => {
-                let rejected =
-                    self.expiration.is_expired(block) || self.total_power == self.votes.total();
+                let rejected = self.tie_break.is_none()
+                    && (self.expiration.is_expired(block)
+                        || self.total_power == self.votes.total());
                 Ok(rejected)
             }
+            // Only top-k proposals, handled above, have multiple
+            // winners.
+            VoteResult::MultipleWinners(_) => Ok(false),
             VoteResult::SingleWinner(winning_choice) => {
                 match (
                     does_vote_count_pass(
@@ -230,9 +249,172 @@ impl MultipleChoiceProposal {
                 }
                 Err(StdError::not_found("max vote weight"))
             }
+            VotingStrategy::TopK { k, .. } => {
+                let (elected, _, open_seats) = self.rank_top_k(k);
+                if open_seats > 0 {
+                    return Ok(VoteResult::Tie);
+                }
+                Ok(VoteResult::MultipleWinners(self.choices_in_order(elected)))
+            }
+        }
+    }
+
+    /// Finds the winning option like `calculate_vote_result`, breaking
+    /// a tie if the proposal has a tie break and has expired.
+    pub fn resolve_vote_result(&self, block: &BlockInfo) -> StdResult<VoteResult> {
+        let vote_result = self.calculate_vote_result()?;
+        match (vote_result, self.tie_break) {
+            (VoteResult::Tie, Some(TieBreak::BlockEntropy {}))
+                if self.expiration.is_expired(block) =>
+            {
+                match self.voting_strategy {
+                    VotingStrategy::SingleChoice { .. } => {
+                        let max_weight = self.votes.vote_weights.iter().max();
+                        let tied: Vec<usize> = self
+                            .votes
+                            .vote_weights
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, weight)| Some(*weight) == max_weight)
+                            .map(|(idx, _)| idx)
+                            .collect();
+                        let winner = tied[(self.tie_break_seed() % tied.len() as u64) as usize];
+                        Ok(VoteResult::SingleWinner(self.choices[winner].clone()))
+                    }
+                    VotingStrategy::TopK { k, .. } => {
+                        // Fill the open seats one at a time from the
+                        // tied options.
+                        let (mut elected, mut tied, open_seats) = self.rank_top_k(k);
+                        let mut seed = self.tie_break_seed();
+                        for _ in 0..open_seats {
+                            elected.push(tied.remove((seed % tied.len() as u64) as usize));
+                            seed = splitmix64(seed);
+                        }
+                        Ok(VoteResult::MultipleWinners(self.choices_in_order(elected)))
+                    }
+                }
+            }
+            (vote_result, _) => Ok(vote_result),
         }
     }
 
+    /// Ranks the options of a `TopK` proposal that received more
+    /// votes than "None of the above" and fills `k` seats with
+    /// them. Returns the indexes of the options elected, the options
+    /// tied for the last seat, and the number of seats left for the
+    /// tied options to fill. The number of seats left is zero unless
+    /// there is a tie at the boundary, in which case it is less than
+    /// the number of tied options.
+    fn rank_top_k(&self, k: u32) -> (Vec<usize>, Vec<usize>, usize) {
+        let weights = &self.votes.vote_weights;
+        let none_weight = self
+            .choices
+            .iter()
+            .filter(|choice| choice.option_type == MultipleChoiceOptionType::None)
+            .map(|choice| weights[choice.index as usize])
+            .max()
+            .unwrap_or_default();
+        let mut ranked: Vec<usize> = self
+            .choices
+            .iter()
+            .filter(|choice| choice.option_type == MultipleChoiceOptionType::Standard)
+            .map(|choice| choice.index as usize)
+            .filter(|idx| weights[*idx] > none_weight)
+            .collect();
+        ranked.sort_by(|a, b| weights[*b].cmp(&weights[*a]));
+
+        let k = k as usize;
+        if ranked.len() <= k || weights[ranked[k - 1]] != weights[ranked[k]] {
+            ranked.truncate(k);
+            return (ranked, vec![], 0);
+        }
+        let cutoff = weights[ranked[k - 1]];
+        let (elected, tied): (Vec<usize>, Vec<usize>) = ranked
+            .into_iter()
+            .filter(|idx| weights[*idx] >= cutoff)
+            .partition(|idx| weights[*idx] > cutoff);
+        let open_seats = k - elected.len();
+        (elected, tied, open_seats)
+    }
+
+    /// Gets the choices with the given indexes in the order they were
+    /// proposed.
+    fn choices_in_order(&self, mut indexes: Vec<usize>) -> Vec<CheckedMultipleChoiceOption> {
+        indexes.sort_unstable();
+        indexes
+            .into_iter()
+            .map(|idx| self.choices[idx].clone())
+            .collect()
+    }
+
+    /// Returns true if the votes on a `TopK` proposal can no longer
+    /// change.
+    fn is_top_k_final(&self, block: &BlockInfo) -> bool {
+        self.expiration.is_expired(block)
+            || (!self.allow_revoting && self.total_power == self.votes.total())
+    }
+
+    /// A `TopK` proposal passes once its votes are final if quorum is
+    /// met and at least one option is elected.
+    fn is_top_k_passed(&self, block: &BlockInfo) -> StdResult<bool> {
+        if !self.is_top_k_final(block)
+            || !does_vote_count_pass(
+                self.votes.total(),
+                self.total_power,
+                self.voting_strategy.get_quorum(),
+            )
+        {
+            return Ok(false);
+        }
+        Ok(matches!(
+            self.resolve_vote_result(block)?,
+            VoteResult::MultipleWinners(winners) if !winners.is_empty()
+        ))
+    }
+
+    /// A `TopK` proposal is rejected once its votes are final if
+    /// quorum is not met, no option is elected, or it is tied at the
+    /// boundary and has no tie break.
+    fn is_top_k_rejected(&self, block: &BlockInfo) -> StdResult<bool> {
+        if !self.is_top_k_final(block) {
+            return Ok(false);
+        }
+        if !does_vote_count_pass(
+            self.votes.total(),
+            self.total_power,
+            self.voting_strategy.get_quorum(),
+        ) {
+            return Ok(true);
+        }
+        Ok(match self.resolve_vote_result(block)? {
+            VoteResult::MultipleWinners(winners) => winners.is_empty(),
+            // Ties that will be broken are resolved once the proposal
+            // expires.
+            VoteResult::Tie => self.tie_break.is_none(),
+            VoteResult::SingleWinner(_) => false,
+        })
+    }
+
+    /// Derives a pseudorandom number from the block this proposal
+    /// resolves at, the block it was created at, and its votes. See
+    /// `TieBreak::BlockEntropy` for why this is not secure.
+    fn tie_break_seed(&self) -> u64 {
+        let resolution = match self.expiration {
+            Expiration::AtHeight(height) => height,
+            Expiration::AtTime(time) => time.nanos(),
+            Expiration::Never {} => 0,
+        };
+        let mut seed = splitmix64(resolution);
+        seed = splitmix64(seed ^ self.start_height);
+        seed = splitmix64(seed ^ self.created.nanos());
+        for weight in self.votes.vote_weights.iter() {
+            let weight = weight.u128();
+            seed = splitmix64(seed ^ (weight as u64));
+            seed = splitmix64(seed ^ ((weight >> 64) as u64));
+        }
+        seed
+    }
+
     /// Ensure that with the remaining vote power, the choice with the second highest votes
     /// cannot overtake the first choice.
     fn is_choice_unbeatable(
@@ -271,6 +453,15 @@ impl MultipleChoiceProposal {
     }
 }
 
+/// The SplitMix64 finalizer. Mixes the bits of `x` so that similar
+/// inputs produce very different outputs.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::state::{MultipleChoiceOption, MultipleChoiceOptions};
@@ -323,6 +514,7 @@ mod tests {
             min_voting_period: None,
             created: block.time,
             last_updated: block.time,
+            tie_break: None,
         }
     }
 
@@ -862,4 +1054,131 @@ mod tests {
         // No quorum reached & proposal has expired => rejection
         assert!(prop.is_rejected(&env.block).unwrap());
     }
+
+    #[test]
+    fn test_block_entropy_tie_break() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::SingleChoice {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+        };
+        let votes = MultipleChoiceVotes {
+            vote_weights: vec![Uint128::new(5), Uint128::new(5), Uint128::new(0)],
+        };
+
+        // All voting power has voted and the proposal is tied. It is
+        // not rejected before expiring as the tie will be broken.
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy.clone(),
+            votes.clone(),
+            Uint128::new(10),
+            false,
+            false,
+        );
+        assert!(prop.is_rejected(&env.block).unwrap());
+        prop.tie_break = Some(TieBreak::BlockEntropy {});
+        assert!(!prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            votes,
+            Uint128::new(10),
+            true,
+            false,
+        );
+        assert!(prop.is_rejected(&env.block).unwrap());
+        prop.tie_break = Some(TieBreak::BlockEntropy {});
+        assert!(prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+
+        let winner = |prop: &MultipleChoiceProposal, block: &BlockInfo| match prop
+            .resolve_vote_result(block)
+            .unwrap()
+        {
+            VoteResult::SingleWinner(choice) => choice.index,
+            VoteResult::Tie => panic!("tie was not broken"),
+            VoteResult::MultipleWinners(_) => panic!("single choice proposal elected options"),
+        };
+
+        // The winner depends only on the proposal, so it is the same
+        // at every block after expiration.
+        assert_eq!(winner(&prop, &env.block), 1);
+        let mut later = env.block.clone();
+        later.height += 100;
+        later.time = later.time.plus_seconds(600);
+        assert_eq!(winner(&prop, &later), 1);
+
+        // A proposal resolving at a different block may break the tie
+        // differently.
+        prop.expiration = Expiration::AtHeight(env.block.height - 4);
+        assert_eq!(winner(&prop, &env.block), 0);
+    }
+
+    #[test]
+    fn test_top_k_tie_break() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::TopK {
+            quorum: voting::threshold::PercentageThreshold::Majority {},
+            k: 2,
+        };
+        // Options 1, 2, and 3 are tied for the second seat. The last
+        // option is "None of the above".
+        let votes = MultipleChoiceVotes {
+            vote_weights: [6, 3, 3, 3, 0, 1]
+                .iter()
+                .map(|w| Uint128::new(*w))
+                .collect(),
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            votes,
+            Uint128::new(16),
+            true,
+            false,
+        );
+        prop.choices = MultipleChoiceOptions {
+            options: (0..5)
+                .map(|option| MultipleChoiceOption {
+                    description: format!("candidate {}", option),
+                    msgs: None,
+                })
+                .collect(),
+        }
+        .into_checked()
+        .unwrap()
+        .options;
+
+        assert!(matches!(
+            prop.calculate_vote_result().unwrap(),
+            VoteResult::Tie
+        ));
+        assert!(prop.is_rejected(&env.block).unwrap());
+
+        // The tie break fills the open seat with one of the tied
+        // options. The option elected outright is always elected.
+        prop.tie_break = Some(TieBreak::BlockEntropy {});
+        assert!(prop.is_passed(&env.block).unwrap());
+        assert!(!prop.is_rejected(&env.block).unwrap());
+        let elected = |prop: &MultipleChoiceProposal| -> Vec<u32> {
+            match prop.resolve_vote_result(&env.block).unwrap() {
+                VoteResult::MultipleWinners(winners) => {
+                    winners.iter().map(|choice| choice.index).collect()
+                }
+                _ => panic!("expected multiple winners"),
+            }
+        };
+        let winners = elected(&prop);
+        assert_eq!(winners.len(), 2);
+        assert_eq!(winners[0], 0);
+        assert!([1, 2, 3].contains(&winners[1]));
+        assert_eq!(elected(&prop), winners);
+
+        // Without a tie at the boundary the tie break is unused.
+        prop.votes.vote_weights[3] = Uint128::new(2);
+        prop.votes.vote_weights[2] = Uint128::new(4);
+        assert_eq!(elected(&prop), vec![0, 2]);
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{proposal::MultipleChoiceProposal, voting_strategy::VotingStrategy, ContractError};
+use crate::{
+    proposal::MultipleChoiceProposal,
+    voting_strategy::{TieBreak, VotingStrategy},
+    ContractError,
+};
 use cosmwasm_std::{Addr, CosmosMsg, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
@@ -47,6 +51,11 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// How a tie between the most voted options of a proposal is
+    /// resolved. None if a tie rejects the proposal. This will only
+    /// apply to proposals created after this is set.
+    #[serde(default)]
+    pub tie_break: Option<TieBreak>,
 }
 
 /// Information about a vote that was cast.
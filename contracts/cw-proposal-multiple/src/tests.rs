@@ -158,6 +158,7 @@ where
         deposit_info,
         voting_strategy,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     let governance_addr = setup_governance(
@@ -776,6 +777,7 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -814,6 +816,7 @@ fn test_propose() {
         voting_strategy: voting_strategy.clone(),
         min_voting_period: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     assert_eq!(config, expected);
@@ -868,6 +871,7 @@ fn test_propose() {
         min_voting_period: None,
         created: current_block.time,
         last_updated: current_block.time,
+        tie_break: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -892,6 +896,7 @@ fn test_propose_wrong_num_choices() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy: voting_strategy.clone(),
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -930,6 +935,7 @@ fn test_propose_wrong_num_choices() {
         dao: governance_addr,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     assert_eq!(config, expected);
@@ -1087,6 +1093,7 @@ fn test_migrate() {
         only_members_execute: true,
         allow_revoting: false,
         deposit_info: None,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -1146,6 +1153,7 @@ fn test_proposal_count_initialized_to_zero() {
         only_members_execute: true,
         allow_revoting: false,
         deposit_info: None,
+        tie_break: None,
     };
     let core_addr = instantiate_with_staked_balances_governance(
         &mut app,
@@ -1185,6 +1193,7 @@ fn test_no_early_pass_with_min_duration() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1282,6 +1291,7 @@ fn test_propose_with_messages() {
         only_members_execute: true,
         allow_revoting: false,
         deposit_info: None,
+        tie_break: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1320,6 +1330,7 @@ fn test_propose_with_messages() {
         allow_revoting: false,
         dao: "dao".to_string(),
         deposit_info: None,
+        tie_break: None,
     };
 
     let wasm_msg = WasmMsg::Execute {
@@ -1405,6 +1416,7 @@ fn test_min_duration_units_missmatch() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
     instantiate_with_staked_balances_governance(
         &mut app,
@@ -1438,6 +1450,7 @@ fn test_min_duration_larger_than_proposal_duration() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
     instantiate_with_staked_balances_governance(
         &mut app,
@@ -1470,6 +1483,7 @@ fn test_min_duration_same_as_proposal_duration() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1589,6 +1603,7 @@ fn test_voting_module_token_proposal_deposit_instantiate() {
         allow_revoting: false,
         deposit_info,
         voting_strategy,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -1672,6 +1687,7 @@ fn test_different_token_proposal_deposit() {
             refund_failed_proposals: true,
         }),
         voting_strategy,
+        tie_break: None,
     };
 
     instantiate_with_cw20_balances_governance(
@@ -1736,6 +1752,7 @@ fn test_bad_token_proposal_deposit() {
         allow_revoting: false,
         deposit_info,
         voting_strategy,
+        tie_break: None,
     };
 
     instantiate_with_cw20_balances_governance(
@@ -1768,6 +1785,7 @@ fn test_take_proposal_deposit() {
         allow_revoting: false,
         deposit_info,
         voting_strategy,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -2197,6 +2215,7 @@ fn test_cant_propose_zero_power() {
             refund_failed_proposals: true,
         }),
         voting_strategy,
+        tie_break: None,
     };
 
     let core_addr = instantiate_with_cw20_balances_governance(
@@ -2360,6 +2379,7 @@ fn test_cant_execute_not_member() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -2699,6 +2719,7 @@ fn test_execute_expired_proposal() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     let core_addr = instantiate_with_staked_balances_governance(
@@ -2875,6 +2896,7 @@ fn test_update_config() {
             allow_revoting: false,
             dao: dao.to_string(),
             deposit_info: None,
+            tie_break: None,
         },
         &[],
     )
@@ -2895,6 +2917,7 @@ fn test_update_config() {
             allow_revoting: false,
             dao: Addr::unchecked(CREATOR_ADDR).to_string(),
             deposit_info: None,
+            tie_break: None,
         },
         &[],
     )
@@ -2916,6 +2939,7 @@ fn test_update_config() {
         allow_revoting: false,
         dao: Addr::unchecked(CREATOR_ADDR),
         deposit_info: None,
+        tie_break: None,
     };
     assert_eq!(govmod_config, expected);
 
@@ -2935,6 +2959,7 @@ fn test_update_config() {
             allow_revoting: false,
             dao: Addr::unchecked(CREATOR_ADDR).to_string(),
             deposit_info: None,
+            tie_break: None,
         },
         &[],
     )
@@ -3016,6 +3041,7 @@ fn test_query_list_proposals() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy: voting_strategy.clone(),
+        tie_break: None,
     };
     let gov_addr = instantiate_with_cw20_balances_governance(
         &mut app,
@@ -3114,6 +3140,7 @@ fn test_query_list_proposals() {
             min_voting_period: None,
             created: current_block.time,
             last_updated: current_block.time,
+            tie_break: None,
         },
     };
     assert_eq!(proposals_forward.proposals[0], expected);
@@ -3160,6 +3187,7 @@ fn test_query_list_proposals() {
             min_voting_period: None,
             created: current_block.time,
             last_updated: current_block.time,
+            tie_break: None,
         },
     };
     assert_eq!(proposals_forward.proposals[0], expected);
@@ -3185,6 +3213,7 @@ fn test_hooks() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -3343,6 +3372,7 @@ fn test_active_threshold_absolute() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_staking_active_threshold(
@@ -3481,6 +3511,7 @@ fn test_active_threshold_percent() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     // 20% needed to be active, 20% of 100000000 is 20000000
@@ -3620,6 +3651,7 @@ fn test_active_threshold_none() {
         allow_revoting: false,
         deposit_info: None,
         voting_strategy,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_staking_active_threshold(
@@ -3764,6 +3796,7 @@ fn test_revoting() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            tie_break: None,
         })
         .unwrap(),
         Some(vec![
@@ -3911,6 +3944,7 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            tie_break: None,
         })
         .unwrap(),
         Some(vec![
@@ -3977,6 +4011,7 @@ fn test_allow_revoting_config_changes() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            tie_break: None,
         },
         &[],
     )
@@ -4073,6 +4108,7 @@ fn test_revoting_same_vote_twice() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            tie_break: None,
         })
         .unwrap(),
         Some(vec![
@@ -4174,6 +4210,7 @@ fn test_invalid_revote_does_not_invalidate_initial_vote() {
                 quorum: PercentageThreshold::Majority {},
             },
             close_proposal_on_execution_failure: false,
+            tie_break: None,
         })
         .unwrap(),
         Some(vec![
@@ -4379,6 +4416,7 @@ fn test_close_failed_proposal() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_staking_active_threshold(
@@ -4541,6 +4579,7 @@ fn test_close_failed_proposal() {
                                     dao: original.dao.to_string(),
                                     deposit_info: None,
                                     close_proposal_on_execution_failure: false,
+                                    tie_break: None,
                                 })
                                 .unwrap(),
                                 funds: vec![],
@@ -4648,6 +4687,7 @@ fn test_no_double_refund_on_execute_fail_and_close() {
             refund_failed_proposals: true,
         }),
         close_proposal_on_execution_failure: true,
+        tie_break: None,
     };
 
     let core_addr = instantiate_with_staking_active_threshold(
@@ -4871,6 +4911,7 @@ fn test_timestamp_updated() {
         deposit_info: None,
         close_proposal_on_execution_failure: true,
         allow_revoting: false,
+        tie_break: None,
     };
 
     let governance_addr = instantiate_with_cw20_balances_governance(
@@ -5079,3 +5120,179 @@ pub fn test_migrate_update_version() {
     assert_eq!(version.version, CONTRACT_VERSION);
     assert_eq!(version.contract, CONTRACT_NAME);
 }
+
+#[test]
+fn test_top_k_election() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        voting_strategy: VotingStrategy::TopK {
+            quorum: PercentageThreshold::Majority {},
+            k: 2,
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        close_proposal_on_execution_failure: true,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        tie_break: None,
+    };
+    let voters = [("a", 30), ("b", 20), ("c", 20), ("d", 15), ("e", 15)];
+    let core_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        to_binary(&instantiate).unwrap(),
+        Some(
+            voters
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        ),
+    );
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod = governance_modules.into_iter().next().unwrap().address;
+
+    // Each option sets an item on the DAO when executed so that the
+    // options elected can be seen.
+    let propose = |app: &mut App, num_options: u32| {
+        let proposal_count: u64 = app
+            .wrap()
+            .query_wasm_smart(govmod.clone(), &QueryMsg::ProposalCount {})
+            .unwrap();
+        let options = (0..num_options)
+            .map(|option| MultipleChoiceOption {
+                description: format!("candidate {}", option),
+                msgs: Some(vec![WasmMsg::Execute {
+                    contract_addr: core_addr.to_string(),
+                    msg: to_binary(&cw_core::msg::ExecuteMsg::SetItem {
+                        key: format!("{}_{}", proposal_count + 1, option),
+                        addr: format!("candidate {}", option),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()]),
+            })
+            .collect();
+        app.execute_contract(
+            Addr::unchecked("a"),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: "Elect a committee".to_string(),
+                description: "Two seats are open.".to_string(),
+                choices: MultipleChoiceOptions { options },
+            },
+            &[],
+        )
+    };
+    let vote = |app: &mut App, proposal_id: u64, votes: &[(&str, u32)]| {
+        for (voter, option_id) in votes {
+            app.execute_contract(
+                Addr::unchecked(*voter),
+                govmod.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id,
+                    vote: MultipleChoiceVote {
+                        option_id: *option_id,
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+        }
+    };
+    let status = |app: &App, proposal_id: u64| -> Status {
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        proposal.proposal.status
+    };
+    let elected = |app: &App, proposal_id: u64| -> Vec<u32> {
+        (0..5)
+            .filter(|option| {
+                let item: cw_core::query::GetItemResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        core_addr.clone(),
+                        &cw_core::msg::QueryMsg::GetItem {
+                            key: format!("{}_{}", proposal_id, option),
+                        },
+                    )
+                    .unwrap();
+                item.item.is_some()
+            })
+            .collect()
+    };
+
+    // There must be more choices than seats.
+    let err = propose(&mut app, 2).unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::TooFewChoicesForTopK { k: 2 }
+    ));
+
+    // The two most voted of five options are elected. Option 5 is
+    // "None of the above". Results are not known until all voting
+    // power has voted or the proposal expires.
+    propose(&mut app, 5).unwrap();
+    vote(&mut app, 1, &[("a", 2), ("b", 0), ("c", 1)]);
+    assert_eq!(status(&app, 1), Status::Open);
+    vote(&mut app, 1, &[("d", 1), ("e", 5)]);
+    assert_eq!(status(&app, 1), Status::Passed);
+    app.execute_contract(
+        Addr::unchecked("a"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(elected(&app, 1), vec![1, 2]);
+
+    // Options tied within the seats are all elected. Options must
+    // receive more votes than "None of the above" to be elected.
+    propose(&mut app, 5).unwrap();
+    vote(
+        &mut app,
+        2,
+        &[("a", 2), ("b", 0), ("c", 5), ("d", 4), ("e", 4)],
+    );
+    assert_eq!(status(&app, 2), Status::Passed);
+    app.execute_contract(
+        Addr::unchecked("a"),
+        govmod.clone(),
+        &ExecuteMsg::Execute { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(elected(&app, 2), vec![2, 4]);
+
+    // Options 0 and 3 are tied for the last seat. Without a tie break
+    // the proposal is rejected.
+    propose(&mut app, 5).unwrap();
+    vote(
+        &mut app,
+        3,
+        &[("a", 2), ("b", 0), ("c", 3), ("d", 1), ("e", 5)],
+    );
+    assert_eq!(status(&app, 3), Status::Rejected);
+
+    // Quorum must be met.
+    propose(&mut app, 5).unwrap();
+    vote(&mut app, 4, &[("a", 2), ("b", 0)]);
+    app.update_block(|block| block.height += 6);
+    assert_eq!(status(&app, 4), Status::Rejected);
+}
@@ -1,24 +1,84 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use voting::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
+use voting::threshold::{validate_quorum, PercentageThreshold};
+
+use crate::{state::MAX_NUM_CHOICES, ContractError};
 
 /// Determines the way votes are counted.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Elects up to `k` options, for example to fill the seats of a
+    /// committee. Each voter votes for one option as with
+    /// `SingleChoice`.
+    ///
+    /// Results are final once the proposal has expired, or once all
+    /// voting power has voted if revoting is not allowed. If quorum
+    /// is met, the options with more votes than "None of the above"
+    /// are ranked by votes and the `k` highest are elected. Fewer
+    /// than `k` options are elected if fewer than `k` options beat
+    /// "None of the above", and the proposal is rejected if none
+    /// do.
+    ///
+    /// Options tied for the last seat are a tie at the boundary: if
+    /// electing all of them would elect more than `k` options, the
+    /// remaining seats are filled using the proposal's tie break, or
+    /// the proposal is rejected if it has none. Ties that fit within
+    /// the `k` seats elect every tied option.
+    ///
+    /// Executing the proposal executes the messages of every elected
+    /// option, in the order the options were proposed.
+    TopK {
+        quorum: PercentageThreshold,
+        /// The maximum number of options to elect. Must be non-zero
+        /// and less than the maximum number of choices a proposal may
+        /// have. Proposals must have more than `k` choices.
+        k: u32,
+    },
 }
 
 impl VotingStrategy {
-    pub fn validate(&self) -> Result<(), ThresholdError> {
+    pub fn validate(&self) -> Result<(), ContractError> {
         match self {
-            VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum)?,
+            VotingStrategy::TopK { quorum, k } => {
+                validate_quorum(quorum)?;
+                if *k == 0 || *k >= MAX_NUM_CHOICES {
+                    return Err(ContractError::InvalidTopK {
+                        max: MAX_NUM_CHOICES - 1,
+                    });
+                }
+            }
         }
+        Ok(())
     }
 
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
             VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::TopK { quorum, .. } => *quorum,
         }
     }
 }
+
+/// How a proposal that ends in a tie between its most voted options
+/// is resolved. Without one a tied proposal is rejected.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Once the proposal has expired one of the tied options is
+    /// selected pseudorandomly. The selection is derived from the
+    /// block the proposal resolves at, as given by its expiration,
+    /// the block it was created at, and its final vote tally, so
+    /// every node and every later query agrees on the winner.
+    ///
+    /// This is NOT cryptographically secure randomness. The winner
+    /// can be computed by anyone once voting has ended, and a voter
+    /// who may still vote can predict how their vote changes it. It
+    /// is only suitable for decisions where "lowest index wins"
+    /// would be an unwelcome bias, not for anything worth gaming.
+    BlockEntropy {},
+}
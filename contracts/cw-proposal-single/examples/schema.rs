@@ -2,12 +2,22 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
-use cosmwasm_std::Addr;
-use cw_core_interface::voting::InfoResponse;
+use cosmwasm_std::{Addr, Uint128};
+use cw_core_interface::{migration::MigrationInfoResponse, voting::InfoResponse};
 use cw_proposal_single::{
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    query::{ProposalListResponse, ProposalResponse, VoteListResponse, VoteResponse},
-    state::Config,
+    msg::{
+        ExecuteMsg, ExecutionCallbackMsg, InstantiateMsg, MigrateMsg, PreExecutionHookQuery,
+        PreExecutionHookResponse, QueryMsg, VoteBatchResult,
+    },
+    query::{
+        CanRevoteResponse, ConfigHistoryResponse, DepositAccountingResponse,
+        DepositSolvencyResponse, FilteredProposalListResponse, FinalResultResponse,
+        MinPeriodStatusResponse, ParticipationStatusResponse, ProposalConfigResponse,
+        ProposalDigestResponse, ProposalListResponse, ProposalResponse, ProposalTargetsResponse,
+        ValidateProposalResponse, VoteHistoryResponse, VoteListResponse, VoteResponse,
+        VoterInfluenceResponse,
+    },
+    state::{Config, PendingConfig},
 };
 use indexable_hooks::HooksResponse;
 
@@ -21,14 +31,39 @@ fn main() {
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(MigrateMsg), &out_dir);
+    export_schema(&schema_for!(ExecutionCallbackMsg), &out_dir);
+    export_schema(&schema_for!(PreExecutionHookQuery), &out_dir);
+    export_schema(&schema_for!(PreExecutionHookResponse), &out_dir);
+    export_schema(&schema_for!(VoteBatchResult), &out_dir);
 
     export_schema(&schema_for!(InfoResponse), &out_dir);
+    export_schema(&schema_for!(MigrationInfoResponse), &out_dir);
     export_schema(&schema_for!(ProposalResponse), &out_dir);
     export_schema(&schema_for!(VoteResponse), &out_dir);
+    export_schema(&schema_for!(ProposalTargetsResponse), &out_dir);
+    export_schema(&schema_for!(ConfigHistoryResponse), &out_dir);
+    export_schema(&schema_for!(VoteHistoryResponse), &out_dir);
+    export_schema(&schema_for!(ParticipationStatusResponse), &out_dir);
+    export_schema(&schema_for!(MinPeriodStatusResponse), &out_dir);
+    export_schema(&schema_for!(ProposalConfigResponse), &out_dir);
+    export_schema(&schema_for!(CanRevoteResponse), &out_dir);
+    export_schema(&schema_for!(ValidateProposalResponse), &out_dir);
+    export_schema(&schema_for!(DepositAccountingResponse), &out_dir);
+    export_schema(&schema_for!(DepositSolvencyResponse), &out_dir);
+    export_schema(&schema_for!(FinalResultResponse), &out_dir);
+    export_schema(&schema_for!(VoterInfluenceResponse), &out_dir);
+    export_schema(&schema_for!(ProposalDigestResponse), &out_dir);
+    export_schema(&schema_for!(FilteredProposalListResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query resonses to the correct name
     export_schema_with_title(&schema_for!(Config), &out_dir, "ConfigResponse");
+    export_schema_with_title(
+        &schema_for!(Option<PendingConfig>),
+        &out_dir,
+        "PendingConfigResponse",
+    );
+    export_schema_with_title(&schema_for!(Addr), &out_dir, "DaoResponse");
     export_schema_with_title(
         &schema_for!(Vec<Addr>),
         &out_dir,
@@ -45,11 +80,21 @@ fn main() {
         "ListVotesResponse",
     );
     export_schema_with_title(&schema_for!(u64), &out_dir, "ProposalCountResponse");
+    export_schema_with_title(
+        &schema_for!(Option<Uint128>),
+        &out_dir,
+        "PowerNeededToPassResponse",
+    );
     export_schema_with_title(
         &schema_for!(ProposalListResponse),
         &out_dir,
         "ReverseProposalsResponse",
     );
+    export_schema_with_title(
+        &schema_for!(ProposalListResponse),
+        &out_dir,
+        "ResolvedProposalsInRangeResponse",
+    );
     export_schema_with_title(
         &schema_for!(HooksResponse),
         &out_dir,
@@ -1,36 +1,73 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply,
-    Response, StdResult, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
+    to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, ContractInfoResponse, CosmosMsg, Deps,
+    DepsMut, Empty, Env, Event, MessageInfo, Order, QueryRequest, Reply, Response, StdError,
+    StdResult, Storage, SubMsg, SubMsgResult, Timestamp, Uint128, WasmMsg, WasmQuery,
 };
 use cw2::set_contract_version;
-use cw_core_interface::voting::IsActiveResponse;
+use cw_core::helpers::instantiated_address_placeholder;
+use cw_core::query::GovernanceFreezeResponse;
+use cw_core_interface::migration::{
+    supported_migrations, MigrationInfoResponse, SupportedMigration,
+};
+use cw_core_interface::voting::{IsActiveResponse, VotingPowerAtHeightResponse};
 use cw_storage_plus::{Bound, Item, Map};
 use cw_utils::{Duration, Expiration};
 use indexable_hooks::Hooks;
-use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
+use proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks, quorum_reached_hooks};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use vote_hooks::new_vote_hooks;
 
 use voting::deposit::{get_deposit_msg, get_return_deposit_msg, CheckedDepositInfo, DepositInfo};
 use voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
-use voting::reply::{mask_proposal_execution_proposal_id, TaggedReplyId};
+use voting::reply::{
+    mask_deposit_refund_proposal_id, mask_execution_callback_proposal_id,
+    mask_proposal_execution_proposal_id, TaggedReplyId,
+};
+use voting::reward::{get_reward_msgs, RewardPayment};
 use voting::status::Status;
 use voting::threshold::Threshold;
 use voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
 
 use crate::msg::MigrateMsg;
-use crate::proposal::SingleChoiceProposal;
-use crate::state::Config;
+use crate::proposal::{ExecutionAttempt, ExecutionError, SingleChoiceProposal};
+use crate::state::{
+    vote_commitment, CheckedCloseReward, Config, ConfigHistoryEntry, DepositAccounting,
+    DepositEscalation, FinalResult, ForfeitedDepositPolicy, PendingConfig, QuorumExtension,
+    ResolutionReason, VoteHistoryEntry, VotingPeriodLimit, CONFIG_HISTORY, DEPOSIT_ACCOUNTING,
+    EXECUTION_SPONSORS, FAILED_EXECUTION_CHECKS, FINAL_RESULTS, ID_PREFIX, MAX_CONFIG_CHANGE_DELAY,
+    MAX_CONFIG_HISTORY, MAX_DEPOSIT_DISTRIBUTION_VOTERS, MAX_ID_PREFIX_LENGTH,
+    MAX_PROPOSAL_METADATA_SIZE, MAX_RECENT_PROPOSALS, MAX_VOTE_BATCH_SIZE, MAX_VOTING_PERIOD_LIMIT,
+    PENDING_CONFIG, PENDING_REFUNDS, PROPOSER_COOLDOWNS, RECENT_PROPOSALS,
+    REGISTERED_DEPOSIT_TOKEN, RESOLVED_PROPOSALS, TOTAL_POWER_CACHE, VOTE_COMMITMENTS,
+    VOTE_HISTORY, VOTING_POWER_CACHE,
+};
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{
+        CloseReward, DepositAsset, ExecuteMsg, ExecutionCallbackMsg, InstantiateMsg,
+        PreExecutionHookQuery, PreExecutionHookResponse, QueryMsg, VoteBatchResult,
+    },
     proposal::advance_proposal_id,
     query::ProposalListResponse,
-    query::{ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Ballot, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    query::{
+        CanRevoteResponse, ConfigHistoryEntryResponse, ConfigHistoryResponse,
+        DepositAccountingResponse, DepositSolvencyResponse, FilteredProposalListResponse,
+        FinalResultResponse, MinPeriodStatusResponse, ParticipationStatusResponse,
+        ProposalConfigResponse, ProposalDigestResponse, ProposalInfluence, ProposalResponse,
+        ProposalTarget, ProposalTargetKind, ProposalTargetsResponse, TokenDepositAccounting,
+        ValidateProposalResponse, VoteHistoryResponse, VoteInfo, VoteListResponse, VoteResponse,
+        VoterInfluenceResponse,
+    },
+    state::{
+        Ballot, BALLOTS, BALLOTS_BY_POSITION, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS,
+        VOTE_HOOKS,
+    },
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cw-govmod-single";
@@ -39,7 +76,7 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -55,6 +92,29 @@ pub fn instantiate(
 
     let (min_voting_period, max_voting_period) =
         validate_voting_period(msg.min_voting_period, msg.max_voting_period)?;
+    let voting_period_limit =
+        validate_voting_period_limit(msg.voting_period_limit, &max_voting_period)?;
+    validate_quorum_extension(&msg.quorum_extension, &msg.threshold, &max_voting_period)?;
+    validate_reveal_period(&msg.reveal_period, &max_voting_period)?;
+    validate_revote_settle_period(&msg.revote_settle_period, &max_voting_period)?;
+    validate_deposit_escalation(&msg.deposit_escalation)?;
+    validate_forfeited_deposit_policy(&msg.forfeited_deposit_policy)?;
+    validate_id_prefix(&msg.id_prefix)?;
+    validate_native_propose_deposit(&msg.native_propose_deposit, &deposit_info)?;
+    validate_total_power_override(msg.total_power_override)?;
+    validate_max_proposal_bytes(msg.max_proposal_bytes)?;
+    validate_config_change_delay(&msg.config_change_delay)?;
+
+    let execution_callback = msg
+        .execution_callback
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let pre_execution_hook = msg
+        .pre_execution_hook
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let hook_allowlist = validate_hook_allowlist(deps.as_ref(), msg.hook_allowlist)?;
+    let close_reward = validate_close_reward(deps.as_ref(), msg.close_reward)?;
 
     let config = Config {
         threshold: msg.threshold,
@@ -65,12 +125,44 @@ pub fn instantiate(
         deposit_info,
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+        exclude_dao_voting_power: msg.exclude_dao_voting_power,
+        execution_callback,
+        require_second: msg.require_second,
+        record_config_history: msg.record_config_history,
+        quorum_reached_hooks: msg.quorum_reached_hooks,
+        require_power_at_resolution: msg.require_power_at_resolution,
+        execution_retry_cooldown: msg.execution_retry_cooldown,
+        post_execution_cooldown: msg.post_execution_cooldown,
+        quorum_extension: msg.quorum_extension,
+        native_propose_deposit: msg.native_propose_deposit,
+        snapshot_offset: msg.snapshot_offset,
+        pre_execution_hook,
+        required_description_sections: msg.required_description_sections,
+        voting_period_limit,
+        register_deposit_token: msg.register_deposit_token,
+        total_power_override: msg.total_power_override,
+        record_vote_history: msg.record_vote_history,
+        execute_on_pass: msg.execute_on_pass,
+        reveal_period: msg.reveal_period,
+        hook_allowlist,
+        revote_settle_period: msg.revote_settle_period,
+        deposit_escalation: msg.deposit_escalation,
+        forfeited_deposit_policy: msg.forfeited_deposit_policy,
+        reject_failing_execution: msg.reject_failing_execution,
+        non_voter_default: msg.non_voter_default,
+        max_proposal_bytes: msg.max_proposal_bytes,
+        config_change_delay: msg.config_change_delay,
+        close_reward,
+        cache_voting_power: msg.cache_voting_power,
     };
 
     // Initialize proposal count to zero so that queries return zero
     // instead of None.
     PROPOSAL_COUNT.save(deps.storage, &0)?;
-    CONFIG.save(deps.storage, &config)?;
+    save_config(deps.storage, &env.block, config)?;
+    if let Some(id_prefix) = &msg.id_prefix {
+        ID_PREFIX.save(deps.storage, id_prefix)?;
+    }
 
     Ok(Response::default()
         .add_attribute("action", "instantiate")
@@ -84,15 +176,66 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    apply_pending_config(deps.storage, &env.block)?;
+
     match msg {
         ExecuteMsg::Propose {
             title,
             description,
             msgs,
-        } => execute_propose(deps, env, info.sender, title, description, msgs),
+            expiration_override,
+            execution_funds,
+            related_proposals,
+            threshold_override,
+            metadata,
+        } => execute_propose(
+            deps,
+            env,
+            info.sender,
+            info.funds,
+            title,
+            description,
+            msgs,
+            expiration_override,
+            execution_funds,
+            related_proposals,
+            threshold_override,
+            metadata,
+        ),
         ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::VoteBatch { votes } => execute_vote_batch(deps, env, info, votes),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::CloseExpired { start_after, limit } => {
+            execute_close_expired(deps, env, info, start_after, limit)
+        }
+        ExecuteMsg::Second { proposal_id } => execute_second(deps, env, info, proposal_id),
+        ExecuteMsg::CommitVote {
+            proposal_id,
+            commitment,
+        } => execute_commit_vote(deps, env, info, proposal_id, commitment),
+        ExecuteMsg::RevealVote {
+            proposal_id,
+            vote,
+            salt,
+        } => execute_reveal_vote(deps, env, info, proposal_id, vote, salt),
+        ExecuteMsg::SyncStatus { proposal_id } => execute_sync_status(deps, env, info, proposal_id),
+        ExecuteMsg::Poke { proposal_id } => execute_poke(deps, env, info, proposal_id),
+        ExecuteMsg::WithdrawProposal { proposal_id } => {
+            execute_withdraw_proposal(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::TransferProposalAuthorship {
+            proposal_id,
+            new_proposer,
+        } => execute_transfer_proposal_authorship(deps, env, info, proposal_id, new_proposer),
+        ExecuteMsg::ForceResolve {
+            proposal_id,
+            status,
+        } => execute_force_resolve(deps, env, info, proposal_id, status),
+        ExecuteMsg::RejectFailingProposal { proposal_id } => {
+            execute_reject_failing_proposal(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::ClaimRefund { proposal_id } => execute_claim_refund(deps, info, proposal_id),
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -102,8 +245,38 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            exclude_dao_voting_power,
+            execution_callback,
+            require_second,
+            record_config_history,
+            quorum_reached_hooks,
+            require_power_at_resolution,
+            execution_retry_cooldown,
+            post_execution_cooldown,
+            quorum_extension,
+            native_propose_deposit,
+            snapshot_offset,
+            pre_execution_hook,
+            required_description_sections,
+            voting_period_limit,
+            register_deposit_token,
+            total_power_override,
+            record_vote_history,
+            execute_on_pass,
+            reveal_period,
+            hook_allowlist,
+            revote_settle_period,
+            deposit_escalation,
+            forfeited_deposit_policy,
+            reject_failing_execution,
+            non_voter_default,
+            max_proposal_bytes,
+            config_change_delay,
+            close_reward,
+            cache_voting_power,
         } => execute_update_config(
             deps,
+            env,
             info,
             threshold,
             max_voting_period,
@@ -113,117 +286,156 @@ pub fn execute(
             dao,
             deposit_info,
             close_proposal_on_execution_failure,
+            exclude_dao_voting_power,
+            execution_callback,
+            require_second,
+            record_config_history,
+            quorum_reached_hooks,
+            require_power_at_resolution,
+            execution_retry_cooldown,
+            post_execution_cooldown,
+            quorum_extension,
+            native_propose_deposit,
+            snapshot_offset,
+            pre_execution_hook,
+            required_description_sections,
+            voting_period_limit,
+            register_deposit_token,
+            total_power_override,
+            record_vote_history,
+            execute_on_pass,
+            reveal_period,
+            hook_allowlist,
+            revote_settle_period,
+            deposit_escalation,
+            forfeited_deposit_policy,
+            reject_failing_execution,
+            non_voter_default,
+            max_proposal_bytes,
+            config_change_delay,
+            close_reward,
+            cache_voting_power,
         ),
-        ExecuteMsg::AddProposalHook { address } => {
-            execute_add_proposal_hook(deps, env, info, address)
-        }
+        ExecuteMsg::AddProposalHook {
+            address,
+            override_allowlist,
+        } => execute_add_proposal_hook(deps, env, info, address, override_allowlist),
         ExecuteMsg::RemoveProposalHook { address } => {
             execute_remove_proposal_hook(deps, env, info, address)
         }
-        ExecuteMsg::AddVoteHook { address } => execute_add_vote_hook(deps, env, info, address),
+        ExecuteMsg::AddVoteHook {
+            address,
+            override_allowlist,
+        } => execute_add_vote_hook(deps, env, info, address, override_allowlist),
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
+        ExecuteMsg::AddHooks {
+            proposal_hooks,
+            vote_hooks,
+            override_allowlist,
+        } => execute_add_hooks(
+            deps,
+            env,
+            info,
+            proposal_hooks,
+            vote_hooks,
+            override_allowlist,
+        ),
+        ExecuteMsg::RemoveHooks {
+            proposal_hooks,
+            vote_hooks,
+        } => execute_remove_hooks(deps, info, proposal_hooks, vote_hooks),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
     sender: Addr,
+    funds: Vec<Coin>,
     title: String,
     description: String,
     msgs: Vec<CosmosMsg<Empty>>,
+    expiration_override: Option<Expiration>,
+    execution_funds: Vec<Coin>,
+    related_proposals: Vec<u64>,
+    threshold_override: Option<Threshold>,
+    metadata: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    let dao = config.dao.clone();
+    let register_deposit_token = config.register_deposit_token;
+    let deposit_escalation = config.deposit_escalation.clone();
 
-    let voting_module: Addr = deps
-        .querier
-        .query_wasm_smart(config.dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})?;
-
-    // Voting modules are not required to implement this
-    // query. Lacking an implementation they are active by default.
-    let active_resp: IsActiveResponse = deps
-        .querier
-        .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::IsActive {},
-        )
-        .unwrap_or(IsActiveResponse { active: true });
-
-    if !active_resp.active {
-        return Err(ContractError::InactiveDao {});
-    }
-
-    // Check that the sender is a member of the governance contract.
-    let sender_power = get_voting_power(
+    let (mut proposal, queried_total_power) = new_proposal(
         deps.as_ref(),
+        &env,
+        config,
         sender.clone(),
-        config.dao.clone(),
-        Some(env.block.height),
-    )?;
-    if sender_power.is_zero() {
-        return Err(ContractError::Unauthorized {});
+        title,
+        description,
+        msgs,
+        expiration_override,
+        execution_funds,
+        related_proposals,
+        threshold_override,
+        metadata,
+    )
+    .map_err(|mut errors| errors.remove(0))?;
+    if let Some(error) = check_proposer(deps.as_ref(), &env.block, &dao, &sender)?
+        .into_iter()
+        .next()
+    {
+        return Err(error);
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    if let Some(escalation) = &deposit_escalation {
+        let multiplier = get_deposit_multiplier(deps.as_ref(), &env.block, &sender, escalation)?;
+        escalate_deposit(&mut proposal, multiplier)?;
+    }
 
-    let total_power = get_total_power(deps.as_ref(), config.dao, Some(env.block.height))?;
+    if let Some(deposit) = &proposal.native_deposit {
+        if funds != [deposit.clone()] {
+            return Err(ContractError::NativeDepositMismatch {
+                expected: deposit.clone(),
+            });
+        }
+    }
 
-    let proposal = {
-        // Limit mutability to this block.
-        let mut proposal = SingleChoiceProposal {
-            title,
-            description,
-            proposer: sender.clone(),
-            start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
-            expiration,
-            threshold: config.threshold,
-            total_power,
-            msgs,
-            status: Status::Open,
-            votes: Votes::zero(),
-            allow_revoting: config.allow_revoting,
-            deposit_info: config.deposit_info.clone(),
-            created: env.block.time,
-            last_updated: env.block.time,
-        };
-        // Update the proposal's status. Addresses case where proposal
-        // expires on the same block as it is created.
-        proposal.update_status(&env.block);
-        proposal
-    };
     let id = advance_proposal_id(deps.storage)?;
 
-    // Limit the size of proposals.
-    //
-    // The Juno mainnet has a larger limit for data that can be
-    // uploaded as part of an execute message than it does for data
-    // that can be queried as part of a query. This means that without
-    // this check it is possible to create a proposal that can not be
-    // queried.
-    //
-    // The size selected was determined by uploading versions of this
-    // contract to the Juno mainnet until queries worked within a
-    // reasonable margin of error.
-    //
-    // `to_vec` is the method used by cosmwasm to convert a struct
-    // into it's byte representation in storage.
-    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
-    if proposal_size > MAX_PROPOSAL_SIZE {
-        return Err(ContractError::ProposalTooLarge {
-            size: proposal_size,
-            max: MAX_PROPOSAL_SIZE,
-        });
+    // Proposals created later with the same snapshot height reuse the
+    // total power rather than querying it again.
+    if let (Some(total_power), Some(voting_module)) = (queried_total_power, &proposal.voting_module)
+    {
+        TOTAL_POWER_CACHE.save(
+            deps.storage,
+            (voting_module.clone(), proposal.snapshot_height()),
+            &total_power,
+        )?;
     }
-
     PROPOSALS.save(deps.storage, id, &proposal)?;
+    record_recent_proposal(deps.storage, &sender, id)?;
 
-    let deposit_msg = get_deposit_msg(&config.deposit_info, &env.contract.address, &sender)?;
+    // Take the deposit described by the proposal's snapshot of the
+    // deposit config so that the deposit taken always matches the one
+    // refunded, even if the module's deposit config changes while the
+    // proposal is open.
+    let deposit_msg = get_deposit_msg(&proposal.deposit_info, &env.contract.address, &sender)?;
+    if let Some(deposit_info) = &proposal.deposit_info {
+        record_deposit(deps.storage, deposit_info, DepositMovement::Collected)?;
+    }
+    let register_msgs = if register_deposit_token {
+        get_register_deposit_token_msgs(deps.storage, &dao, &proposal.deposit_info)?
+    } else {
+        vec![]
+    };
     let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id)?;
     Ok(Response::default()
         .add_messages(deposit_msg)
+        .add_messages(register_msgs)
         .add_submessages(hooks)
         .add_attribute("action", "propose")
         .add_attribute("sender", sender)
@@ -232,30 +444,103 @@ pub fn execute_propose(
 }
 
 pub fn execute_execute(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if config.only_members_execute {
-        let power = get_voting_power(deps.as_ref(), info.sender.clone(), config.dao.clone(), None)?;
-        if power.is_zero() {
-            return Err(ContractError::Unauthorized {});
-        }
+    if !is_authorized_executor(deps.as_ref(), &config, info.sender.clone())? {
+        return Err(ContractError::Unauthorized {});
     }
 
     let mut prop = PROPOSALS
         .may_load(deps.storage, proposal_id)?
         .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
-    // Check here that the proposal is passed. Allow it to be executed
-    // even if it is expired so long as it passed during its voting
-    // period.
     let old_status = prop.status;
-    prop.update_status(&env.block);
+    check_executable(&mut prop, &env.block)?;
+    if let Some(until) = get_governance_freeze(deps.as_ref(), &config.dao) {
+        return Err(ContractError::GovernanceFrozen { until });
+    }
+
+    if prop.require_power_at_resolution {
+        remove_powerless_votes(deps.branch(), &config.dao, proposal_id, &mut prop)?;
+        // Without those votes the proposal may no longer be passed,
+        // in which case it returns to being open or is rejected. A
+        // return to being open is reported to proposal hooks like any
+        // other status change.
+        prop.status = Status::Open;
+        prop.update_status(&env.block);
+    }
+    // Proposals whose messages are found to be unable to execute are
+    // rejected instead of executed. Once closed their result has the
+    // reason `WouldFailExecution`.
+    let failed_check = if prop.status == Status::Passed && config.reject_failing_execution {
+        check_execution(deps.as_ref(), &config.dao, &prop)?
+    } else {
+        None
+    };
+    if let Some(error) = &failed_check {
+        prop.status = Status::Rejected;
+        FAILED_EXECUTION_CHECKS.save(deps.storage, proposal_id, &error.to_string())?;
+    }
     if prop.status != Status::Passed {
-        return Err(ContractError::NotPassed {});
+        prop.last_updated = env.block.time;
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+        let hooks = proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id,
+            old_status.to_string(),
+            prop.status.to_string(),
+        )?;
+        // Nothing is executed so return any funds sent for
+        // execution.
+        let refund: Vec<BankMsg> = if info.funds.is_empty() {
+            vec![]
+        } else {
+            vec![BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: info.funds,
+            }]
+        };
+        let mut response = Response::default()
+            .add_messages(refund)
+            .add_submessages(hooks)
+            .add_attribute("action", "execute")
+            .add_attribute("sender", info.sender)
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("status", prop.status.to_string());
+        if let Some(error) = failed_check {
+            response = response.add_attribute("failed_check", error.to_string());
+        }
+        return Ok(response);
+    }
+
+    // The executor must provide exactly the funds the proposal
+    // declared at creation.
+    let mut funds = info.funds.clone();
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    if funds != prop.execution_funds {
+        return Err(ContractError::ExecutionFundsMismatch {});
+    }
+
+    if is_vetoed_by_hook(deps.as_ref(), &config, proposal_id, &prop) {
+        return Err(ContractError::ExecutionVetoedByHook {});
+    }
+
+    // Failed executions are caught in `reply` either to close the
+    // proposal or to record the attempt for the retry cooldown.
+    let catch_failure =
+        prop.closes_on_execution_failure(&config) || prop.execution_retry_cooldown.is_some();
+    // If a failed execution is caught in `reply` the funds would
+    // otherwise be left with this module. Caught executions reply
+    // whether they succeed or fail, and both replies remove the
+    // sponsor.
+    if !funds.is_empty() && catch_failure {
+        EXECUTION_SPONSORS.save(deps.storage, proposal_id, &info.sender)?;
     }
 
     prop.status = Status::Executed;
@@ -264,28 +549,90 @@ pub fn execute_execute(
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
-    let refund_message = match prop.deposit_info {
-        Some(deposit_info) => get_return_deposit_msg(&deposit_info, &prop.proposer)?,
-        None => vec![],
+    // The proposer's cooldown starts when execution is attempted, so
+    // it also applies if a failed execution is caught in `reply`. It
+    // applies to whoever created the proposal, even if its authorship
+    // has since been transferred.
+    if let Some(cooldown) = config.post_execution_cooldown {
+        let proposer = prop.original_proposer.as_ref().unwrap_or(&prop.proposer);
+        PROPOSER_COOLDOWNS.save(deps.storage, proposer, &cooldown.after(&env.block))?;
+    }
+
+    // The deposit is returned alongside the first attempt to execute
+    // the proposal, even if that attempt fails and is retried.
+    let refund_message = if prop.last_execution_attempt.is_none() {
+        let mut msgs = match &prop.deposit_info {
+            Some(deposit_info) => {
+                record_deposit(deps.storage, deposit_info, DepositMovement::Refunded)?;
+                get_refund_deposit_msgs(proposal_id, deposit_info, &prop.proposer)?
+            }
+            None => vec![],
+        };
+        msgs.extend(
+            get_native_deposit_msg(&prop.native_deposit, &prop.proposer)
+                .into_iter()
+                .map(SubMsg::new),
+        );
+        msgs
+    } else {
+        vec![]
     };
 
     let response = {
         if !prop.msgs.is_empty() {
             let execute_message = WasmMsg::Execute {
                 contract_addr: config.dao.to_string(),
-                msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook { msgs: prop.msgs })?,
-                funds: vec![],
+                msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
+                    msgs: prop.msgs.clone(),
+                })?,
+                funds,
             };
-            match config.close_proposal_on_execution_failure {
-                true => {
-                    let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
-                    Response::default()
-                        .add_submessage(SubMsg::reply_on_error(execute_message, masked_proposal_id))
+            let masked_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
+            // If there is an execution callback we need a reply on
+            // success to send it once execution has succeeded. If
+            // failures are caught the proposal's result is only known
+            // in `reply`, so it is finalized there.
+            match (catch_failure, config.execution_callback.is_some()) {
+                (true, _) => Response::default()
+                    .add_submessage(SubMsg::reply_always(execute_message, masked_proposal_id)),
+                (false, true) => {
+                    finalize_result(
+                        deps.storage,
+                        &env.block,
+                        proposal_id,
+                        &mut prop,
+                        ResolutionReason::Executed,
+                    )?;
+                    Response::default().add_submessage(SubMsg::reply_on_success(
+                        execute_message,
+                        masked_proposal_id,
+                    ))
+                }
+                (false, false) => {
+                    finalize_result(
+                        deps.storage,
+                        &env.block,
+                        proposal_id,
+                        &mut prop,
+                        ResolutionReason::Executed,
+                    )?;
+                    Response::default().add_message(execute_message)
                 }
-                false => Response::default().add_message(execute_message),
             }
         } else {
-            Response::default()
+            // Nothing to execute so execution has already succeeded.
+            finalize_result(
+                deps.storage,
+                &env.block,
+                proposal_id,
+                &mut prop,
+                ResolutionReason::Executed,
+            )?;
+            Response::default().add_submessages(get_execution_callback_msg(
+                &config.execution_callback,
+                proposal_id,
+                prop.msgs,
+            )?)
         }
     };
 
@@ -297,7 +644,7 @@ pub fn execute_execute(
         prop.status.to_string(),
     )?;
     Ok(response
-        .add_messages(refund_message)
+        .add_submessages(refund_message)
         .add_submessages(hooks)
         .add_attribute("action", "execute")
         .add_attribute("sender", info.sender)
@@ -305,202 +652,2352 @@ pub fn execute_execute(
         .add_attribute("dao", config.dao))
 }
 
-pub fn execute_vote(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    proposal_id: u64,
-    vote: Vote,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let mut prop = PROPOSALS
-        .may_load(deps.storage, proposal_id)?
-        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
-    if prop.current_status(&env.block) != Status::Open {
-        return Err(ContractError::NotOpen { id: proposal_id });
+/// Checks, as far as is possible without executing them, that the
+/// messages of `prop` are able to execute: that no message is empty,
+/// that the contracts they target exist, and that the DAO has the
+/// native funds they send once any execution funds are added. Gets
+/// the reason they would fail, if any.
+fn check_execution(
+    deps: Deps,
+    dao: &Addr,
+    prop: &SingleChoiceProposal,
+) -> StdResult<Option<ContractError>> {
+    let mut required: BTreeMap<String, Uint128> = BTreeMap::new();
+    let mut require = |coins: &[Coin]| {
+        for coin in coins {
+            *required.entry(coin.denom.clone()).or_default() += coin.amount;
+        }
+    };
+    // Messages may target contracts instantiated earlier in the
+    // proposal by placeholder, which do not exist yet.
+    let mut instantiated = 0;
+    for (index, msg) in prop.msgs.iter().enumerate() {
+        let (target, empty) = match msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. })
+            | CosmosMsg::Bank(BankMsg::Burn { amount }) => {
+                require(amount.as_slice());
+                (None, amount.iter().all(|coin| coin.amount.is_zero()))
+            }
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                require(funds.as_slice());
+                (Some(contract_addr), msg.is_empty())
+            }
+            CosmosMsg::Wasm(WasmMsg::Instantiate { msg, funds, .. }) => {
+                require(funds.as_slice());
+                instantiated += 1;
+                (None, msg.is_empty())
+            }
+            CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr, msg, ..
+            }) => (Some(contract_addr), msg.is_empty()),
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. })
+            | CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+                (Some(contract_addr), false)
+            }
+            _ => (None, false),
+        };
+        if empty {
+            return Ok(Some(ContractError::EmptyProposalMessage { index }));
+        }
+        if let Some(address) = target {
+            let placeholder =
+                (0..instantiated).any(|i| *address == instantiated_address_placeholder(i));
+            let exists = placeholder
+                || deps
+                    .querier
+                    .query::<ContractInfoResponse>(&QueryRequest::Wasm(WasmQuery::ContractInfo {
+                        contract_addr: address.clone(),
+                    }))
+                    .is_ok();
+            if !exists {
+                return Ok(Some(ContractError::MissingExecutionTarget {
+                    index,
+                    address: address.clone(),
+                }));
+            }
+        }
     }
 
-    let vote_power = get_voting_power(
-        deps.as_ref(),
-        info.sender.clone(),
-        config.dao,
-        Some(prop.start_height),
-    )?;
-    if vote_power.is_zero() {
-        return Err(ContractError::NotRegistered {});
+    for (denom, amount) in required {
+        let balance = deps.querier.query_balance(dao, &denom)?.amount;
+        let execution_funds = prop
+            .execution_funds
+            .iter()
+            .filter(|coin| coin.denom == denom)
+            .fold(Uint128::zero(), |total, coin| total + coin.amount);
+        if balance + execution_funds < amount {
+            return Ok(Some(ContractError::InsufficientExecutionFunds { denom }));
+        }
     }
+    Ok(None)
+}
 
-    BALLOTS.update(
-        deps.storage,
-        (proposal_id, info.sender.clone()),
-        |bal| match bal {
-            Some(current_ballot) => {
-                if prop.allow_revoting {
-                    if current_ballot.vote == vote {
-                        // Don't allow casting the same vote more than
-                        // once. This seems liable to be confusing
-                        // behavior.
-                        Err(ContractError::AlreadyCast {})
-                    } else {
-                        // Remove the old vote if this is a re-vote.
-                        prop.votes
-                            .remove_vote(current_ballot.vote, current_ballot.power);
-                        Ok(Ballot {
-                            power: vote_power,
-                            vote,
-                        })
-                    }
-                } else {
-                    Err(ContractError::AlreadyVoted {})
-                }
-            }
-            None => Ok(Ballot {
-                power: vote_power,
-                vote,
-            }),
-        },
-    )?;
-
-    let old_status = prop.status;
+/// Returns true if `executor` is allowed to execute passed proposals
+/// in this module. If `only_members_execute` is set this is only
+/// addresses with voting power.
+fn is_authorized_executor(deps: Deps, config: &Config, executor: Addr) -> StdResult<bool> {
+    if config.only_members_execute {
+        let power = get_voting_power(deps, executor, config.dao.clone(), None)?;
+        Ok(!power.is_zero())
+    } else {
+        Ok(true)
+    }
+}
 
-    prop.votes.add_vote(vote, vote_power);
-    prop.update_status(&env.block);
+/// Updates the status of `prop` and checks that it may be executed
+/// at `block`. Checks on the executor are left to
+/// `is_authorized_executor`.
+fn check_executable(
+    prop: &mut SingleChoiceProposal,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    // Check here that the proposal is passed. Allow it to be executed
+    // even if it is expired so long as it passed during its voting
+    // period.
+    prop.update_status(block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
 
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    if let (Some(cooldown), Some(attempt)) =
+        (prop.execution_retry_cooldown, &prop.last_execution_attempt)
+    {
+        let retry_at = attempt.retry_at(cooldown);
+        if !retry_at.is_expired(block) {
+            return Err(ContractError::ExecutionCooldown { retry_at });
+        }
+    }
+    Ok(())
+}
 
-    let new_status = prop.status;
-    let change_hooks = proposal_status_changed_hooks(
-        PROPOSAL_HOOKS,
-        deps.storage,
-        proposal_id,
-        old_status.to_string(),
-        new_status.to_string(),
-    )?;
-    let vote_hooks = new_vote_hooks(
-        VOTE_HOOKS,
-        deps.storage,
-        proposal_id,
-        info.sender.to_string(),
-        vote.to_string(),
-    )?;
+/// Checks if the module's pre-execution hook, if any, vetoes the
+/// execution of a proposal.
+fn is_vetoed_by_hook(
+    deps: Deps,
+    config: &Config,
+    proposal_id: u64,
+    prop: &SingleChoiceProposal,
+) -> bool {
+    match &config.pre_execution_hook {
+        // A hook that fails to respond is ignored. Otherwise a broken
+        // hook would prevent the execution of the proposal removing
+        // it.
+        Some(hook) => matches!(
+            deps.querier.query_wasm_smart(
+                hook,
+                &PreExecutionHookQuery::CanExecute {
+                    proposal_id,
+                    msgs: prop.msgs.clone(),
+                },
+            ),
+            Ok(PreExecutionHookResponse { veto: true })
+        ),
+        None => false,
+    }
+}
 
-    Ok(Response::default()
-        .add_submessages(change_hooks)
-        .add_submessages(vote_hooks)
-        .add_attribute("action", "vote")
-        .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("position", vote.to_string())
-        .add_attribute("status", prop.status.to_string()))
+/// Gets the time the DAO's governance freeze lifts if it is frozen.
+fn get_governance_freeze(deps: Deps, dao: &Addr) -> Option<Timestamp> {
+    // Core contracts that predate governance freezes do not support
+    // the query, and are never frozen.
+    deps.querier
+        .query_wasm_smart::<GovernanceFreezeResponse>(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::GovernanceFreeze {},
+        )
+        .ok()
+        .and_then(|response| response.until)
 }
 
-pub fn execute_close(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    proposal_id: u64,
-) -> Result<Response, ContractError> {
-    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
-    let config = CONFIG.load(deps.storage)?;
+/// Builds the proposal that `proposer` would create by proposing the
+/// provided contents at the current block, performing every check on
+/// the DAO's state and the proposal's contents that `Propose` does.
+/// Checks on the proposer themselves are left to the caller, see
+/// `check_proposer`.
+///
+/// Rather than stopping at the first failed check, every check that
+/// can be performed is, and the errors of all that fail are returned
+/// in the order they are checked. The size of the proposal is only
+/// checked once it can be built, which is once the other checks have
+/// passed.
+///
+/// This is shared by `Propose` and the `ValidateProposal` query so
+/// that the query can not disagree with what `Propose` accepts.
+///
+/// Along with the proposal, returns the total power queried from the
+/// voting module if it was queried while `cache_voting_power` is set
+/// so that `Propose` may cache it.
+#[allow(clippy::too_many_arguments)]
+fn new_proposal(
+    deps: Deps,
+    env: &Env,
+    config: Config,
+    proposer: Addr,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    expiration_override: Option<Expiration>,
+    execution_funds: Vec<Coin>,
+    related_proposals: Vec<u64>,
+    threshold_override: Option<Threshold>,
+    metadata: Option<String>,
+) -> Result<(SingleChoiceProposal, Option<Uint128>), Vec<ContractError>> {
+    // Errors that prevent any further checks from being performed.
+    let fail = |error: ContractError| vec![error];
 
-    // Update status to ensure that proposals which were open and have
-    // expired are moved to "rejected."
-    prop.update_status(&env.block);
-    if prop.status != Status::Rejected {
-        return Err(ContractError::WrongCloseStatus {});
+    let voting_module: Addr = deps
+        .querier
+        .query_wasm_smart(config.dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})
+        .map_err(|e| fail(e.into()))?;
+
+    // Voting modules are not required to implement this
+    // query. Lacking an implementation they are active by default.
+    let active_resp: IsActiveResponse = deps
+        .querier
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw_core_interface::voting::Query::IsActive {},
+        )
+        .unwrap_or(IsActiveResponse { active: true });
+
+    if !active_resp.active {
+        return Err(fail(ContractError::InactiveDao {}));
     }
 
-    let old_status = prop.status;
+    // Recorded so that it may later be determined which contracts
+    // governed the proposal.
+    let voting_module_info: ContractInfoResponse = deps
+        .querier
+        .query(&QueryRequest::Wasm(WasmQuery::ContractInfo {
+            contract_addr: voting_module.to_string(),
+        }))
+        .map_err(|e| fail(e.into()))?;
 
-    let refund_message = match &prop.deposit_info {
-        Some(deposit_info) => {
-            let receiver = if deposit_info.refund_failed_proposals {
-                &prop.proposer
+    let mut errors = vec![];
+    if config.require_second.is_some() && expiration_override.is_some() {
+        errors.push(ContractError::SecondWithExpirationOverride {});
+    }
+    if let Err(error) =
+        check_description_sections(&description, &config.required_description_sections)
+    {
+        errors.push(error);
+    }
+    if let Some(metadata) = &metadata {
+        let size = metadata.len() as u64;
+        if size > MAX_PROPOSAL_METADATA_SIZE {
+            errors.push(ContractError::MetadataTooLarge {
+                size,
+                max: MAX_PROPOSAL_METADATA_SIZE,
+            });
+        }
+    }
+    let expiration = get_proposal_expiration(&config, &env.block, expiration_override)
+        .map_err(|error| errors.push(error))
+        .ok();
+    let execution_funds = validate_execution_funds(&msgs, execution_funds)
+        .map_err(|error| errors.push(error))
+        .ok();
+    for id in related_proposals.iter() {
+        if !PROPOSALS.has(deps.storage, *id) {
+            errors.push(ContractError::NoSuchProposal { id: *id });
+        }
+    }
+    let threshold = match threshold_override {
+        Some(threshold) => match threshold.validate() {
+            Err(error) => {
+                errors.push(error.into());
+                None
+            }
+            Ok(()) if !threshold.is_at_least_as_strict_as(&config.threshold) => {
+                errors.push(ContractError::ThresholdOverrideNotStricter {});
+                None
+            }
+            Ok(()) => Some(threshold),
+        },
+        None => Some(config.threshold.clone()),
+    };
+    let (expiration, execution_funds, threshold) = match (expiration, execution_funds, threshold) {
+        (Some(expiration), Some(execution_funds), Some(threshold)) if errors.is_empty() => {
+            (expiration, execution_funds, threshold)
+        }
+        _ => return Err(errors),
+    };
+
+    // Voting power is snapshotted `snapshot_offset` blocks before the
+    // proposal is created so that power acquired just before it is
+    // created does not count.
+    let snapshot_height = env.block.height.saturating_sub(config.snapshot_offset);
+    let max_proposal_size = config.max_proposal_bytes.unwrap_or(MAX_PROPOSAL_SIZE);
+    let mut queried_total_power = None;
+    let total_power = match config.total_power_override {
+        Some(total_power) => total_power,
+        None => {
+            let cached = if config.cache_voting_power {
+                TOTAL_POWER_CACHE
+                    .may_load(deps.storage, (voting_module.clone(), snapshot_height))
+                    .map_err(|e| fail(e.into()))?
             } else {
-                // If we aren't refunding failed proposals then return
-                // the depost to the DAO treasury on close.
-                &config.dao
+                None
+            };
+            let total_power = match cached {
+                Some(total_power) => total_power,
+                None => {
+                    let total_power =
+                        get_total_power(deps, config.dao.clone(), Some(snapshot_height))
+                            .map_err(|e| fail(e.into()))?;
+                    if config.cache_voting_power {
+                        queried_total_power = Some(total_power);
+                    }
+                    total_power
+                }
             };
-            get_return_deposit_msg(deposit_info, receiver)?
+            if config.exclude_dao_voting_power {
+                let dao_power = query_snapshot_power(
+                    deps,
+                    &config,
+                    &Some(voting_module.clone()),
+                    snapshot_height,
+                    &config.dao,
+                )
+                .map_err(|e| fail(e.into()))?;
+                total_power
+                    .checked_sub(dao_power)
+                    .map_err(|e| fail(StdError::overflow(e).into()))?
+            } else {
+                total_power
+            }
         }
-        None => vec![],
     };
 
-    prop.status = Status::Closed;
-    // Update proposal's last updated timestamp.
-    prop.last_updated = env.block.time;
-    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    let mut proposal = SingleChoiceProposal {
+        title,
+        description,
+        proposer,
+        start_height: env.block.height,
+        min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+        expiration,
+        threshold,
+        total_power,
+        msgs,
+        status: match config.require_second {
+            Some(_) => Status::Pending,
+            None => Status::Open,
+        },
+        votes: Votes::zero(),
+        allow_revoting: config.allow_revoting,
+        deposit_info: config.deposit_info,
+        created: env.block.time,
+        last_updated: env.block.time,
+        execution_error: None,
+        seconding_deadline: config.require_second.map(|period| period.after(&env.block)),
+        execution_funds,
+        quorum_reached: false,
+        voting_module: Some(voting_module),
+        require_power_at_resolution: config.require_power_at_resolution,
+        related_proposals,
+        last_execution_attempt: None,
+        extensions: 0,
+        native_deposit: config.native_propose_deposit,
+        snapshot_height: Some(snapshot_height),
+        close_proposal_on_execution_failure: Some(config.close_proposal_on_execution_failure),
+        reveal_period: config.reveal_period,
+        revote_settle_period: config.revote_settle_period,
+        settlement: None,
+        metadata,
+        vote_count: Some(0),
+        voting_module_code_id: Some(voting_module_info.code_id),
+        module_version: Some(CONTRACT_VERSION.to_string()),
+        non_voter_default: config.non_voter_default,
+        resolved_at: None,
+        execution_retry_cooldown: config.execution_retry_cooldown,
+        original_proposer: None,
+    };
+    // Update the proposal's status. Addresses case where proposal
+    // expires on the same block as it is created.
+    proposal.update_status(&env.block);
 
-    let changed_hooks = proposal_status_changed_hooks(
-        PROPOSAL_HOOKS,
-        deps.storage,
+    // Limit the size of proposals.
+    //
+    // The Juno mainnet has a larger limit for data that can be
+    // uploaded as part of an execute message than it does for data
+    // that can be queried as part of a query. This means that without
+    // this check it is possible to create a proposal that can not be
+    // queried.
+    //
+    // The size selected was determined by uploading versions of this
+    // contract to the Juno mainnet until queries worked within a
+    // reasonable margin of error.
+    //
+    // `to_vec` is the method used by cosmwasm to convert a struct
+    // into it's byte representation in storage.
+    //
+    // The DAO may set a lower limit with `max_proposal_bytes`.
+    let proposal_size = cosmwasm_std::to_vec(&proposal)
+        .map_err(|e| fail(e.into()))?
+        .len() as u64;
+    if proposal_size > max_proposal_size {
+        return Err(fail(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: max_proposal_size,
+        }));
+    }
+
+    Ok((proposal, queried_total_power))
+}
+
+/// Checks that `proposer` may create a proposal, returning the reasons
+/// they may not. This is shared by `Propose` and the
+/// `ValidateProposal` query.
+fn check_proposer(
+    deps: Deps,
+    block: &BlockInfo,
+    dao: &Addr,
+    proposer: &Addr,
+) -> StdResult<Vec<ContractError>> {
+    let mut errors = vec![];
+
+    // Check that the proposer is a member of the governance contract.
+    let power = get_voting_power(deps, proposer.clone(), dao.clone(), Some(block.height))?;
+    if power.is_zero() {
+        errors.push(ContractError::Unauthorized {});
+    }
+
+    if let Some(until) = PROPOSER_COOLDOWNS.may_load(deps.storage, proposer)? {
+        if !until.is_expired(block) {
+            errors.push(ContractError::ProposeCooldown { until });
+        }
+    }
+
+    Ok(errors)
+}
+
+/// Removes the votes of voters who currently have no voting power
+/// from a proposal's tally, along with their ballots. The voting
+/// power of every voter on the proposal is queried, so the cost of
+/// this grows with the number of voters.
+fn remove_powerless_votes(
+    deps: DepsMut,
+    dao: &Addr,
+    proposal_id: u64,
+    prop: &mut SingleChoiceProposal,
+) -> Result<(), ContractError> {
+    let ballots = BALLOTS
+        .prefix(proposal_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, Ballot)>>>()?;
+    for (voter, ballot) in ballots {
+        let power = get_voting_power(deps.as_ref(), voter.clone(), dao.clone(), None)?;
+        if power.is_zero() {
+            prop.votes.remove_vote(ballot.vote, ballot.power);
+            BALLOTS_BY_POSITION.remove(
+                deps.storage,
+                (proposal_id, ballot.vote.to_string(), voter.clone()),
+            );
+            BALLOTS.remove(deps.storage, (proposal_id, voter));
+            prop.remove_voter();
+        }
+    }
+    Ok(())
+}
+
+/// Validates the execution funds of a new proposal, returning them
+/// sorted by denom so that they may be compared against the funds
+/// sent with `Execute`.
+fn validate_execution_funds(
+    msgs: &[CosmosMsg<Empty>],
+    mut execution_funds: Vec<Coin>,
+) -> Result<Vec<Coin>, ContractError> {
+    execution_funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    let duplicate_denom = execution_funds
+        .windows(2)
+        .any(|pair| pair[0].denom == pair[1].denom);
+    if duplicate_denom
+        || execution_funds.iter().any(|coin| coin.amount.is_zero())
+        || (msgs.is_empty() && !execution_funds.is_empty())
+    {
+        return Err(ContractError::InvalidExecutionFunds {});
+    }
+    Ok(execution_funds)
+}
+
+/// Checks that `description` contains each of the required section
+/// headers. A line is a header if, ignoring leading `#` characters
+/// and a trailing `:`, it case-insensitively matches the section.
+fn check_description_sections(description: &str, sections: &[String]) -> Result<(), ContractError> {
+    let headers: Vec<String> = description
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches('#')
+                .trim_end_matches(':')
+                .trim()
+                .to_lowercase()
+        })
+        .collect();
+    for section in sections {
+        if !headers.contains(&section.trim().to_lowercase()) {
+            return Err(ContractError::MissingDescriptionSection {
+                section: section.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Gets the message to send to the module's execution callback, if
+/// any, after a proposal has been executed. Errors from the callback
+/// are caught in `reply` so that a failing callback can not revert
+/// the proposal's execution.
+fn get_execution_callback_msg(
+    execution_callback: &Option<Addr>,
+    proposal_id: u64,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> StdResult<Vec<SubMsg>> {
+    match execution_callback {
+        Some(callback) => {
+            let msg = WasmMsg::Execute {
+                contract_addr: callback.to_string(),
+                msg: to_binary(&ExecutionCallbackMsg::ProposalExecuted { proposal_id, msgs })?,
+                funds: vec![],
+            };
+            Ok(vec![SubMsg::reply_on_error(
+                msg,
+                mask_execution_callback_proposal_id(proposal_id),
+            )])
+        }
+        None => Ok(vec![]),
+    }
+}
+
+/// Computes the expiration of a new proposal. If an override is
+/// provided it must be in the future and fall between the end of the
+/// minimum and maximum voting periods, otherwise the proposal expires
+/// at the end of the maximum voting period.
+fn get_proposal_expiration(
+    config: &Config,
+    block: &BlockInfo,
+    expiration_override: Option<Expiration>,
+) -> Result<Expiration, ContractError> {
+    let max = config.max_voting_period.after(block);
+    let expiration = match expiration_override {
+        Some(expiration) => expiration,
+        None => return Ok(max),
+    };
+    if expiration.is_expired(block) {
+        return Err(ContractError::ExpirationInPast {});
+    }
+    // Expirations with different units (or `Never`) are not
+    // comparable, so this also rejects overrides that don't use the
+    // same units as the max voting period.
+    match expiration.partial_cmp(&max) {
+        Some(Ordering::Less) | Some(Ordering::Equal) => (),
+        _ => return Err(ContractError::InvalidExpiration {}),
+    }
+    if let Some(min) = config.min_voting_period.map(|min| min.after(block)) {
+        if expiration < min {
+            return Err(ContractError::InvalidMinVotingPeriod {});
+        }
+    }
+    Ok(expiration)
+}
+
+pub fn execute_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    assert_votable(deps.as_ref(), &config.dao, &env.block, proposal_id, &prop)?;
+    if prop.reveal_period.is_some() {
+        return Err(ContractError::CommitRevealProposal { id: proposal_id });
+    }
+
+    // Voting power is queried as of the proposal's snapshot height so
+    // a voter's power can not change over the life of a proposal.
+    // Revotes reuse the power recorded on the voter's ballot rather
+    // than querying the voting module again.
+    let current_ballot = BALLOTS.may_load(deps.storage, (proposal_id, info.sender.clone()))?;
+    let vote_power = match &current_ballot {
+        Some(current_ballot) => {
+            assert_revote_allowed(&prop)?;
+            if current_ballot.vote == vote {
+                // Don't allow casting the same vote more than
+                // once. This seems liable to be confusing
+                // behavior.
+                return Err(ContractError::AlreadyCast {});
+            }
+            current_ballot.power
+        }
+        None => {
+            let power = load_snapshot_power(
+                deps.branch(),
+                &config,
+                &prop.voting_module,
+                prop.snapshot_height(),
+                &info.sender,
+            )?;
+            if power.is_zero() {
+                return Err(ContractError::NotRegistered {});
+            }
+            // Votes from voters without voting power are removed at
+            // execution, so those voters may not vote. Otherwise a
+            // voter whose vote was removed could vote again with
+            // their power at the snapshot.
+            if prop.require_power_at_resolution
+                && get_voting_power(deps.as_ref(), info.sender.clone(), config.dao.clone(), None)?
+                    .is_zero()
+            {
+                return Err(ContractError::NotRegistered {});
+            }
+            power
+        }
+    };
+
+    let old_status = prop.status;
+    let counted = count_vote(
+        deps.storage,
+        &env.block,
+        &config,
+        proposal_id,
+        &mut prop,
+        &info.sender,
+        Ballot {
+            power: vote_power,
+            vote,
+            height: Some(env.block.height),
+        },
+        current_ballot,
+    )?;
+
+    let change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_submessages(counted.messages)
+        .add_events(counted.events)
+        .add_attribute("action", "vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("position", vote.to_string())
+        .add_attribute("power", vote_power.to_string())
+        .add_attribute("height", env.block.height.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+/// Counts `ballot` as `voter`'s vote on `prop`, replacing `previous`,
+/// their current ballot, if they are revoting, and saves the
+/// proposal. `Vote` and `RevealVote` both count votes this way so
+/// that quorum, settlement, voting extensions, and vote history are
+/// updated however a vote is cast. Gets the vote hooks and the events
+/// for the vote.
+///
+/// Checks that the vote can be counted before any state is written
+/// so that a vote that fails in a `VoteBatch` is skipped without
+/// leaving a trace.
+#[allow(clippy::too_many_arguments)]
+fn count_vote(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    config: &Config,
+    proposal_id: u64,
+    prop: &mut SingleChoiceProposal,
+    voter: &Addr,
+    ballot: Ballot,
+    previous: Option<Ballot>,
+) -> Result<Response, ContractError> {
+    match &previous {
+        // Remove the old vote if this is a re-vote.
+        Some(previous) => prop.votes.remove_vote(previous.vote, previous.power),
+        None => prop.add_voter(),
+    }
+    prop.votes
+        .add_vote(ballot.vote, ballot.power)
+        .map_err(|_| ContractError::Overflow {})?;
+
+    if let Some(previous) = previous {
+        BALLOTS_BY_POSITION.remove(
+            storage,
+            (proposal_id, previous.vote.to_string(), voter.clone()),
+        );
+    }
+    BALLOTS.save(storage, (proposal_id, voter.clone()), &ballot)?;
+    BALLOTS_BY_POSITION.save(
+        storage,
+        (proposal_id, ballot.vote.to_string(), voter.clone()),
+        &ballot.power,
+    )?;
+
+    prop.update_settlement(block);
+    prop.update_status(block);
+
+    let quorum_reached = !prop.quorum_reached && prop.is_quorum_reached();
+    if quorum_reached {
+        prop.quorum_reached = true;
+    }
+
+    // Voting is extended if quorum is first reached close to
+    // expiration and, once extended, whenever a vote is cast close to
+    // the new expiration.
+    let extended = match &config.quorum_extension {
+        Some(extension)
+            if prop.status == Status::Open && (quorum_reached || prop.extensions > 0) =>
+        {
+            prop.extend_expiration(block, extension)
+        }
+        _ => false,
+    };
+
+    PROPOSALS.save(storage, proposal_id, prop)?;
+    if config.record_vote_history {
+        // Later votes in the same block overwrite the entry so that
+        // it holds the tally as of the end of the block.
+        VOTE_HISTORY.save(
+            storage,
+            (proposal_id, block.height),
+            &VoteHistoryEntry {
+                height: block.height,
+                time: block.time,
+                votes: prop.votes.clone(),
+            },
+        )?;
+    }
+
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        storage,
+        proposal_id,
+        voter.to_string(),
+        ballot.vote.to_string(),
+    )?;
+    let mut response = Response::default().add_submessages(vote_hooks);
+
+    if extended {
+        response = response.add_event(
+            Event::new("voting_extended")
+                .add_attribute("proposal_id", proposal_id.to_string())
+                .add_attribute("expiration", prop.expiration.to_string()),
+        );
+    }
+
+    // Notify listeners the first time the proposal reaches quorum.
+    if quorum_reached {
+        response = response.add_event(
+            Event::new("quorum_reached").add_attribute("proposal_id", proposal_id.to_string()),
+        );
+        if config.quorum_reached_hooks {
+            response = response.add_submessages(quorum_reached_hooks(
+                PROPOSAL_HOOKS,
+                storage,
+                proposal_id,
+            )?);
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn execute_vote_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<(u64, Vote)>,
+) -> Result<Response, ContractError> {
+    if votes.is_empty() || votes.len() as u64 > MAX_VOTE_BATCH_SIZE {
+        return Err(ContractError::InvalidVoteBatchSize {
+            max: MAX_VOTE_BATCH_SIZE,
+        });
+    }
+
+    let mut response = Response::default();
+    let mut results = Vec::with_capacity(votes.len());
+    let mut cast = 0;
+    for (proposal_id, vote) in votes {
+        // `execute_vote` makes all of its checks before writing any
+        // state, so a vote that fails leaves nothing to undo. The
+        // only exception is voting power it cached, which is correct
+        // whether or not the vote is cast.
+        let error = match execute_vote(deps.branch(), env.clone(), info.clone(), proposal_id, vote)
+        {
+            Ok(vote_response) => {
+                cast += 1;
+                response = response
+                    .add_submessages(vote_response.messages)
+                    .add_event(Event::new("vote").add_attributes(vote_response.attributes))
+                    .add_events(vote_response.events);
+                None
+            }
+            Err(error) => Some(error.to_string()),
+        };
+        results.push(VoteBatchResult { proposal_id, error });
+    }
+
+    Ok(response
+        .set_data(to_binary(&results)?)
+        .add_attribute("action", "vote_batch")
+        .add_attribute("sender", info.sender)
+        .add_attribute("cast", cast.to_string())
+        .add_attribute("skipped", (results.len() - cast).to_string()))
+}
+
+pub fn execute_commit_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    assert_votable(deps.as_ref(), &config.dao, &env.block, proposal_id, &prop)?;
+    if prop.reveal_period.is_none() {
+        return Err(ContractError::NotCommitRevealProposal { id: proposal_id });
+    }
+    if VOTE_COMMITMENTS.has(deps.storage, (proposal_id, info.sender.clone())) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+
+    let power = load_snapshot_power(
+        deps.branch(),
+        &config,
+        &prop.voting_module,
+        prop.snapshot_height(),
+        &info.sender,
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    VOTE_COMMITMENTS.save(
+        deps.storage,
+        (proposal_id, info.sender.clone()),
+        &commitment,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "commit_vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_reveal_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: Vote,
+    salt: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let old_status = prop.status;
+    prop.update_status(&env.block);
+    if prop.status != Status::Revealing {
+        return Err(ContractError::NotRevealing { id: proposal_id });
+    }
+
+    let commitment = VOTE_COMMITMENTS
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .ok_or(ContractError::NoCommitment {})?;
+    if commitment != vote_commitment(proposal_id, &info.sender, vote, &salt) {
+        return Err(ContractError::CommitmentMismatch {});
+    }
+    VOTE_COMMITMENTS.remove(deps.storage, (proposal_id, info.sender.clone()));
+
+    // Power is queried as of the proposal's snapshot, as it was when
+    // the vote was committed to.
+    let power = load_snapshot_power(
+        deps.branch(),
+        &config,
+        &prop.voting_module,
+        prop.snapshot_height(),
+        &info.sender,
+    )?;
+    // Each voter may only commit once, so a revealed vote is never a
+    // revote.
+    let counted = count_vote(
+        deps.storage,
+        &env.block,
+        &config,
+        proposal_id,
+        &mut prop,
+        &info.sender,
+        Ballot {
+            power,
+            vote,
+            height: Some(env.block.height),
+        },
+        None,
+    )?;
+
+    let change_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(change_hooks)
+        .add_submessages(counted.messages)
+        .add_events(counted.events)
+        .add_attribute("action", "reveal_vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("position", vote.to_string())
+        .add_attribute("power", power.to_string()))
+}
+
+/// Checks that votes may currently be cast on a proposal.
+fn assert_votable(
+    deps: Deps,
+    dao: &Addr,
+    block: &BlockInfo,
+    proposal_id: u64,
+    prop: &SingleChoiceProposal,
+) -> Result<(), ContractError> {
+    // Voting power is queried from the DAO's current voting module
+    // which, if it has been swapped out since the proposal was
+    // created, may not agree with the proposal's `total_power`.
+    if let Some(voting_module) = &prop.voting_module {
+        let current: Addr = deps
+            .querier
+            .query_wasm_smart(dao.clone(), &cw_core::msg::QueryMsg::VotingModule {})?;
+        if *voting_module != current {
+            return Err(ContractError::VotingModuleChanged { id: proposal_id });
+        }
+    }
+    if prop.current_status(block) != Status::Open {
+        // Proposals that closed for voting only because they expired
+        // report when that happened so voters can see how late they
+        // were.
+        if prop.status == Status::Open && prop.expiration.is_expired(block) {
+            return Err(ContractError::ProposalExpired {
+                id: proposal_id,
+                expired_at: prop.expiration,
+            });
+        }
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    Ok(())
+}
+
+/// Checks that a voter who has already voted on a votable proposal
+/// may change their vote.
+fn assert_revote_allowed(prop: &SingleChoiceProposal) -> Result<(), ContractError> {
+    if prop.allow_revoting {
+        Ok(())
+    } else {
+        Err(ContractError::AlreadyVoted {})
+    }
+}
+
+/// The key of `voter`'s power as of `height` in `VOTING_POWER_CACHE`,
+/// or None if voting power is not being cached. Power is never cached
+/// for proposals that did not record their voting module.
+fn voting_power_cache_key(
+    config: &Config,
+    voting_module: &Option<Addr>,
+    height: u64,
+    voter: &Addr,
+) -> Option<(Addr, u64, Addr)> {
+    match voting_module {
+        Some(voting_module) if config.cache_voting_power => {
+            Some((voting_module.clone(), height, voter.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Gets `voter`'s voting power as of `height`, reading it from
+/// `VOTING_POWER_CACHE` if it has been cached.
+fn query_snapshot_power(
+    deps: Deps,
+    config: &Config,
+    voting_module: &Option<Addr>,
+    height: u64,
+    voter: &Addr,
+) -> StdResult<Uint128> {
+    if let Some(key) = voting_power_cache_key(config, voting_module, height, voter) {
+        if let Some(power) = VOTING_POWER_CACHE.may_load(deps.storage, key)? {
+            return Ok(power);
+        }
+    }
+    get_voting_power(deps, voter.clone(), config.dao.clone(), Some(height))
+}
+
+/// Like `query_snapshot_power`, but caches power that had to be
+/// queried. Power is queried from the DAO's current voting module, so
+/// callers must have checked that it is still `voting_module`, as
+/// `assert_votable` does.
+fn load_snapshot_power(
+    deps: DepsMut,
+    config: &Config,
+    voting_module: &Option<Addr>,
+    height: u64,
+    voter: &Addr,
+) -> StdResult<Uint128> {
+    let key = match voting_power_cache_key(config, voting_module, height, voter) {
+        Some(key) => key,
+        None => {
+            return get_voting_power(
+                deps.as_ref(),
+                voter.clone(),
+                config.dao.clone(),
+                Some(height),
+            )
+        }
+    };
+    if let Some(power) = VOTING_POWER_CACHE.may_load(deps.storage, key.clone())? {
+        return Ok(power);
+    }
+    let power = get_voting_power(
+        deps.as_ref(),
+        voter.clone(),
+        config.dao.clone(),
+        Some(height),
+    )?;
+    VOTING_POWER_CACHE.save(deps.storage, key, &power)?;
+    Ok(power)
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    // Update status to ensure that proposals which were open and have
+    // expired are moved to "rejected."
+    prop.update_status(&env.block);
+    if prop.status != Status::Rejected {
+        return Err(ContractError::WrongCloseStatus {});
+    }
+
+    let (refund_message, changed_hooks) = close_proposal(
+        deps.storage,
+        &env.block,
+        &config,
+        &info.sender,
+        proposal_id,
+        prop,
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_submessages(refund_message)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_close_expired(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    // The limit bounds the proposals examined rather than those
+    // closed, so that open and closed proposals can not make closing
+    // cost unbounded gas.
+    let examined = PROPOSALS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+    let last_examined = examined.last().map(|(id, _)| *id);
+
+    let mut closed = vec![];
+    let mut response = Response::default();
+    for (proposal_id, mut prop) in examined {
+        if !prop.expiration.is_expired(&env.block)
+            || prop.current_status(&env.block) != Status::Rejected
+        {
+            continue;
+        }
+        prop.update_status(&env.block);
+        let (refund_message, changed_hooks) = close_proposal(
+            deps.storage,
+            &env.block,
+            &config,
+            &info.sender,
+            proposal_id,
+            prop,
+        )?;
+        response = response
+            .add_submessages(refund_message)
+            .add_submessages(changed_hooks);
+        closed.push(proposal_id);
+    }
+
+    if let Some(id) = last_examined {
+        response = response.add_attribute("last_examined", id.to_string());
+    }
+    Ok(response
+        .set_data(to_binary(&closed)?)
+        .add_attribute("action", "close_expired")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "proposal_ids",
+            closed
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ))
+}
+
+/// Closes a rejected proposal, returning the messages refunding or
+/// forfeiting its deposit and paying `closer` the module's close
+/// reward, and the proposal hooks for its status change.
+fn close_proposal(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    config: &Config,
+    closer: &Addr,
+    proposal_id: u64,
+    mut prop: SingleChoiceProposal,
+) -> Result<(Vec<SubMsg>, Vec<SubMsg>), ContractError> {
+    let old_status = prop.status;
+
+    // Voters who voted no are only rewarded if their votes rejected
+    // the proposal, not if it missed its quorum, expired undecided,
+    // or passed and was rejected as unable to execute.
+    let would_fail_execution = FAILED_EXECUTION_CHECKS.has(storage, proposal_id);
+    let winners = if !would_fail_execution && prop.is_rejected_by_votes(block) {
+        get_winning_voters(storage, &config.forfeited_deposit_policy, proposal_id)?
+    } else {
+        vec![]
+    };
+    // A proposal rejected after a failed execution attempt has
+    // already had its deposit returned with that attempt.
+    let deposit_returned = prop.last_execution_attempt.is_some();
+    // Rewards are only taken from deposits that are forfeited, so that
+    // refunded deposits are returned in full.
+    let reward = match &config.close_reward {
+        Some(reward) => {
+            let forfeited_deposit = match &prop.deposit_info {
+                Some(deposit_info)
+                    if !deposit_returned && !deposit_info.refund_failed_proposals =>
+                {
+                    Some(deposit_info.clone())
+                }
+                _ => None,
+            };
+            get_reward_msgs(&reward.source, &forfeited_deposit, reward.amount, closer)?
+        }
+        None => RewardPayment::default(),
+    };
+    let mut refund_message = if deposit_returned {
+        vec![]
+    } else {
+        get_closed_deposit_msg(
+            storage,
+            proposal_id,
+            &prop,
+            &config.dao,
+            &winners,
+            reward.from_deposit,
+        )?
+    };
+    refund_message.extend(reward.msgs.into_iter().map(SubMsg::new));
+
+    prop.status = Status::Closed;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = block.time;
+    PROPOSALS.save(storage, proposal_id, &prop)?;
+    let reason = if would_fail_execution {
+        ResolutionReason::WouldFailExecution
+    } else {
+        ResolutionReason::Rejected
+    };
+    finalize_result(storage, block, proposal_id, &mut prop, reason)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok((refund_message, changed_hooks))
+}
+
+pub fn execute_second(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.current_status(&env.block) != Status::Pending {
+        return Err(ContractError::NotPending { id: proposal_id });
+    }
+    if info.sender == prop.proposer {
+        return Err(ContractError::SecondOwnProposal {});
+    }
+
+    // Pending proposals are not checked for a change of voting
+    // module, so power queried here is not cached.
+    let power = query_snapshot_power(
+        deps.as_ref(),
+        &config,
+        &prop.voting_module,
+        prop.snapshot_height(),
+        &info.sender,
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    let old_status = prop.status;
+
+    // Start the voting period now that the proposal is open.
+    prop.status = Status::Open;
+    prop.seconding_deadline = None;
+    prop.expiration = config.max_voting_period.after(&env.block);
+    prop.min_voting_period = config.min_voting_period.map(|min| min.after(&env.block));
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_attribute("action", "second")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_sync_status(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let old_status = prop.status;
+    prop.update_status(&env.block);
+    if prop.status != old_status {
+        PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    }
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_attribute("action", "sync_status")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("old_status", old_status.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_poke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Proposals that need execution funds are never executed here, so
+    // any funds sent would be left with this module.
+    if !info.funds.is_empty() {
+        return Err(ContractError::PokeWithFunds {});
+    }
+
+    let executable = config.execute_on_pass
+        && prop.execution_funds.is_empty()
+        && check_executable(&mut prop.clone(), &env.block).is_ok()
+        && get_governance_freeze(deps.as_ref(), &config.dao).is_none()
+        && is_authorized_executor(deps.as_ref(), &config, info.sender.clone())?;
+    if executable {
+        execute_execute(deps, env, info, proposal_id)
+    } else {
+        execute_sync_status(deps, env, info, proposal_id)
+    }
+}
+
+pub fn execute_withdraw_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    prop.update_status(&env.block);
+    match prop.status {
+        Status::Passed => (),
+        Status::Executed | Status::ExecutionFailed => {
+            return Err(ContractError::AlreadyExecuted {})
+        }
+        _ => return Err(ContractError::NotPassed {}),
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let old_status = prop.status;
+
+    // Withdrawn proposals are treated like failed ones for the
+    // purposes of deposit refunds. If execution was already attempted
+    // the deposit has already been returned.
+    let refund_message = if prop.last_execution_attempt.is_some() {
+        vec![]
+    } else {
+        get_closed_deposit_msg(
+            deps.storage,
+            proposal_id,
+            &prop,
+            &config.dao,
+            &[],
+            Uint128::zero(),
+        )?
+    };
+
+    prop.status = Status::Closed;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    finalize_result(
+        deps.storage,
+        &env.block,
+        proposal_id,
+        &mut prop,
+        ResolutionReason::Withdrawn,
+    )?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_submessages(refund_message)
+        .add_attribute("action", "withdraw_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_transfer_proposal_authorship(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    new_proposer: String,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer {
+        return Err(ContractError::Unauthorized {});
+    }
+    match prop.current_status(&env.block) {
+        Status::Pending | Status::Open | Status::Passed => (),
+        _ => return Err(ContractError::AuthorshipNotTransferable { id: proposal_id }),
+    }
+
+    let new_proposer = deps.api.addr_validate(&new_proposer)?;
+    let config = CONFIG.load(deps.storage)?;
+    let power = get_voting_power(
+        deps.as_ref(),
+        new_proposer.clone(),
+        config.dao,
+        Some(env.block.height),
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NewProposerNotMember {});
+    }
+
+    if prop.original_proposer.is_none() {
+        prop.original_proposer = Some(prop.proposer.clone());
+    }
+    prop.proposer = new_proposer.clone();
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "transfer_proposal_authorship")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("new_proposer", new_proposer))
+}
+
+pub fn execute_force_resolve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    status: Status,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    let is_terminal = |status: Status| {
+        matches!(
+            status,
+            Status::Closed | Status::Executed | Status::ExecutionFailed
+        )
+    };
+    if !is_terminal(status) {
+        return Err(ContractError::NonTerminalForceStatus {});
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    prop.update_status(&env.block);
+    if is_terminal(prop.status) {
+        return Err(ContractError::AlreadyResolved { id: proposal_id });
+    }
+
+    // The deposit is handled as it would be had the proposal reached
+    // `status` normally. If execution was already attempted the
+    // deposit has already been returned.
+    let deposit_msgs = if prop.last_execution_attempt.is_some() {
+        vec![]
+    } else if status == Status::Closed {
+        get_closed_deposit_msg(
+            deps.storage,
+            proposal_id,
+            &prop,
+            &config.dao,
+            &[],
+            Uint128::zero(),
+        )?
+    } else {
+        let mut msgs = match &prop.deposit_info {
+            Some(deposit_info) => {
+                record_deposit(deps.storage, deposit_info, DepositMovement::Refunded)?;
+                get_refund_deposit_msgs(proposal_id, deposit_info, &prop.proposer)?
+            }
+            None => vec![],
+        };
+        msgs.extend(
+            get_native_deposit_msg(&prop.native_deposit, &prop.proposer)
+                .into_iter()
+                .map(SubMsg::new),
+        );
+        msgs
+    };
+
+    let old_status = prop.status;
+    prop.status = status;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    finalize_result(
+        deps.storage,
+        &env.block,
+        proposal_id,
+        &mut prop,
+        ResolutionReason::Forced,
+    )?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_submessages(deposit_msgs)
+        .add_attribute("action", "force_resolve")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("old_status", old_status.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_reject_failing_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !config.reject_failing_execution {
+        return Err(ContractError::RejectFailingExecutionDisabled {});
+    }
+
+    let mut prop = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let old_status = prop.status;
+    prop.update_status(&env.block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+    let error = check_execution(deps.as_ref(), &config.dao, &prop)?
+        .ok_or(ContractError::ExecutionCheckPassed {})?;
+
+    prop.status = Status::Rejected;
+    // Update proposal's last updated timestamp.
+    prop.last_updated = env.block.time;
+    PROPOSALS.save(deps.storage, proposal_id, &prop)?;
+    FAILED_EXECUTION_CHECKS.save(deps.storage, proposal_id, &error.to_string())?;
+
+    let changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
     )?;
 
-    Ok(Response::default()
-        .add_submessages(changed_hooks)
-        .add_attribute("action", "close")
-        .add_attribute("sender", info.sender)
-        .add_messages(refund_message)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+    Ok(Response::default()
+        .add_submessages(changed_hooks)
+        .add_attribute("action", "reject_failing_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("failed_check", error.to_string()))
+}
+
+pub fn execute_claim_refund(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let amount = PENDING_REFUNDS
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .ok_or(ContractError::NoPendingRefund { id: proposal_id })?;
+    let prop = PROPOSALS.load(deps.storage, proposal_id)?;
+    // Refunds are only recorded for proposals with a token deposit.
+    let deposit_info = prop
+        .deposit_info
+        .ok_or(ContractError::NoPendingRefund { id: proposal_id })?;
+    PENDING_REFUNDS.remove(deps.storage, (proposal_id, &info.sender));
+
+    // If the transfer fails again the refund remains claimable as
+    // this is not caught.
+    let refund_message = get_return_deposit_msg(
+        &CheckedDepositInfo {
+            deposit: amount,
+            ..deposit_info
+        },
+        &info.sender,
+    )?;
+
+    Ok(Response::default()
+        .add_messages(refund_message)
+        .add_attribute("action", "claim_refund")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Records the result of a proposal that has reached a terminal
+/// status, along with the time it was resolved. A proposal's result
+/// is only recorded the first time this is called for it and is
+/// never recomputed.
+fn finalize_result(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+    prop: &mut SingleChoiceProposal,
+    reason: ResolutionReason,
+) -> StdResult<()> {
+    if FINAL_RESULTS.has(storage, proposal_id) {
+        return Ok(());
+    }
+    prop.resolved_at = Some(block.time);
+    PROPOSALS.save(storage, proposal_id, prop)?;
+    RESOLVED_PROPOSALS.save(storage, (block.time.nanos(), proposal_id), &Empty {})?;
+    FINAL_RESULTS.save(
+        storage,
+        proposal_id,
+        &FinalResult {
+            status: prop.status,
+            reason,
+            votes: prop.votes.clone(),
+            total_power: prop.total_power,
+            threshold: prop.threshold.clone(),
+            height: block.height,
+            time: block.time,
+        },
+    )
+}
+
+/// Gets the messages needed to return a proposal's deposit when it is
+/// closed without being executed and records them in the deposit
+/// accounting. The deposit is returned to the proposer if failed
+/// proposals are refunded and is forfeited otherwise. Native deposits
+/// are always forfeited. Forfeited deposits are split among
+/// `winners`, weighted by their voting power, or sent to the DAO if
+/// there are none, less `spent` of the token deposit which has been
+/// paid out of it elsewhere.
+fn get_closed_deposit_msg(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    prop: &SingleChoiceProposal,
+    dao: &Addr,
+    winners: &[(Addr, Uint128)],
+    spent: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    let mut msgs = match &prop.deposit_info {
+        Some(deposit_info) if deposit_info.refund_failed_proposals => {
+            record_deposit(storage, deposit_info, DepositMovement::Refunded)?;
+            get_refund_deposit_msgs(proposal_id, deposit_info, &prop.proposer)?
+        }
+        Some(deposit_info) => {
+            record_deposit(storage, deposit_info, DepositMovement::Forfeited)?;
+            let remaining = deposit_info.deposit.checked_sub(spent)?;
+            let mut msgs = vec![];
+            for (receiver, deposit) in split_deposit(remaining, winners, dao) {
+                let share = CheckedDepositInfo {
+                    deposit,
+                    ..deposit_info.clone()
+                };
+                msgs.extend(
+                    get_return_deposit_msg(&share, &receiver)?
+                        .into_iter()
+                        .map(SubMsg::new),
+                );
+            }
+            msgs
+        }
+        None => vec![],
+    };
+    if let Some(deposit) = &prop.native_deposit {
+        for (receiver, amount) in split_deposit(deposit.amount, winners, dao) {
+            if !amount.is_zero() {
+                msgs.push(SubMsg::new(BankMsg::Send {
+                    to_address: receiver.to_string(),
+                    amount: vec![Coin {
+                        denom: deposit.denom.clone(),
+                        amount,
+                    }],
+                }));
+            }
+        }
+    }
+    Ok(msgs)
+}
+
+/// Splits `amount` among `recipients` in proportion to their weights.
+/// What is lost to rounding, or all of `amount` if there are no
+/// recipients, is sent to the DAO.
+fn split_deposit(
+    amount: Uint128,
+    recipients: &[(Addr, Uint128)],
+    dao: &Addr,
+) -> Vec<(Addr, Uint128)> {
+    let total = recipients
+        .iter()
+        .fold(Uint128::zero(), |total, (_, weight)| total + *weight);
+    if total.is_zero() {
+        return vec![(dao.clone(), amount)];
+    }
+    let mut shares: Vec<(Addr, Uint128)> = recipients
+        .iter()
+        .map(|(recipient, weight)| (recipient.clone(), amount.multiply_ratio(*weight, total)))
+        .collect();
+    let distributed = shares
+        .iter()
+        .fold(Uint128::zero(), |total, (_, share)| total + *share);
+    shares.push((dao.clone(), amount - distributed));
+    shares
+}
+
+/// Gets the voters who voted no on a proposal their votes rejected
+/// and their voting power, if its forfeited deposit is distributed
+/// among them. Empty if the deposit is sent to the DAO.
+fn get_winning_voters(
+    storage: &dyn Storage,
+    policy: &Option<ForfeitedDepositPolicy>,
+    proposal_id: u64,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    let max_voters = match policy {
+        Some(ForfeitedDepositPolicy::DistributeToWinningVoters { max_voters }) => {
+            *max_voters as usize
+        }
+        None => return Ok(vec![]),
+    };
+    // Only one more ballot than may be paid is loaded, so that
+    // closing a proposal with many voters stays cheap.
+    let winners = BALLOTS_BY_POSITION
+        .prefix((proposal_id, Vote::No.to_string()))
+        .range(storage, None, None, Order::Ascending)
+        .take(max_voters + 1)
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+    if winners.len() > max_voters {
+        Ok(vec![])
+    } else {
+        Ok(winners)
+    }
+}
+
+/// Gets the messages returning a proposal's token deposit to its
+/// proposer. If the transfer fails the deposit is recorded in
+/// `PENDING_REFUNDS` in `reply` so that the proposer may claim it
+/// later with `ClaimRefund`.
+fn get_refund_deposit_msgs(
+    proposal_id: u64,
+    deposit_info: &CheckedDepositInfo,
+    proposer: &Addr,
+) -> StdResult<Vec<SubMsg>> {
+    Ok(get_return_deposit_msg(deposit_info, proposer)?
+        .into_iter()
+        .map(|msg| SubMsg::reply_on_error(msg, mask_deposit_refund_proposal_id(proposal_id)))
+        .collect())
+}
+
+/// Gets the message sending a proposal's native deposit to
+/// `receiver`. None if the proposal has no native deposit.
+fn get_native_deposit_msg(deposit: &Option<Coin>, receiver: &Addr) -> Option<CosmosMsg> {
+    deposit.as_ref().map(|deposit| {
+        BankMsg::Send {
+            to_address: receiver.to_string(),
+            amount: vec![deposit.clone()],
+        }
+        .into()
+    })
+}
+
+/// The counter in `DepositAccounting` that a deposit is added to.
+enum DepositMovement {
+    Collected,
+    Refunded,
+    Forfeited,
+}
+
+/// Adds a proposal's deposit to the module's lifetime deposit
+/// accounting for its token.
+fn record_deposit(
+    storage: &mut dyn Storage,
+    deposit_info: &CheckedDepositInfo,
+    movement: DepositMovement,
+) -> StdResult<()> {
+    DEPOSIT_ACCOUNTING.update(storage, &deposit_info.token, |accounting| -> StdResult<_> {
+        let mut accounting = accounting.unwrap_or_default();
+        let counter = match movement {
+            DepositMovement::Collected => &mut accounting.collected,
+            DepositMovement::Refunded => &mut accounting.refunded,
+            DepositMovement::Forfeited => &mut accounting.forfeited,
+        };
+        *counter = counter.checked_add(deposit_info.deposit)?;
+        Ok(accounting)
+    })?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    threshold: Threshold,
+    max_voting_period: Duration,
+    min_voting_period: Option<Duration>,
+    only_members_execute: bool,
+    allow_revoting: bool,
+    dao: String,
+    deposit_info: Option<DepositInfo>,
+    close_proposal_on_execution_failure: bool,
+    exclude_dao_voting_power: bool,
+    execution_callback: Option<String>,
+    require_second: Option<Duration>,
+    record_config_history: bool,
+    quorum_reached_hooks: bool,
+    require_power_at_resolution: bool,
+    execution_retry_cooldown: Option<Duration>,
+    post_execution_cooldown: Option<Duration>,
+    quorum_extension: Option<QuorumExtension>,
+    native_propose_deposit: Option<Coin>,
+    snapshot_offset: u64,
+    pre_execution_hook: Option<String>,
+    required_description_sections: Vec<String>,
+    voting_period_limit: Option<VotingPeriodLimit>,
+    register_deposit_token: bool,
+    total_power_override: Option<Uint128>,
+    record_vote_history: bool,
+    execute_on_pass: bool,
+    reveal_period: Option<Duration>,
+    hook_allowlist: Option<Vec<String>>,
+    revote_settle_period: Option<Duration>,
+    deposit_escalation: Option<DepositEscalation>,
+    forfeited_deposit_policy: Option<ForfeitedDepositPolicy>,
+    reject_failing_execution: bool,
+    non_voter_default: Option<Vote>,
+    max_proposal_bytes: Option<u64>,
+    config_change_delay: Option<Duration>,
+    close_reward: Option<CloseReward>,
+    cache_voting_power: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != config.dao {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    threshold.validate()?;
+    let dao = deps.api.addr_validate(&dao)?;
+    let deposit_info = deposit_info
+        .map(|info| info.into_checked(deps.as_ref(), dao.clone()))
+        .transpose()?;
+    let execution_callback = execution_callback
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let pre_execution_hook = pre_execution_hook
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let hook_allowlist = validate_hook_allowlist(deps.as_ref(), hook_allowlist)?;
+    let close_reward = validate_close_reward(deps.as_ref(), close_reward)?;
+
+    let (min_voting_period, max_voting_period) =
+        validate_voting_period(min_voting_period, max_voting_period)?;
+    let voting_period_limit =
+        validate_voting_period_limit(voting_period_limit, &max_voting_period)?;
+    validate_quorum_extension(&quorum_extension, &threshold, &max_voting_period)?;
+    validate_reveal_period(&reveal_period, &max_voting_period)?;
+    validate_revote_settle_period(&revote_settle_period, &max_voting_period)?;
+    validate_deposit_escalation(&deposit_escalation)?;
+    validate_forfeited_deposit_policy(&forfeited_deposit_policy)?;
+    validate_native_propose_deposit(&native_propose_deposit, &deposit_info)?;
+    validate_total_power_override(total_power_override)?;
+    validate_max_proposal_bytes(max_proposal_bytes)?;
+    validate_config_change_delay(&config_change_delay)?;
+
+    let update = Config {
+        threshold,
+        max_voting_period,
+        min_voting_period,
+        only_members_execute,
+        allow_revoting,
+        dao,
+        deposit_info,
+        close_proposal_on_execution_failure,
+        exclude_dao_voting_power,
+        execution_callback,
+        require_second,
+        record_config_history,
+        quorum_reached_hooks,
+        require_power_at_resolution,
+        execution_retry_cooldown,
+        post_execution_cooldown,
+        quorum_extension,
+        native_propose_deposit,
+        snapshot_offset,
+        pre_execution_hook,
+        required_description_sections,
+        voting_period_limit,
+        register_deposit_token,
+        total_power_override,
+        record_vote_history,
+        execute_on_pass,
+        reveal_period,
+        hook_allowlist,
+        revote_settle_period,
+        deposit_escalation,
+        forfeited_deposit_policy,
+        reject_failing_execution,
+        non_voter_default,
+        max_proposal_bytes,
+        config_change_delay,
+        close_reward,
+        cache_voting_power,
+    };
+
+    let response = Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender);
+    match config.config_change_delay {
+        // Replaces any update that is already pending.
+        Some(delay) => {
+            let effective = delay.after(&env.block);
+            PENDING_CONFIG.save(
+                deps.storage,
+                &PendingConfig {
+                    config: update,
+                    effective,
+                },
+            )?;
+            Ok(response.add_attribute("effective", effective.to_string()))
+        }
+        None => {
+            save_config(deps.storage, &env.block, update)?;
+            Ok(response)
+        }
+    }
+}
+
+/// Gets the messages asking the DAO to add the deposit token to its
+/// cw20 list if it has not already been registered. The module can
+/// not act as the DAO while it is being instantiated, so this is done
+/// when proposals are created instead. Registering before the first
+/// deposit with the token is taken is sufficient for the DAO to track
+/// any deposits forfeited to it.
+fn get_register_deposit_token_msgs(
+    storage: &mut dyn Storage,
+    dao: &Addr,
+    deposit_info: &Option<CheckedDepositInfo>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let token = match deposit_info {
+        Some(deposit_info) => &deposit_info.token,
+        None => return Ok(vec![]),
+    };
+    if REGISTERED_DEPOSIT_TOKEN.may_load(storage)?.as_ref() == Some(token) {
+        return Ok(vec![]);
+    }
+    REGISTERED_DEPOSIT_TOKEN.save(storage, token)?;
+
+    let update_list: CosmosMsg = WasmMsg::Execute {
+        contract_addr: dao.to_string(),
+        msg: to_binary(&cw_core::msg::ExecuteMsg::UpdateCw20List {
+            to_add: vec![token.to_string()],
+            to_remove: vec![],
+        })?,
+        funds: vec![],
+    }
+    .into();
+    Ok(vec![WasmMsg::Execute {
+        contract_addr: dao.to_string(),
+        msg: to_binary(&cw_core::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: vec![update_list],
+        })?,
+        funds: vec![],
+    }
+    .into()])
+}
+
+/// Checks that a voting period limit is no larger than
+/// `MAX_VOTING_PERIOD_LIMIT` and that the max voting period is within
+/// it, returning the limit. Uses the default limit if none is
+/// provided.
+fn validate_voting_period_limit(
+    voting_period_limit: Option<VotingPeriodLimit>,
+    max_voting_period: &Duration,
+) -> Result<VotingPeriodLimit, ContractError> {
+    let limit = voting_period_limit.unwrap_or_default();
+    if limit.time > MAX_VOTING_PERIOD_LIMIT.time || limit.height > MAX_VOTING_PERIOD_LIMIT.height {
+        return Err(ContractError::VotingPeriodLimitTooLong {});
+    }
+    if !limit.allows(max_voting_period) {
+        return Err(ContractError::VotingPeriodTooLong {});
+    }
+    Ok(limit)
+}
+
+/// Checks that a quorum extension's durations have the same units as
+/// the max voting period, and so the same units as the expirations
+/// of proposals it extends, and that the threshold it is configured
+/// with has a quorum to extend voting on.
+fn validate_quorum_extension(
+    quorum_extension: &Option<QuorumExtension>,
+    threshold: &Threshold,
+    max_voting_period: &Duration,
+) -> Result<(), ContractError> {
+    let same_units = |duration: &Duration| {
+        matches!(
+            (duration, max_voting_period),
+            (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_))
+        )
+    };
+    match quorum_extension {
+        Some(extension) if !same_units(&extension.window) || !same_units(&extension.extension) => {
+            Err(ContractError::QuorumExtensionUnitsConflict {})
+        }
+        Some(_)
+            if matches!(
+                threshold,
+                Threshold::AbsolutePercentage { .. } | Threshold::AbsoluteCount { .. }
+            ) =>
+        {
+            Err(ContractError::QuorumExtensionWithoutQuorum {})
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a reveal period is non-zero and has the same units as
+/// the max voting period, so that a proposal's reveal phase can
+/// always be scheduled after its expiration.
+fn validate_reveal_period(
+    reveal_period: &Option<Duration>,
+    max_voting_period: &Duration,
+) -> Result<(), ContractError> {
+    match (reveal_period, max_voting_period) {
+        (None, _) => Ok(()),
+        (Some(Duration::Height(period)), Duration::Height(_))
+        | (Some(Duration::Time(period)), Duration::Time(_))
+            if *period != 0 =>
+        {
+            Ok(())
+        }
+        _ => Err(ContractError::InvalidRevealPeriod {}),
+    }
+}
+
+/// Checks that a revote settle period is non-zero and has the same
+/// units as the max voting period.
+fn validate_revote_settle_period(
+    revote_settle_period: &Option<Duration>,
+    max_voting_period: &Duration,
+) -> Result<(), ContractError> {
+    match (revote_settle_period, max_voting_period) {
+        (None, _) => Ok(()),
+        (Some(Duration::Height(period)), Duration::Height(_))
+        | (Some(Duration::Time(period)), Duration::Time(_))
+            if *period != 0 =>
+        {
+            Ok(())
+        }
+        _ => Err(ContractError::InvalidRevoteSettlePeriod {}),
+    }
+}
+
+/// Checks that a deposit escalation schedule is non-empty, has no
+/// more multipliers than proposals are remembered for each proposer,
+/// and never lowers the deposit.
+fn validate_deposit_escalation(
+    deposit_escalation: &Option<DepositEscalation>,
+) -> Result<(), ContractError> {
+    match deposit_escalation {
+        Some(DepositEscalation { multipliers })
+            if multipliers.is_empty()
+                || multipliers.len() > MAX_RECENT_PROPOSALS
+                || multipliers.contains(&0) =>
+        {
+            Err(ContractError::InvalidDepositEscalation {
+                max: MAX_RECENT_PROPOSALS as u64,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a forfeited deposit policy distributes deposits among
+/// at least one and at most `MAX_DEPOSIT_DISTRIBUTION_VOTERS` voters.
+fn validate_forfeited_deposit_policy(
+    policy: &Option<ForfeitedDepositPolicy>,
+) -> Result<(), ContractError> {
+    match policy {
+        Some(ForfeitedDepositPolicy::DistributeToWinningVoters { max_voters })
+            if *max_voters == 0 || *max_voters > MAX_DEPOSIT_DISTRIBUTION_VOTERS =>
+        {
+            Err(ContractError::InvalidForfeitedDepositPolicy {
+                max: MAX_DEPOSIT_DISTRIBUTION_VOTERS,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_id_prefix(prefix: &Option<String>) -> Result<(), ContractError> {
+    match prefix {
+        Some(prefix)
+            if prefix.len() > MAX_ID_PREFIX_LENGTH
+                || !prefix.starts_with(|c: char| c.is_ascii_uppercase())
+                || !prefix
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) =>
+        {
+            Err(ContractError::InvalidIdPrefix {
+                max: MAX_ID_PREFIX_LENGTH,
+            })
+        }
+        _ => Ok(()),
+    }
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn execute_update_config(
-    deps: DepsMut,
-    info: MessageInfo,
-    threshold: Threshold,
-    max_voting_period: Duration,
-    min_voting_period: Option<Duration>,
-    only_members_execute: bool,
-    allow_revoting: bool,
-    dao: String,
-    deposit_info: Option<DepositInfo>,
-    close_proposal_on_execution_failure: bool,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+/// Gets the multiplier that `escalation` applies to the deposit of
+/// `proposer` given the outcomes of their recent proposals. One if
+/// they have no rejections.
+fn get_deposit_multiplier(
+    deps: Deps,
+    block: &BlockInfo,
+    proposer: &Addr,
+    escalation: &DepositEscalation,
+) -> StdResult<u64> {
+    let recent = RECENT_PROPOSALS
+        .may_load(deps.storage, proposer)?
+        .unwrap_or_default();
+    let mut rejections = 0;
+    for id in recent.into_iter().rev() {
+        let prop = PROPOSALS.load(deps.storage, id)?;
+        // Proposals since transferred to another proposer are not
+        // counted.
+        if prop.proposer != *proposer {
+            continue;
+        }
+        match prop.current_status(block) {
+            Status::Rejected => rejections += 1,
+            Status::Closed => {
+                // Proposals withdrawn after passing are also closed.
+                let withdrawn = FINAL_RESULTS
+                    .may_load(deps.storage, id)?
+                    .map_or(false, |result| result.reason == ResolutionReason::Withdrawn);
+                if withdrawn {
+                    break;
+                }
+                rejections += 1;
+            }
+            Status::Passed | Status::Executed | Status::ExecutionFailed => break,
+            Status::Pending | Status::Open | Status::Revealing => (),
+        }
+    }
+    Ok(match rejections {
+        0 => 1,
+        n => escalation.multipliers[std::cmp::min(n, escalation.multipliers.len()) - 1],
+    })
+}
 
-    // Only the DAO may call this method.
-    if info.sender != config.dao {
-        return Err(ContractError::Unauthorized {});
+/// Multiplies the cw20 and native deposits of a new proposal by
+/// `multiplier`.
+fn escalate_deposit(proposal: &mut SingleChoiceProposal, multiplier: u64) -> StdResult<()> {
+    let multiplier = Uint128::from(multiplier);
+    if let Some(deposit_info) = &mut proposal.deposit_info {
+        deposit_info.deposit = deposit_info.deposit.checked_mul(multiplier)?;
     }
+    if let Some(deposit) = &mut proposal.native_deposit {
+        deposit.amount = deposit.amount.checked_mul(multiplier)?;
+    }
+    Ok(())
+}
 
-    threshold.validate()?;
-    let dao = deps.api.addr_validate(&dao)?;
-    let deposit_info = deposit_info
-        .map(|info| info.into_checked(deps.as_ref(), dao.clone()))
-        .transpose()?;
+/// Adds a proposal to its proposer's `RECENT_PROPOSALS`, dropping
+/// their oldest proposal once more than `MAX_RECENT_PROPOSALS` are
+/// stored.
+fn record_recent_proposal(storage: &mut dyn Storage, proposer: &Addr, id: u64) -> StdResult<()> {
+    RECENT_PROPOSALS.update(storage, proposer, |recent| -> StdResult<_> {
+        let mut recent = recent.unwrap_or_default();
+        recent.push(id);
+        if recent.len() > MAX_RECENT_PROPOSALS {
+            recent.remove(0);
+        }
+        Ok(recent)
+    })?;
+    Ok(())
+}
 
-    let (min_voting_period, max_voting_period) =
-        validate_voting_period(min_voting_period, max_voting_period)?;
+/// Validates the addresses on a hook allowlist.
+fn validate_hook_allowlist(
+    deps: Deps,
+    hook_allowlist: Option<Vec<String>>,
+) -> StdResult<Option<Vec<Addr>>> {
+    hook_allowlist
+        .map(|allowlist| {
+            allowlist
+                .into_iter()
+                .map(|addr| deps.api.addr_validate(&addr))
+                .collect()
+        })
+        .transpose()
+}
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            threshold,
-            max_voting_period,
-            min_voting_period,
-            only_members_execute,
-            allow_revoting,
-            dao,
-            deposit_info,
-            close_proposal_on_execution_failure,
-        },
-    )?;
+fn validate_close_reward(
+    deps: Deps,
+    close_reward: Option<CloseReward>,
+) -> Result<Option<CheckedCloseReward>, ContractError> {
+    close_reward
+        .map(|reward| {
+            if reward.amount.is_zero() {
+                return Err(ContractError::ZeroCloseReward {});
+            }
+            Ok(CheckedCloseReward {
+                source: reward.source.into_checked(deps)?,
+                amount: reward.amount,
+            })
+        })
+        .transpose()
+}
 
-    Ok(Response::default()
-        .add_attribute("action", "update_config")
-        .add_attribute("sender", info.sender))
+/// Checks that `hook` may be added as a consumer of hooks. If the
+/// module has a hook allowlist only addresses on it may be added
+/// unless `override_allowlist` is set.
+fn check_hook_allowed(
+    config: &Config,
+    hook: &Addr,
+    override_allowlist: bool,
+) -> Result<(), ContractError> {
+    match &config.hook_allowlist {
+        Some(allowlist) if !override_allowlist && !allowlist.contains(hook) => {
+            Err(ContractError::HookNotAllowlisted {
+                address: hook.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a native propose deposit is non-zero and is not
+/// configured alongside a cw20 deposit.
+fn validate_native_propose_deposit(
+    native_propose_deposit: &Option<Coin>,
+    deposit_info: &Option<CheckedDepositInfo>,
+) -> Result<(), ContractError> {
+    match native_propose_deposit {
+        Some(_) if deposit_info.is_some() => Err(ContractError::DepositConflict {}),
+        Some(deposit) if deposit.amount.is_zero() => Err(ContractError::ZeroNativeDeposit {}),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a total power override is non-zero. Proposals with no
+/// total power would pass or fail on any vote.
+fn validate_total_power_override(
+    total_power_override: Option<Uint128>,
+) -> Result<(), ContractError> {
+    match total_power_override {
+        Some(power) if power.is_zero() => Err(ContractError::ZeroTotalPowerOverride {}),
+        _ => Ok(()),
+    }
+}
+
+fn validate_max_proposal_bytes(max_proposal_bytes: Option<u64>) -> Result<(), ContractError> {
+    match max_proposal_bytes {
+        Some(max) if max == 0 || max > MAX_PROPOSAL_SIZE => {
+            Err(ContractError::InvalidMaxProposalBytes {
+                max: MAX_PROPOSAL_SIZE,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a config change delay is no longer than
+/// `MAX_CONFIG_CHANGE_DELAY`.
+fn validate_config_change_delay(
+    config_change_delay: &Option<Duration>,
+) -> Result<(), ContractError> {
+    match config_change_delay {
+        Some(delay) if !MAX_CONFIG_CHANGE_DELAY.allows(delay) => {
+            Err(ContractError::ConfigChangeDelayTooLong {})
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Saves the module's config, appending it to the config history if
+/// `record_config_history` is enabled and removing the oldest entry
+/// once more than `MAX_CONFIG_HISTORY` are stored.
+fn save_config(storage: &mut dyn Storage, block: &BlockInfo, config: Config) -> StdResult<()> {
+    CONFIG.save(storage, &config)?;
+    if config.record_config_history {
+        let id = CONFIG_HISTORY
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+        CONFIG_HISTORY.save(
+            storage,
+            id,
+            &ConfigHistoryEntry {
+                config,
+                height: block.height,
+                time: block.time,
+            },
+        )?;
+        if id >= MAX_CONFIG_HISTORY {
+            CONFIG_HISTORY.remove(storage, id - MAX_CONFIG_HISTORY);
+        }
+    }
+    Ok(())
+}
+
+/// Loads the module's config as of `block`, including a pending
+/// config update that has taken effect but not yet been applied.
+fn load_config(storage: &dyn Storage, block: &BlockInfo) -> StdResult<Config> {
+    match PENDING_CONFIG.may_load(storage)? {
+        Some(pending) if pending.effective.is_expired(block) => Ok(pending.config),
+        _ => CONFIG.load(storage),
+    }
+}
+
+/// Saves the pending config update, if any, as the module's config
+/// once it has taken effect.
+fn apply_pending_config(storage: &mut dyn Storage, block: &BlockInfo) -> StdResult<()> {
+    if let Some(pending) = PENDING_CONFIG.may_load(storage)? {
+        if pending.effective.is_expired(block) {
+            PENDING_CONFIG.remove(storage);
+            save_config(storage, block, pending.config)?;
+        }
+    }
+    Ok(())
 }
+
 pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
+    block: &BlockInfo,
     validated_address: Addr,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook(storage, block, validated_address)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -518,9 +3015,10 @@ pub fn remove_hook(
 
 pub fn execute_add_proposal_hook(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     address: String,
+    override_allowlist: bool,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -529,8 +3027,9 @@ pub fn execute_add_proposal_hook(
     }
 
     let validated_address = deps.api.addr_validate(&address)?;
+    check_hook_allowed(&config, &validated_address, override_allowlist)?;
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    add_hook(PROPOSAL_HOOKS, deps.storage, &env.block, validated_address)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
@@ -560,9 +3059,10 @@ pub fn execute_remove_proposal_hook(
 
 pub fn execute_add_vote_hook(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     address: String,
+    override_allowlist: bool,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -571,8 +3071,9 @@ pub fn execute_add_vote_hook(
     }
 
     let validated_address = deps.api.addr_validate(&address)?;
+    check_hook_allowed(&config, &validated_address, override_allowlist)?;
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(VOTE_HOOKS, deps.storage, &env.block, validated_address)?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
@@ -600,77 +3101,431 @@ pub fn execute_remove_vote_hook(
         .add_attribute("address", address))
 }
 
+pub fn execute_add_hooks(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_hooks: Vec<String>,
+    vote_hooks: Vec<String>,
+    override_allowlist: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can add hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Any error reverts the hooks already added, so either all of
+    // the hooks are added or none are.
+    let mut response = Response::default().add_attribute("action", "add_hooks");
+    for address in proposal_hooks {
+        let validated_address = deps.api.addr_validate(&address)?;
+        check_hook_allowed(&config, &validated_address, override_allowlist)?;
+        add_hook(PROPOSAL_HOOKS, deps.storage, &env.block, validated_address)?;
+        response = response.add_attribute("proposal_hook", address);
+    }
+    for address in vote_hooks {
+        let validated_address = deps.api.addr_validate(&address)?;
+        check_hook_allowed(&config, &validated_address, override_allowlist)?;
+        add_hook(VOTE_HOOKS, deps.storage, &env.block, validated_address)?;
+        response = response.add_attribute("vote_hook", address);
+    }
+    Ok(response)
+}
+
+pub fn execute_remove_hooks(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_hooks: Vec<String>,
+    vote_hooks: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.dao != info.sender {
+        // Only DAO can remove hooks
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut response = Response::default().add_attribute("action", "remove_hooks");
+    for address in proposal_hooks {
+        let validated_address = deps.api.addr_validate(&address)?;
+        remove_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+        response = response.add_attribute("proposal_hook", address);
+    }
+    for address in vote_hooks {
+        let validated_address = deps.api.addr_validate(&address)?;
+        remove_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+        response = response.add_attribute("vote_hook", address);
+    }
+    Ok(response)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::Config {} => query_config(deps, env),
+        QueryMsg::Dao {} => query_dao(deps, env),
+        QueryMsg::PendingConfig {} => query_pending_config(deps, env),
         QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
         QueryMsg::ProposalCount {} => query_proposal_count(deps),
         QueryMsg::GetVote { proposal_id, voter } => query_vote(deps, proposal_id, voter),
+        QueryMsg::CanRevote { proposal_id, voter } => {
+            query_can_revote(deps, env, proposal_id, voter)
+        }
         QueryMsg::ListVotes {
             proposal_id,
             start_after,
             limit,
         } => query_list_votes(deps, proposal_id, start_after, limit),
+        QueryMsg::ReverseVotes {
+            proposal_id,
+            start_before,
+            limit,
+        } => query_reverse_votes(deps, proposal_id, start_before, limit),
+        QueryMsg::ListVotesByPosition {
+            proposal_id,
+            position,
+            start_after,
+            limit,
+        } => query_list_votes_by_position(deps, proposal_id, position, start_after, limit),
         QueryMsg::Info {} => query_info(deps),
+        QueryMsg::MigrationInfo {} => query_migration_info(deps),
+        QueryMsg::FinalResult { proposal_id } => to_binary(&FinalResultResponse {
+            result: FINAL_RESULTS.may_load(deps.storage, proposal_id)?,
+        }),
         QueryMsg::ReverseProposals {
             start_before,
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ResolvedProposalsInRange {
+            from,
+            to,
+            start_after,
+            limit,
+        } => query_resolved_proposals_in_range(deps, env, from, to, start_after, limit),
+        QueryMsg::PowerNeededToPass { proposal_id } => {
+            query_power_needed_to_pass(deps, env, proposal_id)
+        }
+        QueryMsg::OutcomeIfRemainingVote { proposal_id, vote } => {
+            query_outcome_if_remaining_vote(deps, env, proposal_id, vote)
+        }
+        QueryMsg::VoterInfluence {
+            address,
+            start_after,
+            limit,
+        } => query_voter_influence(deps, env, address, start_after, limit),
+        QueryMsg::DepositMultiplier { address } => query_deposit_multiplier(deps, env, address),
+        QueryMsg::MinPeriodStatus { proposal_id } => {
+            query_min_period_status(deps, env, proposal_id)
+        }
+        QueryMsg::ProposalConfig { proposal_id } => query_proposal_config(deps, env, proposal_id),
+        QueryMsg::ParticipationStatus { proposal_id } => {
+            query_participation_status(deps, proposal_id)
+        }
+        QueryMsg::VoteCount { proposal_id } => query_vote_count(deps, proposal_id),
+        QueryMsg::PendingRefund {
+            proposal_id,
+            address,
+        } => query_pending_refund(deps, proposal_id, address),
+        QueryMsg::ValidateProposal {
+            proposer,
+            title,
+            description,
+            msgs,
+            expiration_override,
+            execution_funds,
+            related_proposals,
+            threshold_override,
+            metadata,
+        } => query_validate_proposal(
+            deps,
+            env,
+            proposer,
+            title,
+            description,
+            msgs,
+            expiration_override,
+            execution_funds,
+            related_proposals,
+            threshold_override,
+            metadata,
+        ),
+        QueryMsg::ProposalTargets { proposal_id } => query_proposal_targets(deps, proposal_id),
+        QueryMsg::ProposalDigest { proposal_id } => query_proposal_digest(deps, env, proposal_id),
+        QueryMsg::RelatedProposals { proposal_id } => {
+            query_related_proposals(deps, env, proposal_id)
+        }
+        QueryMsg::ProposalsWithMsgKind {
+            kind,
+            start_after,
+            limit,
+        } => query_proposals_with_msg_kind(deps, env, kind, start_after, limit),
+        QueryMsg::ExecutableByAddress {
+            address,
+            start_after,
+            limit,
+        } => query_executable_by_address(deps, env, address, start_after, limit),
+        QueryMsg::ConfigHistory { start_after, limit } => {
+            query_config_history(deps, start_after, limit)
+        }
+        QueryMsg::VoteHistory {
+            proposal_id,
+            start_after,
+            limit,
+        } => query_vote_history(deps, proposal_id, start_after, limit),
+        QueryMsg::DepositAccounting {} => query_deposit_accounting(deps),
+        QueryMsg::DepositSolvency { asset } => query_deposit_solvency(deps, env, asset),
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::EffectiveVotingPower { address, height } => {
+            query_effective_voting_power(deps, env, address, height)
+        }
     }
 }
 
-pub fn query_config(deps: Deps) -> StdResult<Binary> {
-    let config = CONFIG.load(deps.storage)?;
-    to_binary(&config)
-}
+pub fn query_config(deps: Deps, env: Env) -> StdResult<Binary> {
+    let config = load_config(deps.storage, &env.block)?;
+    to_binary(&config)
+}
+
+pub fn query_pending_config(deps: Deps, env: Env) -> StdResult<Binary> {
+    let pending = PENDING_CONFIG
+        .may_load(deps.storage)?
+        .filter(|pending| !pending.effective.is_expired(&env.block));
+    to_binary(&pending)
+}
+
+pub fn query_dao(deps: Deps, env: Env) -> StdResult<Binary> {
+    let config = load_config(deps.storage, &env.block)?;
+    to_binary(&config.dao)
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal.into_response(&env.block, id, &id_prefix))
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<ProposalResponse> = PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(&env.block, id, &id_prefix))
+        .collect();
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_resolved_proposals_in_range(
+    deps: Deps,
+    env: Env,
+    from: Timestamp,
+    to: Timestamp,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let resolved_after = match start_after {
+        Some(id) => {
+            // Without a resolution time there is no position in the
+            // range to start after.
+            let resolved_at = PROPOSALS
+                .load(deps.storage, id)?
+                .resolved_at
+                .ok_or_else(|| {
+                    StdError::generic_err(format!("Proposal ({}) has not been resolved", id))
+                })?;
+            Some((resolved_at, id)).filter(|(resolved_at, _)| *resolved_at >= from)
+        }
+        None => None,
+    };
+    let min = match resolved_after {
+        Some((resolved_at, id)) => Bound::exclusive((resolved_at.nanos(), id)),
+        None => Bound::inclusive((from.nanos(), 0)),
+    };
+    // Proposal IDs start at one, so this excludes all proposals
+    // resolved at `to`.
+    let max = Bound::exclusive((to.nanos(), 0));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props = RESOLVED_PROPOSALS
+        .keys(deps.storage, Some(min), Some(max), Order::Ascending)
+        .take(limit as usize)
+        .map(|key| {
+            let (_, id) = key?;
+            PROPOSALS
+                .load(deps.storage, id)
+                .map(|proposal| proposal.into_response(&env.block, id, &id_prefix))
+        })
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_related_proposals(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let proposals = proposal
+        .related_proposals
+        .into_iter()
+        .map(|id| {
+            PROPOSALS
+                .load(deps.storage, id)
+                .map(|proposal| proposal.into_response(&env.block, id, &id_prefix))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&ProposalListResponse { proposals })
+}
+
+pub fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let max = start_before.map(Bound::exclusive);
+    let props: Vec<ProposalResponse> = PROPOSALS
+        .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
+        .into_iter()
+        .map(|(id, proposal)| proposal.into_response(&env.block, id, &id_prefix))
+        .collect();
+
+    to_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_deposit_accounting(deps: Deps) -> StdResult<Binary> {
+    let deposits = DEPOSIT_ACCOUNTING
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(token, accounting)| TokenDepositAccounting {
+                token,
+                collected: accounting.collected,
+                refunded: accounting.refunded,
+                forfeited: accounting.forfeited,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&DepositAccountingResponse { deposits })
+}
+
+pub fn query_deposit_solvency(deps: Deps, env: Env, asset: DepositAsset) -> StdResult<Binary> {
+    let balance = match &asset {
+        DepositAsset::Cw20 { address } => {
+            let balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
+                deps.api.addr_validate(address)?,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            balance.balance
+        }
+        DepositAsset::Native { denom } => {
+            deps.querier
+                .query_balance(&env.contract.address, denom)?
+                .amount
+        }
+    };
+    // The deposit `prop` was created with, if it is in `asset`.
+    let deposit_in_asset = |prop: &SingleChoiceProposal| match &asset {
+        DepositAsset::Cw20 { address } => prop
+            .deposit_info
+            .as_ref()
+            .filter(|deposit_info| deposit_info.token.as_str() == address.as_str())
+            .map(|deposit_info| deposit_info.deposit),
+        DepositAsset::Native { denom } => prop
+            .native_deposit
+            .as_ref()
+            .filter(|deposit| deposit.denom == *denom)
+            .map(|deposit| deposit.amount),
+    };
+
+    // Deposits are taken when a proposal is created and released
+    // either on its first execution attempt or when it is closed or
+    // withdrawn. Proposals rejected after a failed execution attempt
+    // no longer hold their deposit.
+    let mut owed = Uint128::zero();
+    for item in PROPOSALS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, prop) = item?;
+        let held = prop.last_execution_attempt.is_none()
+            && match prop.status {
+                Status::Pending
+                | Status::Open
+                | Status::Revealing
+                | Status::Rejected
+                | Status::Passed => true,
+                Status::Executed | Status::ExecutionFailed | Status::Closed => false,
+            };
+        match deposit_in_asset(&prop) {
+            Some(deposit) if held => owed = owed.checked_add(deposit)?,
+            _ => (),
+        }
+    }
+    // Refunds that failed remain with the module until claimed. Only
+    // refunds of cw20 deposits are caught, so a proposal with a
+    // pending refund has no native deposit.
+    for item in PENDING_REFUNDS.range(deps.storage, None, None, Order::Ascending) {
+        let ((proposal_id, _), amount) = item?;
+        let prop = PROPOSALS.load(deps.storage, proposal_id)?;
+        if deposit_in_asset(&prop).is_some() {
+            owed = owed.checked_add(amount)?;
+        }
+    }
 
-pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
-    to_binary(&proposal.into_response(&env.block, id))
+    to_binary(&DepositSolvencyResponse {
+        asset,
+        balance,
+        owed,
+        solvent: balance >= owed,
+    })
 }
 
-pub fn query_list_proposals(
+pub fn query_config_history(
     deps: Deps,
-    env: Env,
     start_after: Option<u64>,
     limit: Option<u64>,
 ) -> StdResult<Binary> {
     let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let props: Vec<ProposalResponse> = PROPOSALS
-        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+    let entries = CONFIG_HISTORY
+        .range(deps.storage, min, None, Order::Ascending)
         .take(limit as usize)
-        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
-        .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
-        .collect();
+        .map(|item| item.map(|(id, entry)| ConfigHistoryEntryResponse { id, entry }))
+        .collect::<StdResult<Vec<_>>>()?;
 
-    to_binary(&ProposalListResponse { proposals: props })
+    to_binary(&ConfigHistoryResponse { entries })
 }
 
-pub fn query_reverse_proposals(
+pub fn query_vote_history(
     deps: Deps,
-    env: Env,
-    start_before: Option<u64>,
+    proposal_id: u64,
+    start_after: Option<u64>,
     limit: Option<u64>,
 ) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let max = start_before.map(Bound::exclusive);
-    let props: Vec<ProposalResponse> = PROPOSALS
-        .range(deps.storage, None, max, cosmwasm_std::Order::Descending)
+    let entries = VOTE_HISTORY
+        .prefix(proposal_id)
+        .range(deps.storage, min, None, Order::Ascending)
         .take(limit as usize)
-        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
-        .into_iter()
-        .map(|(id, proposal)| proposal.into_response(&env.block, id))
-        .collect();
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<Vec<_>>>()?;
 
-    to_binary(&ProposalListResponse { proposals: props })
+    to_binary(&VoteHistoryResponse { entries })
 }
 
 pub fn query_proposal_count(deps: Deps) -> StdResult<Binary> {
@@ -689,6 +3544,29 @@ pub fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<Bina
     to_binary(&VoteResponse { vote })
 }
 
+pub fn query_can_revote(
+    deps: Deps,
+    env: Env,
+    proposal_id: u64,
+    voter: String,
+) -> StdResult<Binary> {
+    let voter = deps.api.addr_validate(&voter)?;
+    let config = load_config(deps.storage, &env.block)?;
+    let prop = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    let result = assert_votable(deps, &config.dao, &env.block, proposal_id, &prop).and_then(|_| {
+        if BALLOTS.has(deps.storage, (proposal_id, voter)) {
+            assert_revote_allowed(&prop)
+        } else {
+            Err(ContractError::NotVoted { id: proposal_id })
+        }
+    });
+    to_binary(&CanRevoteResponse {
+        can_revote: result.is_ok(),
+        reason: result.err().map(|e| e.to_string()),
+    })
+}
+
 pub fn query_list_votes(
     deps: Deps,
     proposal_id: u64,
@@ -718,11 +3596,419 @@ pub fn query_list_votes(
     to_binary(&VoteListResponse { votes })
 }
 
+pub fn query_reverse_votes(
+    deps: Deps,
+    proposal_id: u64,
+    start_before: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_before = start_before
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let max = start_before.map(Bound::<Addr>::exclusive);
+
+    let votes = BALLOTS
+        .prefix(proposal_id)
+        .range(deps.storage, None, max, Order::Descending)
+        .take(limit as usize)
+        .map(|item| {
+            let (voter, ballot) = item?;
+            Ok(VoteInfo {
+                voter,
+                vote: ballot.vote,
+                power: ballot.power,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoteListResponse { votes })
+}
+
+pub fn query_list_votes_by_position(
+    deps: Deps,
+    proposal_id: u64,
+    position: Vote,
+    start_after: Option<String>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let min = start_after.map(Bound::<Addr>::exclusive);
+
+    let votes = BALLOTS_BY_POSITION
+        .prefix((proposal_id, position.to_string()))
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .map(|item| {
+            let (voter, power) = item?;
+            Ok(VoteInfo {
+                voter,
+                vote: position,
+                power,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&VoteListResponse { votes })
+}
+
+pub fn query_power_needed_to_pass(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal.power_needed_to_pass(&env.block))
+}
+
+pub fn query_outcome_if_remaining_vote(
+    deps: Deps,
+    env: Env,
+    id: u64,
+    vote: Vote,
+) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal.outcome_if_remaining_vote(&env.block, vote)?)
+}
+
+pub fn query_proposal_digest(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let fields = proposal.digest_fields(&env.block, id);
+    to_binary(&ProposalDigestResponse {
+        digest: fields.digest()?,
+        fields,
+    })
+}
+
+pub fn query_deposit_multiplier(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let config = load_config(deps.storage, &env.block)?;
+    let proposer = deps.api.addr_validate(&address)?;
+    let multiplier = match &config.deposit_escalation {
+        Some(escalation) => get_deposit_multiplier(deps, &env.block, &proposer, escalation)?,
+        None => 1,
+    };
+    to_binary(&multiplier)
+}
+
+pub fn query_voter_influence(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = load_config(deps.storage, &env.block)?;
+    let voter = deps.api.addr_validate(&address)?;
+
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    // The limit bounds the proposals examined rather than those
+    // returned, so that proposals the address can not vote on can not
+    // make the query cost unbounded gas.
+    let examined = PROPOSALS
+        .range(deps.storage, min, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+    let last_examined = examined.last().map(|(id, _)| *id);
+
+    let mut proposals = vec![];
+    for (proposal_id, prop) in examined {
+        if prop.reveal_period.is_some()
+            || prop.current_status(&env.block) != Status::Open
+            || assert_votable(deps, &config.dao, &env.block, proposal_id, &prop).is_err()
+        {
+            continue;
+        }
+        // Voters who have already voted vote again with the power on
+        // their ballot, replacing their previous vote.
+        let (power, previous) =
+            match BALLOTS.may_load(deps.storage, (proposal_id, voter.clone()))? {
+                Some(_) if !prop.allow_revoting => continue,
+                Some(ballot) => (ballot.power, Some(ballot.vote)),
+                None => {
+                    let power = query_snapshot_power(
+                        deps,
+                        &config,
+                        &prop.voting_module,
+                        prop.snapshot_height(),
+                        &voter,
+                    )?;
+                    (power, None)
+                }
+            };
+        if power.is_zero() {
+            continue;
+        }
+        proposals.push(ProposalInfluence {
+            proposal_id,
+            would_change_outcome: prop.vote_could_change_outcome(&env.block, power, previous)?,
+        });
+    }
+    to_binary(&VoterInfluenceResponse {
+        proposals,
+        last_examined,
+    })
+}
+
+pub fn query_effective_voting_power(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<Binary> {
+    let config = load_config(deps.storage, &env.block)?;
+    let address = deps.api.addr_validate(&address)?;
+    // Voting power may not yet be delegated so an address' effective
+    // power is the power the DAO's voting module reports for it.
+    let response: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+        config.dao,
+        &cw_core_interface::voting::Query::VotingPowerAtHeight {
+            address: address.into_string(),
+            height,
+        },
+    )?;
+    to_binary(&response)
+}
+
+pub fn query_min_period_status(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let remaining = proposal.min_voting_period_remaining(&env.block);
+    to_binary(&MinPeriodStatusResponse {
+        elapsed: remaining.is_none(),
+        remaining,
+        would_pass: proposal.would_pass_without_min_voting_period(&env.block),
+    })
+}
+
+pub fn query_proposal_config(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let config = load_config(deps.storage, &env.block)?;
+    to_binary(&ProposalConfigResponse {
+        close_proposal_on_execution_failure: proposal.closes_on_execution_failure(&config),
+        execution_retry_cooldown: proposal.execution_retry_cooldown,
+        snapshot_height: proposal.snapshot_height(),
+        threshold: proposal.threshold,
+        total_power: proposal.total_power,
+        expiration: proposal.expiration,
+        min_voting_period: proposal.min_voting_period,
+        allow_revoting: proposal.allow_revoting,
+        require_power_at_resolution: proposal.require_power_at_resolution,
+        deposit_info: proposal.deposit_info,
+        native_deposit: proposal.native_deposit,
+        non_voter_default: proposal.non_voter_default,
+    })
+}
+
+pub fn query_participation_status(deps: Deps, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&ParticipationStatusResponse {
+        power_cast: proposal.votes.total(),
+        total_power: proposal.total_power,
+        participation: proposal.participation(),
+    })
+}
+
+pub fn query_vote_count(deps: Deps, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+    to_binary(&proposal.vote_count)
+}
+
+pub fn query_pending_refund(deps: Deps, id: u64, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    to_binary(&PENDING_REFUNDS.may_load(deps.storage, (id, &address))?)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn query_validate_proposal(
+    deps: Deps,
+    env: Env,
+    proposer: Option<String>,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    expiration_override: Option<Expiration>,
+    execution_funds: Vec<Coin>,
+    related_proposals: Vec<u64>,
+    threshold_override: Option<Threshold>,
+    metadata: Option<String>,
+) -> StdResult<Binary> {
+    let config = load_config(deps.storage, &env.block)?;
+    let dao = config.dao.clone();
+    let proposer = proposer
+        .map(|proposer| deps.api.addr_validate(&proposer))
+        .transpose()?;
+
+    let mut errors = new_proposal(
+        deps,
+        &env,
+        config,
+        proposer
+            .clone()
+            .unwrap_or_else(|| env.contract.address.clone()),
+        title,
+        description,
+        msgs,
+        expiration_override,
+        execution_funds,
+        related_proposals,
+        threshold_override,
+        metadata,
+    )
+    .err()
+    .unwrap_or_default();
+    if let Some(proposer) = proposer {
+        errors.extend(check_proposer(deps, &env.block, &dao, &proposer)?);
+    }
+
+    to_binary(&ValidateProposalResponse {
+        errors: errors.into_iter().map(|e| e.to_string()).collect(),
+    })
+}
+
+pub fn query_proposal_targets(deps: Deps, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS.load(deps.storage, id)?;
+
+    let mut targets: Vec<ProposalTarget> = vec![];
+    for target in proposal.msgs.iter().filter_map(get_msg_target) {
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    to_binary(&ProposalTargetsResponse { targets })
+}
+
+pub fn query_proposals_with_msg_kind(
+    deps: Deps,
+    env: Env,
+    kind: ProposalTargetKind,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    // The limit bounds the proposals examined rather than those
+    // returned, so that proposals that do not match can not make the
+    // query cost unbounded gas.
+    let examined = PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+    let last_examined = examined.last().map(|(id, _)| *id);
+
+    let props: Vec<ProposalResponse> = examined
+        .into_iter()
+        .filter(|(_, proposal)| {
+            proposal
+                .msgs
+                .iter()
+                .filter_map(get_msg_target)
+                .any(|target| target.kind == kind)
+        })
+        .map(|(id, proposal)| proposal.into_response(&env.block, id, &id_prefix))
+        .collect();
+
+    to_binary(&FilteredProposalListResponse {
+        proposals: props,
+        last_examined,
+    })
+}
+
+pub fn query_executable_by_address(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let id_prefix = ID_PREFIX.may_load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let config = load_config(deps.storage, &env.block)?;
+    // Nothing may be executed while governance is frozen.
+    if !is_authorized_executor(deps, &config, address)?
+        || get_governance_freeze(deps, &config.dao).is_some()
+    {
+        return to_binary(&FilteredProposalListResponse {
+            proposals: vec![],
+            last_examined: None,
+        });
+    }
+
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    // The limit bounds the proposals examined rather than those
+    // returned, so that proposals that can not be executed can not
+    // make the query cost unbounded gas. This also bounds the number
+    // of pre-execution hook queries made.
+    let examined = PROPOSALS
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<(u64, SingleChoiceProposal)>>>()?;
+    let last_examined = examined.last().map(|(id, _)| *id);
+
+    let props: Vec<ProposalResponse> = examined
+        .into_iter()
+        .filter_map(|(id, mut proposal)| {
+            check_executable(&mut proposal, &env.block)
+                .ok()
+                .filter(|_| !is_vetoed_by_hook(deps, &config, id, &proposal))
+                .map(|_| proposal.into_response(&env.block, id, &id_prefix))
+        })
+        .collect();
+
+    to_binary(&FilteredProposalListResponse {
+        proposals: props,
+        last_examined,
+    })
+}
+
+/// Classifies a proposal message by the address (or type URL) it
+/// targets. Returns None for messages without a target, for example
+/// `WasmMsg::Instantiate`.
+fn get_msg_target(msg: &CosmosMsg<Empty>) -> Option<ProposalTarget> {
+    let (target, kind) = match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            (contract_addr, ProposalTargetKind::WasmExecute {})
+        }
+        CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }) => {
+            (contract_addr, ProposalTargetKind::WasmMigrate {})
+        }
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. }) => {
+            (contract_addr, ProposalTargetKind::WasmUpdateAdmin {})
+        }
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            (contract_addr, ProposalTargetKind::WasmClearAdmin {})
+        }
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => {
+            (to_address, ProposalTargetKind::BankSend {})
+        }
+        CosmosMsg::Stargate { type_url, .. } => (type_url, ProposalTargetKind::Stargate {}),
+        _ => return None,
+    };
+    Some(ProposalTarget {
+        target: target.clone(),
+        kind,
+    })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cw_core_interface::voting::InfoResponse { info })
 }
 
+pub fn query_migration_info(deps: Deps) -> StdResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    let mut migrations = supported_migrations(CONTRACT_VERSION);
+    migrations.push(SupportedMigration {
+        msg: "consolidate_duplicate_ballots".to_string(),
+        from_versions: vec![CONTRACT_VERSION.to_string()],
+    });
+    to_binary(&MigrationInfoResponse {
+        info,
+        supported_migrations: migrations,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
@@ -761,6 +4047,11 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
         pub deposit_info: Option<CheckedDepositInfo>,
     }
 
+    // Hooks registered before registration heights were recorded are
+    // reported as registered at height zero.
+    PROPOSAL_HOOKS.backfill_registrations(deps.storage)?;
+    VOTE_HOOKS.backfill_registrations(deps.storage)?;
+
     match msg {
         MigrateMsg::FromV1 {
             close_proposal_on_execution_failure,
@@ -769,9 +4060,20 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
             // `close_proposal_on_execution_falure` field.
             let config_item: Item<V1Config> = Item::new("config");
             let current_config = config_item.load(deps.storage)?;
-            CONFIG.save(
+            // Deposit accounting starts from zero for the current
+            // deposit token. Deposits taken before the migration are
+            // not counted.
+            if let Some(deposit_info) = &current_config.deposit_info {
+                DEPOSIT_ACCOUNTING.save(
+                    deps.storage,
+                    &deposit_info.token,
+                    &DepositAccounting::default(),
+                )?;
+            }
+            save_config(
                 deps.storage,
-                &Config {
+                &env.block,
+                Config {
                     threshold: current_config.threshold,
                     max_voting_period: current_config.max_voting_period,
                     min_voting_period: current_config.min_voting_period,
@@ -781,6 +4083,35 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                     deposit_info: current_config.deposit_info,
                     // Loads of text, but we're only updating this field.
                     close_proposal_on_execution_failure,
+                    exclude_dao_voting_power: false,
+                    execution_callback: None,
+                    require_second: None,
+                    record_config_history: false,
+                    quorum_reached_hooks: false,
+                    require_power_at_resolution: false,
+                    execution_retry_cooldown: None,
+                    post_execution_cooldown: None,
+                    quorum_extension: None,
+                    native_propose_deposit: None,
+                    snapshot_offset: 0,
+                    pre_execution_hook: None,
+                    required_description_sections: vec![],
+                    voting_period_limit: VotingPeriodLimit::default(),
+                    register_deposit_token: false,
+                    total_power_override: None,
+                    record_vote_history: false,
+                    execute_on_pass: false,
+                    reveal_period: None,
+                    hook_allowlist: None,
+                    revote_settle_period: None,
+                    deposit_escalation: None,
+                    forfeited_deposit_policy: None,
+                    reject_failing_execution: false,
+                    non_voter_default: None,
+                    max_proposal_bytes: None,
+                    config_change_delay: None,
+                    close_reward: None,
+                    cache_voting_power: false,
                 },
             )?;
 
@@ -818,6 +4149,33 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
                         // query for the true timestamp given `start_height`.
                         created: Timestamp::from_seconds(0),
                         last_updated: env.block.time,
+                        execution_error: None,
+                        seconding_deadline: None,
+                        execution_funds: vec![],
+                        quorum_reached: false,
+                        voting_module: None,
+                        require_power_at_resolution: false,
+                        related_proposals: vec![],
+                        last_execution_attempt: None,
+                        extensions: 0,
+                        native_deposit: None,
+                        snapshot_height: None,
+                        // Proposals made before the migration were
+                        // governed by the module's config at the time.
+                        close_proposal_on_execution_failure: Some(
+                            close_proposal_on_execution_failure,
+                        ),
+                        reveal_period: None,
+                        revote_settle_period: None,
+                        settlement: None,
+                        metadata: None,
+                        vote_count: None,
+                        voting_module_code_id: None,
+                        module_version: None,
+                        non_voter_default: None,
+                        resolved_at: None,
+                        execution_retry_cooldown: None,
+                        original_proposer: None,
                     };
 
                     PROPOSALS.save(deps.storage, id, &migrated_proposal)?;
@@ -828,26 +4186,212 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
             Ok(Response::default())
         }
 
-        MigrateMsg::FromCompatible {} => Ok(Response::default()),
+        MigrateMsg::FromCompatible {} => {
+            // Configs stored by earlier versions are missing the
+            // fields added since, which load with their defaults.
+            // Saving the config stores it in the current format.
+            if let Some(config) = CONFIG.may_load(deps.storage)? {
+                CONFIG.save(deps.storage, &config)?;
+                // As in `FromV1`, deposit accounting starts from zero
+                // for the current deposit token if it has none.
+                if let Some(deposit_info) = &config.deposit_info {
+                    if !DEPOSIT_ACCOUNTING.has(deps.storage, &deposit_info.token) {
+                        DEPOSIT_ACCOUNTING.save(
+                            deps.storage,
+                            &deposit_info.token,
+                            &DepositAccounting::default(),
+                        )?;
+                    }
+                }
+            }
+            // Pending config updates embed a config, so are stored in
+            // the current format in the same way once any that have
+            // taken effect are applied.
+            apply_pending_config(deps.storage, &env.block)?;
+            if let Some(pending) = PENDING_CONFIG.may_load(deps.storage)? {
+                PENDING_CONFIG.save(deps.storage, &pending)?;
+            }
+
+            // Index proposals resolved before resolution times were
+            // recorded on proposals using the time their result was
+            // recorded.
+            let results = FINAL_RESULTS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<(u64, FinalResult)>>>()?;
+            for (id, result) in results {
+                let mut prop = PROPOSALS.load(deps.storage, id)?;
+                if prop.resolved_at.is_none() {
+                    prop.resolved_at = Some(result.time);
+                    PROPOSALS.save(deps.storage, id, &prop)?;
+                    RESOLVED_PROPOSALS.save(deps.storage, (result.time.nanos(), id), &Empty {})?;
+                }
+            }
+            Ok(Response::default())
+        }
+
+        MigrateMsg::ConsolidateDuplicateBallots {} => {
+            let (proposal_ids, removed) = consolidate_duplicate_ballots(deps.storage)?;
+            Ok(Response::default()
+                .add_attribute("action", "consolidate_duplicate_ballots")
+                .add_attribute(
+                    "proposal_ids",
+                    proposal_ids
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                )
+                .add_attribute("removed_ballots", removed.to_string()))
+        }
+    }
+}
+
+/// Merges ballots on the same proposal from addresses that are equal
+/// ignoring case into a single ballot under the lowercase address and
+/// recomputes the votes of the affected proposals from their
+/// remaining ballots. The most recently cast ballot is kept, see
+/// `MigrateMsg::ConsolidateDuplicateBallots`. Returns the IDs of the
+/// affected proposals and the number of ballots removed.
+fn consolidate_duplicate_ballots(storage: &mut dyn Storage) -> StdResult<(Vec<u64>, u64)> {
+    let ballots = BALLOTS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<((u64, Addr), Ballot)>>>()?;
+
+    let mut voters: BTreeMap<(u64, String), Vec<(Addr, Ballot)>> = BTreeMap::new();
+    for ((proposal_id, voter), ballot) in ballots {
+        voters
+            .entry((proposal_id, voter.as_str().to_lowercase()))
+            .or_default()
+            .push((voter, ballot));
+    }
+
+    let mut proposal_ids = vec![];
+    let mut removed = 0;
+    for ((proposal_id, normalized), ballots) in voters {
+        if ballots.len() < 2 {
+            continue;
+        }
+        // Ballots without a height are older than those with one, and
+        // `max_by_key` picks the last of equally recent ballots.
+        let kept = ballots
+            .iter()
+            .max_by_key(|(_, ballot)| ballot.height)
+            .map(|(_, ballot)| ballot.clone())
+            .unwrap();
+        for (voter, ballot) in &ballots {
+            BALLOTS_BY_POSITION.remove(
+                storage,
+                (proposal_id, ballot.vote.to_string(), voter.clone()),
+            );
+            BALLOTS.remove(storage, (proposal_id, voter.clone()));
+        }
+        let voter = Addr::unchecked(normalized);
+        BALLOTS.save(storage, (proposal_id, voter.clone()), &kept)?;
+        BALLOTS_BY_POSITION.save(
+            storage,
+            (proposal_id, kept.vote.to_string(), voter),
+            &kept.power,
+        )?;
+        removed += ballots.len() as u64 - 1;
+        if proposal_ids.last() != Some(&proposal_id) {
+            proposal_ids.push(proposal_id);
+        }
+    }
+
+    for proposal_id in &proposal_ids {
+        let mut prop = PROPOSALS.load(storage, *proposal_id)?;
+        let mut votes = Votes::zero();
+        let mut count = 0;
+        for ballot in BALLOTS
+            .prefix(*proposal_id)
+            .range(storage, None, None, Order::Ascending)
+        {
+            let (_, ballot) = ballot?;
+            votes.add_vote(ballot.vote, ballot.power)?;
+            count += 1;
+        }
+        prop.votes = votes;
+        prop.vote_count = Some(count);
+        PROPOSALS.save(storage, *proposal_id, &prop)?;
     }
+
+    Ok((proposal_ids, removed))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
-        TaggedReplyId::FailedProposalExecution(proposal_id) => {
-            PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                Some(mut prop) => {
-                    prop.status = Status::ExecutionFailed;
-                    // Update proposal's last updated timestamp.
-                    prop.last_updated = env.block.time;
-                    Ok(prop)
+        TaggedReplyId::FailedProposalExecution(proposal_id) => match msg.result {
+            // Replies on success are only requested when there is an
+            // execution callback to send or failures are caught.
+            SubMsgResult::Ok(_) => {
+                let config = CONFIG.load(deps.storage)?;
+                let mut prop = PROPOSALS
+                    .may_load(deps.storage, proposal_id)?
+                    .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+                EXECUTION_SPONSORS.remove(deps.storage, proposal_id);
+                finalize_result(
+                    deps.storage,
+                    &env.block,
+                    proposal_id,
+                    &mut prop,
+                    ResolutionReason::Executed,
+                )?;
+                let callback =
+                    get_execution_callback_msg(&config.execution_callback, proposal_id, prop.msgs)?;
+                Ok(Response::new()
+                    .add_submessages(callback)
+                    .add_attribute("proposal executed", proposal_id.to_string()))
+            }
+            SubMsgResult::Err(error) => {
+                // Best-effort categorization of the failure. See
+                // `ExecutionError` for why this is only a heuristic.
+                let execution_error = ExecutionError::from_error_string(error);
+                let config = CONFIG.load(deps.storage)?;
+                let mut prop = PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
+                    Some(mut prop) => {
+                        if prop.closes_on_execution_failure(&config) {
+                            prop.status = Status::ExecutionFailed;
+                        } else {
+                            // The failure was only caught to record
+                            // the attempt, so the proposal may be
+                            // executed again once the cooldown ends.
+                            prop.status = Status::Passed;
+                            prop.last_execution_attempt = Some(ExecutionAttempt::new(&env.block));
+                        }
+                        prop.execution_error = Some(execution_error);
+                        // Update proposal's last updated timestamp.
+                        prop.last_updated = env.block.time;
+                        Ok(prop)
+                    }
+                    None => Err(ContractError::NoSuchProposal { id: proposal_id }),
+                })?;
+                if prop.status == Status::ExecutionFailed {
+                    finalize_result(
+                        deps.storage,
+                        &env.block,
+                        proposal_id,
+                        &mut prop,
+                        ResolutionReason::ExecutionFailed,
+                    )?;
                 }
-                None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-            })?;
-            Ok(Response::new().add_attribute("proposal execution failed", proposal_id.to_string()))
-        }
+                // Return any execution funds to whoever provided them.
+                let refund = match EXECUTION_SPONSORS.may_load(deps.storage, proposal_id)? {
+                    Some(sponsor) => {
+                        EXECUTION_SPONSORS.remove(deps.storage, proposal_id);
+                        vec![BankMsg::Send {
+                            to_address: sponsor.into_string(),
+                            amount: prop.execution_funds,
+                        }]
+                    }
+                    None => vec![],
+                };
+                Ok(Response::new()
+                    .add_messages(refund)
+                    .add_attribute("proposal execution failed", proposal_id.to_string()))
+            }
+        },
         TaggedReplyId::FailedProposalHook(idx) => {
             let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
             Ok(Response::new().add_attribute("removed proposal hook", format!("{addr}:{idx}")))
@@ -856,5 +4400,24 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
             let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
             Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
         }
+        TaggedReplyId::FailedExecutionCallback(proposal_id) => {
+            Ok(Response::new().add_attribute("execution callback failed", proposal_id.to_string()))
+        }
+        TaggedReplyId::FailedDepositRefund(proposal_id) => {
+            let prop = PROPOSALS
+                .may_load(deps.storage, proposal_id)?
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+            // Only deposits returned to the proposer are caught.
+            if let Some(deposit_info) = prop.deposit_info {
+                PENDING_REFUNDS.update(
+                    deps.storage,
+                    (proposal_id, &prop.proposer),
+                    |pending| -> StdResult<_> {
+                        Ok(pending.unwrap_or_default() + deposit_info.deposit)
+                    },
+                )?;
+            }
+            Ok(Response::new().add_attribute("deposit refund failed", proposal_id.to_string()))
+        }
     }
 }
@@ -1,6 +1,7 @@
 use std::u64;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Timestamp};
+use cw_utils::Expiration;
 use indexable_hooks::HookError;
 use thiserror::Error;
 use voting::reply::error::TagError;
@@ -25,39 +26,151 @@ pub enum ContractError {
     #[error("Suggested proposal expiration is larger than the maximum proposal duration")]
     InvalidExpiration {},
 
+    #[error("Suggested proposal expiration has already passed")]
+    ExpirationInPast {},
+
+    #[error("Execution funds must have unique denoms and non-zero amounts, and may only be set on proposals with messages")]
+    InvalidExecutionFunds {},
+
+    #[error("Funds sent must exactly match the proposal's execution funds")]
+    ExecutionFundsMismatch {},
+
+    #[error("Poke does not accept funds")]
+    PokeWithFunds {},
+
+    #[error("Execution of the proposal was vetoed by the pre-execution hook")]
+    ExecutionVetoedByHook {},
+
+    #[error("Proposals may not be executed until the DAO's governance freeze lifts at ({until})")]
+    GovernanceFrozen { until: Timestamp },
+
+    #[error("Proposal message ({index}) is empty")]
+    EmptyProposalMessage { index: usize },
+
+    #[error("Proposal message ({index}) targets ({address}) which is not a contract")]
+    MissingExecutionTarget { index: usize, address: String },
+
+    #[error("The DAO does not have enough ({denom}) to execute the proposal")]
+    InsufficientExecutionFunds { denom: String },
+
+    #[error("Proposal description is missing the required section ({section})")]
+    MissingDescriptionSection { section: String },
+
+    #[error("Max voting period is longer than the module's voting period limit")]
+    VotingPeriodTooLong {},
+
+    #[error("Voting period limit is longer than the largest allowed limit")]
+    VotingPeriodLimitTooLong {},
+
+    #[error("Proposal is not pending ({id})")]
+    NotPending { id: u64 },
+
+    #[error("Proposers may not second their own proposals")]
+    SecondOwnProposal {},
+
+    #[error("Proposals that must be seconded may not override their expiration")]
+    SecondWithExpirationOverride {},
+
     #[error("No such proposal ({id})")]
     NoSuchProposal { id: u64 },
 
     #[error("Proposal is ({size}) bytes, must be <= ({max}) bytes")]
     ProposalTooLarge { size: u64, max: u64 },
 
+    #[error("Proposal metadata is ({size}) bytes, must be <= ({max}) bytes")]
+    MetadataTooLarge { size: u64, max: u64 },
+
     #[error("Proposal is not open ({id})")]
     NotOpen { id: u64 },
 
     #[error("Proposal is expired ({id})")]
     Expired { id: u64 },
 
+    #[error("Voting on proposal ({id}) closed when it expired at ({expired_at})")]
+    ProposalExpired { id: u64, expired_at: Expiration },
+
     #[error("Not registered to vote (no voting power) at time of proposal creation.")]
     NotRegistered {},
 
+    #[error("The DAO's voting module has changed since proposal ({id}) was created")]
+    VotingModuleChanged { id: u64 },
+
+    #[error("A vote batch must have between 1 and ({max}) votes")]
+    InvalidVoteBatchSize { max: u64 },
+
     #[error("Already voted. This proposal does not support revoting.")]
     AlreadyVoted {},
 
     #[error("Already cast a vote with that option. Change your vote to revote.")]
     AlreadyCast {},
 
+    #[error(
+        "Proposal ({id}) uses commit-reveal voting. Commit to a vote with CommitVote instead."
+    )]
+    CommitRevealProposal { id: u64 },
+
+    #[error("Proposal ({id}) does not use commit-reveal voting")]
+    NotCommitRevealProposal { id: u64 },
+
+    #[error("Proposal ({id}) is not in its reveal phase")]
+    NotRevealing { id: u64 },
+
+    #[error("No unrevealed vote commitment on this proposal")]
+    NoCommitment {},
+
+    #[error("Revealed vote and salt do not match the commitment")]
+    CommitmentMismatch {},
+
+    #[error("Has not voted on proposal ({id}) so there is no vote to change")]
+    NotVoted { id: u64 },
+
     #[error("Proposal is not in 'passed' state.")]
     NotPassed {},
 
     #[error("Proposal has already been executed.")]
     AlreadyExecuted {},
 
+    #[error("No refund of proposal ({id})'s deposit is pending for this address")]
+    NoPendingRefund { id: u64 },
+
+    #[error("Authorship of proposal ({id}) may only be transferred while it is pending, open, or passed")]
+    AuthorshipNotTransferable { id: u64 },
+
+    #[error("The new proposer has no voting power")]
+    NewProposerNotMember {},
+
+    #[error("Vote would overflow the proposal's vote count.")]
+    Overflow {},
+
+    #[error("Proposal execution recently failed, it may be retried at ({retry_at})")]
+    ExecutionCooldown { retry_at: Expiration },
+
+    #[error(
+        "A proposal by this proposer was recently executed, they may propose again at ({until})"
+    )]
+    ProposeCooldown { until: Expiration },
+
+    #[error("A proposal's threshold override must be of the same kind as the module's threshold and at least as strict")]
+    ThresholdOverrideNotStricter {},
+
     #[error("Proposal is closed.")]
     Closed {},
 
     #[error("Only rejected proposals may be closed.")]
     WrongCloseStatus {},
 
+    #[error("Proposals may only be forced into a terminal status (closed, executed, or execution_failed)")]
+    NonTerminalForceStatus {},
+
+    #[error("Proposal's messages pass the execution checks")]
+    ExecutionCheckPassed {},
+
+    #[error("Proposals are only checked for failing execution if reject_failing_execution is set")]
+    RejectFailingExecutionDisabled {},
+
+    #[error("Proposal ({id}) has already reached a terminal status")]
+    AlreadyResolved { id: u64 },
+
     #[error("The DAO is currently inactive, you cannot create proposals")]
     InactiveDao {},
 
@@ -67,6 +180,51 @@ pub enum ContractError {
     #[error("Min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
 
+    #[error("quorum_extension window and extension must have the same units as max_voting_period (height or time)")]
+    QuorumExtensionUnitsConflict {},
+
+    #[error("quorum_extension requires a threshold with a quorum")]
+    QuorumExtensionWithoutQuorum {},
+
+    #[error("reveal_period must be non-zero and have the same units as max_voting_period (height or time)")]
+    InvalidRevealPeriod {},
+
+    #[error("revote_settle_period must be non-zero and have the same units as max_voting_period (height or time)")]
+    InvalidRevoteSettlePeriod {},
+
+    #[error("deposit_escalation must have between 1 and ({max}) multipliers, each at least one")]
+    InvalidDepositEscalation { max: u64 },
+
+    #[error("Forfeited deposits may be distributed among between 1 and ({max}) voters")]
+    InvalidForfeitedDepositPolicy { max: u64 },
+
+    #[error("id_prefix must be between 1 and ({max}) uppercase ASCII letters and digits, starting with a letter")]
+    InvalidIdPrefix { max: usize },
+
+    #[error("({address}) is not on the hook allowlist. Set override_allowlist to add it anyway.")]
+    HookNotAllowlisted { address: String },
+
+    #[error("Only one of deposit_info and native_propose_deposit may be set")]
+    DepositConflict {},
+
+    #[error("native_propose_deposit must be non-zero")]
+    ZeroNativeDeposit {},
+
+    #[error("total_power_override must be non-zero")]
+    ZeroTotalPowerOverride {},
+
+    #[error("max_proposal_bytes must be between 1 and ({max})")]
+    InvalidMaxProposalBytes { max: u64 },
+
+    #[error("config_change_delay may be at most one year")]
+    ConfigChangeDelayTooLong {},
+
+    #[error("close_reward must be non-zero")]
+    ZeroCloseReward {},
+
+    #[error("Proposing requires sending exactly ({expected}) as a deposit")]
+    NativeDepositMismatch { expected: Coin },
+
     #[error("{0}")]
     Tag(#[from] TagError),
 }
@@ -11,7 +11,7 @@ use voting::{
 
 use crate::{
     contract::reply,
-    proposal::SingleChoiceProposal,
+    proposal::{ExecutionError, SingleChoiceProposal},
     state::{PROPOSALS, PROPOSAL_HOOKS, VOTE_HOOKS},
 };
 
@@ -45,6 +45,29 @@ fn test_reply_proposal_mock() {
                 deposit_info: None,
                 created: env.block.time,
                 last_updated: env.block.time,
+                execution_error: None,
+                seconding_deadline: None,
+                execution_funds: vec![],
+                quorum_reached: false,
+                voting_module: None,
+                require_power_at_resolution: false,
+                related_proposals: vec![],
+                last_execution_attempt: None,
+                extensions: 0,
+                native_deposit: None,
+                snapshot_height: None,
+                close_proposal_on_execution_failure: None,
+                reveal_period: None,
+                revote_settle_period: None,
+                settlement: None,
+                metadata: None,
+                vote_count: None,
+                voting_module_code_id: None,
+                module_version: None,
+                non_voter_default: None,
+                resolved_at: None,
+                execution_retry_cooldown: None,
+                original_proposer: None,
             },
         )
         .unwrap();
@@ -65,6 +88,32 @@ fn test_reply_proposal_mock() {
 
     let prop = PROPOSALS.load(deps.as_mut().storage, 1).unwrap();
     assert_eq!(prop.status, Status::ExecutionFailed);
+    assert_eq!(
+        prop.execution_error,
+        Some(ExecutionError::Reverted {
+            error: "error_msg".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_execution_error_out_of_gas() {
+    assert_eq!(
+        ExecutionError::from_error_string(
+            "out of gas in location: wasm contract; gasWanted: 100, gasUsed: 200".to_string()
+        ),
+        ExecutionError::OutOfGas {}
+    );
+    assert_eq!(
+        ExecutionError::from_error_string("Out Of Gas".to_string()),
+        ExecutionError::OutOfGas {}
+    );
+    assert_eq!(
+        ExecutionError::from_error_string("Cannot Sub with 0 and 2000".to_string()),
+        ExecutionError::Reverted {
+            error: "Cannot Sub with 0 and 2000".to_string()
+        }
+    );
 }
 
 #[test]
@@ -75,7 +124,11 @@ fn test_reply_hooks_mock() {
     // Proposal hook
     let m_proposal_hook_idx = mask_proposal_hook_index(0);
     PROPOSAL_HOOKS
-        .add_hook(deps.as_mut().storage, Addr::unchecked(CREATOR_ADDR))
+        .add_hook(
+            deps.as_mut().storage,
+            &env.block,
+            Addr::unchecked(CREATOR_ADDR),
+        )
         .unwrap();
 
     let reply_msg = Reply {
@@ -94,7 +147,11 @@ fn test_reply_hooks_mock() {
     // Vote hook
     let m_vote_hook_idx = mask_vote_hook_index(0);
     VOTE_HOOKS
-        .add_hook(deps.as_mut().storage, Addr::unchecked(CREATOR_ADDR))
+        .add_hook(
+            deps.as_mut().storage,
+            &env.block,
+            Addr::unchecked(CREATOR_ADDR),
+        )
         .unwrap();
 
     let reply_msg = Reply {
@@ -1,10 +1,25 @@
-use cosmwasm_std::{CosmosMsg, Empty};
-use cw_utils::Duration;
+use cosmwasm_std::{Binary, Coin, CosmosMsg, Empty, Timestamp, Uint128};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cw_core_macros::govmod_query;
-use voting::{deposit::DepositInfo, threshold::Threshold, voting::Vote};
+use voting::{
+    deposit::DepositInfo, reward::RewardSource, status::Status, threshold::Threshold, voting::Vote,
+};
+
+use crate::query::ProposalTargetKind;
+use crate::state::{DepositEscalation, ForfeitedDepositPolicy, QuorumExtension, VotingPeriodLimit};
+
+/// A token proposal deposits may be taken in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositAsset {
+    /// The cw20 token at the given address.
+    Cw20 { address: String },
+    /// A native token with the given denom.
+    Native { denom: String },
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -40,6 +55,227 @@ pub struct InstantiateMsg {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set to true the DAO's own voting power, for example
+    /// governance tokens held in its treasury, is subtracted from the
+    /// total voting power recorded on new proposals, so that
+    /// thresholds and quorums are measured against the voting power
+    /// held outside of the DAO.
+    ///
+    /// This is narrower than circulating supply. Only the voting
+    /// power the voting module attributes to the DAO's own address is
+    /// excluded. Tokens that can not vote for other reasons, for
+    /// example those held by vesting or staking contracts, still
+    /// count towards the total. With a staking voting module the
+    /// DAO's treasury balance only counts if the DAO has staked it.
+    /// Use `total_power_override` to set an exact total.
+    pub exclude_dao_voting_power: bool,
+    /// A contract to send an `ExecutionCallbackMsg` to after a
+    /// proposal is successfully executed. Failures of the callback
+    /// do not affect the proposal's execution. None if no callback.
+    pub execution_callback: Option<String>,
+    /// If Some, new proposals start out `Pending` and must be
+    /// seconded by a member other than the proposer within this
+    /// amount of time before they open for voting. Proposals that are
+    /// not seconded in time are rejected.
+    pub require_second: Option<Duration>,
+    /// If set to true every config this module is updated to is
+    /// appended to a log that may be read with the `ConfigHistory`
+    /// query. Only the most recent `MAX_CONFIG_HISTORY` configs are
+    /// kept.
+    pub record_config_history: bool,
+    /// If set to true proposal hook consumers are sent
+    /// `ProposalHookMsg::QuorumReached` the first time a proposal
+    /// reaches quorum. Consumers that error on messages they do not
+    /// recognize are removed, so only enable this once every consumer
+    /// supports it.
+    pub quorum_reached_hooks: bool,
+    /// If set to true, when a proposal is executed the voting power
+    /// of each of its voters is checked again and the votes of those
+    /// with no voting power are removed from the tally. A proposal
+    /// that no longer passes is rejected instead of executed, or if it
+    /// has not expired returns to being open, which proposal hooks
+    /// report as a change from `passed` to `open`. Addresses without
+    /// voting power may not vote on these proposals, so voters whose
+    /// votes are removed may not vote again. This queries the voting
+    /// power of every voter on the proposal, so the gas cost of
+    /// execution grows with the number of voters. This will only
+    /// apply to proposals created after this is set.
+    pub require_power_at_resolution: bool,
+    /// If set, after a failed attempt to execute a proposal that
+    /// remains passed (`close_proposal_on_execution_failure` is
+    /// false) it may not be executed again until this much time has
+    /// passed. This keeps automated retries from repeatedly executing
+    /// a proposal that is still failing. Proposals keep the cooldown
+    /// in effect when they were created.
+    pub execution_retry_cooldown: Option<Duration>,
+    /// If set, after one of a proposer's proposals is executed they
+    /// may not create another proposal until this much time has
+    /// passed. This discourages chaining governance actions
+    /// immediately after a large one executes.
+    pub post_execution_cooldown: Option<Duration>,
+    /// If set, voting on proposals that reach quorum shortly before
+    /// they expire is extended to prevent last minute quorum
+    /// sniping. See `QuorumExtension`.
+    pub quorum_extension: Option<QuorumExtension>,
+    /// If set, proposers must send exactly this coin along with
+    /// their `Propose` message. It is returned to the proposer when
+    /// the proposal is executed and sent to the DAO if the proposal
+    /// is closed or withdrawn. This is a lighter weight alternative
+    /// to `deposit_info` for DAOs without a cw20 token and may not
+    /// be set alongside it.
+    pub native_propose_deposit: Option<Coin>,
+    /// The number of blocks before a proposal's creation at which
+    /// voting power is snapshotted for it. Voting power acquired
+    /// within this many blocks of a proposal's creation does not
+    /// count towards it. Zero snapshots voting power at the block the
+    /// proposal is created.
+    pub snapshot_offset: u64,
+    /// A contract that is queried with a `PreExecutionHookQuery`
+    /// before a proposal is executed. If it vetoes the proposal,
+    /// execution fails and the proposal remains passed. If the query
+    /// fails the proposal is executed as usual so that a broken hook
+    /// can not prevent the DAO from removing it. A hook may veto any
+    /// proposal, including one removing it, so only trusted
+    /// contracts should be used. None if no hook.
+    pub pre_execution_hook: Option<String>,
+    /// Section headers that the description of every new proposal
+    /// must contain, for example `["Summary", "Motivation"]`. A
+    /// section is present if a line of the description, ignoring
+    /// leading `#` characters and a trailing `:`, is the header.
+    /// Headers are matched case-insensitively.
+    pub required_description_sections: Vec<String>,
+    /// The longest `max_voting_period` the module may be configured
+    /// with. Guards against voting periods so long that proposals
+    /// effectively never expire. May be no larger than
+    /// `MAX_VOTING_PERIOD_LIMIT`, ten years. If None, defaults to
+    /// three years.
+    pub voting_period_limit: Option<VotingPeriodLimit>,
+    /// If true, the DAO is asked to add the cw20 token used for
+    /// proposal deposits to its list of cw20 tokens, so that
+    /// deposits forfeited to it show up in its treasury. The token
+    /// is registered when the first proposal is created after this
+    /// is enabled or the deposit token changes.
+    pub register_deposit_token: bool,
+    /// A fixed total voting power that quorum and thresholds are
+    /// measured against instead of the total reported by the voting
+    /// module, for DAOs whose circulating supply differs from the
+    /// voting module's. Voters' individual voting power still comes
+    /// from the voting module. Takes precedence over
+    /// `exclude_dao_voting_power`. Must be non-zero.
+    pub total_power_override: Option<Uint128>,
+    /// If set to true, each proposal's vote tally is recorded at
+    /// every block in which votes are cast on it so that it may be
+    /// read with the `VoteHistory` query. This stores an entry for
+    /// each such block, so is disabled by default.
+    pub record_vote_history: bool,
+    /// If set to true, `Poke` executes proposals that have passed
+    /// once they are executable. Proposals that require execution
+    /// funds are never executed by `Poke`, and if
+    /// `only_members_execute` is set the address poking must be a
+    /// member.
+    pub execute_on_pass: bool,
+    /// If set, proposals use commit-reveal voting so that votes are
+    /// not known until voting has ended. During the voting period
+    /// members commit to a vote with `CommitVote` and, once it ends,
+    /// reveal it with `RevealVote` during a reveal phase of this
+    /// length. Only revealed votes are counted. Must be non-zero and
+    /// have the same units as `max_voting_period`.
+    pub reveal_period: Option<Duration>,
+    /// If set, only these addresses may be added as consumers of
+    /// proposal and vote hooks unless the message adding them sets
+    /// `override_allowlist`. Hooks added before the allowlist was set
+    /// are not removed.
+    pub hook_allowlist: Option<Vec<String>>,
+    /// If set, proposals that allow revoting resolve once their
+    /// votes have decided them and no revote has changed the outcome
+    /// for this long, instead of remaining open until they
+    /// expire. Has no effect unless `allow_revoting` is set. Must be
+    /// non-zero and have the same units as `max_voting_period`.
+    pub revote_settle_period: Option<Duration>,
+    /// If set, the proposal deposit of proposers whose recent
+    /// proposals were rejected is multiplied according to this
+    /// schedule. Applies to both cw20 and native deposits.
+    pub deposit_escalation: Option<DepositEscalation>,
+    /// If set, the deposits of rejected proposals that are not
+    /// refunded are distributed according to this policy instead of
+    /// being sent to the DAO.
+    pub forfeited_deposit_policy: Option<ForfeitedDepositPolicy>,
+    /// If set, the messages of a passed proposal are checked when it
+    /// is executed, and if they are found to be unable to execute the
+    /// proposal is rejected instead with
+    /// `ResolutionReason::WouldFailExecution`. The DAO may also
+    /// reject such a proposal before anyone attempts to execute it
+    /// with `RejectFailingProposal`. This is a best-effort
+    /// check that each message is non-empty, that the contracts it
+    /// targets exist, and that the DAO has the native funds it
+    /// sends. Messages that pass the check may still fail.
+    pub reject_failing_execution: bool,
+    /// If set, once a proposal expires the voting power that did not
+    /// vote on it is counted as having voted this way when checking
+    /// if it meets its threshold. This only changes how the
+    /// threshold is checked: quorum is always measured against the
+    /// votes actually cast, and a proposal may still pass or be
+    /// rejected before it expires on the votes cast alone.
+    ///
+    /// `yes` counts non-voters in favor of a proposal and `no` counts
+    /// them against it, which changes nothing under thresholds that
+    /// only count yes votes, as non-voters already count against
+    /// proposals there. What `abstain` does depends on the threshold:
+    ///
+    /// - `AbsolutePercentage` and `ThresholdQuorum` with
+    ///   `MajorityOfNonAbstain` leave abstentions out of the
+    ///   denominator, so non-voters are not counted at all.
+    /// - `ThresholdQuorum` with `MajorityOfParticipation` counts
+    ///   abstentions in the denominator, so non-voters count against
+    ///   the proposal as `no` would.
+    /// - `ThresholdQuorum` with `AbsoluteMajority`, `AbsoluteCount`,
+    ///   and `SimpleQuorumMajority` ignore abstentions, so it has no
+    ///   effect.
+    pub non_voter_default: Option<Vote>,
+    /// If set, proposals whose serialized size is larger than this
+    /// many bytes may not be created. Must be non-zero and no larger
+    /// than `MAX_PROPOSAL_SIZE`, which always applies.
+    pub max_proposal_bytes: Option<u64>,
+    /// If set, config updates do not take effect until this amount of
+    /// time after `UpdateConfig` is executed, giving members notice
+    /// of changes that will apply to future proposals. A scheduled
+    /// update may be read with the `PendingConfig` query. May be at
+    /// most one year.
+    pub config_change_delay: Option<Duration>,
+    /// If set, whoever closes a rejected proposal, with `Close` or
+    /// `CloseExpired`, is paid this reward for each proposal they
+    /// close, so that keepers are paid to close proposals.
+    pub close_reward: Option<CloseReward>,
+    /// If set, voting power queried from the voting module as of a
+    /// proposal's snapshot height is cached, keyed by the voting
+    /// module and snapshot height, so that each voter's power and
+    /// the total power are only queried once per snapshot height.
+    /// Power at a snapshot height can not change, so cached power is
+    /// never stale. This saves a query each time power is needed
+    /// again, for example when a commit-reveal vote is revealed or
+    /// several proposals share a snapshot height, at the cost of a
+    /// storage write the first time it is queried.
+    pub cache_voting_power: bool,
+    /// A prefix, such as `GRANTS`, returned with each of the module's
+    /// proposals so that clients may tell them apart from proposals
+    /// with the same ID in the DAO's other proposal modules. Does
+    /// not change proposal IDs. Must be between 1 and
+    /// `MAX_ID_PREFIX_LENGTH` uppercase ASCII letters and digits,
+    /// starting with a letter, and may not be changed later.
+    pub id_prefix: Option<String>,
+}
+
+/// A reward paid for closing a rejected proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CloseReward {
+    /// Where the reward is paid from. Rewards paid from a proposal's
+    /// deposit are only paid if its deposit is forfeited, and are
+    /// taken from the deposit before it is distributed. Rewards paid
+    /// from a pool are paid from the module's balance, and closing
+    /// fails while the balance is too small to pay them.
+    pub source: RewardSource,
+    /// The amount of the reward. Must be non-zero.
+    pub amount: Uint128,
 }
 
 /// Information about the token to use for proposal deposits.
@@ -67,19 +303,85 @@ pub enum ExecuteMsg {
         /// A description of the proposal.
         description: String,
         /// The messages that should be executed in response to this
-        /// proposal passing.
+        /// proposal passing. They are executed by the DAO, so their
+        /// sender is the DAO's address and not this module's.
         msgs: Vec<CosmosMsg<Empty>>,
+        /// An absolute expiration for the proposal, for example to
+        /// have voting close at a fixed calendar deadline. Must be in
+        /// the future, use the same units as the module's
+        /// `max_voting_period`, and fall between the end of the
+        /// minimum and maximum voting periods. If None, the proposal
+        /// expires `max_voting_period` after creation.
+        expiration_override: Option<Expiration>,
+        /// Funds that whoever executes the proposal must attach to
+        /// their `Execute` message. They are forwarded to the DAO
+        /// along with the proposal's messages, allowing a sponsor to
+        /// fund actions the DAO's treasury can not. Denoms must be
+        /// unique and amounts non-zero, and the proposal must have
+        /// messages.
+        #[serde(default)]
+        execution_funds: Vec<Coin>,
+        /// The IDs of earlier proposals in this module that this
+        /// proposal relates to, for example a proposal implementing a
+        /// budget approved by an earlier one. Each must exist.
+        #[serde(default)]
+        related_proposals: Vec<u64>,
+        /// A threshold to use for this proposal in place of the
+        /// module's, for example to require a supermajority for a
+        /// constitutional change. It must be of the same kind as the
+        /// module's threshold and at least as strict in every respect,
+        /// so that proposers may only make their own proposal harder
+        /// to pass.
+        #[serde(default)]
+        threshold_override: Option<Threshold>,
+        /// Opaque display metadata for the proposal, for example a
+        /// JSON object or a URI describing an image, links, or
+        /// descriptions of its options, so that UIs need not overload
+        /// `description`. At most `MAX_PROPOSAL_METADATA_SIZE` bytes.
+        #[serde(default)]
+        metadata: Option<String>,
     },
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
+    ///
+    /// The `wasm` event of a successful vote serves as a receipt for
+    /// the vote and always has the following attributes:
+    ///
+    /// - `action`: `"vote"`.
+    /// - `sender`: the address of the voter.
+    /// - `proposal_id`: the ID of the proposal voted on.
+    /// - `position`: the vote cast, one of `"yes"`, `"no"`, or
+    ///   `"abstain"`.
+    /// - `power`: the voting power recorded for the vote.
+    /// - `height`: the block height the vote was cast at.
+    /// - `status`: the proposal's status after the vote.
     Vote {
         /// The ID of the proposal to vote on.
         proposal_id: u64,
         /// The senders position on the proposal.
         vote: Vote,
     },
+    /// Casts a vote from the sender on each of several proposals,
+    /// given as `(proposal_id, vote)` pairs. Each vote is cast as
+    /// `Vote` would cast it, and one that fails is skipped rather
+    /// than failing the others. At most `MAX_VOTE_BATCH_SIZE` votes
+    /// may be cast at once.
+    ///
+    /// The receipt of each vote cast is a `vote` event with the same
+    /// attributes as the `wasm` event of `Vote`. The response data is
+    /// a `Vec<VoteBatchResult>` with an entry for each vote, in
+    /// order.
+    VoteBatch { votes: Vec<(u64, Vote)> },
     /// Causes the messages associated with a passed proposal to be
     /// executed by the DAO.
+    ///
+    /// This module never dispatches a proposal's messages itself. It
+    /// sends them to the DAO's `ExecuteProposalHook`, which only
+    /// accepts messages from the DAO's proposal modules and
+    /// dispatches them with the DAO as their sender. Contracts that
+    /// only authorize the DAO's address, including the DAO itself,
+    /// will therefore accept them, while this module's address holds
+    /// no authority of its own.
     Execute {
         /// The ID of the proposal to execute.
         proposal_id: u64,
@@ -91,6 +393,126 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to close.
         proposal_id: u64,
     },
+    /// Closes expired proposals that have been rejected, returning
+    /// their deposits as `Close` would. Proposals are examined in
+    /// order of ID and those that may be closed are closed. The IDs
+    /// of the closed proposals are returned in the response's data
+    /// as a list, and the ID of the last proposal examined in the
+    /// `last_examined` attribute.
+    CloseExpired {
+        /// The ID of the proposal to start examining proposals after.
+        /// Callers may continue from the previous call's
+        /// `last_examined`.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to examine, which bounds
+        /// the gas used. If no limit is set a max of 30 proposals will
+        /// be examined.
+        limit: Option<u64>,
+    },
+    /// Seconds a pending proposal, opening it for voting. The
+    /// proposal's voting period starts when it is seconded. May only
+    /// be called by a member with voting power at the time of the
+    /// proposal's creation who is not the proposer.
+    Second {
+        /// The ID of the proposal to second.
+        proposal_id: u64,
+    },
+    /// Commits to a vote on a proposal that uses commit-reveal
+    /// voting. `commitment` must be the output of
+    /// `state::vote_commitment` for the proposal, the sender, the
+    /// vote, and a secret salt. The
+    /// vote is revealed with `RevealVote` once voting has ended. Each
+    /// member may commit once per proposal.
+    CommitVote {
+        /// The ID of the proposal to commit to a vote on.
+        proposal_id: u64,
+        /// The hash of the proposal ID, the sender, the vote, and a
+        /// secret salt.
+        commitment: Binary,
+    },
+    /// Reveals a vote committed to with `CommitVote` during the
+    /// proposal's reveal phase, counting it as `Vote` would.
+    RevealVote {
+        /// The ID of the proposal to reveal a vote on.
+        proposal_id: u64,
+        /// The position committed to.
+        vote: Vote,
+        /// The salt used to compute the commitment.
+        salt: String,
+    },
+    /// Saves a proposal's current status if it differs from its
+    /// stored one, for example when a proposal has expired but not
+    /// been voted on, closed, or executed since. Proposal queries
+    /// always return the current status, this only updates storage
+    /// for readers that don't recompute it. Callable by anyone and
+    /// does nothing if the stored status is current.
+    SyncStatus {
+        /// The ID of the proposal to sync.
+        proposal_id: u64,
+    },
+    /// Saves a proposal's current status as `SyncStatus` does and,
+    /// if `execute_on_pass` is set and the proposal has passed and may
+    /// be executed, executes it. Callable by anyone and does nothing
+    /// if there is nothing to do, so that keepers may poke proposals
+    /// to drive them to their conclusion.
+    Poke {
+        /// The ID of the proposal to poke.
+        proposal_id: u64,
+    },
+    /// Withdraws a passed proposal that has not yet been executed,
+    /// closing it. Only the proposal's proposer may do this. The
+    /// proposal deposit is handled as if the proposal had failed:
+    /// returned to the proposer if failed proposals are refunded and
+    /// to the DAO otherwise.
+    WithdrawProposal {
+        /// The ID of the proposal to withdraw.
+        proposal_id: u64,
+    },
+    /// Makes another member of the DAO the proposer of a proposal
+    /// that is pending, open, or passed. Only the proposal's current
+    /// proposer may do this. The new proposer may withdraw the
+    /// proposal and receives its deposit when it is refunded. The
+    /// `post_execution_cooldown` still applies to the proposal's
+    /// original proposer.
+    TransferProposalAuthorship {
+        /// The ID of the proposal to transfer.
+        proposal_id: u64,
+        /// The address to make the proposal's proposer. Must have
+        /// voting power in the DAO.
+        new_proposer: String,
+    },
+    /// Moves a proposal that can not otherwise resolve, for example
+    /// because the voting module returns inconsistent totals, to a
+    /// terminal status without executing its messages. Only the DAO
+    /// may do this and it is intended as a last resort.
+    ForceResolve {
+        /// The ID of the proposal to resolve. It must not already be
+        /// in a terminal status.
+        proposal_id: u64,
+        /// The status to resolve the proposal with: `closed`,
+        /// `executed`, or `execution_failed`. The proposal's deposit
+        /// is handled as if it had reached the status normally:
+        /// closed proposals are treated as failed and otherwise the
+        /// deposit is returned to the proposer.
+        status: Status,
+    },
+    /// Rejects a passed proposal whose messages are found to be
+    /// unable to execute by the checks `reject_failing_execution`
+    /// performs. Only the DAO may do this, and only if
+    /// `reject_failing_execution` is set. Once closed the proposal's
+    /// result has the reason `WouldFailExecution`.
+    RejectFailingProposal {
+        /// The ID of the proposal to reject. Errors if its messages
+        /// pass the checks.
+        proposal_id: u64,
+    },
+    /// Claims a proposal deposit that could not be returned to the
+    /// sender when its proposal was closed or executed, for example
+    /// because the deposit token's transfer failed at the time.
+    ClaimRefund {
+        /// The ID of the proposal whose deposit to claim.
+        proposal_id: u64,
+    },
     /// Updates the governance module's config.
     UpdateConfig {
         /// The new proposal passing threshold. This will only apply
@@ -129,22 +551,229 @@ pub enum ExecuteMsg {
         /// remain open until the DAO's treasury was large enough for it to be
         /// executed.
         close_proposal_on_execution_failure: bool,
+        /// If set to true the DAO's own voting power, for example
+        /// governance tokens held in its treasury, is subtracted from
+        /// the total voting power recorded on new proposals. This will
+        /// only apply to proposals created after the config update.
+        exclude_dao_voting_power: bool,
+        /// A contract to send an `ExecutionCallbackMsg` to after a
+        /// proposal is successfully executed. Failures of the callback
+        /// do not affect the proposal's execution. None if no
+        /// callback.
+        execution_callback: Option<String>,
+        /// If Some, new proposals start out `Pending` and must be
+        /// seconded by a member other than the proposer within this
+        /// amount of time before they open for voting. This will only
+        /// apply to proposals created after the config update.
+        require_second: Option<Duration>,
+        /// If set to true every config this module is updated to is
+        /// appended to a log that may be read with the `ConfigHistory`
+        /// query. Only the most recent `MAX_CONFIG_HISTORY` configs are
+        /// kept.
+        record_config_history: bool,
+        /// If set to true proposal hook consumers are sent
+        /// `ProposalHookMsg::QuorumReached` the first time a proposal
+        /// reaches quorum. Consumers that error on messages they do not
+        /// recognize are removed, so only enable this once every consumer
+        /// supports it.
+        quorum_reached_hooks: bool,
+        /// If set to true, when a proposal is executed the voting power
+        /// of each of its voters is checked again and the votes of those
+        /// with no voting power are removed from the tally. A proposal
+        /// that no longer passes is rejected instead of executed, or if
+        /// it has not expired returns to being open, which proposal
+        /// hooks report as a change from `passed` to `open`. Addresses
+        /// without voting power may not vote on these proposals, so
+        /// voters whose votes are removed may not vote again. This
+        /// queries the voting power of every voter on the proposal, so
+        /// the gas cost of execution grows with the number of voters.
+        /// This will only apply to proposals created after this is set.
+        require_power_at_resolution: bool,
+        /// If set, after a failed attempt to execute a proposal that
+        /// remains passed (`close_proposal_on_execution_failure` is
+        /// false) it may not be executed again until this much time
+        /// has passed. This keeps automated retries from repeatedly
+        /// executing a proposal that is still failing. Only applies
+        /// to proposals created after the update.
+        execution_retry_cooldown: Option<Duration>,
+        /// If set, after one of a proposer's proposals is executed
+        /// they may not create another proposal until this much time
+        /// has passed. This discourages chaining governance actions
+        /// immediately after a large one executes.
+        post_execution_cooldown: Option<Duration>,
+        /// If set, voting on proposals that reach quorum shortly
+        /// before they expire is extended to prevent last minute
+        /// quorum sniping. See `QuorumExtension`.
+        quorum_extension: Option<QuorumExtension>,
+        /// If set, proposers must send exactly this coin along with
+        /// their `Propose` message. It is returned to the proposer
+        /// when the proposal is executed and sent to the DAO if the
+        /// proposal is closed or withdrawn. May not be set alongside
+        /// `deposit_info`.
+        native_propose_deposit: Option<Coin>,
+        /// The number of blocks before a proposal's creation at which
+        /// voting power is snapshotted for it. Only applies to
+        /// proposals created after the update.
+        snapshot_offset: u64,
+        /// A contract that is queried with a `PreExecutionHookQuery`
+        /// before a proposal is executed and may veto its execution.
+        /// None if no hook.
+        pre_execution_hook: Option<String>,
+        /// Section headers that the description of every new
+        /// proposal must contain. Only applies to proposals created
+        /// after the update.
+        required_description_sections: Vec<String>,
+        /// The longest `max_voting_period` the module may be
+        /// configured with. May be no larger than
+        /// `MAX_VOTING_PERIOD_LIMIT`. If None, defaults to three
+        /// years.
+        voting_period_limit: Option<VotingPeriodLimit>,
+        /// If true, the DAO is asked to add the deposit token to its
+        /// list of cw20 tokens when the next proposal is created.
+        register_deposit_token: bool,
+        /// A fixed total voting power that quorum and thresholds are
+        /// measured against instead of the voting module's total.
+        /// Only applies to proposals created after the update.
+        total_power_override: Option<Uint128>,
+        /// If set to true, each proposal's vote tally is recorded at
+        /// every block in which votes are cast on it. Applies to
+        /// votes cast after the update.
+        record_vote_history: bool,
+        /// If set to true, `Poke` executes proposals that have
+        /// passed once they are executable.
+        execute_on_pass: bool,
+        /// If set, proposals use commit-reveal voting with a reveal
+        /// phase of this length. Only applies to proposals created
+        /// after the update.
+        reveal_period: Option<Duration>,
+        /// If set, only these addresses may be added as consumers of
+        /// proposal and vote hooks unless the message adding them
+        /// sets `override_allowlist`.
+        hook_allowlist: Option<Vec<String>>,
+        /// If set, proposals that allow revoting resolve once their
+        /// votes have decided them and no revote has changed the
+        /// outcome for this long. Only applies to proposals created
+        /// after the update.
+        revote_settle_period: Option<Duration>,
+        /// If set, the proposal deposit of proposers whose recent
+        /// proposals were rejected is multiplied according to this
+        /// schedule.
+        deposit_escalation: Option<DepositEscalation>,
+        /// If set, the deposits of rejected proposals that are not
+        /// refunded are distributed according to this policy instead
+        /// of being sent to the DAO.
+        forfeited_deposit_policy: Option<ForfeitedDepositPolicy>,
+        /// If set, passed proposals whose messages are found to be
+        /// unable to execute are rejected when execution is
+        /// attempted.
+        reject_failing_execution: bool,
+        /// If set, voting power that did not vote on a proposal is
+        /// counted as having voted this way when checking its
+        /// threshold once it expires. Only applies to proposals
+        /// created after the update.
+        non_voter_default: Option<Vote>,
+        /// If set, proposals whose serialized size is larger than
+        /// this many bytes may not be created.
+        max_proposal_bytes: Option<u64>,
+        /// If set, config updates do not take effect until this
+        /// amount of time after they are made. The delay of the
+        /// config being replaced applies to this update.
+        config_change_delay: Option<Duration>,
+        /// If set, whoever closes a rejected proposal is paid this
+        /// reward for each proposal they close.
+        close_reward: Option<CloseReward>,
+        /// If set, voting power queried as of a proposal's snapshot
+        /// height is cached so that it is only queried once per
+        /// snapshot height.
+        cache_voting_power: bool,
     },
     /// Adds an address as a consumer of proposal hooks. Consumers of
     /// proposal hooks have hook messages executed on them whenever
     /// the status of a proposal changes or a proposal is created. If
     /// a consumer contract errors when handling a hook message it
     /// will be removed from the list of consumers.
-    AddProposalHook { address: String },
+    AddProposalHook {
+        address: String,
+        /// Adds the address even if it is not on the module's hook
+        /// allowlist.
+        #[serde(default)]
+        override_allowlist: bool,
+    },
     /// Removes a consumer of proposal hooks.
     RemoveProposalHook { address: String },
     /// Adds an address as a consumer of vote hooks. Consumers of vote
     /// hooks have hook messages executed on them whenever the a vote
     /// is cast. If a consumer contract errors when handling a hook
     /// message it will be removed from the list of consumers.
-    AddVoteHook { address: String },
+    AddVoteHook {
+        address: String,
+        /// Adds the address even if it is not on the module's hook
+        /// allowlist.
+        #[serde(default)]
+        override_allowlist: bool,
+    },
     /// Removed a consumer of vote hooks.
     RemoveVoteHook { address: String },
+    /// Adds several consumers of proposal and vote hooks at once. If
+    /// any of the addresses is already a consumer of that kind of
+    /// hook, none are added.
+    AddHooks {
+        proposal_hooks: Vec<String>,
+        vote_hooks: Vec<String>,
+        /// Adds the addresses even if they are not on the module's
+        /// hook allowlist.
+        #[serde(default)]
+        override_allowlist: bool,
+    },
+    /// Removes several consumers of proposal and vote hooks at
+    /// once. If any of the addresses is not a consumer of that kind
+    /// of hook, none are removed.
+    RemoveHooks {
+        proposal_hooks: Vec<String>,
+        vote_hooks: Vec<String>,
+    },
+}
+
+/// The message sent to a module's `execution_callback` after a
+/// proposal is successfully executed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionCallbackMsg {
+    ProposalExecuted {
+        /// The ID of the proposal that was executed.
+        proposal_id: u64,
+        /// The messages that the proposal executed.
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+}
+
+/// The query sent to a module's `pre_execution_hook` before a
+/// proposal is executed. Hooks respond with a
+/// `PreExecutionHookResponse`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PreExecutionHookQuery {
+    CanExecute {
+        /// The ID of the proposal about to be executed.
+        proposal_id: u64,
+        /// The messages the proposal will execute.
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PreExecutionHookResponse {
+    /// If true the proposal is not executed.
+    pub veto: bool,
+}
+
+/// The result of one of the votes in a `VoteBatch`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VoteBatchResult {
+    /// The ID of the proposal voted on.
+    pub proposal_id: u64,
+    /// Why the vote was skipped, or None if it was cast.
+    pub error: Option<String>,
 }
 
 #[govmod_query]
@@ -153,6 +782,14 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     /// Gets the governance module's config. Returns `state::Config`.
     Config {},
+    /// Gets the config update scheduled by `UpdateConfig` that has
+    /// not yet taken effect, if any. Returns
+    /// `Option<state::PendingConfig>`.
+    PendingConfig {},
+    /// Gets the address of the DAO this module is associated
+    /// with. Returns `Addr`. Equivalent to `Config.dao` without
+    /// loading the rest of the config.
+    Dao {},
     /// Gets information about a proposal. Returns
     /// `proposals::Proposal`.
     Proposal { proposal_id: u64 },
@@ -181,12 +818,37 @@ pub enum QueryMsg {
         /// returned.
         limit: Option<u64>,
     },
+    /// Lists the proposals that reached a terminal status at or after
+    /// `from` and before `to`, ordered by the time they were resolved
+    /// and then by ID. Only proposals that have been closed, executed,
+    /// or otherwise moved to a terminal status by a message are
+    /// listed: a proposal that is rejected once it expires is not
+    /// listed until it is closed, as it is resolved when it is
+    /// closed. Proposals resolved before resolution times were
+    /// recorded are not listed. Returns
+    /// `query::ProposalListResponse`.
+    ResolvedProposalsInRange {
+        from: Timestamp,
+        to: Timestamp,
+        /// The ID of a resolved proposal to start listing proposals
+        /// after. Errors if the proposal has not been resolved.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
     /// Returns the number of proposals that have been created in this
     /// module.
     ProposalCount {},
     /// Returns a voters position on a propsal. Returns
     /// `query::VoteResponse`.
     GetVote { proposal_id: u64, voter: String },
+    /// Checks if a voter may change their vote on a proposal right
+    /// now using the same checks as `Vote`: the proposal must be
+    /// open, allow revoting, and have been voted on by the
+    /// voter. Returns `query::CanRevoteResponse`.
+    CanRevote { proposal_id: u64, voter: String },
     /// Lists all of the votes that have been cast on a
     /// proposal. Returns `VoteListResponse`.
     ListVotes {
@@ -199,11 +861,230 @@ pub enum QueryMsg {
         /// query. If no limit is specified a max of 30 are returned.
         limit: Option<u64>,
     },
+    /// Lists the votes that have been cast on a proposal in reverse
+    /// alphabetical order of voter. Returns `VoteListResponse`.
+    ReverseVotes {
+        /// The proposal to list the votes of.
+        proposal_id: u64,
+        /// The voter to start listing votes before.
+        start_before: Option<String>,
+        /// The maximum number of votes to return in response to this
+        /// query. If no limit is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Lists the votes cast for `position` on a proposal, ordered
+    /// alphabetically by voter. Votes cast before this query was
+    /// added are not listed. Returns `VoteListResponse`.
+    ListVotesByPosition {
+        /// The proposal to list the votes of.
+        proposal_id: u64,
+        /// The position to list the voters of.
+        position: Vote,
+        /// The voter to start listing votes after.
+        start_after: Option<String>,
+        /// The maximum number of votes to return in response to this
+        /// query. If no limit is specified a max of 30 are returned.
+        limit: Option<u64>,
+    },
+    /// Gets the smallest amount of additional yes voting power that
+    /// would cause a proposal to pass right now. Returns
+    /// `Option<Uint128>`, None if the proposal is no longer open or
+    /// can not currently pass even if all outstanding voting power
+    /// votes yes.
+    PowerNeededToPass { proposal_id: u64 },
+    /// Gets the status a proposal would have at its expiration if all
+    /// voting power that has not yet voted were to vote `vote`.
+    /// Returns `Option<voting::status::Status>`, None if the proposal
+    /// is no longer open.
+    OutcomeIfRemainingVote { proposal_id: u64, vote: Vote },
+    /// Lists the open proposals that `address` may vote on, in
+    /// ascending order of ID, along with whether voting on each with
+    /// all of their voting power could change the status it would
+    /// have at its expiration. Proposals using commit-reveal voting
+    /// are not included as their votes are not tallied until revealed.
+    /// Like `ProposalsWithMsgKind` at most `limit` proposals are
+    /// examined and the response's `last_examined` is the cursor to
+    /// continue from. Returns `query::VoterInfluenceResponse`.
+    VoterInfluence {
+        address: String,
+        /// The proposal ID to start scanning after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to examine as part of
+        /// this query. If no limit is set a max of 30 proposals will
+        /// be examined.
+        limit: Option<u64>,
+    },
+    /// Gets the multiplier `deposit_escalation` would apply to the
+    /// deposit of a proposal made by `address` now. Returns `u64`,
+    /// one if their deposit would not be escalated.
+    DepositMultiplier { address: String },
+    /// Gets whether a proposal's minimum voting period has elapsed
+    /// and, if not, whether its votes would already pass it. This
+    /// distinguishes proposals still being decided from those
+    /// waiting out their minimum voting period. Returns
+    /// `query::MinPeriodStatusResponse`.
+    MinPeriodStatus { proposal_id: u64 },
+    /// Gets the parameters governing a proposal as they were
+    /// snapshotted when it was created, which may differ from the
+    /// module's current config. Returns
+    /// `query::ProposalConfigResponse`.
+    ProposalConfig { proposal_id: u64 },
+    /// Gets how much of a proposal's voting power has voted, computed
+    /// the same way as for quorum: yes, no, and abstain votes out of
+    /// the proposal's `total_power`. Returns
+    /// `query::ParticipationStatusResponse`.
+    ParticipationStatus { proposal_id: u64 },
+    /// Gets the number of unique addresses that have voted on a
+    /// proposal. A revote does not change the count. Returns
+    /// `Option<u64>`, which is None for proposals created before
+    /// votes were counted.
+    VoteCount { proposal_id: u64 },
+    /// Gets the amount of a proposal's deposit that failed to be
+    /// returned to `address` and may be claimed with
+    /// `ClaimRefund`. Returns `Option<Uint128>`.
+    PendingRefund { proposal_id: u64, address: String },
+    /// Checks if a proposal with the provided contents would be
+    /// accepted by `Propose` if proposed now, without creating
+    /// it. Takes the same arguments as `Propose`, and performs the
+    /// same checks, except that the funds sent with `Propose` are not
+    /// checked. Checks on the proposer are only performed if
+    /// `proposer` is set. Returns `query::ValidateProposalResponse`.
+    ValidateProposal {
+        /// The address that would create the proposal. If None, the
+        /// proposal is sized as if this module had created it.
+        #[serde(default)]
+        proposer: Option<String>,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        #[serde(default)]
+        expiration_override: Option<Expiration>,
+        #[serde(default)]
+        execution_funds: Vec<Coin>,
+        #[serde(default)]
+        related_proposals: Vec<u64>,
+        #[serde(default)]
+        threshold_override: Option<Threshold>,
+        #[serde(default)]
+        metadata: Option<String>,
+    },
+    /// Lists the unique contract addresses, bank recipients, and
+    /// stargate type URLs referenced by a proposal's messages along
+    /// with the kind of message referencing each, in the order they
+    /// first appear. Messages without a target address (for example
+    /// `WasmMsg::Instantiate`) are not included. Returns
+    /// `query::ProposalTargetsResponse`.
+    ProposalTargets { proposal_id: u64 },
+    /// Gets a digest of a proposal's governing state, its ID, status,
+    /// votes, total power, threshold, and expiration, along with the
+    /// fields it covers, so that light clients and bridges may attest
+    /// to the proposal without its full contents. See
+    /// `query::ProposalDigestFields` for how the digest is computed.
+    /// Returns `query::ProposalDigestResponse`.
+    ProposalDigest { proposal_id: u64 },
+    /// Lists the proposals that a proposal declared itself related
+    /// to when it was created, in the order they were declared.
+    /// Returns `query::ProposalListResponse`.
+    RelatedProposals { proposal_id: u64 },
+    /// Lists proposals in ascending order of proposal ID that contain
+    /// at least one message of the given kind. Messages are
+    /// classified the same way as in `ProposalTargets`.
+    ///
+    /// At most `limit` proposals are examined, so a page may hold
+    /// fewer matches than `limit`, or none, while more remain. Page
+    /// through results by passing the response's `last_examined` as
+    /// `start_after` until it is None. Returns
+    /// `query::FilteredProposalListResponse`.
+    ProposalsWithMsgKind {
+        kind: ProposalTargetKind,
+        /// The proposal ID to start scanning after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to examine as part of
+        /// this query. If no limit is set a max of 30 proposals will
+        /// be examined.
+        limit: Option<u64>,
+    },
+    /// Lists proposals in ascending order of proposal ID that
+    /// `address` could execute right now. That is, passed proposals
+    /// that are not waiting out an `execution_retry_cooldown` and
+    /// that the pre-execution hook does not veto, or none if
+    /// `only_members_execute` is set and `address` has no voting
+    /// power or if the DAO's governance is frozen. Proposals with `require_power_at_resolution` set
+    /// are listed even though removing votes at execution may leave
+    /// them no longer passed.
+    ///
+    /// Like `ProposalsWithMsgKind` at most `limit` proposals are
+    /// examined and the response's `last_examined` is the cursor to
+    /// continue from. `last_examined` is None if `address` can not
+    /// execute proposals at all. Returns
+    /// `query::FilteredProposalListResponse`.
+    ExecutableByAddress {
+        address: String,
+        /// The proposal ID to start scanning after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to examine as part of
+        /// this query. If no limit is set a max of 30 proposals will
+        /// be examined.
+        limit: Option<u64>,
+    },
+    /// Lists the configs recorded while `record_config_history` is
+    /// enabled in the order they took effect. Returns
+    /// `query::ConfigHistoryResponse`.
+    ConfigHistory {
+        /// The entry ID to start listing entries after.
+        start_after: Option<u64>,
+        /// The maximum number of entries to return as part of this
+        /// query. If no limit is set a max of 30 entries will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists a proposal's vote tally at each block in which votes
+    /// were cast on it while `record_vote_history` was enabled, in
+    /// order of height. Returns `query::VoteHistoryResponse`.
+    VoteHistory {
+        proposal_id: u64,
+        /// The height to start listing entries after.
+        start_after: Option<u64>,
+        /// The maximum number of entries to return as part of this
+        /// query. If no limit is set a max of 30 entries will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Gets the total deposits the module has collected from,
+    /// refunded to, and forfeited from proposers over its lifetime
+    /// for each deposit token. Returns
+    /// `query::DepositAccountingResponse`.
+    DepositAccounting {},
+    /// Compares the module's balance of a cw20 or native deposit token
+    /// with the deposits it owes: those it holds in escrow for
+    /// proposals whose deposits have not yet been refunded or
+    /// forfeited, and refunds that failed and may be claimed with
+    /// `ClaimRefund`. Every proposal is read, so this is intended for
+    /// operators checking for accounting drift. Returns
+    /// `query::DepositSolvencyResponse`.
+    DepositSolvency { asset: DepositAsset },
     /// Lists all of the consumers of proposal hooks for this module.
     ProposalHooks {},
     /// Lists all of the consumers of vote hooks for this
     /// module. Returns indexable_hooks::HooksResponse.
     VoteHooks {},
+    /// Gets the contract's cw2 version and the `MigrateMsg` variants
+    /// it accepts. Returns `cw_core_interface::migration::MigrationInfoResponse`.
+    MigrationInfo {},
+    /// Gets the result recorded when a proposal reached a terminal
+    /// status. Returns `query::FinalResultResponse`.
+    FinalResult { proposal_id: u64 },
+    /// Gets the voting power `address` may vote with at `height`, or
+    /// at the current height if none is provided. Clients should use
+    /// this rather than querying the voting module directly, as it
+    /// will also account for power delegated to and from `address`
+    /// if delegation is added. Currently it returns the address'
+    /// power according to the DAO's voting module. Returns
+    /// `cw_core_interface::voting::VotingPowerAtHeightResponse`.
+    EffectiveVotingPower {
+        address: String,
+        height: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -223,4 +1104,16 @@ pub enum MigrateMsg {
         close_proposal_on_execution_failure: bool,
     },
     FromCompatible {},
+    /// Consolidates ballots cast on the same proposal by addresses
+    /// that differ only by case, which some earlier versions of this
+    /// module stored separately, and recomputes the tallies of the
+    /// proposals they were cast on. The most recently cast ballot is
+    /// kept. Ballots cast before their heights were recorded are
+    /// older than those with a height. Between ballots that are
+    /// equally recent the last ordered by address is kept, which is
+    /// the one stored under the lowercase address if there is one.
+    /// The IDs of the proposals
+    /// fixed and the number of ballots removed are returned as the
+    /// `proposal_ids` and `removed_ballots` attributes.
+    ConsolidateDuplicateBallots {},
 }
@@ -1,16 +1,16 @@
-use crate::query::ProposalResponse;
-use crate::state::PROPOSAL_COUNT;
+use crate::query::{ProposalDigestFields, ProposalResponse};
+use crate::state::{Config, QuorumExtension, PROPOSAL_COUNT};
 use cosmwasm_std::{
-    Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Timestamp, Uint128,
+    Addr, BlockInfo, Coin, CosmosMsg, Decimal, Empty, StdResult, Storage, Timestamp, Uint128,
 };
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use voting::deposit::CheckedDepositInfo;
 use voting::proposal::Proposal;
 use voting::status::Status;
-use voting::threshold::{PercentageThreshold, Threshold};
-use voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
+use voting::threshold::{PercentageThreshold, Threshold, ThresholdMode};
+use voting::voting::{does_vote_count_fail, does_vote_count_pass, Vote, Votes};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct SingleChoiceProposal {
@@ -18,9 +18,7 @@ pub struct SingleChoiceProposal {
     pub description: String,
     /// The address that created this proposal.
     pub proposer: Addr,
-    /// The block height at which this proposal was created. Voting
-    /// power queries should query for voting power at this block
-    /// height.
+    /// The block height at which this proposal was created.
     pub start_height: u64,
     /// The minimum amount of time this proposal must remain open for
     /// voting. The proposal may not pass unless this is expired or
@@ -42,7 +40,9 @@ pub struct SingleChoiceProposal {
     pub allow_revoting: bool,
 
     /// Information about the deposit that was sent as part of this
-    /// proposal. None if no deposit.
+    /// proposal. None if no deposit. This is a snapshot of the
+    /// module's deposit config at proposal creation and is what all
+    /// refunds use, so later config changes do not affect it.
     pub deposit_info: Option<CheckedDepositInfo>,
     /// The timestamp at which this proposal was created.
     pub created: Timestamp,
@@ -50,6 +50,189 @@ pub struct SingleChoiceProposal {
     /// a proposal expires and passes upon expiration, this field will not be updated because it can only update
     /// upon actions on the contract.
     pub last_updated: Timestamp,
+    /// If this proposal's execution failed, a best-effort description
+    /// of why. None if the proposal has not failed execution.
+    #[serde(default)]
+    pub execution_error: Option<ExecutionError>,
+    /// If this proposal is pending, the time by which it must be
+    /// seconded. None otherwise.
+    #[serde(default)]
+    pub seconding_deadline: Option<Expiration>,
+    /// Funds that must be attached when executing this proposal,
+    /// sorted by denom. They are forwarded to the DAO with the
+    /// proposal's messages.
+    #[serde(default)]
+    pub execution_funds: Vec<Coin>,
+    /// Whether this proposal's votes have reached its quorum. Set
+    /// the first time quorum is reached so that the event and hooks
+    /// for it fire exactly once.
+    #[serde(default)]
+    pub quorum_reached: bool,
+    /// The DAO's voting module when this proposal was created. If
+    /// the DAO later changes its voting module new votes are
+    /// rejected, as they would be counted by a module that did not
+    /// produce `total_power`, and the proposal is resolved with the
+    /// votes it already has. None for proposals created before this
+    /// was recorded, which are not checked.
+    #[serde(default)]
+    pub voting_module: Option<Addr>,
+    /// If votes from voters without voting power when this proposal
+    /// is executed are removed from the tally before executing it.
+    #[serde(default)]
+    pub require_power_at_resolution: bool,
+    /// The IDs of earlier proposals in this module that this proposal
+    /// relates to.
+    #[serde(default)]
+    pub related_proposals: Vec<u64>,
+    /// The block of the most recent failed attempt to execute this
+    /// proposal that left it passed. Used to enforce the module's
+    /// `execution_retry_cooldown`. None if no such attempt was made.
+    #[serde(default)]
+    pub last_execution_attempt: Option<ExecutionAttempt>,
+    /// The number of times voting on this proposal has been extended
+    /// by the module's `quorum_extension`.
+    #[serde(default)]
+    pub extensions: u64,
+    /// The native deposit sent as part of this proposal. None if no
+    /// native deposit. Like `deposit_info` this is a snapshot taken
+    /// at proposal creation.
+    #[serde(default)]
+    pub native_deposit: Option<Coin>,
+    /// The block height at which voting power is snapshotted for
+    /// this proposal. None for proposals created before the module's
+    /// `snapshot_offset` was added, which snapshot voting power at
+    /// `start_height`. Use `snapshot_height()` to read this.
+    #[serde(default)]
+    pub snapshot_height: Option<u64>,
+    /// If this proposal is closed when its execution fails, from the
+    /// module's config at proposal creation. None for proposals
+    /// created before this was snapshotted, which use the module's
+    /// current config. Use `closes_on_execution_failure()` to read
+    /// this.
+    #[serde(default)]
+    pub close_proposal_on_execution_failure: Option<bool>,
+    /// If this proposal uses commit-reveal voting, the length of its
+    /// reveal phase, which begins when it expires. None if votes are
+    /// cast directly. Use `reveal_expiration()` to get the end of the
+    /// reveal phase.
+    #[serde(default)]
+    pub reveal_period: Option<Duration>,
+    /// How long a decided outcome must hold before this proposal
+    /// resolves, if it allows revoting. None if it remains open
+    /// until it expires.
+    #[serde(default)]
+    pub revote_settle_period: Option<Duration>,
+    /// The outcome this proposal's votes have decided while it
+    /// allows revoting and has a `revote_settle_period`, pending
+    /// settlement. The proposal remains open until it settles. None
+    /// if its votes have not decided it.
+    #[serde(default)]
+    pub settlement: Option<Settlement>,
+    /// Opaque display metadata provided by the proposer. None for
+    /// proposals without metadata or created before it was added.
+    #[serde(default)]
+    pub metadata: Option<String>,
+    /// The number of unique addresses with a ballot on this
+    /// proposal. None for proposals created before votes were
+    /// counted.
+    #[serde(default)]
+    pub vote_count: Option<u64>,
+    /// The code ID of `voting_module` when this proposal was
+    /// created. None for proposals created before this was recorded.
+    #[serde(default)]
+    pub voting_module_code_id: Option<u64>,
+    /// The cw2 version of this module when it created this
+    /// proposal. None for proposals created before this was
+    /// recorded.
+    #[serde(default)]
+    pub module_version: Option<String>,
+    /// The position that voting power which did not vote on this
+    /// proposal is counted as having taken once it expires, for the
+    /// purposes of its threshold but not its quorum. From the
+    /// module's config at proposal creation.
+    #[serde(default)]
+    pub non_voter_default: Option<Vote>,
+    /// The time the proposal reached a terminal status and its
+    /// result was recorded. None if it has not been resolved or was
+    /// resolved before this was recorded.
+    #[serde(default)]
+    pub resolved_at: Option<Timestamp>,
+    /// How long after a failed execution attempt this proposal may
+    /// not be executed again, from the module's config at proposal
+    /// creation. None if there is no cooldown, as for proposals
+    /// created before this was snapshotted.
+    #[serde(default)]
+    pub execution_retry_cooldown: Option<Duration>,
+    /// The address that created this proposal if its authorship has
+    /// since been transferred to `proposer`. None if it has not been
+    /// transferred.
+    #[serde(default)]
+    pub original_proposer: Option<Addr>,
+}
+
+/// An outcome decided by the votes on a proposal that allows
+/// revoting, which it resolves with unless a revote changes it before
+/// it settles.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Settlement {
+    /// True if the votes decided the proposal as passed, false if as
+    /// rejected.
+    pub passed: bool,
+    /// When the outcome settles and the proposal resolves.
+    pub settles_at: Expiration,
+}
+
+/// The block at which an attempt to execute a proposal was made.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ExecutionAttempt {
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+impl ExecutionAttempt {
+    pub fn new(block: &BlockInfo) -> Self {
+        Self {
+            height: block.height,
+            time: block.time,
+        }
+    }
+
+    /// The earliest point at which another attempt may be made given
+    /// a cooldown of `cooldown` after this one.
+    pub fn retry_at(&self, cooldown: Duration) -> Expiration {
+        match cooldown {
+            Duration::Height(height) => Expiration::AtHeight(self.height + height),
+            Duration::Time(time) => Expiration::AtTime(self.time.plus_seconds(time)),
+        }
+    }
+}
+
+/// The reason a proposal's execution failed, as recorded by the reply
+/// handler. This is a heuristic: the chain redacts most submessage
+/// errors to a generic string and an out of gas error will often
+/// abort the whole transaction before a reply is ever delivered, so
+/// this should be treated as a diagnostic hint and not relied on.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionError {
+    /// The error message mentioned running out of gas.
+    OutOfGas {},
+    /// A message reverted with an error. `error` is the error string
+    /// returned by the chain.
+    Reverted { error: String },
+}
+
+impl ExecutionError {
+    /// Categorizes a submessage error string. Any error that mentions
+    /// running out of gas is considered an out of gas error,
+    /// everything else is considered a deliberate revert.
+    pub fn from_error_string(error: String) -> Self {
+        if error.to_lowercase().contains("out of gas") {
+            Self::OutOfGas {}
+        } else {
+            Self::Reverted { error }
+        }
+    }
 }
 
 impl Proposal for SingleChoiceProposal {
@@ -78,14 +261,101 @@ impl SingleChoiceProposal {
     /// the proposal expiring has changed its status. This method
     /// recomputes the status so that queries get accurate
     /// information.
-    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
+    pub fn into_response(
+        mut self,
+        block: &BlockInfo,
+        id: u64,
+        id_prefix: &Option<String>,
+    ) -> ProposalResponse {
         self.update_status(block);
-        ProposalResponse { id, proposal: self }
+        ProposalResponse {
+            id,
+            id_prefix: id_prefix.clone(),
+            proposal: self,
+        }
+    }
+
+    /// Gets the parts of this proposal's state covered by its digest,
+    /// with its status as of `block`.
+    pub fn digest_fields(&self, block: &BlockInfo, id: u64) -> ProposalDigestFields {
+        ProposalDigestFields {
+            proposal_id: id,
+            status: self.current_status(block),
+            votes: self.votes.clone(),
+            total_power: self.total_power,
+            threshold: self.threshold.clone(),
+            expiration: self.expiration,
+        }
+    }
+
+    /// Gets the block height at which voting power queries for this
+    /// proposal should be made.
+    pub fn snapshot_height(&self) -> u64 {
+        self.snapshot_height.unwrap_or(self.start_height)
+    }
+
+    /// Gets whether this proposal is closed when its execution
+    /// fails, falling back to `config` if this was not snapshotted
+    /// when the proposal was created.
+    pub fn closes_on_execution_failure(&self, config: &Config) -> bool {
+        self.close_proposal_on_execution_failure
+            .unwrap_or(config.close_proposal_on_execution_failure)
+    }
+
+    /// Records that an address without a ballot on this proposal has
+    /// cast one.
+    pub fn add_voter(&mut self) {
+        if let Some(count) = self.vote_count.as_mut() {
+            *count += 1;
+        }
+    }
+
+    /// Records that the ballot of an address on this proposal has
+    /// been removed.
+    pub fn remove_voter(&mut self) {
+        if let Some(count) = self.vote_count.as_mut() {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    /// Gets the time at which this proposal's reveal phase ends, or
+    /// None if it does not use commit-reveal voting.
+    pub fn reveal_expiration(&self) -> Option<Expiration> {
+        match (self.expiration, self.reveal_period?) {
+            (Expiration::AtHeight(height), Duration::Height(period)) => {
+                Some(Expiration::AtHeight(height + period))
+            }
+            (Expiration::AtTime(time), Duration::Time(period)) => {
+                Some(Expiration::AtTime(time.plus_seconds(period)))
+            }
+            // The reveal period is validated to have the same units
+            // as the max voting period, which expirations share.
+            _ => None,
+        }
     }
 
     /// Gets the current status of the proposal.
     pub fn current_status(&self, block: &BlockInfo) -> Status {
-        if self.status == Status::Open && self.is_passed(block) {
+        if self.status == Status::Pending {
+            match self.seconding_deadline {
+                Some(deadline) if deadline.is_expired(block) => Status::Rejected,
+                _ => Status::Pending,
+            }
+        } else if let (Status::Open | Status::Revealing, Some(reveal_expiration)) =
+            (self.status, self.reveal_expiration())
+        {
+            // Votes are only counted as they are revealed, so the
+            // outcome is not known until revealing has ended.
+            if !self.expiration.is_expired(block) {
+                Status::Open
+            } else if !reveal_expiration.is_expired(block) {
+                Status::Revealing
+            } else if self.is_passed(block) {
+                Status::Passed
+            } else {
+                Status::Rejected
+            }
+        } else if self.status == Status::Open && self.is_passed(block) {
             Status::Passed
         } else if self.status == Status::Open
             && (self.expiration.is_expired(block) || self.is_rejected(block))
@@ -96,6 +366,40 @@ impl SingleChoiceProposal {
         }
     }
 
+    /// Starts, restarts, or cancels this proposal's settlement after
+    /// its votes change. A settlement starts once the votes decide the
+    /// proposal and restarts if they decide it the other way. It is
+    /// cancelled if a revote leaves the proposal undecided.
+    pub fn update_settlement(&mut self, block: &BlockInfo) {
+        let period = match self.revote_settle_period {
+            Some(period) if self.allow_revoting => period,
+            _ => return,
+        };
+        let passed = if self.is_tally_passed(block) {
+            true
+        } else if self.is_tally_rejected(block) {
+            false
+        } else {
+            self.settlement = None;
+            return;
+        };
+        match &self.settlement {
+            Some(settlement) if settlement.passed == passed => (),
+            _ => {
+                self.settlement = Some(Settlement {
+                    passed,
+                    settles_at: period.after(block),
+                })
+            }
+        }
+    }
+
+    /// Returns true if this proposal has a settlement that has
+    /// settled.
+    fn is_settled(&self, block: &BlockInfo) -> bool {
+        matches!(&self.settlement, Some(settlement) if settlement.settles_at.is_expired(block))
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) {
         let new_status = self.current_status(block);
@@ -106,13 +410,89 @@ impl SingleChoiceProposal {
         self.status = new_status
     }
 
+    /// The fraction of `total_power` that has voted on this
+    /// proposal, counting yes, no, and abstain votes. This is the
+    /// same ratio that is compared against quorum.
+    pub fn participation(&self) -> Decimal {
+        if self.total_power.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(self.votes.total(), self.total_power)
+        }
+    }
+
+    /// Returns true if the votes cast on this proposal meet its
+    /// quorum. Thresholds without a quorum never reach one.
+    pub fn is_quorum_reached(&self) -> bool {
+        match self.threshold {
+            Threshold::ThresholdQuorum { quorum, .. }
+            | Threshold::SimpleQuorumMajority { quorum } => {
+                does_vote_count_pass(self.votes.total(), self.total_power, quorum)
+            }
+            Threshold::AbsolutePercentage { .. } | Threshold::AbsoluteCount { .. } => false,
+        }
+    }
+
+    /// Gets the votes this proposal's threshold is checked against.
+    /// Once it has expired the voting power that did not vote is
+    /// counted as its `non_voter_default` position, if it has one.
+    /// Quorum is always checked against `votes`.
+    fn threshold_votes(&self, block: &BlockInfo) -> Votes {
+        let mut votes = self.votes.clone();
+        if let Some(default) = self.non_voter_default {
+            if self.expiration.is_expired(block) {
+                // Can not overflow as the votes then total no more
+                // than `total_power`.
+                let uncast = self.total_power.saturating_sub(self.votes.total());
+                match default {
+                    Vote::Yes => votes.yes += uncast,
+                    Vote::No => votes.no += uncast,
+                    Vote::Abstain => votes.abstain += uncast,
+                }
+            }
+        }
+        votes
+    }
+
+    /// Pushes back this proposal's expiration by `extension.extension`
+    /// if `block` is within `extension.window` of it and the proposal
+    /// has been extended fewer than `extension.max_extensions` times.
+    /// Returns true if the proposal was extended.
+    pub fn extend_expiration(&mut self, block: &BlockInfo, extension: &QuorumExtension) -> bool {
+        if self.extensions >= extension.max_extensions {
+            return false;
+        }
+        self.expiration = match (self.expiration, extension.window, extension.extension) {
+            (
+                Expiration::AtHeight(height),
+                Duration::Height(window),
+                Duration::Height(extension),
+            ) if block.height.saturating_add(window) >= height => {
+                Expiration::AtHeight(height + extension)
+            }
+            (Expiration::AtTime(time), Duration::Time(window), Duration::Time(extension))
+                if block.time.plus_seconds(window) >= time =>
+            {
+                Expiration::AtTime(time.plus_seconds(extension))
+            }
+            _ => return false,
+        };
+        self.extensions += 1;
+        true
+    }
+
     /// Returns true iff this proposal is sure to pass (even before
     /// expiration if no future sequence of possible votes can cause
     /// it to fail).
+    ///
+    /// Votes may exceed `total_power` if the voting module misreports
+    /// power, so differences with it saturate at zero instead of
+    /// panicking. `add_vote` ensures the vote sums here can not
+    /// overflow.
     pub fn is_passed(&self, block: &BlockInfo) -> bool {
         // If re-voting is allowed nothing is known until the proposal
-        // has expired.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // has expired or its outcome has settled.
+        if self.allow_revoting && !self.expiration.is_expired(block) && !self.is_settled(block) {
             return false;
         }
         // If the min voting period is set and not expired the
@@ -125,47 +505,109 @@ impl SingleChoiceProposal {
                 return false;
             }
         }
+        self.is_tally_passed(block)
+    }
 
+    /// Returns true if this proposal's votes are sure to pass it,
+    /// ignoring that they may still change if it allows revoting and
+    /// its minimum voting period.
+    fn is_tally_passed(&self, block: &BlockInfo) -> bool {
+        let votes = self.threshold_votes(block);
         match self.threshold {
             Threshold::AbsolutePercentage { percentage } => {
-                let options = self.total_power - self.votes.abstain;
-                does_vote_count_pass(self.votes.yes, options, percentage)
+                let options = self.total_power.saturating_sub(votes.abstain);
+                does_vote_count_pass(votes.yes, options, percentage)
             }
-            Threshold::ThresholdQuorum { threshold, quorum } => {
+            Threshold::ThresholdQuorum {
+                threshold,
+                quorum,
+                mode,
+            } => {
+                if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
+                    return false;
+                }
+                let expired = self.expiration.is_expired(block);
+
+                match mode {
+                    ThresholdMode::MajorityOfNonAbstain {} => {
+                        if expired {
+                            // If the quorum is met and the proposal is
+                            // expired the number of votes needed to pass a
+                            // proposal is compared to the number of votes on
+                            // the proposal.
+                            let options = votes.total() - votes.abstain;
+                            does_vote_count_pass(votes.yes, options, threshold)
+                        } else {
+                            let options = self.total_power.saturating_sub(votes.abstain);
+                            does_vote_count_pass(votes.yes, options, threshold)
+                        }
+                    }
+                    ThresholdMode::MajorityOfParticipation {} => {
+                        // Before expiration the remaining voting
+                        // power may still participate, so compare
+                        // against the largest possible turnout.
+                        let options = if expired {
+                            votes.total()
+                        } else {
+                            self.total_power
+                        };
+                        does_vote_count_pass(votes.yes, options, threshold)
+                    }
+                    ThresholdMode::AbsoluteMajority {} => {
+                        does_vote_count_pass(votes.yes, self.total_power, threshold)
+                    }
+                }
+            }
+            Threshold::AbsoluteCount { threshold } => votes.yes >= threshold,
+            Threshold::SimpleQuorumMajority { quorum } => {
                 if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
                     return false;
                 }
-
                 if self.expiration.is_expired(block) {
-                    // If the quorum is met and the proposal is
-                    // expired the number of votes needed to pass a
-                    // proposal is compared to the number of votes on
-                    // the proposal.
-                    let options = self.votes.total() - self.votes.abstain;
-                    does_vote_count_pass(self.votes.yes, options, threshold)
+                    votes.yes > votes.no
                 } else {
-                    let options = self.total_power - self.votes.abstain;
-                    does_vote_count_pass(self.votes.yes, options, threshold)
+                    // Passed once all outstanding votes voting no
+                    // would still leave more yes votes than no votes.
+                    let outstanding_votes = self.total_power.saturating_sub(votes.total());
+                    votes.yes > votes.no + outstanding_votes
                 }
             }
-            Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
         }
     }
 
     /// As above for the passed check, used to check if a proposal is
     /// already rejected.
     pub fn is_rejected(&self, block: &BlockInfo) -> bool {
-        // If re-voting is allowed and the proposal is not expired no
-        // information is known.
-        if self.allow_revoting && !self.expiration.is_expired(block) {
+        // If re-voting is allowed and the proposal is not expired or
+        // settled no information is known.
+        if self.allow_revoting && !self.expiration.is_expired(block) && !self.is_settled(block) {
             return false;
         }
+        self.is_tally_rejected(block)
+    }
+
+    /// Returns true if the votes against this proposal rejected it,
+    /// as opposed to it failing to reach its quorum or expiring
+    /// without enough votes either way.
+    pub fn is_rejected_by_votes(&self, block: &BlockInfo) -> bool {
+        let quorum_met = match self.threshold {
+            Threshold::ThresholdQuorum { .. } | Threshold::SimpleQuorumMajority { .. } => {
+                self.is_quorum_reached()
+            }
+            Threshold::AbsolutePercentage { .. } | Threshold::AbsoluteCount { .. } => true,
+        };
+        quorum_met && self.is_tally_rejected(block)
+    }
 
+    /// Returns true if this proposal's votes are sure to reject it,
+    /// ignoring that they may still change if it allows revoting.
+    fn is_tally_rejected(&self, block: &BlockInfo) -> bool {
+        let votes = self.threshold_votes(block);
         match self.threshold {
             Threshold::AbsolutePercentage {
                 percentage: percentage_needed,
             } => {
-                let options = self.total_power - self.votes.abstain;
+                let options = self.total_power.saturating_sub(votes.abstain);
 
                 // If there is a 100% passing threshold..
                 if percentage_needed == PercentageThreshold::Percent(Decimal::percent(100)) {
@@ -183,86 +625,229 @@ impl SingleChoiceProposal {
                         // threshold`) we get a 0% requirement for no
                         // votes. Zero no votes do indeed meet a 0%
                         // threshold.
-                        return self.votes.no >= Uint128::new(1);
+                        return votes.no >= Uint128::new(1);
                     }
                 }
 
-                does_vote_count_fail(self.votes.no, options, percentage_needed)
+                does_vote_count_fail(votes.no, options, percentage_needed)
             }
-            Threshold::ThresholdQuorum { threshold, quorum } => {
-                match (
-                    does_vote_count_pass(self.votes.total(), self.total_power, quorum),
-                    self.expiration.is_expired(block),
-                ) {
-                    // Has met quorum and is expired.
-                    (true, true) => {
-                        // => consider only votes cast and see if no
-                        //    votes meet threshold.
-                        let options = self.votes.total() - self.votes.abstain;
-
-                        // If there is a 100% passing threshold..
-                        if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
-                            if options == Uint128::zero() {
-                                // and there are no possible votes (zero
-                                // voting power or all abstain), then this
-                                // proposal has been rejected.
-                                return true;
-                            } else {
-                                // and there are possible votes, then this is
-                                // rejected if there is a single no vote.
-                                //
-                                // We need this check becuase
-                                // otherwise when we invert the
-                                // threshold (`Decimal::one() -
-                                // threshold`) we get a 0% requirement
-                                // for no votes. Zero no votes do
-                                // indeed meet a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
-                            }
-                        }
-                        does_vote_count_fail(self.votes.no, options, threshold)
+            Threshold::ThresholdQuorum {
+                threshold,
+                quorum,
+                mode,
+            } => {
+                let quorum_met = does_vote_count_pass(self.votes.total(), self.total_power, quorum);
+                let expired = self.expiration.is_expired(block);
+
+                // Hasn't met quorum requirement and voting has closed => rejected.
+                if !quorum_met && expired {
+                    return true;
+                }
+
+                // The votes counting against the proposal and the
+                // number of votes they are compared against. If the
+                // proposal has met quorum and is expired only votes
+                // cast are considered, otherwise all possible votes
+                // are considered.
+                let (against, options) = match (mode, quorum_met && expired) {
+                    (ThresholdMode::MajorityOfNonAbstain {}, true) => {
+                        (votes.no, votes.total() - votes.abstain)
                     }
-                    // Has met quorum and is not expired.
-                    // | Hasn't met quorum and is not expired.
-                    (true, false) | (false, false) => {
-                        // => consider all possible votes and see if
-                        //    no votes meet threshold.
-                        let options = self.total_power - self.votes.abstain;
-
-                        // If there is a 100% passing threshold..
-                        if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
-                            if options == Uint128::zero() {
-                                // and there are no possible votes (zero
-                                // voting power or all abstain), then this
-                                // proposal has been rejected.
-                                return true;
-                            } else {
-                                // and there are possible votes, then this is
-                                // rejected if there is a single no vote.
-                                //
-                                // We need this check becuase otherwise
-                                // when we invert the threshold
-                                // (`Decimal::one() - threshold`) we
-                                // get a 0% requirement for no
-                                // votes. Zero no votes do indeed meet
-                                // a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
-                            }
-                        }
+                    (ThresholdMode::MajorityOfNonAbstain {}, false) => {
+                        (votes.no, self.total_power.saturating_sub(votes.abstain))
+                    }
+                    (ThresholdMode::MajorityOfParticipation {}, true) => {
+                        (votes.no + votes.abstain, votes.total())
+                    }
+                    (ThresholdMode::MajorityOfParticipation {}, false) => {
+                        (votes.no + votes.abstain, self.total_power)
+                    }
+                    // Every bit of voting power that did not vote
+                    // yes counts against the proposal.
+                    (ThresholdMode::AbsoluteMajority {}, true) => {
+                        (self.total_power.saturating_sub(votes.yes), self.total_power)
+                    }
+                    (ThresholdMode::AbsoluteMajority {}, false) => {
+                        (votes.no + votes.abstain, self.total_power)
+                    }
+                };
 
-                        does_vote_count_fail(self.votes.no, options, threshold)
+                // If there is a 100% passing threshold..
+                if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
+                    if options == Uint128::zero() {
+                        // and there are no possible votes (zero
+                        // voting power or all abstain), then this
+                        // proposal has been rejected.
+                        return true;
+                    } else {
+                        // and there are possible votes, then this is
+                        // rejected if there is a single vote against.
+                        //
+                        // We need this check becuase otherwise when
+                        // we invert the threshold (`Decimal::one() -
+                        // threshold`) we get a 0% requirement for no
+                        // votes. Zero no votes do indeed meet a 0%
+                        // threshold.
+                        return against >= Uint128::new(1);
                     }
-                    // Hasn't met quorum requirement and voting has closed => rejected.
-                    (false, true) => true,
                 }
+
+                does_vote_count_fail(against, options, threshold)
             }
             Threshold::AbsoluteCount { threshold } => {
                 // If all the outstanding votes voting yes would not
                 // cause this proposal to pass then it is rejected.
-                let outstanding_votes = self.total_power - self.votes.total();
-                self.votes.yes + outstanding_votes < threshold
+                let outstanding_votes = self.total_power.saturating_sub(votes.total());
+                votes.yes + outstanding_votes < threshold
+            }
+            Threshold::SimpleQuorumMajority { quorum } => {
+                if self.expiration.is_expired(block) {
+                    let quorum_met =
+                        does_vote_count_pass(self.votes.total(), self.total_power, quorum);
+                    !quorum_met || votes.yes <= votes.no
+                } else {
+                    // Rejected once all outstanding votes voting yes
+                    // would not leave more yes votes than no votes.
+                    let outstanding_votes = self.total_power.saturating_sub(votes.total());
+                    votes.yes + outstanding_votes <= votes.no
+                }
+            }
+        }
+    }
+
+    /// Returns how much of this proposal's minimum voting period
+    /// remains. None if the proposal has no minimum voting period or
+    /// it has elapsed.
+    pub fn min_voting_period_remaining(&self, block: &BlockInfo) -> Option<Duration> {
+        match self.min_voting_period? {
+            Expiration::AtHeight(height) if height > block.height => {
+                Some(Duration::Height(height - block.height))
+            }
+            Expiration::AtTime(time) if time > block.time => {
+                Some(Duration::Time(time.seconds() - block.time.seconds()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if this proposal is open and its votes would
+    /// pass it now if not for its minimum voting period.
+    pub fn would_pass_without_min_voting_period(&self, block: &BlockInfo) -> bool {
+        if self.current_status(block) != Status::Open {
+            return false;
+        }
+        let hypothetical = SingleChoiceProposal {
+            min_voting_period: None,
+            ..self.clone()
+        };
+        hypothetical.is_passed(block)
+    }
+
+    /// Returns the smallest amount of additional yes voting power
+    /// that would cause this proposal to pass right now. Returns None
+    /// if the proposal is no longer open or can not pass right now
+    /// even if all outstanding voting power votes yes, for example
+    /// because its minimum voting period has not elapsed.
+    pub fn power_needed_to_pass(&self, block: &BlockInfo) -> Option<Uint128> {
+        if self.current_status(block) != Status::Open {
+            return None;
+        }
+
+        let mut hypothetical = self.clone();
+        let mut passes_with = |additional: Uint128| {
+            hypothetical.votes.yes = self.votes.yes + additional;
+            hypothetical.is_passed(block)
+        };
+
+        let outstanding = self.total_power.saturating_sub(self.votes.total());
+        if !passes_with(outstanding) {
+            return None;
+        }
+
+        // Adding yes votes never makes a proposal less likely to
+        // pass, so binary search for the smallest amount that
+        // passes. The proposal is open so `low` does not pass.
+        let mut low = Uint128::zero();
+        let mut high = outstanding;
+        while high - low > Uint128::new(1) {
+            let mid = low + (high - low) / Uint128::new(2);
+            if passes_with(mid) {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Some(high)
+    }
+
+    /// Returns the status this proposal would have at its expiration
+    /// if all voting power that has not yet voted were to vote
+    /// `vote`. If no voting power remains uncast this is the status
+    /// the proposal's current votes would have at expiration. Returns
+    /// None if the proposal is no longer open.
+    pub fn outcome_if_remaining_vote(
+        &self,
+        block: &BlockInfo,
+        vote: Vote,
+    ) -> StdResult<Option<Status>> {
+        if self.current_status(block) != Status::Open {
+            return Ok(None);
+        }
+
+        let mut votes = self.votes.clone();
+        let uncast = self.total_power.saturating_sub(self.votes.total());
+        votes.add_vote(vote, uncast)?;
+        Ok(Some(self.outcome_at_expiration(block, votes)))
+    }
+
+    /// Returns true if a voter with `power` voting power voting on
+    /// this proposal now could change the status it would have at its
+    /// expiration were no other votes cast. `previous` is the voter's
+    /// current vote, if any, which their new vote replaces. Returns
+    /// false if the proposal is no longer open.
+    pub fn vote_could_change_outcome(
+        &self,
+        block: &BlockInfo,
+        power: Uint128,
+        previous: Option<Vote>,
+    ) -> StdResult<bool> {
+        if self.current_status(block) != Status::Open {
+            return Ok(false);
+        }
+
+        let expected = self.outcome_at_expiration(block, self.votes.clone());
+        for vote in [Vote::Yes, Vote::No, Vote::Abstain] {
+            let mut votes = self.votes.clone();
+            if let Some(previous) = previous {
+                votes.remove_vote(previous, power);
+            }
+            votes.add_vote(vote, power)?;
+            if self.outcome_at_expiration(block, votes) != expected {
+                return Ok(true);
             }
         }
+        Ok(false)
+    }
+
+    /// Returns the status this proposal would have at its expiration
+    /// if its votes were `votes`.
+    fn outcome_at_expiration(&self, block: &BlockInfo, votes: Votes) -> Status {
+        let hypothetical = SingleChoiceProposal {
+            votes,
+            ..self.clone()
+        };
+        let at_expiration = match self.expiration {
+            Expiration::AtHeight(height) => BlockInfo {
+                height,
+                ..block.clone()
+            },
+            Expiration::AtTime(time) => BlockInfo {
+                time,
+                ..block.clone()
+            },
+            Expiration::Never {} => block.clone(),
+        };
+        hypothetical.current_status(&at_expiration)
     }
 }
 
@@ -305,6 +890,29 @@ mod test {
             deposit_info: None,
             created: block.time,
             last_updated: block.time,
+            execution_error: None,
+            seconding_deadline: None,
+            execution_funds: vec![],
+            quorum_reached: false,
+            voting_module: None,
+            require_power_at_resolution: false,
+            related_proposals: vec![],
+            last_execution_attempt: None,
+            extensions: 0,
+            native_deposit: None,
+            snapshot_height: None,
+            close_proposal_on_execution_failure: None,
+            reveal_period: None,
+            revote_settle_period: None,
+            settlement: None,
+            metadata: None,
+            vote_count: None,
+            voting_module_code_id: None,
+            module_version: None,
+            non_voter_default: None,
+            resolved_at: None,
+            execution_retry_cooldown: None,
+            original_proposer: None,
         };
         (prop, block)
     }
@@ -803,6 +1411,7 @@ mod test {
         let quorum = Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Percent(Decimal::percent(50)),
             quorum: PercentageThreshold::Percent(Decimal::percent(40)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         };
         // all non-yes votes are counted for quorum
         let passing = Votes {
@@ -915,6 +1524,7 @@ mod test {
         let quorum = Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(40)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         };
         // all non-yes votes are counted for quorum
         let rejecting = Votes {
@@ -1045,6 +1655,7 @@ mod test {
         let quorum = Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Percent(Decimal::percent(60)),
             quorum: PercentageThreshold::Percent(Decimal::percent(80)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         };
 
         // Try 9 yes, 1 no (out of 15) -> 90% voter threshold, 60%
@@ -1118,4 +1729,674 @@ mod test {
             false
         ));
     }
+
+    fn quorum_with_mode(mode: ThresholdMode) -> Threshold {
+        Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(40)),
+            mode,
+        }
+    }
+
+    #[test]
+    fn threshold_mode_abstain_pivotal() {
+        // All voting power has voted and 6 of 15 abstained. Ignoring
+        // abstain votes 7 of 9 is a majority, counting them 7 of 15
+        // is not.
+        let votes = Votes {
+            yes: Uint128::new(7),
+            no: Uint128::new(2),
+            abstain: Uint128::new(6),
+        };
+
+        let non_abstain = quorum_with_mode(ThresholdMode::MajorityOfNonAbstain {});
+        assert!(check_is_passed(
+            non_abstain.clone(),
+            votes.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            non_abstain,
+            votes.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+
+        let participation = quorum_with_mode(ThresholdMode::MajorityOfParticipation {});
+        assert!(!check_is_passed(
+            participation.clone(),
+            votes.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+        assert!(check_is_rejected(
+            participation,
+            votes.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+
+        let absolute = quorum_with_mode(ThresholdMode::AbsoluteMajority {});
+        assert!(!check_is_passed(
+            absolute.clone(),
+            votes.clone(),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+        assert!(check_is_rejected(
+            absolute,
+            votes,
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn threshold_mode_expired() {
+        // 9 of 20 voting power has voted, meeting the 40% quorum.
+        let votes = Votes {
+            yes: Uint128::new(6),
+            no: Uint128::new(2),
+            abstain: Uint128::new(1),
+        };
+
+        // 6 of 8 non-abstain votes.
+        let non_abstain = quorum_with_mode(ThresholdMode::MajorityOfNonAbstain {});
+        assert!(check_is_passed(
+            non_abstain,
+            votes.clone(),
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+
+        // 6 of 9 votes cast.
+        let participation = quorum_with_mode(ThresholdMode::MajorityOfParticipation {});
+        assert!(check_is_passed(
+            participation,
+            votes.clone(),
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+
+        // 6 of 20 total voting power.
+        let absolute = quorum_with_mode(ThresholdMode::AbsoluteMajority {});
+        assert!(!check_is_passed(
+            absolute.clone(),
+            votes.clone(),
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+        assert!(check_is_rejected(
+            absolute,
+            votes,
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn non_voter_default() {
+        // 9 of 20 voting power has voted, meeting the 40% quorum.
+        let votes = Votes {
+            yes: Uint128::new(6),
+            no: Uint128::new(2),
+            abstain: Uint128::new(1),
+        };
+        let status = |threshold: Threshold, votes: Votes, default: Option<Vote>, expired: bool| {
+            let (mut prop, block) =
+                setup_prop(threshold, votes, Uint128::new(20), expired, true, false);
+            prop.non_voter_default = default;
+            prop.current_status(&block)
+        };
+
+        // 6 of 8 non-abstain votes, 6 of 19 with the 11 non-voters
+        // voting no, and 6 of 8 again with them abstaining.
+        let non_abstain = quorum_with_mode(ThresholdMode::MajorityOfNonAbstain {});
+        assert_eq!(
+            status(non_abstain.clone(), votes.clone(), None, true),
+            Status::Passed
+        );
+        assert_eq!(
+            status(non_abstain.clone(), votes.clone(), Some(Vote::No), true),
+            Status::Rejected
+        );
+        assert_eq!(
+            status(
+                non_abstain.clone(),
+                votes.clone(),
+                Some(Vote::Abstain),
+                true
+            ),
+            Status::Passed
+        );
+
+        // 6 of 9 votes cast, and 6 of 20 once non-voters participate
+        // either way.
+        let participation = quorum_with_mode(ThresholdMode::MajorityOfParticipation {});
+        assert_eq!(
+            status(participation.clone(), votes.clone(), None, true),
+            Status::Passed
+        );
+        assert_eq!(
+            status(participation.clone(), votes.clone(), Some(Vote::No), true),
+            Status::Rejected
+        );
+        assert_eq!(
+            status(participation, votes.clone(), Some(Vote::Abstain), true),
+            Status::Rejected
+        );
+
+        // Non-voters are not counted before expiration.
+        assert_eq!(
+            status(non_abstain.clone(), votes, Some(Vote::No), false),
+            Status::Open
+        );
+
+        // Non-voters do not count towards quorum: 6 of 20 voting
+        // power falls short of the 40% quorum even if non-voters are
+        // counted as voting yes.
+        let votes = Votes {
+            yes: Uint128::new(6),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+        };
+        assert_eq!(
+            status(non_abstain, votes, Some(Vote::Yes), true),
+            Status::Rejected
+        );
+    }
+
+    #[test]
+    fn threshold_mode_open_undecided() {
+        // 11 of 20 voting power has not voted so no mode may resolve
+        // the proposal early.
+        let votes = Votes {
+            yes: Uint128::new(6),
+            no: Uint128::new(2),
+            abstain: Uint128::new(1),
+        };
+        for mode in [
+            ThresholdMode::MajorityOfNonAbstain {},
+            ThresholdMode::MajorityOfParticipation {},
+            ThresholdMode::AbsoluteMajority {},
+        ] {
+            assert!(!check_is_passed(
+                quorum_with_mode(mode),
+                votes.clone(),
+                Uint128::new(20),
+                false,
+                true,
+                false
+            ));
+            assert!(!check_is_rejected(
+                quorum_with_mode(mode),
+                votes.clone(),
+                Uint128::new(20),
+                false,
+                true,
+                false
+            ));
+        }
+
+        // A majority of all voting power voting yes passes the
+        // proposal early in every mode.
+        let votes = Votes {
+            yes: Uint128::new(11),
+            no: Uint128::new(0),
+            abstain: Uint128::new(0),
+        };
+        for mode in [
+            ThresholdMode::MajorityOfNonAbstain {},
+            ThresholdMode::MajorityOfParticipation {},
+            ThresholdMode::AbsoluteMajority {},
+        ] {
+            assert!(check_is_passed(
+                quorum_with_mode(mode),
+                votes.clone(),
+                Uint128::new(20),
+                false,
+                true,
+                false
+            ));
+        }
+    }
+
+    #[test]
+    fn simple_quorum_majority_abstain_pivotal() {
+        let simple = Threshold::SimpleQuorumMajority {
+            quorum: PercentageThreshold::Percent(Decimal::percent(40)),
+        };
+        let votes = |yes: u128, no: u128, abstain: u128| Votes {
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            abstain: Uint128::new(abstain),
+        };
+
+        // 8 of 20 voting power has voted, meeting the 40% quorum only
+        // because of the abstain votes. Abstain votes do not count
+        // against the proposal so 3 yes to 2 no passes, unlike in the
+        // `ThresholdQuorum` modes that count them.
+        assert!(check_is_passed(
+            simple.clone(),
+            votes(3, 2, 3),
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+        for mode in [
+            ThresholdMode::MajorityOfParticipation {},
+            ThresholdMode::AbsoluteMajority {},
+        ] {
+            assert!(check_is_rejected(
+                quorum_with_mode(mode),
+                votes(3, 2, 3),
+                Uint128::new(20),
+                true,
+                true,
+                false
+            ));
+        }
+        // Without the abstain votes quorum is not met.
+        assert!(check_is_rejected(
+            simple.clone(),
+            votes(3, 2, 0),
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+        // Ties are rejected.
+        assert!(check_is_rejected(
+            simple.clone(),
+            votes(3, 3, 2),
+            Uint128::new(20),
+            true,
+            true,
+            false
+        ));
+
+        // With all voting power voted, abstain votes can not prevent
+        // an early pass.
+        assert!(check_is_passed(
+            simple.clone(),
+            votes(7, 2, 6),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+        assert!(check_is_rejected(
+            quorum_with_mode(ThresholdMode::MajorityOfParticipation {}),
+            votes(7, 2, 6),
+            Uint128::new(15),
+            false,
+            true,
+            false
+        ));
+
+        // 11 of 20 voting power is outstanding and could still make
+        // the no votes level with the yes votes, or the yes votes
+        // exceed the no votes.
+        assert!(!check_is_passed(
+            simple.clone(),
+            votes(6, 2, 1),
+            Uint128::new(20),
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            simple.clone(),
+            votes(6, 2, 1),
+            Uint128::new(20),
+            false,
+            true,
+            false
+        ));
+        // 4 of 20 voting power is outstanding, which voting yes would
+        // only bring the yes votes level with the no votes.
+        assert!(check_is_rejected(
+            simple,
+            votes(4, 8, 4),
+            Uint128::new(20),
+            false,
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn power_needed_to_pass() {
+        let votes = Votes {
+            yes: Uint128::new(3),
+            no: Uint128::new(2),
+            abstain: Uint128::new(1),
+        };
+        let needed = |threshold: Threshold, votes: Votes| {
+            let (prop, block) = setup_prop(threshold, votes, Uint128::new(20), false, true, false);
+            prop.power_needed_to_pass(&block)
+        };
+
+        // 19 non-abstain votes possible, so 10 yes votes are needed.
+        assert_eq!(
+            needed(
+                Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Majority {},
+                },
+                votes.clone()
+            ),
+            Some(Uint128::new(7))
+        );
+        assert_eq!(
+            needed(
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::new(10),
+                },
+                votes.clone()
+            ),
+            Some(Uint128::new(7))
+        );
+        // Quorum of 40% is met by 8 votes, majority of the 19
+        // possible non-abstain votes is still 10.
+        assert_eq!(
+            needed(
+                quorum_with_mode(ThresholdMode::MajorityOfNonAbstain {}),
+                votes.clone()
+            ),
+            Some(Uint128::new(7))
+        );
+        // Counting abstain votes, a majority of all 20 is 11.
+        assert_eq!(
+            needed(
+                quorum_with_mode(ThresholdMode::MajorityOfParticipation {}),
+                votes.clone()
+            ),
+            Some(Uint128::new(8))
+        );
+        // Passing would need 21 yes votes but only 14 are outstanding.
+        assert_eq!(
+            needed(
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::new(21),
+                },
+                votes.clone()
+            ),
+            None
+        );
+
+        // Proposals that have already passed need no more power.
+        let passed = Votes {
+            yes: Uint128::new(11),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+        };
+        assert_eq!(
+            needed(
+                Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Majority {},
+                },
+                passed
+            ),
+            None
+        );
+
+        // Proposals can not pass before the min voting period ends.
+        let (prop, block) = setup_prop(
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            votes,
+            Uint128::new(20),
+            false,
+            false,
+            false,
+        );
+        assert_eq!(prop.power_needed_to_pass(&block), None);
+    }
+
+    #[test]
+    fn outcome_if_remaining_vote() {
+        let votes = Votes {
+            yes: Uint128::new(8),
+            no: Uint128::new(2),
+            abstain: Uint128::new(1),
+        };
+        let outcome = |threshold: Threshold, votes: Votes, vote: Vote| {
+            let (prop, block) = setup_prop(threshold, votes, Uint128::new(20), false, true, false);
+            prop.outcome_if_remaining_vote(&block, vote).unwrap()
+        };
+        let majority = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        };
+
+        // 9 votes are uncast, so yes can win with 17 of 19 or lose
+        // with 8 of 19.
+        assert_eq!(
+            outcome(majority.clone(), votes.clone(), Vote::Yes),
+            Some(Status::Passed)
+        );
+        assert_eq!(
+            outcome(majority.clone(), votes.clone(), Vote::No),
+            Some(Status::Rejected)
+        );
+        // Abstaining leaves 8 of 10 non-abstain votes voting yes.
+        assert_eq!(
+            outcome(majority.clone(), votes.clone(), Vote::Abstain),
+            Some(Status::Passed)
+        );
+        // With quorum only the votes cast at expiration are counted
+        // so the same applies.
+        assert_eq!(
+            outcome(
+                quorum_with_mode(ThresholdMode::MajorityOfNonAbstain {}),
+                votes.clone(),
+                Vote::Abstain
+            ),
+            Some(Status::Passed)
+        );
+        assert_eq!(
+            outcome(
+                quorum_with_mode(ThresholdMode::MajorityOfParticipation {}),
+                votes.clone(),
+                Vote::Abstain
+            ),
+            Some(Status::Rejected)
+        );
+
+        // With no power uncast the current votes decide the
+        // outcome. Revoting keeps the proposal open until expiration.
+        let all_cast = Votes {
+            yes: Uint128::new(12),
+            no: Uint128::new(6),
+            abstain: Uint128::new(2),
+        };
+        let (prop, block) = setup_prop(
+            majority.clone(),
+            all_cast,
+            Uint128::new(20),
+            false,
+            true,
+            true,
+        );
+        for vote in [Vote::Yes, Vote::No, Vote::Abstain] {
+            assert_eq!(
+                prop.outcome_if_remaining_vote(&block, vote).unwrap(),
+                Some(Status::Passed)
+            );
+        }
+
+        // Proposals that are no longer open have no hypothetical
+        // outcome.
+        let passed = Votes {
+            yes: Uint128::new(11),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+        };
+        assert_eq!(outcome(majority, passed, Vote::No), None);
+    }
+
+    #[test]
+    fn large_totals_do_not_panic() {
+        let max = u128::MAX;
+        let values = [0, 1, max / 3, max / 2, max / 2 + 1, max - 1, max];
+
+        let percentages = [
+            PercentageThreshold::Majority {},
+            PercentageThreshold::Percent(Decimal::percent(1)),
+            PercentageThreshold::Percent(Decimal::percent(50)),
+            PercentageThreshold::Percent(Decimal::from_ratio(2u64, 3u64)),
+            PercentageThreshold::Percent(Decimal::percent(100)),
+        ];
+        let mut thresholds = vec![
+            Threshold::AbsoluteCount {
+                threshold: Uint128::new(1),
+            },
+            Threshold::AbsoluteCount {
+                threshold: Uint128::MAX,
+            },
+        ];
+        for percentage in percentages {
+            thresholds.push(Threshold::AbsolutePercentage { percentage });
+            for quorum in [
+                PercentageThreshold::Majority {},
+                PercentageThreshold::Percent(Decimal::zero()),
+                PercentageThreshold::Percent(Decimal::percent(100)),
+            ] {
+                for mode in [
+                    ThresholdMode::MajorityOfNonAbstain {},
+                    ThresholdMode::MajorityOfParticipation {},
+                    ThresholdMode::AbsoluteMajority {},
+                ] {
+                    thresholds.push(Threshold::ThresholdQuorum {
+                        threshold: percentage,
+                        quorum,
+                        mode,
+                    });
+                }
+            }
+            thresholds.push(Threshold::SimpleQuorumMajority { quorum: percentage });
+        }
+
+        for threshold in thresholds.iter() {
+            for total_power in values {
+                for yes in values {
+                    for no in values {
+                        for abstain in values {
+                            // Tallies that `add_vote` would refuse.
+                            if yes
+                                .checked_add(no)
+                                .and_then(|sum| sum.checked_add(abstain))
+                                .is_none()
+                            {
+                                continue;
+                            }
+                            let votes = Votes {
+                                yes: Uint128::new(yes),
+                                no: Uint128::new(no),
+                                abstain: Uint128::new(abstain),
+                            };
+                            for is_expired in [true, false] {
+                                let (prop, block) = setup_prop(
+                                    threshold.clone(),
+                                    votes.clone(),
+                                    Uint128::new(total_power),
+                                    is_expired,
+                                    true,
+                                    false,
+                                );
+                                let passed = prop.is_passed(&block);
+                                let rejected = prop.is_rejected(&block);
+
+                                // Unanimous votes resolve the way
+                                // they were cast.
+                                let count_reachable = match threshold {
+                                    Threshold::AbsoluteCount { threshold } => {
+                                        threshold.u128() <= total_power
+                                    }
+                                    _ => true,
+                                };
+                                let unanimous = total_power != 0 && abstain == 0;
+                                if unanimous && yes == total_power && no == 0 && count_reachable {
+                                    assert!(passed, "{:?} {:?}", threshold, votes);
+                                }
+                                if unanimous && no == total_power && yes == 0 {
+                                    assert!(rejected, "{:?} {:?}", threshold, votes);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_digest_is_deterministic() {
+        let (prop, block) = setup_prop(
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            Votes::with_yes(Uint128::new(10)),
+            Uint128::new(100),
+            false,
+            true,
+            false,
+        );
+        let fields = prop.digest_fields(&block, 1);
+
+        // The exact serialization hashed. Changing it changes every
+        // proposal's digest.
+        assert_eq!(
+            String::from_utf8(cosmwasm_std::to_vec(&fields).unwrap()).unwrap(),
+            r#"{"proposal_id":1,"status":"open","votes":{"yes":"10","no":"0","abstain":"0"},"total_power":"100","threshold":{"absolute_percentage":{"percentage":{"majority":{}}}},"expiration":{"at_height":12445}}"#
+        );
+        assert_eq!(
+            fields.digest().unwrap().to_base64(),
+            "dtZpJcc+cFwcw387pBQ1F7sYp++21Iy3Y1sUnJ5zfts="
+        );
+
+        // Fields the digest does not cover do not change it.
+        let retitled = SingleChoiceProposal {
+            title: "Another title".to_string(),
+            last_updated: block.time.plus_seconds(1),
+            ..prop.clone()
+        };
+        assert_eq!(
+            retitled.digest_fields(&block, 1).digest().unwrap(),
+            fields.digest().unwrap()
+        );
+
+        // The status is computed as of the block, so the digest
+        // changes once the proposal is rejected on expiry even though
+        // its stored status has not.
+        let expired = BlockInfo {
+            height: 12445,
+            ..block
+        };
+        let expired_fields = prop.digest_fields(&expired, 1);
+        assert_eq!(expired_fields.status, Status::Rejected);
+        assert_ne!(expired_fields.digest().unwrap(), fields.digest().unwrap());
+    }
 }
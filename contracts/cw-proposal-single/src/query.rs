@@ -1,16 +1,31 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, StdResult, Uint128};
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use voting::voting::Vote;
+use voting::{
+    deposit::CheckedDepositInfo,
+    status::Status,
+    threshold::Threshold,
+    voting::{Vote, Votes},
+};
 
-use crate::proposal::SingleChoiceProposal;
+use crate::{
+    msg::DepositAsset,
+    proposal::SingleChoiceProposal,
+    state::{ConfigHistoryEntry, FinalResult, VoteHistoryEntry},
+};
 
 /// Information about a proposal returned by proposal queries.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ProposalResponse {
     /// The ID of the proposal being returned.
     pub id: u64,
+    /// The module's `id_prefix`, if it has one. Clients may display
+    /// the proposal as `{id_prefix}-{id}` to tell it apart from
+    /// proposals with the same ID in the DAO's other modules.
+    pub id_prefix: Option<String>,
     pub proposal: SingleChoiceProposal,
 }
 
@@ -32,6 +47,24 @@ pub struct VoteResponse {
     pub vote: Option<VoteInfo>,
 }
 
+/// Returned by `FinalResult`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FinalResultResponse {
+    /// None if the proposal has not reached a terminal status, Some
+    /// otherwise.
+    pub result: Option<FinalResult>,
+}
+
+/// Returned by `CanRevote`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct CanRevoteResponse {
+    /// If the voter may currently change their vote.
+    pub can_revote: bool,
+    /// Why the voter may not change their vote, or `None` if they
+    /// may.
+    pub reason: Option<String>,
+}
+
 /// Information about the votes for a proposal.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct VoteListResponse {
@@ -44,3 +77,234 @@ pub struct VoteListResponse {
 pub struct ProposalListResponse {
     pub proposals: Vec<ProposalResponse>,
 }
+
+/// The proposals matching a filter among those examined by
+/// `ProposalsWithMsgKind` and `ExecutableByAddress`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct FilteredProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+    /// The ID of the last proposal examined, or None if there were
+    /// no proposals left to examine. Pass this as `start_after` to
+    /// continue scanning.
+    pub last_examined: Option<u64>,
+}
+
+/// The state of a proposal's minimum voting period, returned by
+/// `MinPeriodStatus`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct MinPeriodStatusResponse {
+    /// True if the proposal's minimum voting period has elapsed or
+    /// it has none.
+    pub elapsed: bool,
+    /// How much of the minimum voting period remains, in the units
+    /// of the voting period. None if it has elapsed.
+    pub remaining: Option<Duration>,
+    /// True if the proposal is open and would pass now if not for its
+    /// minimum voting period.
+    pub would_pass: bool,
+}
+
+/// Whether a voter could change the outcome of an open proposal,
+/// returned as part of `VoterInfluenceResponse`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalInfluence {
+    pub proposal_id: u64,
+    /// True if the voter voting on the proposal with all of their
+    /// voting power would change the status it would have at its
+    /// expiration were no other votes cast.
+    pub would_change_outcome: bool,
+}
+
+/// The open proposals an address may vote on, returned by
+/// `VoterInfluence`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoterInfluenceResponse {
+    pub proposals: Vec<ProposalInfluence>,
+    /// The ID of the last proposal examined, or None if there were
+    /// no proposals left to examine. Pass this as `start_after` to
+    /// continue scanning.
+    pub last_examined: Option<u64>,
+}
+
+/// The parts of a proposal's state covered by its digest.
+///
+/// The digest is the SHA-256 hash of the JSON encoding of this struct
+/// produced by `cosmwasm_std::to_vec`: fields in the order declared
+/// here without whitespace, `u64`s as JSON numbers, `Uint128`s and
+/// `Decimal`s as strings, and enums in their snake_case externally
+/// tagged form. For example:
+///
+/// `{"proposal_id":1,"status":"open","votes":{"yes":"10","no":"0","abstain":"0"},"total_power":"100","threshold":{"absolute_percentage":{"percentage":{"majority":{}}}},"expiration":{"at_height":12445}}`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalDigestFields {
+    pub proposal_id: u64,
+    /// The proposal's status as of the block the digest was computed
+    /// in.
+    pub status: Status,
+    pub votes: Votes,
+    pub total_power: Uint128,
+    pub threshold: Threshold,
+    pub expiration: Expiration,
+}
+
+impl ProposalDigestFields {
+    /// Computes the digest of these fields.
+    pub fn digest(&self) -> StdResult<Binary> {
+        let mut hasher = Sha256::new();
+        hasher.update(cosmwasm_std::to_vec(self)?);
+        Ok(Binary::from(hasher.finalize().as_slice()))
+    }
+}
+
+/// A digest of a proposal's governing state, returned by
+/// `ProposalDigest`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalDigestResponse {
+    /// The SHA-256 hash of the JSON encoding of `fields`.
+    pub digest: Binary,
+    pub fields: ProposalDigestFields,
+}
+
+/// The parameters governing a proposal as snapshotted when it was
+/// created, returned by `ProposalConfig`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProposalConfigResponse {
+    /// The threshold the proposal must reach to pass.
+    pub threshold: Threshold,
+    /// The total voting power that the threshold and quorum are
+    /// measured against.
+    pub total_power: Uint128,
+    /// The block height at which voting power is snapshotted for the
+    /// proposal.
+    pub snapshot_height: u64,
+    /// When voting on the proposal closes.
+    pub expiration: Expiration,
+    /// When the proposal's minimum voting period ends. None if it
+    /// has none.
+    pub min_voting_period: Option<Expiration>,
+    pub allow_revoting: bool,
+    /// If votes from voters without voting power when the proposal is
+    /// executed are removed from the tally before executing it.
+    pub require_power_at_resolution: bool,
+    /// If the proposal is closed when its execution fails.
+    pub close_proposal_on_execution_failure: bool,
+    /// How long after a failed execution attempt the proposal may not
+    /// be executed again. None if there is no cooldown.
+    pub execution_retry_cooldown: Option<Duration>,
+    /// The cw20 deposit taken for the proposal. None if no deposit.
+    pub deposit_info: Option<CheckedDepositInfo>,
+    /// The native deposit taken for the proposal. None if no native
+    /// deposit.
+    pub native_deposit: Option<Coin>,
+    /// The position non-voters are counted as having taken for the
+    /// proposal's threshold once it expires. None if they are not
+    /// counted.
+    pub non_voter_default: Option<Vote>,
+}
+
+/// How much of the voting power on a proposal has voted, returned by
+/// `ParticipationStatus`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ParticipationStatusResponse {
+    /// The voting power that has voted, including abstain votes.
+    pub power_cast: Uint128,
+    /// The total voting power on the proposal. This is the
+    /// denominator used when checking quorum.
+    pub total_power: Uint128,
+    /// `power_cast / total_power`, or zero if there is no voting
+    /// power.
+    pub participation: Decimal,
+}
+
+/// Returned by `ValidateProposal`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ValidateProposalResponse {
+    /// The errors that the proposal fails validation with, in the
+    /// order they are checked by `Propose`, which fails with the
+    /// first. Empty if the proposal would be accepted.
+    pub errors: Vec<String>,
+}
+
+/// A recorded config returned by `ConfigHistory`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ConfigHistoryEntryResponse {
+    /// The ID of the entry. IDs increase with each recorded config.
+    pub id: u64,
+    pub entry: ConfigHistoryEntry,
+}
+
+/// The configs returned by `ConfigHistory`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ConfigHistoryResponse {
+    pub entries: Vec<ConfigHistoryEntryResponse>,
+}
+
+/// The vote tallies returned by `VoteHistory`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct VoteHistoryResponse {
+    pub entries: Vec<VoteHistoryEntry>,
+}
+
+/// The kind of message that references a proposal target.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalTargetKind {
+    WasmExecute {},
+    WasmMigrate {},
+    WasmUpdateAdmin {},
+    WasmClearAdmin {},
+    BankSend {},
+    Stargate {},
+}
+
+/// Something a proposal's messages would interact with.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalTarget {
+    /// The contract address or bank recipient of the message. For
+    /// stargate messages this is the message's type URL.
+    pub target: String,
+    /// The kind of message that references the target.
+    pub kind: ProposalTargetKind,
+}
+
+/// The targets of a proposal's messages returned by
+/// `ProposalTargets`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ProposalTargetsResponse {
+    pub targets: Vec<ProposalTarget>,
+}
+
+/// The lifetime deposit accounting of one deposit token.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct TokenDepositAccounting {
+    /// The cw20 token the deposits were made in.
+    pub token: Addr,
+    /// Deposits taken from proposers when proposing.
+    pub collected: Uint128,
+    /// Deposits returned to proposers.
+    pub refunded: Uint128,
+    /// Deposits of failed proposals sent to the DAO.
+    pub forfeited: Uint128,
+}
+
+/// The response to a `DepositSolvency` query.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DepositSolvencyResponse {
+    pub asset: DepositAsset,
+    /// The module's current balance of the token.
+    pub balance: Uint128,
+    /// The sum of the deposits in the token that the module holds for
+    /// proposals and will either refund or forfeit, and of the
+    /// refunds in the token waiting to be claimed.
+    pub owed: Uint128,
+    /// Whether the module's balance covers what it owes.
+    pub solvent: bool,
+}
+
+/// The response to a `DepositAccounting` query.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct DepositAccountingResponse {
+    /// Accounting for every token deposits have been made in, ordered
+    /// by token address.
+    pub deposits: Vec<TokenDepositAccounting>,
+}
@@ -107,6 +107,7 @@ fn instantiate_with_staked_balances_voting() {
                 threshold: Threshold::ThresholdQuorum {
                     threshold: voting::threshold::PercentageThreshold::Majority {},
                     quorum: voting::threshold::PercentageThreshold::Percent(Decimal::percent(30)),
+                    mode: voting::threshold::ThresholdMode::MajorityOfNonAbstain {},
                 },
                 max_voting_period: Duration::Height(10u64),
                 min_voting_period: None,
@@ -114,10 +115,41 @@ fn instantiate_with_staked_balances_voting() {
                 allow_revoting: false,
                 deposit_info: None,
                 close_proposal_on_execution_failure: true,
+                exclude_dao_voting_power: false,
+                execution_callback: None,
+                require_second: None,
+                record_config_history: false,
+                quorum_reached_hooks: false,
+                require_power_at_resolution: false,
+                execution_retry_cooldown: None,
+                post_execution_cooldown: None,
+                quorum_extension: None,
+                native_propose_deposit: None,
+                snapshot_offset: 0,
+                pre_execution_hook: None,
+                required_description_sections: vec![],
+                voting_period_limit: None,
+                register_deposit_token: false,
+                total_power_override: None,
+                record_vote_history: false,
+                execute_on_pass: false,
+                reveal_period: None,
+                hook_allowlist: None,
+                revote_settle_period: None,
+                deposit_escalation: None,
+                forfeited_deposit_policy: None,
+                reject_failing_execution: false,
+                id_prefix: None,
+                non_voter_default: None,
+                max_proposal_bytes: None,
+                config_change_delay: None,
+                close_reward: None,
+                cache_voting_power: false,
             })
             .unwrap(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -1,12 +1,19 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Empty, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 
 use indexable_hooks::Hooks;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use voting::{deposit::CheckedDepositInfo, threshold::Threshold, voting::Vote};
+use voting::{
+    deposit::CheckedDepositInfo,
+    reward::CheckedRewardSource,
+    status::Status,
+    threshold::Threshold,
+    voting::{Vote, Votes},
+};
 
 use crate::proposal::SingleChoiceProposal;
 
@@ -17,6 +24,10 @@ pub struct Ballot {
     pub power: Uint128,
     /// The position.
     pub vote: Vote,
+    /// The block height the vote was cast at. None if it was cast
+    /// before this was recorded.
+    #[serde(default)]
+    pub height: Option<u64>,
 }
 /// The governance module's configuration.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -55,6 +66,337 @@ pub struct Config {
     /// remain open until the DAO's treasury was large enough for it to be
     /// executed.
     pub close_proposal_on_execution_failure: bool,
+    /// If set to true the DAO's own voting power, as reported by the
+    /// voting module, is subtracted from the total voting power
+    /// recorded on new proposals. This is not a full circulating
+    /// supply: only power attributed to the DAO's address is
+    /// excluded, and tokens held by other non-voting addresses still
+    /// count.
+    #[serde(default)]
+    pub exclude_dao_voting_power: bool,
+    /// A contract that is sent an `ExecutionCallbackMsg` after a
+    /// proposal is successfully executed. None if no callback.
+    #[serde(default)]
+    pub execution_callback: Option<Addr>,
+    /// If Some, new proposals start out `Pending` and must be
+    /// seconded by a member other than the proposer within this
+    /// amount of time before they open for voting. Proposals that are
+    /// not seconded in time are rejected.
+    #[serde(default)]
+    pub require_second: Option<Duration>,
+    /// If set to true every config this module is updated to is
+    /// appended to a log that may be read with the `ConfigHistory`
+    /// query. Only the most recent `MAX_CONFIG_HISTORY` configs are
+    /// kept.
+    #[serde(default)]
+    pub record_config_history: bool,
+    /// If set to true proposal hook consumers are sent
+    /// `ProposalHookMsg::QuorumReached` the first time a proposal
+    /// reaches quorum. Consumers that error on messages they do not
+    /// recognize are removed, so only enable this once every consumer
+    /// supports it.
+    #[serde(default)]
+    pub quorum_reached_hooks: bool,
+    /// If set to true, when a proposal is executed the voting power
+    /// of each of its voters is checked again and the votes of those
+    /// with no voting power are removed from the tally. A proposal
+    /// that no longer passes is rejected instead of executed, or
+    /// returns to being open if it has not expired. Addresses without
+    /// voting power may not vote on these proposals. This queries the voting power of every voter on the proposal, so
+    /// the gas cost of execution grows with the number of voters.
+    #[serde(default)]
+    pub require_power_at_resolution: bool,
+    /// If set, after a failed attempt to execute a proposal that
+    /// remains passed (`close_proposal_on_execution_failure` is
+    /// false) it may not be executed again until this much time has
+    /// passed.
+    #[serde(default)]
+    pub execution_retry_cooldown: Option<Duration>,
+    /// If set, after one of a proposer's proposals is executed they
+    /// may not create another proposal until this much time has
+    /// passed.
+    #[serde(default)]
+    pub post_execution_cooldown: Option<Duration>,
+    /// If set, voting on proposals that reach quorum shortly before
+    /// they expire is extended.
+    #[serde(default)]
+    pub quorum_extension: Option<QuorumExtension>,
+    /// A native coin that must be sent along with `Propose`. Never
+    /// set alongside `deposit_info`.
+    #[serde(default)]
+    pub native_propose_deposit: Option<Coin>,
+    /// The number of blocks before a proposal's creation at which
+    /// voting power is snapshotted for it. Voting power acquired
+    /// within this many blocks of a proposal being created does not
+    /// count towards it, so tokens may not be acquired and staked
+    /// just to vote on a proposal as it is created. Zero snapshots
+    /// voting power at the block the proposal is created.
+    #[serde(default)]
+    pub snapshot_offset: u64,
+    /// A contract that is queried with a `PreExecutionHookQuery`
+    /// before a proposal is executed and may veto its execution.
+    /// None if no hook.
+    #[serde(default)]
+    pub pre_execution_hook: Option<Addr>,
+    /// Section headers that the description of every new proposal
+    /// must contain. Headers are matched case-insensitively.
+    #[serde(default)]
+    pub required_description_sections: Vec<String>,
+    /// The longest `max_voting_period` the module may be configured
+    /// with.
+    #[serde(default)]
+    pub voting_period_limit: VotingPeriodLimit,
+    /// If the DAO should be asked to add the deposit token to its
+    /// list of cw20 tokens.
+    #[serde(default)]
+    pub register_deposit_token: bool,
+    /// A fixed total voting power used in place of the voting
+    /// module's total when proposals are created.
+    #[serde(default)]
+    pub total_power_override: Option<Uint128>,
+    /// If each proposal's vote tally is recorded at every block in
+    /// which votes are cast on it.
+    #[serde(default)]
+    pub record_vote_history: bool,
+    /// If `Poke` executes passed proposals.
+    #[serde(default)]
+    pub execute_on_pass: bool,
+    /// The length of the reveal phase of proposals using
+    /// commit-reveal voting. None if proposals are voted on directly.
+    #[serde(default)]
+    pub reveal_period: Option<Duration>,
+    /// The addresses that may be added as consumers of hooks without
+    /// overriding the allowlist. None if any address may be added.
+    #[serde(default)]
+    pub hook_allowlist: Option<Vec<Addr>>,
+    /// How long a decided outcome must hold before a proposal that
+    /// allows revoting resolves. None if such proposals remain open
+    /// until they expire.
+    #[serde(default)]
+    pub revote_settle_period: Option<Duration>,
+    /// Raises the deposit of proposers whose recent proposals were
+    /// rejected. None if deposits are never escalated.
+    #[serde(default)]
+    pub deposit_escalation: Option<DepositEscalation>,
+    /// How the deposits of rejected proposals that are not refunded
+    /// are distributed. None if they are sent to the DAO.
+    #[serde(default)]
+    pub forfeited_deposit_policy: Option<ForfeitedDepositPolicy>,
+    /// If passed proposals whose messages are found to be unable to
+    /// execute are rejected when execution is attempted.
+    #[serde(default)]
+    pub reject_failing_execution: bool,
+    /// The position that voting power which did not vote is counted
+    /// as having taken once a proposal expires, for the purposes of
+    /// its threshold but not its quorum. None if it is not counted.
+    /// Whether `abstain` excludes non-voters from the threshold or
+    /// counts them against the proposal depends on the threshold's
+    /// mode, see `InstantiateMsg::non_voter_default`.
+    #[serde(default)]
+    pub non_voter_default: Option<Vote>,
+    /// The maximum serialized size of a proposal in bytes. None if
+    /// only `MAX_PROPOSAL_SIZE` applies.
+    #[serde(default)]
+    pub max_proposal_bytes: Option<u64>,
+    /// The amount of time after `UpdateConfig` is executed before the
+    /// update takes effect. None if updates take effect immediately.
+    #[serde(default)]
+    pub config_change_delay: Option<Duration>,
+    /// The reward paid to whoever closes a rejected proposal. None if
+    /// no reward is paid.
+    #[serde(default)]
+    pub close_reward: Option<CheckedCloseReward>,
+    /// If voting power queried as of a proposal's snapshot height is
+    /// cached in `VOTING_POWER_CACHE` and `TOTAL_POWER_CACHE`.
+    #[serde(default)]
+    pub cache_voting_power: bool,
+}
+
+/// Counterpart to `msg::CloseReward` which has been processed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CheckedCloseReward {
+    pub source: CheckedRewardSource,
+    pub amount: Uint128,
+}
+
+/// Extends voting on proposals that first reach quorum shortly before
+/// they expire, so that quorum can not be reached at the last moment
+/// without giving other members a chance to respond.
+///
+/// If the vote that first brings a proposal to quorum is cast within
+/// `window` of the proposal's expiration, its expiration is pushed
+/// back by `extension`. Once extended, each vote cast within `window`
+/// of the new expiration extends it again, up to `max_extensions`
+/// times in total. Only `ThresholdQuorum` and `SimpleQuorumMajority`
+/// thresholds have a quorum, so an extension may only be configured
+/// with one of those.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct QuorumExtension {
+    /// How close to expiration quorum must be reached for voting to
+    /// be extended. Must have the same units as `max_voting_period`.
+    pub window: Duration,
+    /// How much to extend voting by. Must have the same units as
+    /// `max_voting_period`.
+    pub extension: Duration,
+    /// The maximum number of times a proposal may be extended.
+    pub max_extensions: u64,
+}
+
+/// Escalates the proposal deposit of proposers whose most recent
+/// proposals were rejected, to discourage repeatedly making proposals
+/// that fail.
+///
+/// A proposer's rejections are counted back from their most recent
+/// proposal, skipping those still being voted on, until one that
+/// passed. Proposals closed after being rejected count as rejected
+/// and proposals withdrawn after passing count as passed. Only the
+/// proposer's last `MAX_RECENT_PROPOSALS` proposals are considered.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct DepositEscalation {
+    /// The multipliers applied to the deposit of a proposer with one
+    /// rejection, two rejections, and so on. The last multiplier
+    /// applies to any larger number of rejections. For example `[1,
+    /// 2, 4]` leaves the deposit unchanged after one rejection,
+    /// doubles it after two, and quadruples it after three or more.
+    /// Must be non-empty, have at most `MAX_RECENT_PROPOSALS`
+    /// entries, and have no multiplier below one.
+    pub multipliers: Vec<u64>,
+}
+
+/// How the deposit of a rejected proposal is distributed when it is
+/// not refunded to the proposer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ForfeitedDepositPolicy {
+    /// Splits the deposit among the voters who voted no, in
+    /// proportion to their voting power, as a reward for voting with
+    /// the outcome. Paying each voter costs gas when the proposal is
+    /// closed, so if more than `max_voters` voted no the deposit is
+    /// sent to the DAO instead, as it is if nobody voted no or the
+    /// proposal was not rejected by its votes, for example as it
+    /// missed its quorum or expired undecided. Amounts
+    /// lost to rounding are also sent to the DAO. Applies to both
+    /// cw20 and native deposits.
+    DistributeToWinningVoters {
+        /// The most voters the deposit is split among. Must be
+        /// between one and `MAX_DEPOSIT_DISTRIBUTION_VOTERS`.
+        max_voters: u64,
+    },
+}
+
+/// The longest voting period allowed, in each of the units a voting
+/// period may be expressed in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VotingPeriodLimit {
+    /// The limit in seconds for voting periods measured in time.
+    pub time: u64,
+    /// The limit in blocks for voting periods measured in blocks.
+    pub height: u64,
+}
+
+impl Default for VotingPeriodLimit {
+    /// Three years, assuming six second blocks.
+    fn default() -> Self {
+        Self {
+            time: 3 * 365 * 24 * 60 * 60,
+            height: 3 * 365 * 24 * 60 * 10,
+        }
+    }
+}
+
+impl VotingPeriodLimit {
+    /// Returns true if `duration` is no longer than the limit.
+    pub fn allows(&self, duration: &Duration) -> bool {
+        match duration {
+            Duration::Time(time) => *time <= self.time,
+            Duration::Height(height) => *height <= self.height,
+        }
+    }
+}
+
+/// A config and the block at which it took effect.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigHistoryEntry {
+    pub config: Config,
+    /// The height of the block in which the config took effect.
+    pub height: u64,
+    /// The time of the block in which the config took effect.
+    pub time: Timestamp,
+}
+
+/// A config update scheduled by `UpdateConfig` that has not yet
+/// taken effect. The embedded config is read with the same defaults
+/// as `Config` for fields added after it was stored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfig {
+    pub config: Config,
+    /// When the config takes effect.
+    pub effective: Expiration,
+}
+
+/// A proposal's vote tally as of the end of a block in which votes
+/// were cast on it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VoteHistoryEntry {
+    /// The height of the block.
+    pub height: u64,
+    /// The time of the block.
+    pub time: Timestamp,
+    /// The proposal's votes after the block's votes were counted.
+    pub votes: Votes,
+}
+
+/// Running totals of the deposits handled by the module in one
+/// deposit token.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct DepositAccounting {
+    /// Deposits taken from proposers when proposing.
+    pub collected: Uint128,
+    /// Deposits returned to proposers.
+    pub refunded: Uint128,
+    /// Deposits of failed proposals sent to the DAO.
+    pub forfeited: Uint128,
+}
+
+/// Why a proposal reached a terminal status.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionReason {
+    /// The proposal passed and its messages were executed.
+    Executed,
+    /// The proposal passed but its messages failed to execute and
+    /// `close_proposal_on_execution_failure` was set.
+    ExecutionFailed,
+    /// The proposal was rejected and then closed.
+    Rejected,
+    /// The proposal passed and was withdrawn by its proposer before
+    /// being executed.
+    Withdrawn,
+    /// The DAO forced the proposal into its terminal status with
+    /// `ForceResolve`.
+    Forced,
+    /// The proposal passed but was rejected, when execution was
+    /// attempted or by the DAO with `RejectFailingProposal`, as its
+    /// messages were found to be unable to execute, and then closed.
+    WouldFailExecution,
+}
+
+/// The result of a proposal as recorded when it reached a terminal
+/// status. Unlike the proposal itself, this is written once and never
+/// updated, so it is unaffected by later changes to how proposal
+/// results are computed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FinalResult {
+    /// The proposal's terminal status.
+    pub status: Status,
+    pub reason: ResolutionReason,
+    pub votes: Votes,
+    pub total_power: Uint128,
+    pub threshold: Threshold,
+    /// The height of the block in which the proposal was finalized.
+    pub height: u64,
+    /// The time of the block in which the proposal was finalized.
+    pub time: Timestamp,
 }
 
 /// The current top level config for the module.  The "config" key was
@@ -64,6 +406,122 @@ pub const CONFIG: Item<Config> = Item::new("config_v2");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
+/// The voters on each proposal keyed by the position they voted for,
+/// mapping to the power of their ballot. Kept in sync with `BALLOTS`
+/// so that the voters for a position may be listed without scanning
+/// every ballot. Ballots cast before this was introduced are not
+/// indexed.
+pub const BALLOTS_BY_POSITION: Map<(u64, String, Addr), Uint128> = Map::new("ballots_by_position");
+/// The address that funded a proposal's execution, kept until the
+/// execution's reply so that the funds can be returned if it fails.
+pub const EXECUTION_SPONSORS: Map<u64, Addr> = Map::new("execution_sponsors");
+/// Configs recorded while `record_config_history` is enabled, keyed
+/// by an increasing sequence number.
+pub const CONFIG_HISTORY: Map<u64, ConfigHistoryEntry> = Map::new("config_history");
+/// The maximum number of entries retained in `CONFIG_HISTORY`. Once
+/// reached, the oldest entry is removed whenever a new one is
+/// recorded.
+pub const MAX_CONFIG_HISTORY: u64 = 50;
+/// A config update waiting for the module's `config_change_delay` to
+/// pass. Applied by the first message executed once it takes effect.
+pub const PENDING_CONFIG: Item<PendingConfig> = Item::new("pending_config");
+/// Proposals' vote tallies keyed by proposal ID and block height,
+/// recorded while `record_vote_history` is enabled.
+pub const VOTE_HISTORY: Map<(u64, u64), VoteHistoryEntry> = Map::new("vote_history");
+/// The commitments made by voters on proposals using commit-reveal
+/// voting that have not yet been revealed. Commitments are removed
+/// as they are revealed and unrevealed ones are never counted.
+pub const VOTE_COMMITMENTS: Map<(u64, Addr), Binary> = Map::new("vote_commitments");
+
+/// Computes `voter`'s commitment to `vote` on proposal `proposal_id`
+/// for commit-reveal voting: the SHA-256 hash of the proposal ID as
+/// eight big-endian bytes, the voter's address, the vote's name
+/// (`yes`, `no`, or `abstain`), and `salt`, with a zero byte after
+/// each of the address and the vote's name. Binding the commitment
+/// to the proposal and voter stops it being copied by other voters
+/// or reused on other proposals. Voters should choose a salt
+/// that can not be guessed, else their vote may be found by trying
+/// each position.
+pub fn vote_commitment(proposal_id: u64, voter: &Addr, vote: Vote, salt: &str) -> Binary {
+    let mut hasher = Sha256::new();
+    hasher.update(proposal_id.to_be_bytes());
+    hasher.update(voter.as_bytes());
+    hasher.update([0]);
+    hasher.update(vote.to_string().as_bytes());
+    hasher.update([0]);
+    hasher.update(salt.as_bytes());
+    Binary::from(hasher.finalize().as_slice())
+}
+/// The largest `VotingPeriodLimit` governance may configure. Ten
+/// years, assuming six second blocks.
+pub const MAX_VOTING_PERIOD_LIMIT: VotingPeriodLimit = VotingPeriodLimit {
+    time: 10 * 365 * 24 * 60 * 60,
+    height: 10 * 365 * 24 * 60 * 10,
+};
+/// The longest `config_change_delay` governance may configure. One
+/// year, assuming six second blocks. Longer delays would leave the
+/// DAO unable to respond to problems with its config.
+pub const MAX_CONFIG_CHANGE_DELAY: VotingPeriodLimit = VotingPeriodLimit {
+    time: 365 * 24 * 60 * 60,
+    height: 365 * 24 * 60 * 10,
+};
+/// The maximum size in bytes of a proposal's metadata. Metadata is
+/// stored with the proposal, so it also counts towards the proposal's
+/// `MAX_PROPOSAL_SIZE`.
+pub const MAX_PROPOSAL_METADATA_SIZE: u64 = 2_000;
+/// The most votes that may be cast in one `VoteBatch`.
+pub const MAX_VOTE_BATCH_SIZE: u64 = 30;
+/// Lifetime deposit accounting, keyed by deposit token. Only deposits
+/// collected, refunded, or forfeited after this was introduced are
+/// counted, so a module with proposals that were open at the time may
+/// have refunded more than it has collected.
+pub const DEPOSIT_ACCOUNTING: Map<&Addr, DepositAccounting> = Map::new("deposit_accounting");
+/// The point at which each proposer may propose again after one of
+/// their proposals was executed while `post_execution_cooldown` was
+/// set.
+pub const PROPOSER_COOLDOWNS: Map<&Addr, Expiration> = Map::new("proposer_cooldowns");
+/// The IDs of each proposer's most recent proposals, oldest first,
+/// used to escalate their deposit. At most `MAX_RECENT_PROPOSALS` are
+/// kept.
+pub const RECENT_PROPOSALS: Map<&Addr, Vec<u64>> = Map::new("recent_proposals");
+/// The number of proposals kept for each proposer in
+/// `RECENT_PROPOSALS`.
+pub const MAX_RECENT_PROPOSALS: usize = 20;
+/// The most voters a forfeited deposit may be distributed among.
+pub const MAX_DEPOSIT_DISTRIBUTION_VOTERS: u64 = 50;
+/// The prefix set at instantiation that clients may display before
+/// the module's proposal IDs.
+pub const ID_PREFIX: Item<String> = Item::new("id_prefix");
+/// The longest `id_prefix` that may be set.
+pub const MAX_ID_PREFIX_LENGTH: usize = 16;
+/// The deposit token most recently added to the DAO's cw20 list while
+/// `register_deposit_token` was set.
+pub const REGISTERED_DEPOSIT_TOKEN: Item<Addr> = Item::new("registered_deposit_token");
+/// The results of proposals that have reached a terminal status,
+/// keyed by proposal ID. Entries are never overwritten.
+pub const FINAL_RESULTS: Map<u64, FinalResult> = Map::new("final_results");
+/// Index of resolved proposals keyed by the time they were resolved,
+/// in nanoseconds, and their ID. Used to list the proposals resolved
+/// within a time range.
+pub const RESOLVED_PROPOSALS: Map<(u64, u64), Empty> = Map::new("resolved_proposals");
+/// Why the messages of proposals rejected by
+/// `reject_failing_execution` were found to be unable to execute.
+pub const FAILED_EXECUTION_CHECKS: Map<u64, String> = Map::new("failed_execution_checks");
+/// Deposits that could not be returned to their proposer, keyed by
+/// proposal ID and proposer. Removed once claimed with `ClaimRefund`.
+pub const PENDING_REFUNDS: Map<(u64, &Addr), Uint128> = Map::new("pending_refunds");
+/// Voting power queried while `cache_voting_power` is set, keyed by
+/// the voting module it was queried from, the snapshot height it was
+/// queried at, and the voter. A voting module's power at a height
+/// can not change once that height is reached, so entries are never
+/// invalidated. Proposals only read the entries for their own voting
+/// module and snapshot height, so a proposal with a new snapshot
+/// height starts from an empty cache.
+pub const VOTING_POWER_CACHE: Map<(Addr, u64, Addr), Uint128> = Map::new("voting_power_cache");
+/// Total voting power queried while `cache_voting_power` is set,
+/// keyed by voting module and snapshot height like
+/// `VOTING_POWER_CACHE`.
+pub const TOTAL_POWER_CACHE: Map<(Addr, u64), Uint128> = Map::new("total_power_cache");
 /// Consumers of proposal state change hooks.
 pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
 /// Consumers of vote hooks.
@@ -1,36 +1,60 @@
 use std::u128;
 
 use cosmwasm_std::{
+    from_slice,
     testing::{mock_dependencies, mock_env},
-    to_binary, Addr, Coin, CosmosMsg, Decimal, Empty, Order, Timestamp, Uint128, WasmMsg,
+    to_binary, to_vec, Addr, BankMsg, Binary, Coin, ContractInfoResponse, CosmosMsg, Decimal, Deps,
+    DepsMut, Empty, Env, MessageInfo, Order, QueryRequest, Response, StdError, StdResult, Storage,
+    Timestamp, Uint128, WasmMsg, WasmQuery,
 };
+use cw2::ContractVersion;
 use cw20::Cw20Coin;
 use cw20_staked_balance_voting::msg::ActiveThreshold;
 use cw_multi_test::{next_block, App, BankSudo, Contract, ContractWrapper, Executor, SudoMsg};
 
 use cw_core::{msg::ModuleInstantiateInfo, state::ProposalModule};
+use cw_core_interface::migration::MigrationInfoResponse;
 use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use cw_utils::Expiration;
 
-use indexable_hooks::HooksResponse;
+use indexable_hooks::{HookError, HookRegistration, HooksResponse};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use testing::{ShouldExecute, TestSingleChoiceVote};
 use voting::{
     deposit::{CheckedDepositInfo, DepositInfo, DepositToken},
+    proposal::MAX_PROPOSAL_SIZE,
+    reward::RewardSource,
     status::Status,
-    threshold::{PercentageThreshold, Threshold},
+    threshold::{PercentageThreshold, Threshold, ThresholdError, ThresholdMode},
     voting::{Vote, Votes},
 };
 
 use crate::{
-    contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    proposal::SingleChoiceProposal,
-    query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteResponse},
-    state::Config,
+    contract::{migrate, query, CONTRACT_NAME, CONTRACT_VERSION},
+    msg::{
+        CloseReward, DepositAsset, ExecuteMsg, ExecutionCallbackMsg, InstantiateMsg, MigrateMsg,
+        PreExecutionHookQuery, PreExecutionHookResponse, QueryMsg, VoteBatchResult,
+    },
+    proposal::{ExecutionError, Settlement, SingleChoiceProposal},
+    query::{
+        CanRevoteResponse, ConfigHistoryEntryResponse, ConfigHistoryResponse,
+        DepositAccountingResponse, DepositSolvencyResponse, FilteredProposalListResponse,
+        FinalResultResponse, MinPeriodStatusResponse, ParticipationStatusResponse,
+        ProposalConfigResponse, ProposalInfluence, ProposalListResponse, ProposalResponse,
+        ProposalTarget, ProposalTargetKind, ProposalTargetsResponse, TokenDepositAccounting,
+        ValidateProposalResponse, VoteHistoryResponse, VoteInfo, VoteListResponse, VoteResponse,
+        VoterInfluenceResponse,
+    },
+    state::{
+        vote_commitment, Ballot, Config, DepositEscalation, FinalResult, ForfeitedDepositPolicy,
+        PendingConfig, QuorumExtension, ResolutionReason, VoteHistoryEntry, VotingPeriodLimit,
+        BALLOTS, BALLOTS_BY_POSITION, CONFIG, EXECUTION_SPONSORS, MAX_CONFIG_CHANGE_DELAY,
+        MAX_CONFIG_HISTORY, MAX_PROPOSAL_METADATA_SIZE, MAX_VOTE_BATCH_SIZE,
+        MAX_VOTING_PERIOD_LIMIT, PROPOSALS, PROPOSAL_HOOKS, TOTAL_POWER_CACHE, VOTING_POWER_CACHE,
+    },
     ContractError,
 };
 
@@ -73,6 +97,111 @@ fn cw20_contract() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+/// A cw20 token that rejects transfers of zero tokens, as some token
+/// contracts do.
+fn strict_cw20_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |deps: DepsMut,
+         env: Env,
+         info: MessageInfo,
+         msg: cw20_base::msg::ExecuteMsg|
+         -> Result<Response, cw20_base::ContractError> {
+            let amount = match &msg {
+                cw20_base::msg::ExecuteMsg::Transfer { amount, .. }
+                | cw20_base::msg::ExecuteMsg::TransferFrom { amount, .. }
+                | cw20_base::msg::ExecuteMsg::Send { amount, .. }
+                | cw20_base::msg::ExecuteMsg::SendFrom { amount, .. } => Some(*amount),
+                _ => None,
+            };
+            if amount == Some(Uint128::zero()) {
+                return Err(cw20_base::ContractError::InvalidZeroAmount {});
+            }
+            cw20_base::contract::execute(deps, env, info, msg)
+        },
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// A cw20 token whose transfers may be paused with a sudo message,
+/// used to simulate a token that temporarily rejects transfers.
+fn pausable_cw20_contract() -> Box<dyn Contract<Empty>> {
+    const PAUSED: Item<bool> = Item::new("paused");
+
+    let contract = ContractWrapper::new(
+        |deps: DepsMut,
+         env: Env,
+         info: MessageInfo,
+         msg: cw20_base::msg::ExecuteMsg|
+         -> Result<Response, cw20_base::ContractError> {
+            if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+                return Err(StdError::generic_err("transfers are paused").into());
+            }
+            cw20_base::contract::execute(deps, env, info, msg)
+        },
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    )
+    .with_sudo(
+        |deps: DepsMut, _env: Env, paused: bool| -> StdResult<Response> {
+            PAUSED.save(deps.storage, &paused)?;
+            Ok(Response::default())
+        },
+    );
+    Box::new(contract)
+}
+
+/// A contract that records the last execution callback it received
+/// and can be instantiated to fail on every callback.
+fn execution_callback_contract() -> Box<dyn Contract<Empty>> {
+    const FAIL: Item<bool> = Item::new("fail");
+    const LAST_CALLBACK: Item<ExecutionCallbackMsg> = Item::new("last_callback");
+
+    let contract = ContractWrapper::new(
+        |deps: DepsMut,
+         _env: Env,
+         _info: MessageInfo,
+         msg: ExecutionCallbackMsg|
+         -> StdResult<Response> {
+            if FAIL.load(deps.storage)? {
+                return Err(StdError::generic_err("callback failed"));
+            }
+            LAST_CALLBACK.save(deps.storage, &msg)?;
+            Ok(Response::default())
+        },
+        |deps: DepsMut, _env: Env, _info: MessageInfo, fail: bool| -> StdResult<Response> {
+            FAIL.save(deps.storage, &fail)?;
+            Ok(Response::default())
+        },
+        |deps: Deps, _env: Env, _msg: Empty| -> StdResult<Binary> {
+            to_binary(&LAST_CALLBACK.may_load(deps.storage)?)
+        },
+    );
+    Box::new(contract)
+}
+
+fn pre_execution_hook_contract() -> Box<dyn Contract<Empty>> {
+    const VETO: Item<bool> = Item::new("veto");
+
+    let contract = ContractWrapper::new(
+        |deps: DepsMut, _env: Env, _info: MessageInfo, veto: bool| -> StdResult<Response> {
+            VETO.save(deps.storage, &veto)?;
+            Ok(Response::default())
+        },
+        |deps: DepsMut, _env: Env, _info: MessageInfo, veto: bool| -> StdResult<Response> {
+            VETO.save(deps.storage, &veto)?;
+            Ok(Response::default())
+        },
+        |deps: Deps, _env: Env, _msg: PreExecutionHookQuery| -> StdResult<Binary> {
+            to_binary(&PreExecutionHookResponse {
+                veto: VETO.load(deps.storage)?,
+            })
+        },
+    );
+    Box::new(contract)
+}
+
 fn cw20_stake_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         cw20_stake::contract::execute,
@@ -249,6 +378,7 @@ fn instantiate_with_staked_cw721_governance(
                 manager: None,
                 unstaking_duration: None,
                 nft_address: nft_address.to_string(),
+                token_weights: None,
             })
             .unwrap(),
             admin: cw_core::msg::Admin::None {},
@@ -261,6 +391,7 @@ fn instantiate_with_staked_cw721_governance(
             msg: to_binary(&proposal_module_instantiate).unwrap(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -360,6 +491,7 @@ fn instantiate_with_native_staked_balances_governance(
                 manager: None,
                 denom: "ujuno".to_string(),
                 unstaking_duration: None,
+                min_stake: None,
             })
             .unwrap(),
             admin: cw_core::msg::Admin::None {},
@@ -372,6 +504,7 @@ fn instantiate_with_native_staked_balances_governance(
             msg: to_binary(&proposal_module_instantiate).unwrap(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -486,6 +619,7 @@ fn instantiate_with_staked_balances_governance(
             msg: to_binary(&proposal_module_instantiate).unwrap(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -595,6 +729,7 @@ fn instantiate_with_staking_active_threshold(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     app.instantiate_contract(
@@ -664,6 +799,7 @@ fn instantiate_with_cw4_groups_governance(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let addr = app
@@ -747,6 +883,7 @@ fn instantiate_with_cw20_balances_governance(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     app.instantiate_contract(
@@ -884,6 +1021,36 @@ where
         allow_revoting: false,
         deposit_info,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
     let governance_addr =
@@ -932,6 +1099,11 @@ where
             title: "A simple text proposal".to_string(),
             description: "This is a simple text proposal".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
@@ -1026,6 +1198,116 @@ fn do_test_votes_cw20_balances(
     )
 }
 
+fn contract_code_id(app: &App, contract: &Addr) -> u64 {
+    let info: ContractInfoResponse = app
+        .wrap()
+        .query(&QueryRequest::Wasm(WasmQuery::ContractInfo {
+            contract_addr: contract.to_string(),
+        }))
+        .unwrap();
+    info.code_id
+}
+
+#[test]
+fn test_instantiate_invalid_threshold() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let invalid = [
+        (
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(0)),
+            },
+            ThresholdError::ZeroThreshold {},
+        ),
+        (
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(101)),
+            },
+            ThresholdError::UnreachableThreshold {},
+        ),
+        (
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Percent(Decimal::percent(150)),
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            ThresholdError::UnreachableThreshold {},
+        ),
+        (
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(101)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            ThresholdError::UnreachableQuorum {},
+        ),
+        (
+            Threshold::AbsoluteCount {
+                threshold: Uint128::zero(),
+            },
+            ThresholdError::ZeroAbsoluteCount {},
+        ),
+    ];
+
+    for (threshold, expected) in invalid {
+        let err: ContractError = app
+            .instantiate_contract(
+                govmod_id,
+                Addr::unchecked(CREATOR_ADDR),
+                &InstantiateMsg {
+                    threshold,
+                    max_voting_period: Duration::Height(6),
+                    min_voting_period: None,
+                    only_members_execute: false,
+                    allow_revoting: false,
+                    deposit_info: None,
+                    close_proposal_on_execution_failure: true,
+                    exclude_dao_voting_power: false,
+                    execution_callback: None,
+                    require_second: None,
+                    record_config_history: false,
+                    quorum_reached_hooks: false,
+                    require_power_at_resolution: false,
+                    execution_retry_cooldown: None,
+                    post_execution_cooldown: None,
+                    quorum_extension: None,
+                    native_propose_deposit: None,
+                    snapshot_offset: 0,
+                    pre_execution_hook: None,
+                    required_description_sections: vec![],
+                    voting_period_limit: None,
+                    register_deposit_token: false,
+                    total_power_override: None,
+                    record_vote_history: false,
+                    execute_on_pass: false,
+                    reveal_period: None,
+                    hook_allowlist: None,
+                    revote_settle_period: None,
+                    deposit_escalation: None,
+                    forfeited_deposit_policy: None,
+                    reject_failing_execution: false,
+                    id_prefix: None,
+                    non_voter_default: None,
+                    max_proposal_bytes: None,
+                    config_change_delay: None,
+                    close_reward: None,
+                    cache_voting_power: false,
+                },
+                &[],
+                "proposal module",
+                None,
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        match err {
+            ContractError::ThresholdError(err) => assert_eq!(err, expected),
+            err => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 #[test]
 fn test_propose() {
     let mut app = App::default();
@@ -1043,10 +1325,48 @@ fn test_propose() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::VotingModule {},
+        )
+        .unwrap();
+    let voting_module_code_id = contract_code_id(&app, &voting_module);
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
@@ -1075,6 +1395,35 @@ fn test_propose() {
         dao: governance_addr,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: VotingPeriodLimit::default(),
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
     assert_eq!(config, expected);
 
@@ -1086,6 +1435,11 @@ fn test_propose() {
             title: "A simple text proposal".to_string(),
             description: "This is a simple text proposal".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
@@ -1112,6 +1466,29 @@ fn test_propose() {
         deposit_info: None,
         created: current_block.time,
         last_updated: current_block.time,
+        execution_error: None,
+        seconding_deadline: None,
+        execution_funds: vec![],
+        quorum_reached: false,
+        voting_module: Some(voting_module),
+        require_power_at_resolution: false,
+        related_proposals: vec![],
+        last_execution_attempt: None,
+        extensions: 0,
+        native_deposit: None,
+        snapshot_height: Some(current_block.height),
+        close_proposal_on_execution_failure: Some(true),
+        reveal_period: None,
+        revote_settle_period: None,
+        settlement: None,
+        metadata: None,
+        vote_count: Some(0),
+        voting_module_code_id: Some(voting_module_code_id),
+        module_version: Some(CONTRACT_VERSION.to_string()),
+        non_voter_default: None,
+        resolved_at: None,
+        execution_retry_cooldown: None,
+        original_proposer: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -1135,10 +1512,48 @@ fn test_propose_supports_stargate_message() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::VotingModule {},
+        )
+        .unwrap();
+    let voting_module_code_id = contract_code_id(&app, &voting_module);
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
@@ -1164,6 +1579,11 @@ fn test_propose_supports_stargate_message() {
                 type_url: "foo_type".to_string(),
                 value: to_binary("foo_bin").unwrap(),
             }],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
@@ -1193,6 +1613,29 @@ fn test_propose_supports_stargate_message() {
         deposit_info: None,
         created: current_block.time,
         last_updated: current_block.time,
+        execution_error: None,
+        seconding_deadline: None,
+        execution_funds: vec![],
+        quorum_reached: false,
+        voting_module: Some(voting_module),
+        require_power_at_resolution: false,
+        related_proposals: vec![],
+        last_execution_attempt: None,
+        extensions: 0,
+        native_deposit: None,
+        snapshot_height: Some(current_block.height),
+        close_proposal_on_execution_failure: Some(true),
+        reveal_period: None,
+        revote_settle_period: None,
+        settlement: None,
+        metadata: None,
+        vote_count: Some(0),
+        voting_module_code_id: Some(voting_module_code_id),
+        module_version: Some(CONTRACT_VERSION.to_string()),
+        non_voter_default: None,
+        resolved_at: None,
+        execution_retry_cooldown: None,
+        original_proposer: None,
     };
 
     assert_eq!(created.proposal, expected);
@@ -1200,646 +1643,11770 @@ fn test_propose_supports_stargate_message() {
 }
 
 #[test]
-fn test_vote_simple() {
-    testing::test_simple_votes(do_votes_cw20_balances);
-    testing::test_simple_votes(do_votes_cw4_weights);
-    testing::test_simple_votes(do_votes_staked_balances);
-    testing::test_simple_votes(do_votes_nft_balances);
-    testing::test_simple_votes(do_votes_native_staked_balances)
-}
-
-#[test]
-fn test_simple_vote_no_overflow() {
-    testing::test_simple_vote_no_overflow(do_votes_cw20_balances);
-    testing::test_simple_vote_no_overflow(do_votes_staked_balances);
-    testing::test_simple_vote_no_overflow(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_vote_no_overflow() {
-    testing::test_vote_no_overflow(do_votes_cw20_balances);
-    testing::test_vote_no_overflow(do_votes_staked_balances);
-    testing::test_vote_no_overflow(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_simple_early_rejection() {
-    testing::test_simple_early_rejection(do_votes_cw20_balances);
-    testing::test_simple_early_rejection(do_votes_cw4_weights);
-    testing::test_simple_early_rejection(do_votes_staked_balances);
-    testing::test_simple_early_rejection(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_vote_abstain_only() {
-    testing::test_vote_abstain_only(do_votes_cw20_balances);
-    testing::test_vote_abstain_only(do_votes_cw4_weights);
-    testing::test_vote_abstain_only(do_votes_staked_balances);
-    testing::test_vote_abstain_only(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_tricky_rounding() {
-    testing::test_tricky_rounding(do_votes_cw20_balances);
-    testing::test_tricky_rounding(do_votes_cw4_weights);
-    testing::test_tricky_rounding(do_votes_staked_balances);
-    testing::test_tricky_rounding(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_no_double_votes() {
-    testing::test_no_double_votes(do_votes_cw20_balances);
-    testing::test_no_double_votes(do_votes_cw4_weights);
-    testing::test_no_double_votes(do_votes_staked_balances);
-    testing::test_no_double_votes(do_votes_nft_balances);
-    testing::test_no_double_votes(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_votes_favor_yes() {
-    testing::test_votes_favor_yes(do_votes_cw20_balances);
-    testing::test_votes_favor_yes(do_votes_staked_balances);
-    testing::test_votes_favor_yes(do_votes_nft_balances);
-    testing::test_votes_favor_yes(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_votes_low_threshold() {
-    testing::test_votes_low_threshold(do_votes_cw20_balances);
-    testing::test_votes_low_threshold(do_votes_cw4_weights);
-    testing::test_votes_low_threshold(do_votes_staked_balances);
-    testing::test_votes_low_threshold(do_votes_nft_balances);
-    testing::test_votes_low_threshold(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_majority_vs_half() {
-    testing::test_majority_vs_half(do_votes_cw20_balances);
-    testing::test_majority_vs_half(do_votes_cw4_weights);
-    testing::test_majority_vs_half(do_votes_staked_balances);
-    testing::test_majority_vs_half(do_votes_nft_balances);
-    testing::test_majority_vs_half(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_pass_threshold_not_quorum() {
-    testing::test_pass_threshold_not_quorum(do_votes_cw20_balances);
-    testing::test_pass_threshold_not_quorum(do_votes_cw4_weights);
-    testing::test_pass_threshold_not_quorum(do_votes_staked_balances);
-    testing::test_pass_threshold_not_quorum(do_votes_nft_balances);
-    testing::test_pass_threshold_not_quorum(do_votes_native_staked_balances);
-}
-
-#[test]
-fn test_pass_threshold_exactly_quorum() {
-    testing::test_pass_exactly_quorum(do_votes_cw20_balances);
-    testing::test_pass_exactly_quorum(do_votes_cw4_weights);
-    testing::test_pass_exactly_quorum(do_votes_staked_balances);
-    testing::test_pass_exactly_quorum(do_votes_nft_balances);
-    testing::test_pass_exactly_quorum(do_votes_native_staked_balances);
-}
-
-/// Generate some random voting selections and make sure they behave
-/// as expected. We split this test up as these take a while and cargo
-/// can parallize tests.
-#[test]
-fn fuzz_voting_cw20_balances() {
-    testing::fuzz_voting(do_votes_cw20_balances)
-}
-
-#[test]
-fn fuzz_voting_cw4_weights() {
-    testing::fuzz_voting(do_votes_cw4_weights)
-}
-
-#[test]
-fn fuzz_voting_staked_balances() {
-    testing::fuzz_voting(do_votes_staked_balances)
-}
-
-#[test]
-fn fuzz_voting_native_staked_balances() {
-    testing::fuzz_voting(do_votes_native_staked_balances)
-}
-
-/// Instantiate the contract and use the voting module's token
-/// contract as the proposal deposit token.
-#[test]
-fn test_voting_module_token_proposal_deposit_instantiate() {
+fn test_propose_expiration_override() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
-        min_voting_period: None,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Time(604800),
+        min_voting_period: Some(Duration::Time(60)),
         only_members_execute: false,
         allow_revoting: false,
-        deposit_info: Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true,
-        }),
+        deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
-
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
-
     let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let governance_modules = gov_state.proposal_modules;
-    let voting_module = gov_state.voting_module;
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+    let now = app.block_info().time;
+    let propose = |app: &mut App, expiration_override: Expiration| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A fixed deadline proposal".to_string(),
+                description: "Voting closes at quarter end".to_string(),
+                msgs: vec![],
+                expiration_override: Some(expiration_override),
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+    };
 
-    let config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Config {})
+    // Expirations in the past are rejected.
+    let err: ContractError = propose(&mut app, Expiration::AtTime(now))
+        .unwrap_err()
+        .downcast()
         .unwrap();
-    let expected_token: Addr = app
-        .wrap()
-        .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::TokenContract {},
-        )
+    assert!(matches!(err, ContractError::ExpirationInPast {}));
+
+    // Expirations past the max voting period are rejected.
+    let err: ContractError = propose(&mut app, Expiration::AtTime(now.plus_seconds(604801)))
+        .unwrap_err()
+        .downcast()
         .unwrap();
+    assert!(matches!(err, ContractError::InvalidExpiration {}));
 
-    assert_eq!(
-        config.deposit_info,
-        Some(CheckedDepositInfo {
-            token: expected_token,
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true
-        })
-    )
+    let err: ContractError = propose(&mut app, Expiration::Never {})
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExpiration {}));
+
+    // Expirations must use the same units as the max voting period.
+    let height = app.block_info().height;
+    let err: ContractError = propose(&mut app, Expiration::AtHeight(height + 10))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExpiration {}));
+
+    // Expirations before the min voting period ends are rejected.
+    let err: ContractError = propose(&mut app, Expiration::AtTime(now.plus_seconds(30)))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidMinVotingPeriod {}));
+
+    // A valid absolute expiration is used as-is.
+    let deadline = Expiration::AtTime(now.plus_seconds(86400));
+    propose(&mut app, deadline).unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.expiration, deadline);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // The proposal closes at the deadline rather than at the end of
+    // the max voting period.
+    app.update_block(|block| block.time = now.plus_seconds(86400));
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Rejected);
 }
 
-/// Instantiate the contract and use a cw20 unrealated to the voting
-/// module for the proposal deposit.
 #[test]
-fn test_different_token_proposal_deposit() {
+fn test_query_proposal_targets() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
-    let cw20_id = app.store_code(cw20_contract());
-    let cw20_addr = app
-        .instantiate_contract(
-            cw20_id,
-            Addr::unchecked(CREATOR_ADDR),
-            &cw20_base::msg::InstantiateMsg {
-                name: "OAD OAD".to_string(),
-                symbol: "OAD".to_string(),
-                decimals: 6,
-                initial_balances: vec![],
-                mint: None,
-                marketing: None,
-            },
-            &[],
-            "random-cw20",
-            None,
-        )
-        .unwrap();
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
-        deposit_info: Some(DepositInfo {
-            token: DepositToken::Token {
-                address: cw20_addr.to_string(),
-            },
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true,
-        }),
+        deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let execute = |contract_addr: &str| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary("foo").unwrap(),
+            funds: vec![],
+        }
+        .into()
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A proposal with many targets".to_string(),
+            description: "Touches contracts, accounts, and modules".to_string(),
+            msgs: vec![
+                execute("contract1"),
+                execute("contract2"),
+                // Duplicates are only listed once.
+                execute("contract1"),
+                WasmMsg::Migrate {
+                    contract_addr: "contract1".to_string(),
+                    new_code_id: 2,
+                    msg: to_binary("bar").unwrap(),
+                }
+                .into(),
+                WasmMsg::UpdateAdmin {
+                    contract_addr: "contract2".to_string(),
+                    admin: "admin".to_string(),
+                }
+                .into(),
+                WasmMsg::ClearAdmin {
+                    contract_addr: "contract3".to_string(),
+                }
+                .into(),
+                // Instantiate messages have no target.
+                WasmMsg::Instantiate {
+                    admin: None,
+                    code_id: 1,
+                    msg: to_binary("baz").unwrap(),
+                    funds: vec![],
+                    label: "new contract".to_string(),
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: "recipient".to_string(),
+                    amount: vec![Coin {
+                        denom: "ujuno".to_string(),
+                        amount: Uint128::new(10),
+                    }],
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: "recipient".to_string(),
+                    amount: vec![Coin {
+                        denom: "uatom".to_string(),
+                        amount: Uint128::new(10),
+                    }],
+                }
+                .into(),
+                CosmosMsg::Stargate {
+                    type_url: "foo_type".to_string(),
+                    value: to_binary("foo_bin").unwrap(),
+                },
+            ],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let targets: ProposalTargetsResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::ProposalTargets { proposal_id: 1 })
+        .unwrap();
+    let target = |target: &str, kind: ProposalTargetKind| ProposalTarget {
+        target: target.to_string(),
+        kind,
+    };
+    assert_eq!(
+        targets.targets,
+        vec![
+            target("contract1", ProposalTargetKind::WasmExecute {}),
+            target("contract2", ProposalTargetKind::WasmExecute {}),
+            target("contract1", ProposalTargetKind::WasmMigrate {}),
+            target("contract2", ProposalTargetKind::WasmUpdateAdmin {}),
+            target("contract3", ProposalTargetKind::WasmClearAdmin {}),
+            target("recipient", ProposalTargetKind::BankSend {}),
+            target("foo_type", ProposalTargetKind::Stargate {}),
+        ]
+    );
 }
 
-/// Try to instantiate the governance module with a non-cw20 as its
-/// proposal deposit token. This should error as the `TokenInfo {}`
-/// query ought to fail.
 #[test]
-#[should_panic(expected = "Error parsing into type cw20_balance_voting::msg::QueryMsg")]
-fn test_bad_token_proposal_deposit() {
+fn test_query_proposals_with_msg_kind() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
-    let cw20_id = app.store_code(cw20_contract());
-    let votemod_id = app.store_code(cw20_balances_voting());
 
-    let votemod_addr = app
-        .instantiate_contract(
-            votemod_id,
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let migrate: CosmosMsg = WasmMsg::Migrate {
+        contract_addr: "contract1".to_string(),
+        new_code_id: 2,
+        msg: to_binary("bar").unwrap(),
+    }
+    .into();
+    let execute: CosmosMsg = WasmMsg::Execute {
+        contract_addr: "contract2".to_string(),
+        msg: to_binary("foo").unwrap(),
+        funds: vec![],
+    }
+    .into();
+    for msgs in [
+        vec![],
+        vec![migrate.clone()],
+        vec![execute.clone()],
+        vec![execute, migrate],
+    ] {
+        app.execute_contract(
             Addr::unchecked(CREATOR_ADDR),
-            &cw20_balance_voting::msg::InstantiateMsg {
-                token_info: cw20_balance_voting::msg::TokenInfo::New {
-                    code_id: cw20_id,
-                    label: "DAO DAO governance token".to_string(),
-                    name: "DAO".to_string(),
-                    symbol: "DAO".to_string(),
-                    decimals: 6,
-                    initial_balances: vec![Cw20Coin {
-                        address: CREATOR_ADDR.to_string(),
-                        amount: Uint128::new(1),
-                    }],
-                    marketing: None,
-                },
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
             },
             &[],
-            "random-vote-module",
-            None,
         )
         .unwrap();
+    }
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
-    let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
-        min_voting_period: None,
-        only_members_execute: false,
-        allow_revoting: false,
-        deposit_info: Some(DepositInfo {
-            token: DepositToken::Token {
-                address: votemod_addr.to_string(),
-            },
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true,
-        }),
-        close_proposal_on_execution_failure: true,
+    let query_ids = |kind: ProposalTargetKind, start_after: Option<u64>, limit: Option<u64>| {
+        let res: FilteredProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ProposalsWithMsgKind {
+                    kind,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        (
+            res.proposals
+                .into_iter()
+                .map(|p| p.id)
+                .collect::<Vec<u64>>(),
+            res.last_examined,
+        )
     };
 
-    instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    assert_eq!(
+        query_ids(ProposalTargetKind::WasmMigrate {}, None, None),
+        (vec![2, 4], Some(4))
+    );
+    assert_eq!(
+        query_ids(ProposalTargetKind::WasmExecute {}, None, None),
+        (vec![3, 4], Some(4))
+    );
+    assert_eq!(
+        query_ids(ProposalTargetKind::WasmMigrate {}, Some(2), None),
+        (vec![4], Some(4))
+    );
+    // The limit applies to scanned proposals, not matching ones, so
+    // a page may be empty while matches remain.
+    assert_eq!(
+        query_ids(ProposalTargetKind::WasmMigrate {}, None, Some(1)),
+        (vec![], Some(1))
+    );
+    assert_eq!(
+        query_ids(ProposalTargetKind::WasmMigrate {}, Some(1), Some(2)),
+        (vec![2], Some(3))
+    );
+    assert_eq!(
+        query_ids(ProposalTargetKind::BankSend {}, None, None),
+        (vec![], Some(4))
+    );
+    assert_eq!(
+        query_ids(ProposalTargetKind::BankSend {}, Some(4), None),
+        (vec![], None)
+    );
 }
 
 #[test]
-fn test_take_proposal_deposit() {
+fn test_query_power_needed_to_pass() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
-        deposit_info: Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true,
-        }),
+        deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
-
     let governance_addr = instantiate_with_cw20_balances_governance(
         &mut app,
         govmod_id,
         instantiate,
-        Some(vec![Cw20Coin {
-            address: "ekez".to_string(),
-            amount: Uint128::new(2),
-        }]),
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60),
+            },
+        ]),
     );
-
     let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let governance_modules = gov_state.proposal_modules;
-
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let CheckedDepositInfo {
-        token,
-        deposit,
-        refund_failed_proposals,
-    } = govmod_config.deposit_info.unwrap();
-    assert!(refund_failed_proposals);
-    assert_eq!(deposit, Uint128::new(1));
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    // This should fail because we have not created an allowance for
-    // the proposal deposit.
     app.execute_contract(
-        Addr::unchecked("ekez"),
+        Addr::unchecked(CREATOR_ADDR),
         govmod_single.clone(),
         &ExecuteMsg::Propose {
             title: "A simple text proposal".to_string(),
             description: "This is a simple text proposal".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
-    .unwrap_err();
+    .unwrap();
+
+    let needed: Option<Uint128> = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::PowerNeededToPass { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(needed, Some(Uint128::new(51)));
 
-    // Allow a proposal deposit.
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        token.clone(),
-        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-            spender: govmod_single.to_string(),
-            amount: Uint128::new(1),
-            expires: None,
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
+    let needed: Option<Uint128> = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::PowerNeededToPass { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(needed, Some(Uint128::new(11)));
 
-    // Now we can create a proposal.
+    // Once passed no more power is needed.
     app.execute_contract(
         Addr::unchecked("ekez"),
-        govmod_single,
-        &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            msgs: vec![],
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
-
-    // Check that our balance was deducted.
-    let balance: cw20::BalanceResponse = app
+    let needed: Option<Uint128> = app
         .wrap()
         .query_wasm_smart(
-            token,
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
-            },
+            govmod_single,
+            &QueryMsg::PowerNeededToPass { proposal_id: 1 },
         )
         .unwrap();
-    assert_eq!(balance.balance, Uint128::new(1))
+    assert_eq!(needed, None);
 }
 
 #[test]
-fn test_deposit_return_on_execute() {
-    // Will create a proposal and execute it, one token will be
-    // deposited to create said proposal, expectation is that the
-    // token is then returned once the proposal is executed.
-    let (mut app, governance_addr) = do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::Yes,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+fn test_query_participation_status() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
-        Status::Passed,
-        None,
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: false,
-        }),
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(70),
+            },
+        ]),
     );
     let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let governance_modules = gov_state.proposal_modules;
-
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
-    let balance: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            token.clone(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
-            },
-        )
-        .unwrap();
-
-    // Proposal has not been executed so deposit has not been
-    // refunded.
-    assert_eq!(balance.balance, Uint128::new(9));
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    // Execute the proposal, this should cause the deposit to be
-    // refunded.
     app.execute_contract(
         Addr::unchecked("ekez"),
-        govmod_single,
-        &ExecuteMsg::Execute { proposal_id: 1 },
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
         &[],
     )
     .unwrap();
 
-    let balance: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            token,
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
+    let participation = |app: &App| -> (ParticipationStatusResponse, bool) {
+        let status: ParticipationStatusResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ParticipationStatus { proposal_id: 1 },
+            )
+            .unwrap();
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::Proposal { proposal_id: 1 },
+            )
+            .unwrap();
+        (status, proposal.proposal.quorum_reached)
+    };
+    let vote = |app: &mut App, voter: &str, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote,
             },
+            &[],
         )
         .unwrap();
+    };
 
-    // Proposal has been executed so deposit has been refunded.
-    assert_eq!(balance.balance, Uint128::new(10));
+    assert_eq!(
+        participation(&app),
+        (
+            ParticipationStatusResponse {
+                power_cast: Uint128::zero(),
+                total_power: Uint128::new(100),
+                participation: Decimal::zero(),
+            },
+            false
+        )
+    );
+
+    // Abstain votes count towards participation, as they do for
+    // quorum.
+    vote(&mut app, "ekez", Vote::Abstain);
+    assert_eq!(
+        participation(&app),
+        (
+            ParticipationStatusResponse {
+                power_cast: Uint128::new(10),
+                total_power: Uint128::new(100),
+                participation: Decimal::percent(10),
+            },
+            false
+        )
+    );
+
+    // Participation reaching the quorum percentage reaches quorum.
+    vote(&mut app, "keze", Vote::No);
+    assert_eq!(
+        participation(&app),
+        (
+            ParticipationStatusResponse {
+                power_cast: Uint128::new(30),
+                total_power: Uint128::new(100),
+                participation: Decimal::percent(30),
+            },
+            true
+        )
+    );
 }
 
 #[test]
-fn test_close_open_proposal() {
-    let (mut app, governance_addr) = do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::No,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
-        },
-        Status::Open,
-        Some(Uint128::new(100)),
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: true,
-        }),
-    );
+fn test_validate_proposal() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
     let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let governance_modules = gov_state.proposal_modules;
-
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    // Close the proposal, this should error as the proposal is still
-    // open and not expired.
-    app.execute_contract(
-        Addr::unchecked("keze"),
-        govmod_single.clone(),
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
-    )
-    .unwrap_err();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    // Make the proposal expire.
-    app.update_block(|block| block.height += 10);
+    let validate = |app: &App, description: String| -> ValidateProposalResponse {
+        app.wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ValidateProposal {
+                    proposer: Some(CREATOR_ADDR.to_string()),
+                    title: "A simple text proposal".to_string(),
+                    description,
+                    msgs: vec![],
+                    expiration_override: None,
+                    execution_funds: vec![],
+                    related_proposals: vec![],
+                    threshold_override: None,
+                    metadata: None,
+                },
+            )
+            .unwrap()
+    };
 
-    // Close the proposal, this should work as the proposal is now
-    // open and expired.
-    app.execute_contract(
-        Addr::unchecked("keze"),
-        govmod_single.clone(),
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+    let resp = validate(&app, "This is a simple text proposal".to_string());
+    assert_eq!(resp, ValidateProposalResponse { errors: vec![] });
 
-    // Check that a refund was issued.
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Config {})
-        .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
-    let balance: cw20::BalanceResponse = app
+    // Every failed check is reported, including those on the
+    // proposer.
+    let resp: ValidateProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            token,
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
+            govmod_single.clone(),
+            &QueryMsg::ValidateProposal {
+                proposer: Some("ekez".to_string()),
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![1, 2],
+                threshold_override: None,
+                metadata: Some("a".repeat(MAX_PROPOSAL_METADATA_SIZE as usize + 1)),
             },
         )
         .unwrap();
+    assert_eq!(
+        resp.errors,
+        vec![
+            ContractError::MetadataTooLarge {
+                size: MAX_PROPOSAL_METADATA_SIZE + 1,
+                max: MAX_PROPOSAL_METADATA_SIZE,
+            }
+            .to_string(),
+            ContractError::NoSuchProposal { id: 1 }.to_string(),
+            ContractError::NoSuchProposal { id: 2 }.to_string(),
+            ContractError::Unauthorized {}.to_string(),
+        ]
+    );
 
-    // Proposal has not been closed so deposit has been
-    // refunded.
-    assert_eq!(balance.balance, Uint128::new(10));
-}
-
-#[test]
-fn test_zero_deposit() {
-    do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::Yes,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
-        },
-        Status::Passed,
-        None,
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(0),
-            refund_failed_proposals: false,
-        }),
-    );
+    // A proposal too large to be queried is rejected by both the
+    // query and `Propose`.
+    let description = "a".repeat(MAX_PROPOSAL_SIZE as usize);
+    let resp = validate(&app, description.clone());
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description,
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ProposalTooLarge { .. }));
+    assert_eq!(resp.errors.len(), 1);
+    assert!(resp.errors[0].starts_with("Proposal is ("));
+
+    // Validating a proposal does not create one.
+    let count: u64 = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::ProposalCount {})
+        .unwrap();
+    assert_eq!(count, 0);
 }
 
 #[test]
-fn test_deposit_return_on_close() {
-    let (mut app, governance_addr) = do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::No,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
-        },
-        Status::Rejected,
-        None,
-        Some(DepositInfo {
+fn test_vote_simple() {
+    testing::test_simple_votes(do_votes_cw20_balances);
+    testing::test_simple_votes(do_votes_cw4_weights);
+    testing::test_simple_votes(do_votes_staked_balances);
+    testing::test_simple_votes(do_votes_nft_balances);
+    testing::test_simple_votes(do_votes_native_staked_balances)
+}
+
+#[test]
+fn test_simple_vote_no_overflow() {
+    testing::test_simple_vote_no_overflow(do_votes_cw20_balances);
+    testing::test_simple_vote_no_overflow(do_votes_staked_balances);
+    testing::test_simple_vote_no_overflow(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_vote_no_overflow() {
+    testing::test_vote_no_overflow(do_votes_cw20_balances);
+    testing::test_vote_no_overflow(do_votes_staked_balances);
+    testing::test_vote_no_overflow(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_simple_early_rejection() {
+    testing::test_simple_early_rejection(do_votes_cw20_balances);
+    testing::test_simple_early_rejection(do_votes_cw4_weights);
+    testing::test_simple_early_rejection(do_votes_staked_balances);
+    testing::test_simple_early_rejection(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_vote_abstain_only() {
+    testing::test_vote_abstain_only(do_votes_cw20_balances);
+    testing::test_vote_abstain_only(do_votes_cw4_weights);
+    testing::test_vote_abstain_only(do_votes_staked_balances);
+    testing::test_vote_abstain_only(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_tricky_rounding() {
+    testing::test_tricky_rounding(do_votes_cw20_balances);
+    testing::test_tricky_rounding(do_votes_cw4_weights);
+    testing::test_tricky_rounding(do_votes_staked_balances);
+    testing::test_tricky_rounding(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_no_double_votes() {
+    testing::test_no_double_votes(do_votes_cw20_balances);
+    testing::test_no_double_votes(do_votes_cw4_weights);
+    testing::test_no_double_votes(do_votes_staked_balances);
+    testing::test_no_double_votes(do_votes_nft_balances);
+    testing::test_no_double_votes(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_votes_favor_yes() {
+    testing::test_votes_favor_yes(do_votes_cw20_balances);
+    testing::test_votes_favor_yes(do_votes_staked_balances);
+    testing::test_votes_favor_yes(do_votes_nft_balances);
+    testing::test_votes_favor_yes(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_votes_low_threshold() {
+    testing::test_votes_low_threshold(do_votes_cw20_balances);
+    testing::test_votes_low_threshold(do_votes_cw4_weights);
+    testing::test_votes_low_threshold(do_votes_staked_balances);
+    testing::test_votes_low_threshold(do_votes_nft_balances);
+    testing::test_votes_low_threshold(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_majority_vs_half() {
+    testing::test_majority_vs_half(do_votes_cw20_balances);
+    testing::test_majority_vs_half(do_votes_cw4_weights);
+    testing::test_majority_vs_half(do_votes_staked_balances);
+    testing::test_majority_vs_half(do_votes_nft_balances);
+    testing::test_majority_vs_half(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_pass_threshold_not_quorum() {
+    testing::test_pass_threshold_not_quorum(do_votes_cw20_balances);
+    testing::test_pass_threshold_not_quorum(do_votes_cw4_weights);
+    testing::test_pass_threshold_not_quorum(do_votes_staked_balances);
+    testing::test_pass_threshold_not_quorum(do_votes_nft_balances);
+    testing::test_pass_threshold_not_quorum(do_votes_native_staked_balances);
+}
+
+#[test]
+fn test_pass_threshold_exactly_quorum() {
+    testing::test_pass_exactly_quorum(do_votes_cw20_balances);
+    testing::test_pass_exactly_quorum(do_votes_cw4_weights);
+    testing::test_pass_exactly_quorum(do_votes_staked_balances);
+    testing::test_pass_exactly_quorum(do_votes_nft_balances);
+    testing::test_pass_exactly_quorum(do_votes_native_staked_balances);
+}
+
+/// Generate some random voting selections and make sure they behave
+/// as expected. We split this test up as these take a while and cargo
+/// can parallize tests.
+#[test]
+fn fuzz_voting_cw20_balances() {
+    testing::fuzz_voting(do_votes_cw20_balances)
+}
+
+#[test]
+fn fuzz_voting_cw4_weights() {
+    testing::fuzz_voting(do_votes_cw4_weights)
+}
+
+#[test]
+fn fuzz_voting_staked_balances() {
+    testing::fuzz_voting(do_votes_staked_balances)
+}
+
+#[test]
+fn fuzz_voting_native_staked_balances() {
+    testing::fuzz_voting(do_votes_native_staked_balances)
+}
+
+/// Instantiate the contract and use the voting module's token
+/// contract as the proposal deposit token.
+#[test]
+fn test_voting_module_token_proposal_deposit_instantiate() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
             token: DepositToken::VotingModuleToken {},
             deposit: Uint128::new(1),
             refund_failed_proposals: true,
         }),
-    );
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+
     let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
     let governance_modules = gov_state.proposal_modules;
+    let voting_module = gov_state.voting_module;
 
     assert_eq!(governance_modules.len(), 1);
     let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
-    let govmod_config: Config = app
+    let config: Config = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .query_wasm_smart(govmod_single, &QueryMsg::Config {})
         .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
-    let balance: cw20::BalanceResponse = app
+    let expected_token: Addr = app
         .wrap()
         .query_wasm_smart(
-            token.clone(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
-            },
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
         )
         .unwrap();
 
-    // Proposal has not been closed so deposit has not been
-    // refunded.
-    assert_eq!(balance.balance, Uint128::new(9));
-
-    // Close the proposal, this should cause the deposit to be
-    // refunded.
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        govmod_single,
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
+    assert_eq!(
+        config.deposit_info,
+        Some(CheckedDepositInfo {
+            token: expected_token,
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true
+        })
     )
-    .unwrap();
+}
 
-    let balance: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            token,
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
+/// Instantiate the contract and use a cw20 unrealated to the voting
+/// module for the proposal deposit.
+#[test]
+fn test_different_token_proposal_deposit() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let cw20_id = app.store_code(cw20_contract());
+    let cw20_addr = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "OAD OAD".to_string(),
+                symbol: "OAD".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
             },
+            &[],
+            "random-cw20",
+            None,
         )
         .unwrap();
-
-    // Proposal has been closed so deposit has been refunded.
-    assert_eq!(balance.balance, Uint128::new(10));
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Token {
+                address: cw20_addr.to_string(),
+            },
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+}
+
+/// Try to instantiate the governance module with a non-cw20 as its
+/// proposal deposit token. This should error as the `TokenInfo {}`
+/// query ought to fail.
+#[test]
+#[should_panic(expected = "Error parsing into type cw20_balance_voting::msg::QueryMsg")]
+fn test_bad_token_proposal_deposit() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let cw20_id = app.store_code(cw20_contract());
+    let votemod_id = app.store_code(cw20_balances_voting());
+
+    let votemod_addr = app
+        .instantiate_contract(
+            votemod_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_balance_voting::msg::InstantiateMsg {
+                token_info: cw20_balance_voting::msg::TokenInfo::New {
+                    code_id: cw20_id,
+                    label: "DAO DAO governance token".to_string(),
+                    name: "DAO".to_string(),
+                    symbol: "DAO".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![Cw20Coin {
+                        address: CREATOR_ADDR.to_string(),
+                        amount: Uint128::new(1),
+                    }],
+                    marketing: None,
+                },
+            },
+            &[],
+            "random-vote-module",
+            None,
+        )
+        .unwrap();
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Token {
+                address: votemod_addr.to_string(),
+            },
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+}
+
+#[test]
+fn test_take_proposal_deposit() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(2),
+        }]),
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo {
+        token,
+        deposit,
+        refund_failed_proposals,
+    } = govmod_config.deposit_info.unwrap();
+    assert!(refund_failed_proposals);
+    assert_eq!(deposit, Uint128::new(1));
+
+    // This should fail because we have not created an allowance for
+    // the proposal deposit.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    // Allow a proposal deposit.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Now we can create a proposal.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check that our balance was deducted.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(1))
+}
+
+#[test]
+fn test_deposit_return_on_execute() {
+    // Will create a proposal and execute it, one token will be
+    // deposited to create said proposal, expectation is that the
+    // token is then returned once the proposal is executed.
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Passed,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: false,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Proposal has not been executed so deposit has not been
+    // refunded.
+    assert_eq!(balance.balance, Uint128::new(9));
+
+    // Execute the proposal, this should cause the deposit to be
+    // refunded.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Proposal has been executed so deposit has been refunded.
+    assert_eq!(balance.balance, Uint128::new(10));
+}
+
+#[test]
+fn test_deposit_solvency() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Passed,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: false,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+
+    let solvency = |app: &App| -> DepositSolvencyResponse {
+        app.wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::DepositSolvency {
+                    asset: DepositAsset::Cw20 {
+                        address: token.to_string(),
+                    },
+                },
+            )
+            .unwrap()
+    };
+    let expected = |balance: u128, owed: u128| DepositSolvencyResponse {
+        asset: DepositAsset::Cw20 {
+            address: token.to_string(),
+        },
+        balance: Uint128::new(balance),
+        owed: Uint128::new(owed),
+        solvent: balance >= owed,
+    };
+
+    // The passed proposal's deposit is held until it is executed.
+    assert_eq!(solvency(&app), expected(1, 1));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(solvency(&app), expected(0, 0));
+
+    // A rejected proposal's deposit is held until it is closed.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(solvency(&app), expected(1, 1));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(solvency(&app), expected(1, 1));
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(solvency(&app), expected(0, 0));
+
+    // Tokens sent to the module outside of deposits are not owed.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        token.clone(),
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: govmod_single.to_string(),
+            amount: Uint128::new(2),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(solvency(&app), expected(2, 0));
+}
+
+#[test]
+fn test_withdraw_passed_proposal() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Passed,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // Only the proposer may withdraw their proposal.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Closed);
+    assert_eq!(proposal.proposal.last_updated, app.block_info().time);
+
+    // Failed proposals are refunded so the deposit is returned.
+    let CheckedDepositInfo { token, .. } = proposal.proposal.deposit_info.unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(10));
+
+    // Withdrawn proposals can not be executed or withdrawn again.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotPassed {}));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single,
+            &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotPassed {}));
+}
+
+#[test]
+fn test_withdraw_executed_proposal() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        Status::Passed,
+        None,
+        None,
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single,
+            &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyExecuted {}));
+}
+
+#[test]
+fn test_withdraw_after_failed_execution() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(10),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: false,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: Some(Duration::Height(5)),
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let token = config.deposit_info.unwrap().token;
+
+    // Proposal 1 sends funds the DAO does not have, so its execution
+    // fails. Proposal 2's deposit is also held by the module.
+    for proposer in ["ekez", "keze"] {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            token.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: govmod_single.to_string(),
+                amount: Uint128::new(10),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "Pay ekez".to_string(),
+                description: "Send ekez some ujuno".to_string(),
+                msgs: vec![BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: vec![Coin::new(10, "ujuno")],
+                }
+                .into()],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance = |app: &App, address: &str| -> Uint128 {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    };
+
+    // The deposit is returned with the failed execution attempt.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(balance(&app, "ekez"), Uint128::new(100));
+    assert_eq!(balance(&app, govmod_single.as_str()), Uint128::new(10));
+
+    // Withdrawing the proposal does not return it again.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Closed);
+    assert_eq!(balance(&app, "ekez"), Uint128::new(100));
+    assert_eq!(balance(&app, govmod_single.as_str()), Uint128::new(10));
+}
+
+#[test]
+fn test_force_resolve() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        Status::Open,
+        Some(Uint128::new(100)),
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // Only the DAO may force a proposal's resolution.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::ForceResolve {
+                proposal_id: 1,
+                status: Status::Closed,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // Only terminal statuses may be forced.
+    let err: ContractError = app
+        .execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::ForceResolve {
+                proposal_id: 1,
+                status: Status::Passed,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NonTerminalForceStatus {}));
+
+    let res = app
+        .execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::ForceResolve {
+                proposal_id: 1,
+                status: Status::Closed,
+            },
+            &[],
+        )
+        .unwrap();
+    let wasm = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm
+        .attributes
+        .iter()
+        .any(|a| a.key == "old_status" && a.value == "open"));
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Closed);
+    let result: FinalResultResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::FinalResult { proposal_id: 1 },
+        )
+        .unwrap();
+    let result = result.result.unwrap();
+    assert_eq!(result.status, Status::Closed);
+    assert_eq!(result.reason, ResolutionReason::Forced);
+
+    // The proposal is treated as failed, so its deposit is refunded.
+    let CheckedDepositInfo { token, .. } = proposal.proposal.deposit_info.unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(10));
+
+    // Resolved proposals may not be forced again.
+    let err: ContractError = app
+        .execute_contract(
+            governance_addr,
+            govmod_single,
+            &ExecuteMsg::ForceResolve {
+                proposal_id: 1,
+                status: Status::Executed,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyResolved { id: 1 }));
+}
+
+#[test]
+fn test_transfer_proposal_authorship() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![
+            TestSingleChoiceVote {
+                voter: "ekez".to_string(),
+                position: Vote::Yes,
+                weight: Uint128::new(10),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestSingleChoiceVote {
+                voter: "keze".to_string(),
+                position: Vote::Yes,
+                weight: Uint128::new(1),
+                should_execute: ShouldExecute::Yes,
+            },
+        ],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(100)),
+        },
+        Status::Passed,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let transfer = |app: &mut App, sender: &str, new_proposer: &str| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            govmod_single.clone(),
+            &ExecuteMsg::TransferProposalAuthorship {
+                proposal_id: 1,
+                new_proposer: new_proposer.to_string(),
+            },
+            &[],
+        )
+    };
+
+    // Only the proposer may transfer authorship, and only to a
+    // member of the DAO.
+    let err: ContractError = transfer(&mut app, "keze", "keze")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let err: ContractError = transfer(&mut app, "ekez", "nobody")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NewProposerNotMember {}));
+
+    transfer(&mut app, "ekez", "keze").unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.proposer, Addr::unchecked("keze"));
+
+    // The original proposer no longer controls the proposal.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    // The new proposer may withdraw the proposal and receives its
+    // deposit.
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        govmod_single.clone(),
+        &ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let CheckedDepositInfo { token, .. } = proposal.proposal.deposit_info.unwrap();
+    let balance = |app: &App, address: &str| {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    };
+    assert_eq!(balance(&app, "ekez"), Uint128::new(9));
+    assert_eq!(balance(&app, "keze"), Uint128::new(2));
+
+    // Authorship of closed proposals may not be transferred.
+    let err: ContractError = transfer(&mut app, "keze", "ekez")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::AuthorshipNotTransferable { id: 1 }
+    ));
+}
+
+#[test]
+fn test_second_proposal() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: Some(Duration::Height(4)),
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // Proposals that need a second can not choose their own
+    // expiration.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: Some(Expiration::AtHeight(app.block_info().height + 2)),
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::SecondWithExpirationOverride {}
+    ));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Pending);
+    assert_eq!(
+        proposal.proposal.seconding_deadline,
+        Some(Expiration::AtHeight(app.block_info().height + 4))
+    );
+
+    // Pending proposals can not be voted on.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotOpen { id: 1 }));
+
+    // Proposers can not second their own proposals.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Second { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::SecondOwnProposal {}));
+
+    // Neither can addresses without voting power.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            govmod_single.clone(),
+            &ExecuteMsg::Second { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    app.update_block(next_block);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Second { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // The voting period starts once the proposal is seconded.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(proposal.proposal.seconding_deadline, None);
+    assert_eq!(
+        proposal.proposal.expiration,
+        Expiration::AtHeight(app.block_info().height + 6)
+    );
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Second { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotPending { id: 1 }));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
+
+#[test]
+fn test_unseconded_proposal_rejected() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: Some(Duration::Height(4)),
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let token = config.deposit_info.unwrap().token;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Nobody seconds the proposal before the deadline.
+    app.update_block(|block| block.height += 4);
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Second { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotPending { id: 1 }));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    // The deposit is refunded as the proposal failed.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(10));
+}
+
+#[test]
+fn test_close_open_proposal() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Open,
+        Some(Uint128::new(100)),
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    // Close the proposal, this should error as the proposal is still
+    // open and not expired.
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap_err();
+
+    // Make the proposal expire.
+    app.update_block(|block| block.height += 10);
+
+    // Close the proposal, this should work as the proposal is now
+    // open and expired.
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Check that a refund was issued.
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Proposal has not been closed so deposit has been
+    // refunded.
+    assert_eq!(balance.balance, Uint128::new(10));
+}
+
+#[test]
+fn test_sync_status() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Open,
+        Some(Uint128::new(100)),
+        None,
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let stored_status = |app: &App| {
+        PROPOSALS
+            .query(&app.wrap(), govmod_single.clone(), 1)
+            .unwrap()
+            .unwrap()
+            .status
+    };
+
+    // Returns the `old_status` and `status` attributes of a sync.
+    let sync = |app: &mut App| -> (String, String) {
+        let res = app
+            .execute_contract(
+                Addr::unchecked("keze"),
+                govmod_single.clone(),
+                &ExecuteMsg::SyncStatus { proposal_id: 1 },
+                &[],
+            )
+            .unwrap();
+        let attribute = |key: &str| -> String {
+            res.events
+                .iter()
+                .filter(|event| event.ty == "wasm")
+                .flat_map(|event| event.attributes.iter())
+                .find(|attribute| attribute.key == key)
+                .unwrap()
+                .value
+                .clone()
+        };
+        (attribute("old_status"), attribute("status"))
+    };
+
+    // Syncing an open proposal does nothing.
+    assert_eq!(sync(&mut app), ("open".to_string(), "open".to_string()));
+    assert_eq!(stored_status(&app), Status::Open);
+
+    // Once expired the proposal is rejected, but nothing has updated
+    // the stored status.
+    app.update_block(|block| block.height += 10);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+    assert_eq!(stored_status(&app), Status::Open);
+
+    assert_eq!(sync(&mut app), ("open".to_string(), "rejected".to_string()));
+    assert_eq!(stored_status(&app), Status::Rejected);
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            govmod_single,
+            &ExecuteMsg::SyncStatus { proposal_id: 2 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchProposal { id: 2 }));
+}
+
+#[test]
+fn test_poke() {
+    fn setup(execute_on_pass: bool) -> (App, Addr) {
+        let mut app = App::default();
+        let govmod_id = app.store_code(proposal_contract());
+        let instantiate = InstantiateMsg {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(6),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        };
+        let governance_addr =
+            instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+        let gov_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+            .unwrap();
+        let govmod_single = gov_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address;
+
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        (app, govmod_single)
+    }
+
+    // Pokes the proposal and returns the `action` and `status` of
+    // the proposal stored afterwards.
+    fn poke(app: &mut App, govmod_single: &Addr) -> (String, Status) {
+        let res = app
+            .execute_contract(
+                Addr::unchecked("keeper"),
+                govmod_single.clone(),
+                &ExecuteMsg::Poke { proposal_id: 1 },
+                &[],
+            )
+            .unwrap();
+        let action = res
+            .events
+            .iter()
+            .filter(|event| event.ty == "wasm")
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == "action")
+            .unwrap()
+            .value
+            .clone();
+        let status = PROPOSALS
+            .query(&app.wrap(), govmod_single.clone(), 1)
+            .unwrap()
+            .unwrap()
+            .status;
+        (action, status)
+    }
+
+    fn vote_yes(app: &mut App, govmod_single: &Addr) {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let (mut app, govmod_single) = setup(true);
+
+    // Poking an open proposal does nothing.
+    assert_eq!(
+        poke(&mut app, &govmod_single),
+        ("sync_status".to_string(), Status::Open)
+    );
+
+    // Once passed, poking executes the proposal.
+    vote_yes(&mut app, &govmod_single);
+    assert_eq!(
+        poke(&mut app, &govmod_single),
+        ("execute".to_string(), Status::Executed)
+    );
+
+    // Poking it again does nothing.
+    assert_eq!(
+        poke(&mut app, &govmod_single),
+        ("sync_status".to_string(), Status::Executed)
+    );
+
+    // Without `execute_on_pass` passed proposals are left for
+    // execution.
+    let (mut app, govmod_single) = setup(false);
+    vote_yes(&mut app, &govmod_single);
+    assert_eq!(
+        poke(&mut app, &govmod_single),
+        ("sync_status".to_string(), Status::Passed)
+    );
+
+    // Expired proposals are rejected and saved as such.
+    let (mut app, govmod_single) = setup(true);
+    app.update_block(|block| block.height += 10);
+    assert_eq!(
+        poke(&mut app, &govmod_single),
+        ("sync_status".to_string(), Status::Rejected)
+    );
+}
+
+#[test]
+fn test_commit_reveal_voting() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: true,
+        execute_on_pass: false,
+        reveal_period: Some(Duration::Height(4)),
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(60),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple proposal".to_string(),
+            description: "This is a simple proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let commit = |app: &mut App, voter: &str, vote: Vote, salt: &str| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::CommitVote {
+                proposal_id: 1,
+                commitment: vote_commitment(1, &Addr::unchecked(voter), vote, salt),
+            },
+            &[],
+        )
+    };
+    let reveal = |app: &mut App, voter: &str, vote: Vote, salt: &str| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::RevealVote {
+                proposal_id: 1,
+                vote,
+                salt: salt.to_string(),
+            },
+            &[],
+        )
+    };
+    let query_proposal = |app: &App| {
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::Proposal { proposal_id: 1 },
+            )
+            .unwrap();
+        proposal.proposal
+    };
+
+    // Votes may not be cast directly.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CommitRevealProposal { id: 1 }));
+
+    commit(&mut app, "ekez", Vote::Yes, "ekez's salt").unwrap();
+    commit(&mut app, "keze", Vote::No, "keze's salt").unwrap();
+    // zeke copies ekez's commitment, so can never reveal it.
+    app.execute_contract(
+        Addr::unchecked("zeke"),
+        govmod_single.clone(),
+        &ExecuteMsg::CommitVote {
+            proposal_id: 1,
+            commitment: vote_commitment(1, &Addr::unchecked("ekez"), Vote::Yes, "ekez's salt"),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = commit(&mut app, "ekez", Vote::No, "ekez's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::AlreadyVoted {}));
+    let err: ContractError = commit(&mut app, "nobody", Vote::No, "salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    // Nothing may be revealed while voting is open and committed
+    // votes are not counted.
+    let err: ContractError = reveal(&mut app, "ekez", Vote::Yes, "ekez's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRevealing { id: 1 }));
+    let proposal = query_proposal(&app);
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(proposal.votes, Votes::zero());
+
+    // Once voting ends the proposal moves to its reveal phase and
+    // commitments may no longer be made.
+    app.update_block(|block| block.height += 6);
+    assert_eq!(query_proposal(&app).status, Status::Revealing);
+    let err: ContractError = commit(&mut app, "zeke", Vote::Yes, "salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ProposalExpired { id: 1, .. }));
+
+    // Reveals must match the commitment.
+    let err: ContractError = reveal(&mut app, "keze", Vote::Yes, "keze's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CommitmentMismatch {}));
+    let err: ContractError = reveal(&mut app, "keze", Vote::No, "ekez's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CommitmentMismatch {}));
+
+    // Commitments are bound to their voter, so one copied from
+    // another voter can not be revealed.
+    let err: ContractError = reveal(&mut app, "zeke", Vote::Yes, "ekez's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::CommitmentMismatch {}));
+
+    reveal(&mut app, "ekez", Vote::Yes, "ekez's salt").unwrap();
+    reveal(&mut app, "keze", Vote::No, "keze's salt").unwrap();
+    let err: ContractError = reveal(&mut app, "keze", Vote::No, "keze's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoCommitment {}));
+
+    // Revealed votes are counted but the outcome is not known until
+    // revealing ends, even though ekez alone has a majority.
+    let proposal = query_proposal(&app);
+    assert_eq!(proposal.status, Status::Revealing);
+    assert_eq!(
+        proposal.votes,
+        Votes {
+            yes: Uint128::new(60),
+            no: Uint128::new(30),
+            abstain: Uint128::zero(),
+        }
+    );
+    let ballot: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: "keze".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(ballot.vote.unwrap().vote, Vote::No);
+    // Revealed votes are recorded in the vote history as votes cast
+    // directly are.
+    let history: VoteHistoryResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::VoteHistory {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(history.entries.last().unwrap().votes, proposal.votes);
+
+    // zeke's commitment is never counted.
+    app.update_block(|block| block.height += 4);
+    let proposal = query_proposal(&app);
+    assert_eq!(proposal.status, Status::Passed);
+    assert_eq!(proposal.votes.no, Uint128::new(30));
+    let err: ContractError = reveal(&mut app, "zeke", Vote::No, "zeke's salt")
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRevealing { id: 1 }));
+}
+
+#[test]
+fn test_vote_receipt() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Abstain,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let receipt: Vec<(String, String)> = res
+        .events
+        .iter()
+        .find(|event| {
+            event.ty == "wasm"
+                && event.attributes.iter().any(|attribute| {
+                    attribute.key == "_contract_addr" && attribute.value == govmod_single.as_str()
+                })
+        })
+        .unwrap()
+        .attributes
+        .iter()
+        .filter(|attribute| attribute.key != "_contract_addr")
+        .map(|attribute| (attribute.key.clone(), attribute.value.clone()))
+        .collect();
+    let height = app.block_info().height.to_string();
+    assert_eq!(
+        receipt,
+        [
+            ("action", "vote"),
+            ("sender", "ekez"),
+            ("proposal_id", "1"),
+            ("position", "abstain"),
+            ("power", "10"),
+            ("height", height.as_str()),
+            ("status", "open"),
+        ]
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_zero_deposit() {
+    do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Passed,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(0),
+            refund_failed_proposals: false,
+        }),
+    );
+}
+
+/// Zero deposits are neither taken nor returned, so proposals with a
+/// zero deposit in a token that rejects zero transfers may still be
+/// created, executed, and closed.
+#[test]
+fn test_zero_deposit_strict_token() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let strict_cw20_id = app.store_code(strict_cw20_contract());
+    let token = app
+        .instantiate_contract(
+            strict_cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Strict".to_string(),
+                symbol: "STRICT".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::new(10),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "strict-cw20",
+            None,
+        )
+        .unwrap();
+
+    // The token does reject zero transfers.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::Transfer {
+            recipient: "ekez".to_string(),
+            amount: Uint128::zero(),
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Token {
+                address: token.to_string(),
+            },
+            deposit: Uint128::zero(),
+            refund_failed_proposals: false,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose_and_vote = |app: &mut App, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+    };
+    let status = |app: &App, proposal_id: u64| {
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        proposal.proposal.status
+    };
+
+    propose_and_vote(&mut app, 1, Vote::Yes);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(status(&app, 1), Status::Executed);
+
+    propose_and_vote(&mut app, 2, Vote::No);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(status(&app, 2), Status::Closed);
+
+    // No tokens moved.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(10));
+}
+
+#[test]
+fn test_register_deposit_token() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let cw20_id = app.store_code(cw20_contract());
+    let token = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Deposit".to_string(),
+                symbol: "DEPOSIT".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::new(10),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "deposit-cw20",
+            None,
+        )
+        .unwrap();
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Token {
+                address: token.to_string(),
+            },
+            deposit: Uint128::new(1),
+            refund_failed_proposals: false,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: true,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let cw20_list = |app: &App| -> Vec<Addr> {
+        app.wrap()
+            .query_wasm_smart(
+                governance_addr.clone(),
+                &cw_core::msg::QueryMsg::Cw20TokenList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap()
+    };
+    assert!(!cw20_list(&app).contains(&token));
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(2),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap()
+    };
+
+    // The DAO tracks the deposit token once a proposal has been
+    // created.
+    propose(&mut app);
+    let list = cw20_list(&app);
+    assert!(list.contains(&token));
+
+    // The token is only registered once.
+    let res = propose(&mut app);
+    assert!(!res
+        .events
+        .iter()
+        .any(|e| e.ty == "wasm" && e.attributes.iter().any(|a| a.value == "update_cw20_list")));
+    assert_eq!(cw20_list(&app), list);
+}
+
+#[test]
+fn test_deposit_return_on_close() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Rejected,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Proposal has not been closed so deposit has not been
+    // refunded.
+    assert_eq!(balance.balance, Uint128::new(9));
+
+    // Close the proposal, this should cause the deposit to be
+    // refunded.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Proposal has been closed so deposit has been refunded.
+    assert_eq!(balance.balance, Uint128::new(10));
+}
+
+/// A deposit that can not be returned when its proposal is closed is
+/// recorded so that the proposer may claim it once the token accepts
+/// transfers again.
+#[test]
+fn test_claim_failed_deposit_refund() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let pausable_cw20_id = app.store_code(pausable_cw20_contract());
+    let token = app
+        .instantiate_contract(
+            pausable_cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "Pausable".to_string(),
+                symbol: "PAUSE".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::new(10),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "pausable-cw20",
+            None,
+        )
+        .unwrap();
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::Token {
+                address: token.to_string(),
+            },
+            deposit: Uint128::new(10),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(10),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple proposal".to_string(),
+            description: "This is a simple proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance = |app: &App| {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: CREATOR_ADDR.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    };
+    let pending_refund = |app: &App| -> Option<Uint128> {
+        app.wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::PendingRefund {
+                    proposal_id: 1,
+                    address: CREATOR_ADDR.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(balance(&app), Uint128::zero());
+
+    // The refund fails while the token is paused, but the proposal
+    // still closes.
+    app.wasm_sudo(token.clone(), &true).unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Closed);
+    assert_eq!(balance(&app), Uint128::zero());
+    assert_eq!(pending_refund(&app), Some(Uint128::new(10)));
+    // The module still owes the refund.
+    let solvency = |app: &App| -> DepositSolvencyResponse {
+        app.wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::DepositSolvency {
+                    asset: DepositAsset::Cw20 {
+                        address: token.to_string(),
+                    },
+                },
+            )
+            .unwrap()
+    };
+    assert_eq!(solvency(&app).owed, Uint128::new(10));
+
+    // Claiming while the token is still paused fails and leaves the
+    // refund claimable.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::ClaimRefund { proposal_id: 1 },
+        &[],
+    )
+    .unwrap_err();
+    assert_eq!(pending_refund(&app), Some(Uint128::new(10)));
+
+    // Only the proposer may claim the refund.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::ClaimRefund { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoPendingRefund { id: 1 }));
+
+    app.wasm_sudo(token.clone(), &false).unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::ClaimRefund { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(balance(&app), Uint128::new(10));
+    assert_eq!(pending_refund(&app), None);
+    assert_eq!(solvency(&app).owed, Uint128::zero());
+
+    // The refund may only be claimed once.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single,
+            &ExecuteMsg::ClaimRefund { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoPendingRefund { id: 1 }));
+}
+
+#[test]
+fn test_distribute_forfeited_deposit_to_winning_voters() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::SimpleQuorumMajority {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(101),
+            refund_failed_proposals: false,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: Some(ForfeitedDepositPolicy::DistributeToWinningVoters {
+            max_voters: 2,
+        }),
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(303),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "ezek".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let token = config.deposit_info.unwrap().token;
+
+    let balance = |app: &App, address: &str| -> Uint128 {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    };
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(303),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let propose_and_vote = |app: &mut App, votes: Vec<(&str, Vote)>| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposal_id: u64 = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalCount {})
+            .unwrap();
+        for (voter, vote) in votes {
+            app.execute_contract(
+                Addr::unchecked(voter),
+                govmod_single.clone(),
+                &ExecuteMsg::Vote { proposal_id, vote },
+                &[],
+            )
+            .unwrap();
+        }
+        app.update_block(|block| block.height += 10);
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Close { proposal_id },
+            &[],
+        )
+        .unwrap();
+    };
+
+    let dao_balance = balance(&app, governance_addr.as_str());
+    propose_and_vote(
+        &mut app,
+        vec![("keze", Vote::No), ("zeke", Vote::No), ("ezek", Vote::Yes)],
+    );
+
+    // The deposit of 101 is split 3:1 between the voters who voted
+    // no, with the token lost to rounding sent to the DAO.
+    assert_eq!(balance(&app, "keze"), Uint128::new(30 + 75));
+    assert_eq!(balance(&app, "zeke"), Uint128::new(10 + 25));
+    assert_eq!(balance(&app, "ezek"), Uint128::new(20));
+    assert_eq!(
+        balance(&app, governance_addr.as_str()),
+        dao_balance + Uint128::new(1)
+    );
+
+    // With more voters than may be paid the deposit is sent to the
+    // DAO.
+    let dao_balance = balance(&app, governance_addr.as_str());
+    propose_and_vote(
+        &mut app,
+        vec![("keze", Vote::No), ("zeke", Vote::No), ("ezek", Vote::No)],
+    );
+    assert_eq!(balance(&app, "keze"), Uint128::new(30 + 75));
+    assert_eq!(balance(&app, "zeke"), Uint128::new(10 + 25));
+    assert_eq!(balance(&app, "ezek"), Uint128::new(20));
+    assert_eq!(
+        balance(&app, governance_addr.as_str()),
+        dao_balance + Uint128::new(101)
+    );
+
+    // A proposal that misses its quorum was not rejected by the
+    // voters who voted no, so the deposit is sent to the DAO.
+    let dao_balance = balance(&app, governance_addr.as_str());
+    propose_and_vote(&mut app, vec![("keze", Vote::No)]);
+    assert_eq!(balance(&app, "keze"), Uint128::new(30 + 75));
+    assert_eq!(
+        balance(&app, governance_addr.as_str()),
+        dao_balance + Uint128::new(101)
+    );
+}
+
+#[test]
+fn test_close_reward() {
+    let instantiate = |source: RewardSource, amount: u128| InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(10),
+            refund_failed_proposals: false,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: Some(CloseReward {
+            source,
+            amount: Uint128::new(amount),
+        }),
+        cache_voting_power: false,
+    };
+
+    // Creates a proposal that is rejected as it expires without votes
+    // and has "keeper" close it, returning the cw20 balances of the
+    // keeper and the DAO and the keeper's native balance.
+    let close_rejected = |source: RewardSource| -> (Uint128, Uint128, Uint128) {
+        let mut app = App::default();
+        let govmod_id = app.store_code(proposal_contract());
+        let governance_addr = instantiate_with_cw20_balances_governance(
+            &mut app,
+            govmod_id,
+            instantiate(source, 4),
+            Some(vec![Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            }]),
+        );
+        let gov_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(
+                governance_addr.clone(),
+                &cw_core::msg::QueryMsg::DumpState {},
+            )
+            .unwrap();
+        let govmod_single = gov_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address;
+        let config: Config = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+            .unwrap();
+        let token = config.deposit_info.unwrap().token;
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: govmod_single.to_string(),
+            amount: vec![Coin::new(4, "ujuno")],
+        }))
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            token.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: govmod_single.to_string(),
+                amount: Uint128::new(10),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.height += 10);
+        app.execute_contract(
+            Addr::unchecked("keeper"),
+            govmod_single,
+            &ExecuteMsg::Close { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        let balance = |address: &str| -> Uint128 {
+            let balance: cw20::BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+            balance.balance
+        };
+        (
+            balance("keeper"),
+            balance(governance_addr.as_str()),
+            app.wrap().query_balance("keeper", "ujuno").unwrap().amount,
+        )
+    };
+
+    // A reward from the deposit is taken out of the forfeited
+    // deposit before the rest is sent to the DAO.
+    assert_eq!(
+        close_rejected(RewardSource::Deposit {}),
+        (Uint128::new(4), Uint128::new(6), Uint128::zero())
+    );
+    // A reward from a pool leaves the deposit untouched.
+    assert_eq!(
+        close_rejected(RewardSource::Native {
+            denom: "ujuno".to_string(),
+        }),
+        (Uint128::zero(), Uint128::new(10), Uint128::new(4))
+    );
+
+    // A zero reward is rejected.
+    let err = crate::contract::instantiate(
+        mock_dependencies().as_mut(),
+        mock_env(),
+        MessageInfo {
+            sender: Addr::unchecked(CREATOR_ADDR),
+            funds: vec![],
+        },
+        InstantiateMsg {
+            deposit_info: None,
+            ..instantiate(RewardSource::Deposit {}, 0)
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ZeroCloseReward {}));
+}
+
+#[test]
+fn test_close_expired() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let token = govmod_config.deposit_info.unwrap().token;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: govmod_single.to_string(),
+            amount: Uint128::new(4),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let close_expired = |app: &mut App, start_after: Option<u64>, limit: Option<u64>| -> Vec<u64> {
+        let res = app
+            .execute_contract(
+                Addr::unchecked("keeper"),
+                govmod_single.clone(),
+                &ExecuteMsg::CloseExpired { start_after, limit },
+                &[],
+            )
+            .unwrap();
+        from_slice(&res.data.unwrap()).unwrap()
+    };
+    let status = |app: &App, proposal_id: u64| {
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        proposal.proposal.status
+    };
+    let balance = |app: &App| {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: CREATOR_ADDR.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance.u128()
+    };
+
+    // Three proposals expire and a fourth is created after they do.
+    propose(&mut app);
+    propose(&mut app);
+    propose(&mut app);
+    app.update_block(|block| block.height += 6);
+    propose(&mut app);
+    assert_eq!(balance(&app), 6);
+
+    // Closing is bounded by the limit.
+    assert_eq!(close_expired(&mut app, None, Some(2)), vec![1, 2]);
+    assert_eq!(status(&app, 1), Status::Closed);
+    assert_eq!(status(&app, 2), Status::Closed);
+    assert_eq!(status(&app, 3), Status::Rejected);
+    assert_eq!(balance(&app), 8);
+
+    // The limit bounds the proposals examined, which start after
+    // `start_after`, so examining only the open proposal closes
+    // nothing.
+    assert_eq!(close_expired(&mut app, Some(3), Some(1)), Vec::<u64>::new());
+    assert_eq!(status(&app, 3), Status::Rejected);
+
+    // Closed and open proposals are skipped.
+    assert_eq!(close_expired(&mut app, Some(1), None), vec![3]);
+    assert_eq!(status(&app, 3), Status::Closed);
+    assert_eq!(status(&app, 4), Status::Open);
+    assert_eq!(balance(&app), 9);
+
+    // Closing with nothing to close does nothing.
+    assert_eq!(close_expired(&mut app, None, None), Vec::<u64>::new());
+
+    // Closed proposals may not be closed again individually.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("keeper"),
+            govmod_single.clone(),
+            &ExecuteMsg::Close { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+}
+
+#[test]
+fn test_deposit_accounting() {
+    // Creates a rejected proposal with a deposit of one token, closes
+    // it, and returns the accounting before and after closing.
+    let collect_then_close =
+        |refund_failed_proposals: bool| -> (DepositAccountingResponse, DepositAccountingResponse) {
+            let (mut app, governance_addr) = do_test_votes_cw20_balances(
+                vec![TestSingleChoiceVote {
+                    voter: "ekez".to_string(),
+                    position: Vote::No,
+                    weight: Uint128::new(10),
+                    should_execute: ShouldExecute::Yes,
+                }],
+                Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+                },
+                Status::Rejected,
+                None,
+                Some(DepositInfo {
+                    token: DepositToken::VotingModuleToken {},
+                    deposit: Uint128::new(1),
+                    refund_failed_proposals,
+                }),
+            );
+            let gov_state: cw_core::query::DumpStateResponse = app
+                .wrap()
+                .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+                .unwrap();
+            let govmod_single = gov_state
+                .proposal_modules
+                .into_iter()
+                .next()
+                .unwrap()
+                .address;
+
+            let collected: DepositAccountingResponse = app
+                .wrap()
+                .query_wasm_smart(govmod_single.clone(), &QueryMsg::DepositAccounting {})
+                .unwrap();
+            app.execute_contract(
+                Addr::unchecked("ekez"),
+                govmod_single.clone(),
+                &ExecuteMsg::Close { proposal_id: 1 },
+                &[],
+            )
+            .unwrap();
+            let closed: DepositAccountingResponse = app
+                .wrap()
+                .query_wasm_smart(govmod_single, &QueryMsg::DepositAccounting {})
+                .unwrap();
+            (collected, closed)
+        };
+
+    let counters = |response: &DepositAccountingResponse| {
+        assert_eq!(response.deposits.len(), 1);
+        let accounting = &response.deposits[0];
+        (
+            accounting.collected.u128(),
+            accounting.refunded.u128(),
+            accounting.forfeited.u128(),
+        )
+    };
+
+    let (collected, refunded) = collect_then_close(true);
+    assert_eq!(counters(&collected), (1, 0, 0));
+    assert_eq!(counters(&refunded), (1, 1, 0));
+
+    let (collected, forfeited) = collect_then_close(false);
+    assert_eq!(counters(&collected), (1, 0, 0));
+    assert_eq!(counters(&forfeited), (1, 0, 1));
+}
+
+/// Tests that changing the deposit config while a proposal is open
+/// does not change the deposit that proposal refunds.
+#[test]
+fn test_deposit_config_change_refunds_original_deposit() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Rejected,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: true,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let original: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let original_deposit = original.deposit_info.clone().unwrap();
+
+    // Switch deposits to a different token with a larger amount.
+    let cw20_id = app.store_code(cw20_contract());
+    let new_token = app
+        .instantiate_contract(
+            cw20_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &cw20_base::msg::InstantiateMsg {
+                name: "OAD OAD".to_string(),
+                symbol: "OAD".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "random-cw20",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        governance_addr,
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: original.threshold,
+            max_voting_period: original.max_voting_period,
+            min_voting_period: original.min_voting_period,
+            only_members_execute: original.only_members_execute,
+            allow_revoting: original.allow_revoting,
+            dao: original.dao.to_string(),
+            deposit_info: Some(DepositInfo {
+                token: DepositToken::Token {
+                    address: new_token.to_string(),
+                },
+                deposit: Uint128::new(5),
+                refund_failed_proposals: false,
+            }),
+            close_proposal_on_execution_failure: original.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The proposal still records the deposit that was taken.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(
+        proposal.proposal.deposit_info,
+        Some(original_deposit.clone())
+    );
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // The original deposit is refunded in the original token.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            original_deposit.token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(10));
+}
+
+#[test]
+fn test_execute_expired_proposal() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "innactive".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_modules = gov_state.proposal_modules;
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "This proposal will expire.".to_string(),
+            description: "What will happen?".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Proposal has now reached quorum but should not be passed.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Expire the proposal. It should now be passed as we had 100% yes
+    // votes inside the quorum.
+    app.update_block(|b| b.height += 10);
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // Try to close the proposal. This should fail as the proposal is
+    // passed.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap_err();
+
+    // Check that we can execute the proposal despite the fact that it
+    // is technically expired.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Can't execute more than once.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap_err();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_execute_during_governance_freeze() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        Status::Passed,
+        None,
+        None,
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let until = app.block_info().time.plus_seconds(100);
+    app.execute_contract(
+        governance_addr.clone(),
+        governance_addr,
+        &cw_core::msg::ExecuteMsg::SetGovernanceFreeze { until },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::GovernanceFrozen { until: u } if u == until));
+
+    // The proposal remains passed while the DAO is frozen.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    let executable = |app: &App| {
+        let res: FilteredProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ExecutableByAddress {
+                    address: "ekez".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    assert_eq!(executable(&app), Vec::<u64>::new());
+
+    app.update_block(|block| block.time = until);
+    assert_eq!(executable(&app), vec![1]);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+#[test]
+fn test_reject_failing_execution() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: true,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: "ekez".to_string(),
+            amount: Uint128::new(10),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose_and_pass = |app: &mut App, msgs: Vec<CosmosMsg>| -> u64 {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposal_id: u64 = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalCount {})
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+        proposal_id
+    };
+    let execute = |app: &mut App, proposal_id: u64| -> Result<(), ContractError> {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    };
+    let reject = |app: &mut App, sender: &Addr, proposal_id: u64| -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::RejectFailingProposal { proposal_id },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    };
+    let status = |app: &App, proposal_id: u64| -> Status {
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        proposal.proposal.status
+    };
+    let send: CosmosMsg = BankMsg::Send {
+        to_address: "ekez".to_string(),
+        amount: vec![Coin::new(100, "ujuno")],
+    }
+    .into();
+
+    // The DAO does not have the funds the proposal sends, so it is
+    // rejected instead of executed.
+    let proposal_id = propose_and_pass(&mut app, vec![send.clone()]);
+    execute(&mut app, proposal_id).unwrap();
+    assert_eq!(status(&app, proposal_id), Status::Rejected);
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id },
+        &[],
+    )
+    .unwrap();
+    let result: FinalResultResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::FinalResult { proposal_id },
+        )
+        .unwrap();
+    assert_eq!(
+        result.result.unwrap().reason,
+        ResolutionReason::WouldFailExecution
+    );
+
+    // Nor may proposals execute contracts that do not exist.
+    let proposal_id = propose_and_pass(
+        &mut app,
+        vec![WasmMsg::Execute {
+            contract_addr: "nothing".to_string(),
+            msg: to_binary(&ExecuteMsg::Close { proposal_id: 1 }).unwrap(),
+            funds: vec![],
+        }
+        .into()],
+    );
+    execute(&mut app, proposal_id).unwrap();
+    assert_eq!(status(&app, proposal_id), Status::Rejected);
+
+    // The DAO may reject a failing proposal before its execution is
+    // attempted.
+    let proposal_id = propose_and_pass(&mut app, vec![send.clone()]);
+    let err = reject(&mut app, &Addr::unchecked("ekez"), proposal_id).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    reject(&mut app, &governance_addr, proposal_id).unwrap();
+    assert_eq!(status(&app, proposal_id), Status::Rejected);
+
+    // Once the DAO has the funds the proposal is executed, and may not
+    // be rejected.
+    let proposal_id = propose_and_pass(&mut app, vec![send]);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: governance_addr.to_string(),
+        amount: vec![Coin::new(100, "ujuno")],
+    }))
+    .unwrap();
+    let err = reject(&mut app, &governance_addr, proposal_id).unwrap_err();
+    assert!(matches!(err, ContractError::ExecutionCheckPassed {}));
+    execute(&mut app, proposal_id).unwrap();
+    assert_eq!(status(&app, proposal_id), Status::Executed);
+}
+
+#[test]
+fn test_close_rejected_after_failed_execution() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(10),
+            refund_failed_proposals: false,
+        }),
+        close_proposal_on_execution_failure: false,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: Some(Duration::Height(5)),
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: true,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: Some(CloseReward {
+            source: RewardSource::Deposit {},
+            amount: Uint128::new(4),
+        }),
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let token = config.deposit_info.unwrap().token;
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: governance_addr.to_string(),
+        amount: vec![Coin::new(100, "ujuno")],
+    }))
+    .unwrap();
+
+    let propose = |app: &mut App, proposer: &str, msgs: Vec<CosmosMsg>| {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            token.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: govmod_single.to_string(),
+                amount: Uint128::new(10),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let execute = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap();
+    };
+    let balance = |app: &App, address: &str| -> Uint128 {
+        let balance: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    };
+
+    // Proposal 1 sends the DAO's funds with a message the token
+    // contract does not understand, so it passes the execution checks
+    // but fails to execute. Proposal 2 spends the DAO's funds and
+    // proposal 3's deposit stays with the module.
+    propose(
+        &mut app,
+        "ekez",
+        vec![WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&ExecuteMsg::Close { proposal_id: 1 }).unwrap(),
+            funds: vec![Coin::new(100, "ujuno")],
+        }
+        .into()],
+    );
+    propose(
+        &mut app,
+        "keze",
+        vec![BankMsg::Send {
+            to_address: "keze".to_string(),
+            amount: vec![Coin::new(100, "ujuno")],
+        }
+        .into()],
+    );
+    propose(&mut app, "keze", vec![]);
+    for proposal_id in [1, 2] {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // The deposit is returned with the failed execution attempt.
+    execute(&mut app, 1);
+    assert_eq!(balance(&app, "ekez"), Uint128::new(100));
+    execute(&mut app, 2);
+
+    // Without the funds proposal 1 is now rejected as unable to
+    // execute. Closing it neither forfeits its deposit again nor pays
+    // the close reward from it.
+    app.execute_contract(
+        governance_addr.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::RejectFailingProposal { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("keeper"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(balance(&app, "keeper"), Uint128::zero());
+    assert_eq!(balance(&app, governance_addr.as_str()), Uint128::zero());
+    assert_eq!(balance(&app, govmod_single.as_str()), Uint128::new(10));
+
+    // Only proposal 3's deposit is held.
+    let solvency: DepositSolvencyResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single,
+            &QueryMsg::DepositSolvency {
+                asset: DepositAsset::Cw20 {
+                    address: token.to_string(),
+                },
+            },
+        )
+        .unwrap();
+    assert_eq!(solvency.owed, Uint128::new(10));
+    assert!(solvency.solvent);
+}
+
+#[test]
+fn test_update_config() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Rejected,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: false,
+        }),
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(
+        govmod_config.threshold,
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        }
+    );
+
+    let dao = govmod_config.dao;
+
+    // Attempt to update the config from a non-dao address. This
+    // should fail as it is unauthorized.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: cw_utils::Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: CREATOR_ADDR.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    // Update the config from the DAO address. This should succede.
+    app.execute_contract(
+        dao.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: cw_utils::Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: CREATOR_ADDR.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+
+    let expected = Config {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        dao: Addr::unchecked(CREATOR_ADDR),
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: VotingPeriodLimit::default(),
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    assert_eq!(govmod_config, expected);
+
+    // As we have changed the DAO address updating the config using
+    // the original one should now fail.
+    app.execute_contract(
+        dao,
+        govmod_single,
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: cw_utils::Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: CREATOR_ADDR.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_config_history() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: true,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let instantiate_height = app.block_info().height;
+
+    let update_quorum = |app: &mut App, quorum: u64, record_config_history: bool| {
+        app.update_block(next_block);
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::UpdateConfig {
+                threshold: Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Percent(Decimal::percent(quorum)),
+                    mode: ThresholdMode::MajorityOfNonAbstain {},
+                },
+                max_voting_period: Duration::Height(6),
+                min_voting_period: None,
+                only_members_execute: false,
+                allow_revoting: false,
+                dao: governance_addr.to_string(),
+                deposit_info: None,
+                close_proposal_on_execution_failure: true,
+                exclude_dao_voting_power: false,
+                execution_callback: None,
+                require_second: None,
+                record_config_history,
+                quorum_reached_hooks: false,
+                require_power_at_resolution: false,
+                execution_retry_cooldown: None,
+                post_execution_cooldown: None,
+                quorum_extension: None,
+                native_propose_deposit: None,
+                snapshot_offset: 0,
+                pre_execution_hook: None,
+                required_description_sections: vec![],
+                voting_period_limit: None,
+                register_deposit_token: false,
+                total_power_override: None,
+                record_vote_history: false,
+                execute_on_pass: false,
+                reveal_period: None,
+                hook_allowlist: None,
+                revote_settle_period: None,
+                deposit_escalation: None,
+                forfeited_deposit_policy: None,
+                reject_failing_execution: false,
+                non_voter_default: None,
+                max_proposal_bytes: None,
+                config_change_delay: None,
+                close_reward: None,
+                cache_voting_power: false,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let query_history = |app: &App, start_after: Option<u64>, limit: Option<u64>| {
+        let res: ConfigHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ConfigHistory { start_after, limit },
+            )
+            .unwrap();
+        res.entries
+    };
+    let quorum = |entry: &ConfigHistoryEntryResponse| match entry.entry.config.threshold {
+        Threshold::ThresholdQuorum {
+            quorum: PercentageThreshold::Percent(quorum),
+            ..
+        } => quorum,
+        _ => panic!("unexpected threshold"),
+    };
+
+    update_quorum(&mut app, 20, true);
+    update_quorum(&mut app, 30, true);
+
+    let history = query_history(&app, None, None);
+    assert_eq!(history.len(), 3);
+    assert_eq!(
+        history.iter().map(|e| e.id).collect::<Vec<u64>>(),
+        vec![0, 1, 2]
+    );
+    assert_eq!(
+        history.iter().map(quorum).collect::<Vec<Decimal>>(),
+        vec![
+            Decimal::percent(10),
+            Decimal::percent(20),
+            Decimal::percent(30)
+        ]
+    );
+    // Each entry records the block the config took effect in.
+    assert_eq!(
+        history.iter().map(|e| e.entry.height).collect::<Vec<u64>>(),
+        vec![
+            instantiate_height,
+            instantiate_height + 1,
+            instantiate_height + 2
+        ]
+    );
+    assert_eq!(query_history(&app, Some(0), Some(1))[0].id, 1);
+
+    // Configs are not recorded while history is disabled.
+    update_quorum(&mut app, 40, false);
+    update_quorum(&mut app, 50, false);
+    assert_eq!(query_history(&app, None, None).len(), 3);
+
+    // Only the most recent entries are retained.
+    for _ in 0..MAX_CONFIG_HISTORY {
+        update_quorum(&mut app, 60, true);
+    }
+    let history = query_history(&app, None, Some(MAX_CONFIG_HISTORY + 1));
+    assert_eq!(history.len() as u64, MAX_CONFIG_HISTORY);
+    assert_eq!(history[0].id, 3);
+    assert!(history
+        .iter()
+        .all(|entry| quorum(entry) == Decimal::percent(60)));
+}
+
+#[test]
+fn test_vote_history() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: true,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(20),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(30),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple proposal".to_string(),
+            description: "This is a simple proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let vote = |app: &mut App, voter: &str, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let history = |app: &App, start_after: Option<u64>, limit: Option<u64>| {
+        let history: VoteHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::VoteHistory {
+                    proposal_id: 1,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        history.entries
+    };
+    let votes = |yes: u128, no: u128| Votes {
+        yes: Uint128::new(yes),
+        no: Uint128::new(no),
+        abstain: Uint128::zero(),
+    };
+
+    assert_eq!(history(&app, None, None), vec![]);
+
+    let first = app.block_info();
+    vote(&mut app, "ekez", Vote::Yes);
+    app.update_block(next_block);
+
+    // Votes cast in the same block are recorded as one entry with
+    // the tally as of the end of the block.
+    let second = app.block_info();
+    vote(&mut app, "keze", Vote::No);
+    vote(&mut app, "zeke", Vote::Yes);
+
+    let entries = history(&app, None, None);
+    assert_eq!(
+        entries,
+        vec![
+            VoteHistoryEntry {
+                height: first.height,
+                time: first.time,
+                votes: votes(10, 0),
+            },
+            VoteHistoryEntry {
+                height: second.height,
+                time: second.time,
+                votes: votes(40, 20),
+            },
+        ]
+    );
+
+    // Entries may be paged through by height.
+    assert_eq!(history(&app, None, Some(1)), entries[..1].to_vec());
+    assert_eq!(
+        history(&app, Some(first.height), Some(1)),
+        entries[1..].to_vec()
+    );
+    assert_eq!(history(&app, Some(second.height), None), vec![]);
+}
+
+#[test]
+fn test_query_dao() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::Yes,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        Status::Passed,
+        None,
+        None,
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, governance_addr);
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(dao, config.dao);
+
+    // Changing the DAO in the config should be reflected by the
+    // query.
+    app.execute_contract(
+        governance_addr,
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            only_members_execute: config.only_members_execute,
+            allow_revoting: config.allow_revoting,
+            dao: CREATOR_ADDR.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(CREATOR_ADDR));
+}
+
+#[test]
+fn test_exclude_dao_voting_power() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            amount: Uint128::new(100),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let token_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            gov_state.voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Move most of the supply into the DAO's treasury.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract,
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: governance_addr.to_string(),
+            amount: Uint128::new(80),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let propose_and_vote = |app: &mut App, proposal_id: u64| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        proposal.proposal
+    };
+
+    // With the DAO's tokens counted quorum can not be reached.
+    let proposal = propose_and_vote(&mut app, 1);
+    assert_eq!(proposal.total_power, Uint128::new(100));
+    assert_eq!(proposal.status, Status::Open);
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    app.execute_contract(
+        governance_addr,
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            only_members_execute: config.only_members_execute,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: true,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Excluding them the creator holds all of the circulating supply.
+    let proposal = propose_and_vote(&mut app, 2);
+    assert_eq!(proposal.total_power, Uint128::new(20));
+    assert_eq!(proposal.status, Status::Passed);
+
+    // Proposals created before the config change are unaffected.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.total_power, Uint128::new(100));
+}
+
+#[test]
+fn test_voting_module_changed() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(40),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(60),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let old_voting_module = gov_state.voting_module;
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Swap the DAO's voting module for one with a new token where
+    // keze holds all of the voting power.
+    let cw20_id = app.store_code(cw20_contract());
+    let votemod_id = app.store_code(cw20_balances_voting());
+    app.execute_contract(
+        governance_addr.clone(),
+        governance_addr.clone(),
+        &cw_core::msg::ExecuteMsg::UpdateVotingModule {
+            module: ModuleInstantiateInfo {
+                code_id: votemod_id,
+                msg: to_binary(&cw20_balance_voting::msg::InstantiateMsg {
+                    token_info: cw20_balance_voting::msg::TokenInfo::New {
+                        code_id: cw20_id,
+                        label: "New DAO DAO governance token".to_string(),
+                        name: "DAO".to_string(),
+                        symbol: "DAO".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![Cw20Coin {
+                            address: "keze".to_string(),
+                            amount: Uint128::new(1000),
+                        }],
+                        marketing: None,
+                    },
+                })
+                .unwrap(),
+                admin: cw_core::msg::Admin::CoreContract {},
+                label: "New DAO DAO voting module".to_string(),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    assert_ne!(voting_module, old_voting_module);
+
+    // Votes counted by the new module would not agree with the
+    // proposal's total power, so they are rejected.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("keze"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::VotingModuleChanged { id: 1 }));
+
+    // The proposal is resolved using its snapshotted total power and
+    // the votes cast before the change.
+    app.update_block(|block| block.height += 6);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.voting_module, Some(old_voting_module));
+    assert_eq!(proposal.proposal.total_power, Uint128::new(100));
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(40));
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+}
+
+#[test]
+fn test_no_return_if_no_refunds() {
+    let (mut app, governance_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Rejected,
+        None,
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: false,
+        }),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let governance_modules = gov_state.proposal_modules;
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+
+    // Close the proposal, this should cause the deposit to be
+    // refunded.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Proposal has been closed but deposit has not been refunded.
+    assert_eq!(balance.balance, Uint128::new(9));
+}
+
+#[test]
+fn test_query_list_proposals() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let gov_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: cw_utils::Duration::Height(100),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            amount: Uint128::new(100),
+        }]),
+    );
+
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(gov_addr.clone(), &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let voting_module_code_id = contract_code_id(&app, &voting_module);
+    let gov_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            gov_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(gov_modules.len(), 1);
+
+    let govmod = gov_modules.into_iter().next().unwrap().address;
+
+    for i in 1..10 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod.clone(),
+            &ExecuteMsg::Propose {
+                title: format!("Text proposal {}.", i),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    let proposals_forward: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::ListProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let mut proposals_backward: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::ReverseProposals {
+                start_before: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    proposals_backward.proposals.reverse();
+
+    assert_eq!(proposals_forward.proposals, proposals_backward.proposals);
+
+    let expected = ProposalResponse {
+        id: 1,
+        id_prefix: None,
+        proposal: SingleChoiceProposal {
+            title: "Text proposal 1.".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            proposer: Addr::unchecked(CREATOR_ADDR),
+            start_height: app.block_info().height,
+            expiration: cw_utils::Expiration::AtHeight(app.block_info().height + 100),
+            min_voting_period: None,
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            allow_revoting: false,
+            total_power: Uint128::new(100),
+            msgs: vec![],
+            status: Status::Open,
+            votes: Votes::zero(),
+            deposit_info: None,
+            created: app.block_info().time,
+            last_updated: app.block_info().time,
+            execution_error: None,
+            seconding_deadline: None,
+            execution_funds: vec![],
+            quorum_reached: false,
+            voting_module: Some(voting_module.clone()),
+            require_power_at_resolution: false,
+            related_proposals: vec![],
+            last_execution_attempt: None,
+            extensions: 0,
+            native_deposit: None,
+            snapshot_height: Some(app.block_info().height),
+            close_proposal_on_execution_failure: Some(true),
+            reveal_period: None,
+            revote_settle_period: None,
+            settlement: None,
+            metadata: None,
+            vote_count: Some(0),
+            voting_module_code_id: Some(voting_module_code_id),
+            module_version: Some(CONTRACT_VERSION.to_string()),
+            non_voter_default: None,
+            resolved_at: None,
+            execution_retry_cooldown: None,
+            original_proposer: None,
+        },
+    };
+    assert_eq!(proposals_forward.proposals[0], expected);
+
+    // Get proposals (3, 5]
+    let proposals_forward: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod.clone(),
+            &QueryMsg::ListProposals {
+                start_after: Some(3),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    let mut proposals_backward: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod,
+            &QueryMsg::ReverseProposals {
+                start_before: Some(6),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+
+    let expected = ProposalResponse {
+        id: 4,
+        id_prefix: None,
+        proposal: SingleChoiceProposal {
+            title: "Text proposal 4.".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            proposer: Addr::unchecked(CREATOR_ADDR),
+            start_height: app.block_info().height,
+            expiration: cw_utils::Expiration::AtHeight(app.block_info().height + 100),
+            min_voting_period: None,
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            allow_revoting: false,
+            total_power: Uint128::new(100),
+            msgs: vec![],
+            status: Status::Open,
+            votes: Votes::zero(),
+            deposit_info: None,
+            created: app.block_info().time,
+            last_updated: app.block_info().time,
+            execution_error: None,
+            seconding_deadline: None,
+            execution_funds: vec![],
+            quorum_reached: false,
+            voting_module: Some(voting_module),
+            require_power_at_resolution: false,
+            related_proposals: vec![],
+            last_execution_attempt: None,
+            extensions: 0,
+            native_deposit: None,
+            snapshot_height: Some(app.block_info().height),
+            close_proposal_on_execution_failure: Some(true),
+            reveal_period: None,
+            revote_settle_period: None,
+            settlement: None,
+            metadata: None,
+            vote_count: Some(0),
+            voting_module_code_id: Some(voting_module_code_id),
+            module_version: Some(CONTRACT_VERSION.to_string()),
+            non_voter_default: None,
+            resolved_at: None,
+            execution_retry_cooldown: None,
+            original_proposer: None,
+        },
+    };
+    assert_eq!(proposals_forward.proposals[0], expected);
+    assert_eq!(proposals_backward.proposals[1], expected);
+
+    proposals_backward.proposals.reverse();
+    assert_eq!(proposals_forward.proposals, proposals_backward.proposals);
+}
+
+#[test]
+fn test_id_prefix() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: Some("GRANTS".to_string()),
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple proposal".to_string(),
+            description: "This is a simple proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The prefix is returned alongside the unchanged numeric ID.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.id, 1);
+    assert_eq!(proposal.id_prefix, Some("GRANTS".to_string()));
+
+    let proposals: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single,
+            &QueryMsg::ListProposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(proposals.proposals, vec![proposal]);
+}
+
+#[test]
+#[should_panic(
+    expected = "id_prefix must be between 1 and (16) uppercase ASCII letters and digits, starting with a letter"
+)]
+fn test_invalid_id_prefix() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: Some("grants-1".to_string()),
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+}
+
+#[test]
+fn test_hooks() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let dao = govmod_config.dao;
+
+    // Expect no hooks
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks.len(), 0);
+
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks.len(), 0);
+
+    let msg = ExecuteMsg::AddProposalHook {
+        address: "some_addr".to_string(),
+        override_allowlist: false,
+    };
+
+    // Expect error as sender is not DAO
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+
+    // Expect success as sender is now DAO
+    let _res = app
+        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
+        .unwrap();
+
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks.len(), 1);
+
+    // Expect error as hook is already set
+    let _err = app
+        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
+        .unwrap_err();
+
+    // Expect error as hook does not exist
+    let _err = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::RemoveProposalHook {
+                address: "not_exist".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    let msg = ExecuteMsg::RemoveProposalHook {
+        address: "some_addr".to_string(),
+    };
+
+    // Expect error as sender is not DAO
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+
+    // Expect success
+    let _res = app
+        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = ExecuteMsg::AddVoteHook {
+        address: "some_addr".to_string(),
+        override_allowlist: false,
+    };
+
+    // Expect error as sender is not DAO
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+
+    // Expect success as sender is now DAO
+    let _res = app
+        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
+        .unwrap();
+
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks.len(), 1);
+
+    // Expect error as hook is already set
+    let _err = app
+        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
+        .unwrap_err();
+
+    // Expect error as hook does not exist
+    let _err = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::RemoveVoteHook {
+                address: "not_exist".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    let msg = ExecuteMsg::RemoveVoteHook {
+        address: "some_addr".to_string(),
+    };
+
+    // Expect error as sender is not DAO
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+
+    // Expect success
+    let _res = app.execute_contract(dao, govmod_single, &msg, &[]).unwrap();
+}
+
+#[test]
+fn test_hook_registration_heights() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let dao = instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let start = app.block_info().height;
+    let execute = |app: &mut App, msg: ExecuteMsg| {
+        app.execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
+            .unwrap();
+        app.update_block(next_block);
+    };
+    execute(
+        &mut app,
+        ExecuteMsg::AddProposalHook {
+            address: "one".to_string(),
+            override_allowlist: false,
+        },
+    );
+    execute(
+        &mut app,
+        ExecuteMsg::AddVoteHook {
+            address: "one".to_string(),
+            override_allowlist: false,
+        },
+    );
+    execute(
+        &mut app,
+        ExecuteMsg::AddProposalHook {
+            address: "two".to_string(),
+            override_allowlist: false,
+        },
+    );
+
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec!["one".to_string(), "two".to_string()]);
+    assert_eq!(
+        hooks.registrations,
+        vec![
+            HookRegistration {
+                address: "one".to_string(),
+                height: start,
+            },
+            HookRegistration {
+                address: "two".to_string(),
+                height: start + 2,
+            },
+        ]
+    );
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+        .unwrap();
+    assert_eq!(
+        hooks.registrations,
+        vec![HookRegistration {
+            address: "one".to_string(),
+            height: start + 1,
+        }]
+    );
+
+    // Re-registering a hook records the new height.
+    execute(
+        &mut app,
+        ExecuteMsg::RemoveProposalHook {
+            address: "one".to_string(),
+        },
+    );
+    execute(
+        &mut app,
+        ExecuteMsg::AddProposalHook {
+            address: "one".to_string(),
+            override_allowlist: false,
+        },
+    );
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::ProposalHooks {})
+        .unwrap();
+    assert_eq!(
+        hooks.registrations,
+        vec![
+            HookRegistration {
+                address: "two".to_string(),
+                height: start + 2,
+            },
+            HookRegistration {
+                address: "one".to_string(),
+                height: start + 4,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_migrate_backfills_hook_registrations() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    // "one" was registered before registration heights were recorded.
+    Item::<Vec<Addr>>::new("proposal_hooks")
+        .save(&mut deps.storage, &vec![Addr::unchecked("one")])
+        .unwrap();
+    PROPOSAL_HOOKS
+        .add_hook(&mut deps.storage, &env.block, Addr::unchecked("two"))
+        .unwrap();
+
+    migrate(deps.as_mut(), env.clone(), MigrateMsg::FromCompatible {}).unwrap();
+
+    let registrations = Map::<(&str, Addr), u64>::new("hook_registrations");
+    assert_eq!(
+        registrations
+            .load(&deps.storage, ("proposal_hooks", Addr::unchecked("one")))
+            .unwrap(),
+        0
+    );
+    // Recorded heights are left as they are.
+    assert_eq!(
+        registrations
+            .load(&deps.storage, ("proposal_hooks", Addr::unchecked("two")))
+            .unwrap(),
+        env.block.height
+    );
+}
+
+#[test]
+fn test_add_remove_hooks() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let dao = instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let strings = |addrs: &[&str]| -> Vec<String> { addrs.iter().map(|a| a.to_string()).collect() };
+    let hooks = |app: &App| -> (Vec<String>, Vec<String>) {
+        let proposal_hooks: HooksResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+            .unwrap();
+        let vote_hooks: HooksResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+            .unwrap();
+        (proposal_hooks.hooks, vote_hooks.hooks)
+    };
+
+    // Only the DAO may add hooks.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::AddHooks {
+                proposal_hooks: strings(&["one"]),
+                vote_hooks: vec![],
+                override_allowlist: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        dao.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::AddHooks {
+            proposal_hooks: strings(&["one", "two"]),
+            vote_hooks: strings(&["one", "three"]),
+            override_allowlist: false,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        hooks(&app),
+        (strings(&["one", "two"]), strings(&["one", "three"]))
+    );
+
+    // A duplicate aborts the whole batch.
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::AddHooks {
+                proposal_hooks: strings(&["four"]),
+                vote_hooks: strings(&["five", "three"]),
+                override_allowlist: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::HookError(HookError::HookAlreadyRegistered {})
+    ));
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::AddHooks {
+                proposal_hooks: strings(&["four", "four"]),
+                vote_hooks: vec![],
+                override_allowlist: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::HookError(HookError::HookAlreadyRegistered {})
+    ));
+    assert_eq!(
+        hooks(&app),
+        (strings(&["one", "two"]), strings(&["one", "three"]))
+    );
+
+    // Likewise removing a hook that is not registered removes none.
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::RemoveHooks {
+                proposal_hooks: strings(&["one"]),
+                vote_hooks: strings(&["two"]),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::HookError(HookError::HookNotRegistered {})
+    ));
+    assert_eq!(
+        hooks(&app),
+        (strings(&["one", "two"]), strings(&["one", "three"]))
+    );
+
+    app.execute_contract(
+        dao,
+        govmod_single.clone(),
+        &ExecuteMsg::RemoveHooks {
+            proposal_hooks: strings(&["one", "two"]),
+            vote_hooks: strings(&["three"]),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(hooks(&app), (vec![], strings(&["one"])));
+}
+
+#[test]
+fn test_hook_allowlist() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: Some(vec!["audited".to_string()]),
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let dao =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate.clone(), None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            dao.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let hooks = |app: &App| -> (Vec<String>, Vec<String>) {
+        let proposal_hooks: HooksResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
+            .unwrap();
+        let vote_hooks: HooksResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
+            .unwrap();
+        (proposal_hooks.hooks, vote_hooks.hooks)
+    };
+
+    // Allowlisted addresses may be added.
+    app.execute_contract(
+        dao.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::AddProposalHook {
+            address: "audited".to_string(),
+            override_allowlist: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Other addresses are rejected, even when the DAO adds them.
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::AddVoteHook {
+                address: "rogue".to_string(),
+                override_allowlist: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::HookNotAllowlisted { address } if address == "rogue"));
+
+    // A batch with an address not on the allowlist adds none of its
+    // hooks.
+    let err: ContractError = app
+        .execute_contract(
+            dao.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::AddHooks {
+                proposal_hooks: vec![],
+                vote_hooks: vec!["audited".to_string(), "rogue".to_string()],
+                override_allowlist: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::HookNotAllowlisted { .. }));
+    assert_eq!(hooks(&app), (vec!["audited".to_string()], vec![]));
+
+    // The allowlist may be overridden.
+    app.execute_contract(
+        dao.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::AddVoteHook {
+            address: "rogue".to_string(),
+            override_allowlist: true,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        hooks(&app),
+        (vec!["audited".to_string()], vec!["rogue".to_string()])
+    );
+
+    // The allowlist is managed by the DAO through config updates.
+    let msg = instantiate;
+    app.execute_contract(
+        dao.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: msg.threshold,
+            max_voting_period: msg.max_voting_period,
+            min_voting_period: msg.min_voting_period,
+            only_members_execute: msg.only_members_execute,
+            allow_revoting: msg.allow_revoting,
+            dao: dao.to_string(),
+            deposit_info: msg.deposit_info,
+            close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: msg.exclude_dao_voting_power,
+            execution_callback: msg.execution_callback,
+            require_second: msg.require_second,
+            record_config_history: msg.record_config_history,
+            quorum_reached_hooks: msg.quorum_reached_hooks,
+            require_power_at_resolution: msg.require_power_at_resolution,
+            execution_retry_cooldown: msg.execution_retry_cooldown,
+            post_execution_cooldown: msg.post_execution_cooldown,
+            quorum_extension: msg.quorum_extension,
+            native_propose_deposit: msg.native_propose_deposit,
+            snapshot_offset: msg.snapshot_offset,
+            pre_execution_hook: msg.pre_execution_hook,
+            required_description_sections: msg.required_description_sections,
+            voting_period_limit: msg.voting_period_limit,
+            register_deposit_token: msg.register_deposit_token,
+            total_power_override: msg.total_power_override,
+            record_vote_history: msg.record_vote_history,
+            execute_on_pass: msg.execute_on_pass,
+            reveal_period: msg.reveal_period,
+            hook_allowlist: Some(vec!["audited".to_string(), "reviewed".to_string()]),
+            revote_settle_period: msg.revote_settle_period,
+            deposit_escalation: msg.deposit_escalation,
+            forfeited_deposit_policy: msg.forfeited_deposit_policy,
+            reject_failing_execution: msg.reject_failing_execution,
+            non_voter_default: msg.non_voter_default,
+            max_proposal_bytes: msg.max_proposal_bytes,
+            config_change_delay: msg.config_change_delay,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.hook_allowlist,
+        Some(vec![
+            Addr::unchecked("audited"),
+            Addr::unchecked("reviewed")
+        ])
+    );
+
+    app.execute_contract(
+        dao,
+        govmod_single,
+        &ExecuteMsg::AddProposalHook {
+            address: "reviewed".to_string(),
+            override_allowlist: false,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_quorum_reached_event() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let voters = ["ekez", "keze", "zeke", "ezek"];
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(
+            voters
+                .iter()
+                .map(|voter| Cw20Coin {
+                    address: voter.to_string(),
+                    amount: Uint128::new(10),
+                })
+                .collect(),
+        ),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote = |app: &mut App, voter: &str, vote: Vote| {
+        let res = app
+            .execute_contract(
+                Addr::unchecked(voter),
+                govmod_single.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote,
+                },
+                &[],
+            )
+            .unwrap();
+        res.events
+            .iter()
+            .filter(|event| event.ty == "wasm-quorum_reached")
+            .count()
+    };
+
+    // 10 of 40 voting power is below the 50% quorum.
+    assert_eq!(vote(&mut app, "ekez", Vote::Yes), 0);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(!proposal.proposal.quorum_reached);
+
+    // The second vote reaches quorum.
+    assert_eq!(vote(&mut app, "keze", Vote::No), 1);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(proposal.proposal.quorum_reached);
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Later votes do not fire the event again.
+    assert_eq!(vote(&mut app, "zeke", Vote::Abstain), 0);
+    assert_eq!(vote(&mut app, "ezek", Vote::Yes), 0);
+}
+
+#[test]
+fn test_active_threshold_absolute() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr = instantiate_with_staking_active_threshold(
+        &mut app,
+        govmod_id,
+        instantiate,
+        None,
+        Some(ActiveThreshold::AbsoluteCount {
+            count: Uint128::new(100),
+        }),
+    );
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let dao = govmod_config.dao;
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    let token_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Try and create a proposal, will fail as inactive
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    // Stake enough tokens
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount: Uint128::new(100),
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), token_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // Try and create a proposal, will now succeed as enough tokens are staked
+    let _res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Unstake some tokens to make it inactive again
+    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
+        amount: Uint128::new(50),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // Try and create a proposal, will fail as no longer active
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single,
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn test_active_threshold_percent() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    // 20% needed to be active, 20% of 100000000 is 20000000
+    let governance_addr = instantiate_with_staking_active_threshold(
+        &mut app,
+        govmod_id,
+        instantiate,
+        None,
+        Some(ActiveThreshold::Percentage {
+            percent: Decimal::percent(20),
+        }),
+    );
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let dao = govmod_config.dao;
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    let token_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Try and create a proposal, will fail as inactive
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    // Stake enough tokens
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount: Uint128::new(20000000),
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), token_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // Try and create a proposal, will now succeed as enough tokens are staked
+    let _res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Unstake some tokens to make it inactive again
+    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
+        amount: Uint128::new(1000),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // Try and create a proposal, will fail as no longer active
+    let _err = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single,
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn test_active_threshold_none() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr =
+        instantiate_with_staking_active_threshold(&mut app, govmod_id, instantiate, None, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let govmod_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let dao = govmod_config.dao;
+    let voting_module: Addr = app
+        .wrap()
+        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        .unwrap();
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+    let token_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
+        )
+        .unwrap();
+
+    // Stake some tokens so we can propose
+    let msg = cw20::Cw20ExecuteMsg::Send {
+        contract: staking_contract.to_string(),
+        amount: Uint128::new(2000),
+        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+    };
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), token_contract, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    // Try and create a proposal, will succeed as no threshold
+    let _res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single,
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // Now try with balance voting to test when IsActive is not implemented
+    // on the contract
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    // Try and create a proposal, will succeed as IsActive is not implemented
+    let _res = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single,
+            &crate::msg::ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+/// Simple test for revoting.
+#[test]
+fn test_revoting() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // The supreme galatic floob rules over many DAOs with benevolance
+    // and grace. The people of floob have become complacent in the
+    // goodness of the floob.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Supreme galactic floob.".to_string(),
+            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The people initially jump at the chance to recognize the supreme
+    // galactic floob!
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // The wise slarbibfast does not agree. After some digging they
+    // discover that the floob has been bugging the hotel rooms of
+    // political rivals.
+    app.execute_contract(
+        Addr::unchecked("slarbibfast"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Time passes.
+    app.update_block(|b| b.height += 5);
+
+    // Word spreads.
+    app.update_block(|b| b.height += 4);
+
+    // At the last moment the people realize their mistake.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(100),
+            abstain: Uint128::zero()
+        }
+    );
+
+    // As the clock strikes midnight on the last day of the proposal,
+    // revoting has saved the day!
+    app.update_block(|b| b.height += 1);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+}
+
+#[test]
+fn test_vote_count() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Count the voters".to_string(),
+            description: "Each voter should be counted once.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote_count = |app: &App| -> Option<u64> {
+        app.wrap()
+            .query_wasm_smart(
+                proposal_module.clone(),
+                &QueryMsg::VoteCount { proposal_id: 1 },
+            )
+            .unwrap()
+    };
+    assert_eq!(vote_count(&app), Some(0));
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(vote_count(&app), Some(1));
+
+    // Revoting does not count the voter again.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(vote_count(&app), Some(1));
+
+    app.execute_contract(
+        Addr::unchecked("slarbibfast"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Abstain,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(vote_count(&app), Some(2));
+
+    // Addresses without voting power are not counted.
+    app.execute_contract(
+        Addr::unchecked("floob"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.vote_count, Some(2));
+}
+
+#[test]
+fn test_vote_batch() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    for _ in 0..3 {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    app.execute_contract(
+        Addr::unchecked("slarbibfast"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Votes that fail are skipped and the rest are cast.
+    let res = app
+        .execute_contract(
+            Addr::unchecked("slarbibfast"),
+            proposal_module.clone(),
+            &ExecuteMsg::VoteBatch {
+                votes: vec![
+                    (1, Vote::No),
+                    (2, Vote::No),
+                    (3, Vote::Abstain),
+                    (4, Vote::Yes),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+    let results: Vec<VoteBatchResult> = from_slice(&res.data.unwrap()).unwrap();
+    assert_eq!(
+        results,
+        vec![
+            VoteBatchResult {
+                proposal_id: 1,
+                error: Some(ContractError::AlreadyVoted {}.to_string()),
+            },
+            VoteBatchResult {
+                proposal_id: 2,
+                error: None,
+            },
+            VoteBatchResult {
+                proposal_id: 3,
+                error: None,
+            },
+            VoteBatchResult {
+                proposal_id: 4,
+                error: Some(ContractError::NoSuchProposal { id: 4 }.to_string()),
+            },
+        ]
+    );
+
+    let ballot = |app: &App, proposal_id: u64| -> Option<Vote> {
+        let vote: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                proposal_module.clone(),
+                &QueryMsg::GetVote {
+                    proposal_id,
+                    voter: "slarbibfast".to_string(),
+                },
+            )
+            .unwrap();
+        vote.vote.map(|vote| vote.vote)
+    };
+    assert_eq!(ballot(&app, 1), Some(Vote::Yes));
+    assert_eq!(ballot(&app, 2), Some(Vote::No));
+    assert_eq!(ballot(&app, 3), Some(Vote::Abstain));
+
+    // The skipped vote left the proposal's tally unchanged.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(10));
+    assert_eq!(proposal.proposal.votes.no, Uint128::zero());
+    assert_eq!(proposal.proposal.vote_count, Some(1));
+
+    // Batches must be non-empty and no larger than the cap.
+    for votes in [
+        vec![],
+        vec![(1, Vote::Yes); MAX_VOTE_BATCH_SIZE as usize + 1],
+    ] {
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked("ekez"),
+                proposal_module.clone(),
+                &ExecuteMsg::VoteBatch { votes },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(
+            err,
+            ContractError::InvalidVoteBatchSize {
+                max: MAX_VOTE_BATCH_SIZE
+            }
+        ));
+    }
+}
+
+#[test]
+fn test_revote_settle_period() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(20),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: Some(Duration::Height(3)),
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose {
+                title: "Supreme galactic floob.".to_string(),
+                description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let vote = |app: &mut App, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+    };
+    let query_proposal = |app: &App, proposal_id: u64| -> SingleChoiceProposal {
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(proposal_module.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        proposal.proposal
+    };
+
+    // A decisive vote starts the settle period. The proposal stays
+    // open until it elapses and then passes, well before expiring.
+    propose(&mut app);
+    vote(&mut app, 1, Vote::Yes);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(
+        proposal.settlement,
+        Some(Settlement {
+            passed: true,
+            settles_at: Expiration::AtHeight(app.block_info().height + 3),
+        })
+    );
+    app.update_block(|b| b.height += 2);
+    assert_eq!(query_proposal(&app, 1).status, Status::Open);
+    app.update_block(|b| b.height += 1);
+    assert_eq!(query_proposal(&app, 1).status, Status::Passed);
+
+    // A revote that decides the proposal the other way restarts the
+    // settle period.
+    propose(&mut app);
+    vote(&mut app, 2, Vote::Yes);
+    app.update_block(|b| b.height += 2);
+    vote(&mut app, 2, Vote::No);
+    assert_eq!(
+        query_proposal(&app, 2).settlement,
+        Some(Settlement {
+            passed: false,
+            settles_at: Expiration::AtHeight(app.block_info().height + 3),
+        })
+    );
+    app.update_block(|b| b.height += 2);
+    assert_eq!(query_proposal(&app, 2).status, Status::Open);
+    app.update_block(|b| b.height += 1);
+    assert_eq!(query_proposal(&app, 2).status, Status::Rejected);
+
+    // A revote that leaves the proposal undecided cancels the
+    // settlement and it remains open until it expires.
+    propose(&mut app);
+    vote(&mut app, 3, Vote::Yes);
+    app.update_block(|b| b.height += 1);
+    vote(&mut app, 3, Vote::Abstain);
+    let proposal = query_proposal(&app, 3);
+    assert_eq!(proposal.settlement, None);
+    app.update_block(|b| b.height += 5);
+    assert_eq!(query_proposal(&app, 3).status, Status::Open);
+
+    // Once settled a proposal may no longer be revoted on.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::No,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotOpen { .. }));
+}
+
+#[test]
+fn test_list_votes_by_position() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(50),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(30),
+            },
+            Cw20Coin {
+                address: "zeke".to_string(),
+                amount: Uint128::new(20),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple proposal".to_string(),
+            description: "This is a simple proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let vote = |app: &mut App, voter: &str, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let voters = |app: &App, position: Vote, start_after: Option<&str>| -> Vec<(String, u128)> {
+        let votes: VoteListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                proposal_module.clone(),
+                &QueryMsg::ListVotesByPosition {
+                    proposal_id: 1,
+                    position,
+                    start_after: start_after.map(|voter| voter.to_string()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        votes
+            .votes
+            .into_iter()
+            .map(|vote| {
+                assert_eq!(vote.vote, position);
+                (vote.voter.into_string(), vote.power.u128())
+            })
+            .collect()
+    };
+
+    vote(&mut app, "ekez", Vote::Yes);
+    vote(&mut app, "keze", Vote::No);
+    vote(&mut app, "zeke", Vote::Yes);
+    assert_eq!(
+        voters(&app, Vote::Yes, None),
+        vec![("ekez".to_string(), 50), ("zeke".to_string(), 20)]
+    );
+    assert_eq!(
+        voters(&app, Vote::Yes, Some("ekez")),
+        vec![("zeke".to_string(), 20)]
+    );
+    assert_eq!(voters(&app, Vote::No, None), vec![("keze".to_string(), 30)]);
+    assert_eq!(voters(&app, Vote::Abstain, None), vec![]);
+
+    // Revoting moves the voter to their new position.
+    vote(&mut app, "ekez", Vote::No);
+    vote(&mut app, "zeke", Vote::Abstain);
+    assert_eq!(voters(&app, Vote::Yes, None), vec![]);
+    assert_eq!(
+        voters(&app, Vote::No, None),
+        vec![("ekez".to_string(), 50), ("keze".to_string(), 30)]
+    );
+    assert_eq!(
+        voters(&app, Vote::Abstain, None),
+        vec![("zeke".to_string(), 20)]
+    );
+
+    vote(&mut app, "ekez", Vote::Yes);
+    assert_eq!(
+        voters(&app, Vote::Yes, None),
+        vec![("ekez".to_string(), 50)]
+    );
+    assert_eq!(voters(&app, Vote::No, None), vec![("keze".to_string(), 30)]);
+
+    // All votes may also be listed in reverse.
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::ReverseVotes {
+                proposal_id: 1,
+                start_before: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let reversed: Vec<(String, Vote)> = votes
+        .votes
+        .into_iter()
+        .map(|vote| (vote.voter.into_string(), vote.vote))
+        .collect();
+    assert_eq!(
+        reversed,
+        vec![
+            ("zeke".to_string(), Vote::Abstain),
+            ("keze".to_string(), Vote::No),
+            ("ekez".to_string(), Vote::Yes),
+        ]
+    );
+    let votes: VoteListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module,
+            &QueryMsg::ReverseVotes {
+                proposal_id: 1,
+                start_before: Some("zeke".to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(votes.votes.len(), 1);
+    assert_eq!(votes.votes[0].voter, Addr::unchecked("keze"));
+}
+
+/// Tests that revotes reuse the voting power recorded on the voter's
+/// ballot and that it matches the voter's power at the proposal's
+/// start height.
+#[test]
+fn test_revote_reuses_ballot_power() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    for vote in [Vote::Yes, Vote::No, Vote::Abstain] {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(next_block);
+    }
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    let ballot: VoteResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module,
+            &QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: "ekez".to_string(),
+            },
+        )
+        .unwrap();
+    let fresh: cw_core_interface::voting::VotingPowerAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            core_addr,
+            &cw_core::msg::QueryMsg::VotingPowerAtHeight {
+                address: "ekez".to_string(),
+                height: Some(proposal.proposal.start_height),
+            },
+        )
+        .unwrap();
+
+    let ballot = ballot.vote.unwrap();
+    assert_eq!(ballot.vote, Vote::Abstain);
+    assert_eq!(ballot.power, fresh.power);
+    assert_eq!(
+        proposal.proposal.votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: Uint128::zero(),
+            abstain: fresh.power,
+        }
+    );
+}
+
+/// Tests that voting power cached while `cache_voting_power` is set
+/// matches a fresh query at the proposal's snapshot height, that
+/// proposals sharing a snapshot height share cached power, and that
+/// a new snapshot height does not reuse power cached for an older one.
+#[test]
+fn test_cache_voting_power() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: true,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "keze".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let voting_module = core_state.voting_module;
+    let staking_contract: Addr = app
+        .wrap()
+        .query_wasm_smart(
+            voting_module.clone(),
+            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+        )
+        .unwrap();
+
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+    };
+    let query_proposal = |app: &App, proposal_id: u64| -> ProposalResponse {
+        app.wrap()
+            .query_wasm_smart(proposal_module.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap()
+    };
+    let ballot_power = |app: &App, proposal_id: u64, voter: &str| -> Uint128 {
+        let ballot: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                proposal_module.clone(),
+                &QueryMsg::GetVote {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .unwrap();
+        ballot.vote.unwrap().power
+    };
+    let cached_power = |app: &App, height: u64, voter: &str| -> Option<Uint128> {
+        app.wrap()
+            .query_wasm_raw(
+                proposal_module.clone(),
+                VOTING_POWER_CACHE
+                    .key((voting_module.clone(), height, Addr::unchecked(voter)))
+                    .to_vec(),
+            )
+            .unwrap()
+            .map(|value| from_slice(&value).unwrap())
+    };
+    let cached_total_power = |app: &App, height: u64| -> Option<Uint128> {
+        app.wrap()
+            .query_wasm_raw(
+                proposal_module.clone(),
+                TOTAL_POWER_CACHE
+                    .key((voting_module.clone(), height))
+                    .to_vec(),
+            )
+            .unwrap()
+            .map(|value| from_slice(&value).unwrap())
+    };
+    let fresh_power = |app: &App, height: u64, voter: &str| -> Uint128 {
+        let power: cw_core_interface::voting::VotingPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &cw_core::msg::QueryMsg::VotingPowerAtHeight {
+                    address: voter.to_string(),
+                    height: Some(height),
+                },
+            )
+            .unwrap();
+        power.power
+    };
+    let fresh_total_power = |app: &App, height: u64| -> Uint128 {
+        let power: cw_core_interface::voting::TotalPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                core_addr.clone(),
+                &cw_core::msg::QueryMsg::TotalPowerAtHeight {
+                    height: Some(height),
+                },
+            )
+            .unwrap();
+        power.power
+    };
+
+    // Both proposals are created in the same block and so share a
+    // snapshot height.
+    propose(&mut app);
+    propose(&mut app);
+    let height = query_proposal(&app, 1).proposal.start_height;
+    assert_eq!(query_proposal(&app, 2).proposal.start_height, height);
+    assert_eq!(
+        cached_total_power(&app, height),
+        Some(fresh_total_power(&app, height))
+    );
+    assert_eq!(
+        query_proposal(&app, 2).proposal.total_power,
+        fresh_total_power(&app, height)
+    );
+
+    // Power is cached on a voter's first vote and not before.
+    assert_eq!(cached_power(&app, height, "ekez"), None);
+    assert_eq!(cached_power(&app, height, "keze"), None);
+    vote(&mut app, "ekez", 1, Vote::Yes);
+    vote(&mut app, "keze", 2, Vote::Yes);
+    assert_eq!(
+        cached_power(&app, height, "ekez"),
+        Some(fresh_power(&app, height, "ekez"))
+    );
+    assert_eq!(
+        cached_power(&app, height, "keze"),
+        Some(fresh_power(&app, height, "keze"))
+    );
+    assert_eq!(ballot_power(&app, 2, "keze"), Uint128::new(10));
+
+    // Unstaking after the snapshot height changes neither the cached
+    // power nor a fresh query at the snapshot height.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        staking_contract,
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(40),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    vote(&mut app, "ekez", 2, Vote::No);
+    assert_eq!(ballot_power(&app, 2, "ekez"), Uint128::new(90));
+    assert_eq!(fresh_power(&app, height, "ekez"), Uint128::new(90));
+    assert_eq!(cached_power(&app, height, "ekez"), Some(Uint128::new(90)));
+
+    // A proposal with a new snapshot height starts with an empty cache.
+    propose(&mut app);
+    let new_height = query_proposal(&app, 3).proposal.start_height;
+    assert_ne!(new_height, height);
+    assert_eq!(cached_power(&app, new_height, "ekez"), None);
+    assert_eq!(cached_total_power(&app, new_height), Some(Uint128::new(60)));
+    assert_eq!(
+        query_proposal(&app, 3).proposal.total_power,
+        Uint128::new(60)
+    );
+
+    vote(&mut app, "ekez", 3, Vote::Yes);
+    assert_eq!(ballot_power(&app, 3, "ekez"), Uint128::new(50));
+    assert_eq!(
+        cached_power(&app, new_height, "ekez"),
+        Some(fresh_power(&app, new_height, "ekez"))
+    );
+    assert_eq!(cached_power(&app, height, "ekez"), Some(Uint128::new(90)));
+}
+
+/// Tests that revoting is stored at a per-proposal level. Proposals
+/// created while revoting is enabled should not have it disabled if a
+/// config change turns if off.
+#[test]
+fn test_allow_revoting_config_changes() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // Create a proposal. This proposal should allow revoting.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Supreme galactic floob.".to_string(),
+            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Disable revoting.
+    app.execute_contract(
+        core_addr.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            dao: core_addr.to_string(),
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    // The first created proposal should still allow revoting.
+    assert!(proposal.proposal.allow_revoting);
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // New proposals should not allow revoting.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Supreme galactic floob.".to_string(),
+            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("slarbibfast"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("slarbibfast"),
+            proposal_module,
+            &ExecuteMsg::Vote {
+                proposal_id: 2,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    assert!(matches!(err, ContractError::AlreadyVoted {}))
+}
+
+/// Tests that we error if a revote casts the same vote as the
+/// previous vote.
+#[test]
+fn test_revoting_same_vote_twice() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(90),
+            },
+            Cw20Coin {
+                address: "slarbibfast".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Supreme galactic floob.".to_string(),
+            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    // Can't cast the same vote twice.
+    assert!(matches!(err, ContractError::AlreadyCast {}));
+
+    // Casting a different vote is fine. You can do that as many times
+    // as you'd like.
+    for _ in 1..5 {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::No,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_can_revote() {
+    for allow_revoting in [true, false] {
+        let mut app = App::default();
+        let proposal_id = app.store_code(proposal_contract());
+        let core_addr = instantiate_with_staked_balances_governance(
+            &mut app,
+            proposal_id,
+            InstantiateMsg {
+                threshold: Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                    mode: ThresholdMode::MajorityOfNonAbstain {},
+                },
+                max_voting_period: Duration::Height(10),
+                min_voting_period: None,
+                only_members_execute: true,
+                allow_revoting,
+                deposit_info: None,
+                close_proposal_on_execution_failure: true,
+                exclude_dao_voting_power: false,
+                execution_callback: None,
+                require_second: None,
+                record_config_history: false,
+                quorum_reached_hooks: false,
+                require_power_at_resolution: false,
+                execution_retry_cooldown: None,
+                post_execution_cooldown: None,
+                quorum_extension: None,
+                native_propose_deposit: None,
+                snapshot_offset: 0,
+                pre_execution_hook: None,
+                required_description_sections: vec![],
+                voting_period_limit: None,
+                register_deposit_token: false,
+                total_power_override: None,
+                record_vote_history: false,
+                execute_on_pass: false,
+                reveal_period: None,
+                hook_allowlist: None,
+                revote_settle_period: None,
+                deposit_escalation: None,
+                forfeited_deposit_policy: None,
+                reject_failing_execution: false,
+                id_prefix: None,
+                non_voter_default: None,
+                max_proposal_bytes: None,
+                config_change_delay: None,
+                close_reward: None,
+                cache_voting_power: false,
+            },
+            Some(vec![
+                Cw20Coin {
+                    address: "ekez".to_string(),
+                    amount: Uint128::new(40),
+                },
+                Cw20Coin {
+                    address: "keze".to_string(),
+                    amount: Uint128::new(60),
+                },
+            ]),
+        );
+        let core_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+            .unwrap();
+        let proposal_module = core_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address;
+
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let can_revote = |app: &App, voter: &str| -> CanRevoteResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    proposal_module.clone(),
+                    &QueryMsg::CanRevote {
+                        proposal_id: 1,
+                        voter: voter.to_string(),
+                    },
+                )
+                .unwrap()
+        };
+
+        // There is no vote to change before voting.
+        assert_eq!(
+            can_revote(&app, "ekez"),
+            CanRevoteResponse {
+                can_revote: false,
+                reason: Some(ContractError::NotVoted { id: 1 }.to_string()),
+            }
+        );
+
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            proposal_module.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::No,
+            },
+            &[],
+        )
+        .unwrap();
+
+        if allow_revoting {
+            assert_eq!(
+                can_revote(&app, "ekez"),
+                CanRevoteResponse {
+                    can_revote: true,
+                    reason: None,
+                }
+            );
+        } else {
+            assert_eq!(
+                can_revote(&app, "ekez"),
+                CanRevoteResponse {
+                    can_revote: false,
+                    reason: Some(ContractError::AlreadyVoted {}.to_string()),
+                }
+            );
+        }
+
+        // Votes may not be changed once the proposal has closed.
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(
+                proposal_module.clone(),
+                &QueryMsg::Proposal { proposal_id: 1 },
+            )
+            .unwrap();
+        app.update_block(|block| block.height += 10);
+        assert_eq!(
+            can_revote(&app, "ekez"),
+            CanRevoteResponse {
+                can_revote: false,
+                reason: Some(
+                    ContractError::ProposalExpired {
+                        id: 1,
+                        expired_at: proposal.proposal.expiration,
+                    }
+                    .to_string()
+                ),
+            }
+        );
+    }
+}
+
+#[test]
+fn test_vote_after_expiration() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        InstantiateMsg {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(6),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        None,
+    );
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let expired_at = Expiration::AtHeight(app.block_info().height + 6);
+
+    app.update_block(|block| block.height += 8);
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module,
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    match err {
+        ContractError::ProposalExpired { id, expired_at: at } => {
+            assert_eq!(id, 1);
+            assert_eq!(at, expired_at);
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn test_related_proposals() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose = |app: &mut App, related_proposals: Vec<u64>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals,
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+    };
+
+    propose(&mut app, vec![]).unwrap();
+
+    // Proposals may only reference proposals that exist.
+    let err: ContractError = propose(&mut app, vec![1, 2])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NoSuchProposal { id: 2 }));
+
+    propose(&mut app, vec![1]).unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.related_proposals, vec![1]);
+
+    let related: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::RelatedProposals { proposal_id: 2 },
+        )
+        .unwrap();
+    assert_eq!(related.proposals.len(), 1);
+    assert_eq!(related.proposals[0].id, 1);
+    assert!(related.proposals[0].proposal.related_proposals.is_empty());
+
+    let related: ProposalListResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single,
+            &QueryMsg::RelatedProposals { proposal_id: 1 },
+        )
+        .unwrap();
+    assert!(related.proposals.is_empty());
+}
+
+/// Tests a simple three of five multisig configuration.
+#[test]
+fn test_three_of_five_multisig() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(3),
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "one".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "two".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "three".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "four".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "five".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("one"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Propose a thing.".to_string(),
+            description: "Do the thing.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("one"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("two"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Make sure it doesn't pass early.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    app.execute_contract(
+        Addr::unchecked("three"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    app.execute_contract(
+        Addr::unchecked("four"),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+/// Tests proposal rejection with three of five multisig style voting.
+#[test]
+fn test_three_of_five_multisig_reject() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(3),
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "one".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "two".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "three".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "four".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "five".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("one"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Propose a thing.".to_string(),
+            description: "Do the thing.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("one"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("two"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("three"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("four"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Still one vote outstanding but the module ought to have
+    // rejected it already as that one vote can not make the proposal
+    // pass.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Rejected);
+
+    app.execute_contract(
+        Addr::unchecked("four"),
+        proposal_module.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Closed);
+}
+
+/// Tests that we fail to instantiate when using multisig style voting
+/// power and `VotingModuleToken {}`.
+#[test]
+#[should_panic]
+fn test_voting_module_token_with_multisig_style_voting() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    instantiate_with_cw4_groups_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(3),
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: Some(DepositInfo {
+                token: DepositToken::VotingModuleToken {},
+                deposit: Uint128::new(1),
+                refund_failed_proposals: true,
+            }),
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "one".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "two".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "three".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+}
+
+/// Tests revoting with multisig style absolute count thresholds.
+#[test]
+fn test_three_of_five_multisig_revoting() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_cw4_groups_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(3),
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: true,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "one".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "two".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "three".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "four".to_string(),
+                amount: Uint128::new(1),
+            },
+            Cw20Coin {
+                address: "five".to_string(),
+                amount: Uint128::new(1),
+            },
+        ]),
+    );
+
+    let core_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_module = core_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    app.execute_contract(
+        Addr::unchecked("one"),
+        proposal_module.clone(),
+        &ExecuteMsg::Propose {
+            title: "Propose a thing.".to_string(),
+            description: "Do the thing.".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("one"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("two"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("three"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("four"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Make sure it doesn't pass early.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // Four changes their mind.
+    app.execute_contract(
+        Addr::unchecked("four"),
+        proposal_module.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| b.height += 10);
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_module.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    app.execute_contract(
+        Addr::unchecked("four"),
+        proposal_module.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Executed);
+}
+
+/// Tests that absolute count style thresholds work with token style
+/// voting.
+#[test]
+fn test_absolute_count_threshold_non_multisig() {
+    do_votes_staked_balances(
+        vec![
+            TestSingleChoiceVote {
+                voter: "one".to_string(),
+                position: Vote::Yes,
+                weight: Uint128::new(10),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestSingleChoiceVote {
+                voter: "two".to_string(),
+                position: Vote::No,
+                weight: Uint128::new(200),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestSingleChoiceVote {
+                voter: "three".to_string(),
+                position: Vote::Yes,
+                weight: Uint128::new(1),
+                should_execute: ShouldExecute::Yes,
+            },
+        ],
+        Threshold::AbsoluteCount {
+            threshold: Uint128::new(11),
+        },
+        Status::Passed,
+        None,
+    );
+}
+
+/// Tests that we do not overflow when faced with really high token /
+/// vote supply.
+#[test]
+fn test_large_absolute_count_threshold() {
+    do_votes_staked_balances(
+        vec![
+            // Instant rejection after this.
+            TestSingleChoiceVote {
+                voter: "two".to_string(),
+                position: Vote::No,
+                weight: Uint128::new(1),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestSingleChoiceVote {
+                voter: "one".to_string(),
+                position: Vote::Yes,
+                weight: Uint128::new(u128::MAX - 1),
+                should_execute: ShouldExecute::No,
+            },
+        ],
+        Threshold::AbsoluteCount {
+            threshold: Uint128::new(u128::MAX),
+        },
+        Status::Rejected,
+        None,
+    );
+
+    do_votes_staked_balances(
+        vec![
+            TestSingleChoiceVote {
+                voter: "one".to_string(),
+                position: Vote::Yes,
+                weight: Uint128::new(u128::MAX - 1),
+                should_execute: ShouldExecute::Yes,
+            },
+            TestSingleChoiceVote {
+                voter: "two".to_string(),
+                position: Vote::No,
+                weight: Uint128::new(1),
+                should_execute: ShouldExecute::Yes,
+            },
+        ],
+        Threshold::AbsoluteCount {
+            threshold: Uint128::new(u128::MAX),
+        },
+        Status::Rejected,
+        None,
+    );
+}
+
+#[test]
+fn test_migrate_from_compatible() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let max_voting_period = cw_utils::Duration::Height(6);
+    let instantiate = InstantiateMsg {
+        threshold,
+        max_voting_period,
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(governance_modules.len(), 1);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+
+    app.execute(
+        governance_addr,
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: govmod_single.to_string(),
+            new_code_id: govmod_id,
+            msg: to_binary(&MigrateMsg::FromCompatible {}).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let new_config: Config = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(config, new_config);
+}
+
+#[test]
+fn test_final_result() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let threshold = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Majority {},
+    };
+    let instantiate = InstantiateMsg {
+        threshold: threshold.clone(),
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let propose_and_vote = |app: &mut App, msgs: Vec<CosmosMsg>, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposal_id: u64 = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalCount {})
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+        proposal_id
+    };
+    let final_result = |app: &App, proposal_id: u64| -> Option<FinalResult> {
+        let res: FinalResultResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::FinalResult { proposal_id },
+            )
+            .unwrap();
+        res.result
+    };
+
+    // An executed proposal.
+    let executed = propose_and_vote(&mut app, vec![], Vote::Yes);
+    assert_eq!(final_result(&app, executed), None);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute {
+            proposal_id: executed,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // A proposal whose messages fail to execute. The DAO has no
+    // native tokens to send.
+    let failed = propose_and_vote(
+        &mut app,
+        vec![BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: vec![Coin::new(10, "ujuno")],
+        }
+        .into()],
+        Vote::Yes,
+    );
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute {
+            proposal_id: failed,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // A rejected proposal that is closed.
+    let rejected = propose_and_vote(&mut app, vec![], Vote::No);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Close {
+            proposal_id: rejected,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let block = app.block_info();
+    let expected = |status: Status, reason: ResolutionReason, votes: Votes| FinalResult {
+        status,
+        reason,
+        votes,
+        total_power: Uint128::new(100_000_000),
+        threshold: threshold.clone(),
+        height: block.height,
+        time: block.time,
+    };
+    let yes = Votes {
+        yes: Uint128::new(100_000_000),
+        no: Uint128::zero(),
+        abstain: Uint128::zero(),
+    };
+    let no = Votes {
+        yes: Uint128::zero(),
+        no: Uint128::new(100_000_000),
+        abstain: Uint128::zero(),
+    };
+    let results = [
+        expected(Status::Executed, ResolutionReason::Executed, yes.clone()),
+        expected(
+            Status::ExecutionFailed,
+            ResolutionReason::ExecutionFailed,
+            yes,
+        ),
+        expected(Status::Closed, ResolutionReason::Rejected, no),
+    ];
+    for (id, result) in [executed, failed, rejected].iter().zip(results.iter()) {
+        assert_eq!(final_result(&app, *id).as_ref(), Some(result));
+    }
+
+    // Results are unchanged by a migration and later config changes.
+    app.update_block(next_block);
+    app.execute(
+        governance_addr.clone(),
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: govmod_single.to_string(),
+            new_code_id: govmod_id,
+            msg: to_binary(&MigrateMsg::FromCompatible {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    app.execute_contract(
+        governance_addr.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsoluteCount {
+                threshold: Uint128::new(1),
+            },
+            max_voting_period: Duration::Height(6),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            dao: governance_addr.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
+    for (id, result) in [executed, failed, rejected].iter().zip(results.iter()) {
+        assert_eq!(final_result(&app, *id).as_ref(), Some(result));
+    }
+}
+
+#[test]
+fn test_resolved_proposals_in_range() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let propose_and_vote = |app: &mut App, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposal_id: u64 = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalCount {})
+            .unwrap();
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+        proposal_id
+    };
+    // Executes passed proposals and closes rejected ones.
+    let resolve = |app: &mut App, vote: Vote| {
+        let proposal_id = propose_and_vote(app, vote);
+        let msg = match vote {
+            Vote::Yes => ExecuteMsg::Execute { proposal_id },
+            _ => ExecuteMsg::Close { proposal_id },
+        };
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap();
+        proposal_id
+    };
+    let in_range = |app: &App,
+                    from: Timestamp,
+                    to: Timestamp,
+                    start_after: Option<u64>,
+                    limit: Option<u64>|
+     -> Vec<(u64, Option<Timestamp>)> {
+        let res: ProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ResolvedProposalsInRange {
+                    from,
+                    to,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        res.proposals
+            .into_iter()
+            .map(|prop| (prop.id, prop.proposal.resolved_at))
+            .collect()
+    };
+    let advance = |app: &mut App| {
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(100);
+        })
+    };
+
+    let start = app.block_info().time;
+    let first = start.plus_seconds(100);
+    let second = start.plus_seconds(200);
+
+    let executed = resolve(&mut app, Vote::Yes);
+    // Passed now and executed in the first interval.
+    let passed = propose_and_vote(&mut app, Vote::Yes);
+    advance(&mut app);
+    let closed = resolve(&mut app, Vote::No);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute {
+            proposal_id: passed,
+        },
+        &[],
+    )
+    .unwrap();
+    advance(&mut app);
+    let late = resolve(&mut app, Vote::Yes);
+    // Not resolved, so never listed.
+    let unresolved = propose_and_vote(&mut app, Vote::Abstain);
+    let prop: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal {
+                proposal_id: unresolved,
+            },
+        )
+        .unwrap();
+    assert_eq!(prop.proposal.resolved_at, None);
+    // Nor may listing start after it.
+    app.wrap()
+        .query_wasm_smart::<ProposalListResponse>(
+            govmod_single.clone(),
+            &QueryMsg::ResolvedProposalsInRange {
+                from: start,
+                to: second,
+                start_after: Some(unresolved),
+                limit: None,
+            },
+        )
+        .unwrap_err();
+
+    // Proposals are listed by when they were resolved, not when they
+    // were created. Proposals resolved at the same time are ordered
+    // by ID.
+    assert_eq!(
+        in_range(&app, first, second, None, None),
+        vec![(passed, Some(first)), (closed, Some(first))]
+    );
+
+    // The range includes `from` and excludes `to`.
+    assert_eq!(
+        in_range(&app, start, first, None, None),
+        vec![(executed, Some(start))]
+    );
+    assert_eq!(
+        in_range(&app, second, second.plus_seconds(100), None, None),
+        vec![(late, Some(second))]
+    );
+    assert_eq!(in_range(&app, first, first, None, None), vec![]);
+
+    // Paginating through every resolved proposal.
+    let end = second.plus_seconds(100);
+    assert_eq!(
+        in_range(&app, start, end, None, Some(2)),
+        vec![(executed, Some(start)), (passed, Some(first))]
+    );
+    assert_eq!(
+        in_range(&app, start, end, Some(passed), Some(2)),
+        vec![(closed, Some(first)), (late, Some(second))]
+    );
+    assert_eq!(in_range(&app, start, end, Some(late), None), vec![]);
+
+    // Starting after a proposal resolved before the range starts at
+    // the beginning of the range.
+    assert_eq!(
+        in_range(&app, first, end, Some(executed), Some(1)),
+        vec![(passed, Some(first))]
+    );
+}
+
+#[test]
+fn test_migration_info() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, CONTRACT_VERSION).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MigrationInfo {}).unwrap();
+    let res: MigrationInfoResponse = from_slice(&res).unwrap();
+    assert_eq!(
+        res.info,
+        ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: CONTRACT_VERSION.to_string(),
+        }
+    );
+
+    // Every `MigrateMsg` variant is listed.
+    let msgs = [
+        MigrateMsg::FromV1 {
+            close_proposal_on_execution_failure: true,
+        },
+        MigrateMsg::FromCompatible {},
+        MigrateMsg::ConsolidateDuplicateBallots {},
+    ];
+    assert_eq!(res.supported_migrations.len(), msgs.len());
+    for msg in msgs {
+        let msg = String::from_utf8(to_vec(&msg).unwrap()).unwrap();
+        assert!(res
+            .supported_migrations
+            .iter()
+            .any(|m| msg.starts_with(&format!("{{\"{}\":", m.msg))));
+    }
+
+    // Migrating from the current version is always supported.
+    assert!(res
+        .supported_migrations
+        .iter()
+        .any(|m| m.from_versions.contains(&CONTRACT_VERSION.to_string())));
+}
+
+#[test]
+fn test_proposal_count_initialized_to_zero() {
+    let mut app = App::default();
+    let proposal_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        proposal_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "innactive".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let proposal_modules = gov_state.proposal_modules;
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+
+    let proposal_count: u64 = app
+        .wrap()
+        .query_wasm_smart(proposal_single, &QueryMsg::ProposalCount {})
+        .unwrap();
+    assert_eq!(proposal_count, 0);
 }
 
 #[test]
-fn test_execute_expired_proposal() {
+fn test_no_early_pass_with_min_duration() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
     let core_addr = instantiate_with_staked_balances_governance(
@@ -1849,13 +13416,44 @@ fn test_execute_expired_proposal() {
             threshold: Threshold::ThresholdQuorum {
                 threshold: PercentageThreshold::Majority {},
                 quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
             },
             max_voting_period: Duration::Height(10),
-            min_voting_period: None,
+            min_voting_period: Some(Duration::Height(2)),
             only_members_execute: true,
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
         },
         Some(vec![
             Cw20Coin {
@@ -1863,7 +13461,7 @@ fn test_execute_expired_proposal() {
                 amount: Uint128::new(10),
             },
             Cw20Coin {
-                address: "innactive".to_string(),
+                address: "wale".to_string(),
                 amount: Uint128::new(90),
             },
         ]),
@@ -1879,18 +13477,26 @@ fn test_execute_expired_proposal() {
     let proposal_single = proposal_modules.into_iter().next().unwrap().address;
 
     app.execute_contract(
-        Addr::unchecked("ekez"),
+        Addr::unchecked("wale"),
         proposal_single.clone(),
         &ExecuteMsg::Propose {
-            title: "This proposal will expire.".to_string(),
-            description: "What will happen?".to_string(),
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
     .unwrap();
+
+    // Wale votes yes which under normal curcumstances would cause the
+    // proposal to pass. Because there is a min duration it does not.
     app.execute_contract(
-        Addr::unchecked("ekez"),
+        Addr::unchecked("wale"),
         proposal_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
@@ -1900,20 +13506,6 @@ fn test_execute_expired_proposal() {
     )
     .unwrap();
 
-    // Proposal has now reached quorum but should not be passed.
-    let proposal: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            proposal_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
-        )
-        .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Open);
-
-    // Expire the proposal. It should now be passed as we had 100% yes
-    // votes inside the quorum.
-    app.update_block(|b| b.height += 10);
-
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
@@ -1921,386 +13513,658 @@ fn test_execute_expired_proposal() {
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Passed);
-
-    // Try to close the proposal. This should fail as the proposal is
-    // passed.
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_single.clone(),
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
-    )
-    .unwrap_err();
 
-    // Check that we can execute the proposal despite the fact that it
-    // is technically expired.
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
 
-    // Can't execute more than once.
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
-        &[],
-    )
-    .unwrap_err();
+    // Let the min voting period pass.
+    app.update_block(|b| b.height += 2);
 
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(proposal_single, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Executed);
+
+    assert_eq!(proposal.proposal.status, Status::Passed);
 }
 
 #[test]
-fn test_update_config() {
-    let (mut app, governance_addr) = do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::No,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
-        },
-        Status::Rejected,
-        None,
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: false,
-        }),
-    );
+fn test_min_period_status() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
-    let gov_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+    let mut setup = |min_voting_period: Option<Duration>| {
+        let core_addr = instantiate_with_staked_balances_governance(
+            &mut app,
+            govmod_id,
+            InstantiateMsg {
+                threshold: Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                    mode: ThresholdMode::MajorityOfNonAbstain {},
+                },
+                max_voting_period: Duration::Height(10),
+                min_voting_period,
+                only_members_execute: true,
+                allow_revoting: false,
+                deposit_info: None,
+                close_proposal_on_execution_failure: true,
+                exclude_dao_voting_power: false,
+                execution_callback: None,
+                require_second: None,
+                record_config_history: false,
+                quorum_reached_hooks: false,
+                require_power_at_resolution: false,
+                execution_retry_cooldown: None,
+                post_execution_cooldown: None,
+                quorum_extension: None,
+                native_propose_deposit: None,
+                snapshot_offset: 0,
+                pre_execution_hook: None,
+                required_description_sections: vec![],
+                voting_period_limit: None,
+                register_deposit_token: false,
+                total_power_override: None,
+                record_vote_history: false,
+                execute_on_pass: false,
+                reveal_period: None,
+                hook_allowlist: None,
+                revote_settle_period: None,
+                deposit_escalation: None,
+                forfeited_deposit_policy: None,
+                reject_failing_execution: false,
+                id_prefix: None,
+                non_voter_default: None,
+                max_proposal_bytes: None,
+                config_change_delay: None,
+                close_reward: None,
+                cache_voting_power: false,
+            },
+            Some(vec![
+                Cw20Coin {
+                    address: "ekez".to_string(),
+                    amount: Uint128::new(10),
+                },
+                Cw20Coin {
+                    address: "wale".to_string(),
+                    amount: Uint128::new(90),
+                },
+            ]),
+        );
+        let gov_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+            .unwrap();
+        gov_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address
+    };
+    let with_min = setup(Some(Duration::Height(2)));
+    let without_min = setup(None);
+    for module in [&with_min, &without_min].iter() {
+        app.execute_contract(
+            Addr::unchecked("wale"),
+            (*module).clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
         .unwrap();
-    let governance_modules = gov_state.proposal_modules;
-
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+    }
 
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
+    let min_period_status = |app: &App, module: &Addr| -> MinPeriodStatusResponse {
+        app.wrap()
+            .query_wasm_smart(module, &QueryMsg::MinPeriodStatus { proposal_id: 1 })
+            .unwrap()
+    };
 
+    // Proposals without a min voting period have no period to wait
+    // out.
     assert_eq!(
-        govmod_config.threshold,
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        min_period_status(&app, &without_min),
+        MinPeriodStatusResponse {
+            elapsed: true,
+            remaining: None,
+            would_pass: false,
         }
     );
 
-    let dao = govmod_config.dao;
-
-    // Attempt to update the config from a non-dao address. This
-    // should fail as it is unauthorized.
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        govmod_single.clone(),
-        &ExecuteMsg::UpdateConfig {
-            threshold: Threshold::AbsolutePercentage {
-                percentage: PercentageThreshold::Majority {},
-            },
-            max_voting_period: cw_utils::Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: false,
-            allow_revoting: false,
-            dao: CREATOR_ADDR.to_string(),
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
-        },
-        &[],
-    )
-    .unwrap_err();
+    // Still being decided.
+    assert_eq!(
+        min_period_status(&app, &with_min),
+        MinPeriodStatusResponse {
+            elapsed: false,
+            remaining: Some(Duration::Height(2)),
+            would_pass: false,
+        }
+    );
 
-    // Update the config from the DAO address. This should succede.
+    // Wale's vote decides the proposal, which waits out the min
+    // voting period.
     app.execute_contract(
-        dao.clone(),
-        govmod_single.clone(),
-        &ExecuteMsg::UpdateConfig {
-            threshold: Threshold::AbsolutePercentage {
-                percentage: PercentageThreshold::Majority {},
-            },
-            max_voting_period: cw_utils::Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: false,
-            allow_revoting: false,
-            dao: CREATOR_ADDR.to_string(),
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+        Addr::unchecked("wale"),
+        with_min.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        min_period_status(&app, &with_min),
+        MinPeriodStatusResponse {
+            elapsed: false,
+            remaining: Some(Duration::Height(1)),
+            would_pass: true,
+        }
+    );
 
-    let govmod_config: Config = app
+    // Once the period elapses the proposal passes.
+    app.update_block(next_block);
+    assert_eq!(
+        min_period_status(&app, &with_min),
+        MinPeriodStatusResponse {
+            elapsed: true,
+            remaining: None,
+            would_pass: false,
+        }
+    );
+    let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .query_wasm_smart(with_min, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+}
 
-    let expected = Config {
+#[test]
+fn test_proposal_config() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
         threshold: Threshold::AbsolutePercentage {
             percentage: PercentageThreshold::Majority {},
         },
-        max_voting_period: cw_utils::Duration::Height(10),
+        max_voting_period: Duration::Height(6),
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
-        dao: Addr::unchecked(CREATOR_ADDR),
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
-    assert_eq!(govmod_config, expected);
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    // As we have changed the DAO address updating the config using
-    // the original one should now fail.
-    app.execute_contract(
-        dao,
-        govmod_single,
-        &ExecuteMsg::UpdateConfig {
-            threshold: Threshold::AbsolutePercentage {
-                percentage: PercentageThreshold::Majority {},
+    let propose = |app: &mut App, msgs: Vec<CosmosMsg>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
             },
-            max_voting_period: cw_utils::Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: false,
-            allow_revoting: false,
-            dao: CREATOR_ADDR.to_string(),
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+            &[],
+        )
+        .unwrap();
+    };
+    let proposal_config = |app: &App, proposal_id: u64| {
+        let config: ProposalConfigResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ProposalConfig { proposal_id },
+            )
+            .unwrap();
+        config
+    };
+
+    // The DAO has no funds so executing this proposal fails.
+    propose(
+        &mut app,
+        vec![BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: vec![Coin::new(1, "ujuno")],
+        }
+        .into()],
+    );
+    let created = app.block_info();
+    let original = ProposalConfigResponse {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
-        &[],
-    )
-    .unwrap_err();
-}
+        total_power: Uint128::new(100_000_000),
+        snapshot_height: created.height,
+        expiration: Expiration::AtHeight(created.height + 6),
+        min_voting_period: None,
+        allow_revoting: false,
+        require_power_at_resolution: false,
+        close_proposal_on_execution_failure: true,
+        execution_retry_cooldown: None,
+        deposit_info: None,
+        native_deposit: None,
+        non_voter_default: None,
+    };
+    assert_eq!(proposal_config(&app, 1), original);
 
-#[test]
-fn test_no_return_if_no_refunds() {
-    let (mut app, governance_addr) = do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::No,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+    app.execute_contract(
+        governance_addr.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: Some(Duration::Height(2)),
+            only_members_execute: false,
+            allow_revoting: true,
+            dao: governance_addr.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: false,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: true,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
         },
-        Status::Rejected,
-        None,
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: false,
-        }),
-    );
-    let gov_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
-        .unwrap();
-    let governance_modules = gov_state.proposal_modules;
+        &[],
+    )
+    .unwrap();
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+    // The config change does not affect the existing proposal.
+    assert_eq!(proposal_config(&app, 1), original);
 
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let CheckedDepositInfo { token, .. } = govmod_config.deposit_info.unwrap();
+    propose(&mut app, vec![]);
+    let created = app.block_info();
+    assert_eq!(
+        proposal_config(&app, 2),
+        ProposalConfigResponse {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+            },
+            expiration: Expiration::AtHeight(created.height + 10),
+            min_voting_period: Some(Expiration::AtHeight(created.height + 2)),
+            allow_revoting: true,
+            require_power_at_resolution: true,
+            close_proposal_on_execution_failure: false,
+            ..original
+        }
+    );
 
-    // Close the proposal, this should cause the deposit to be
-    // refunded.
+    // The first proposal is still closed when its execution fails.
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        govmod_single,
-        &ExecuteMsg::Close { proposal_id: 1 },
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
         &[],
     )
     .unwrap();
-
-    let balance: cw20::BalanceResponse = app
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(
-            token,
-            &cw20::Cw20QueryMsg::Balance {
-                address: "ekez".to_string(),
-            },
-        )
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-
-    // Proposal has been closed but deposit has not been refunded.
-    assert_eq!(balance.balance, Uint128::new(9));
+    assert_eq!(proposal.proposal.status, Status::ExecutionFailed);
 }
 
 #[test]
-fn test_query_list_proposals() {
+#[should_panic(
+    expected = "min_voting_period and max_voting_period must have the same units (height or time)"
+)]
+fn test_min_duration_units_missmatch() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
-    let gov_addr = instantiate_with_cw20_balances_governance(
+    instantiate_with_staked_balances_governance(
         &mut app,
         govmod_id,
         InstantiateMsg {
             threshold: Threshold::ThresholdQuorum {
                 threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
             },
-            max_voting_period: cw_utils::Duration::Height(100),
-            min_voting_period: None,
+            max_voting_period: Duration::Height(10),
+            min_voting_period: Some(Duration::Time(2)),
             only_members_execute: true,
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
         },
-        Some(vec![Cw20Coin {
-            address: CREATOR_ADDR.to_string(),
-            amount: Uint128::new(100),
-        }]),
-    );
-
-    let gov_modules: Vec<ProposalModule> = app
-        .wrap()
-        .query_wasm_smart(
-            gov_addr,
-            &cw_core::msg::QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
             },
-        )
-        .unwrap();
-    assert_eq!(gov_modules.len(), 1);
-
-    let govmod = gov_modules.into_iter().next().unwrap().address;
-
-    for i in 1..10 {
-        app.execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod.clone(),
-            &ExecuteMsg::Propose {
-                title: format!("Text proposal {}.", i),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
+            Cw20Coin {
+                address: "wale".to_string(),
+                amount: Uint128::new(90),
             },
-            &[],
-        )
-        .unwrap();
-    }
+        ]),
+    );
+}
 
-    let proposals_forward: ProposalListResponse = app
-        .wrap()
-        .query_wasm_smart(
-            govmod.clone(),
-            &QueryMsg::ListProposals {
-                start_after: None,
-                limit: None,
+#[test]
+#[should_panic(expected = "Min voting period must be less than or equal to max voting period")]
+fn test_min_duration_larger_than_proposal_duration() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
             },
-        )
-        .unwrap();
-    let mut proposals_backward: ProposalListResponse = app
-        .wrap()
-        .query_wasm_smart(
-            govmod.clone(),
-            &QueryMsg::ReverseProposals {
-                start_before: None,
-                limit: None,
+            max_voting_period: Duration::Height(10),
+            min_voting_period: Some(Duration::Height(11)),
+            only_members_execute: true,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
             },
-        )
-        .unwrap();
-
-    proposals_backward.proposals.reverse();
-
-    assert_eq!(proposals_forward.proposals, proposals_backward.proposals);
+            Cw20Coin {
+                address: "wale".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
+}
 
-    let expected = ProposalResponse {
-        id: 1,
-        proposal: SingleChoiceProposal {
-            title: "Text proposal 1.".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            proposer: Addr::unchecked(CREATOR_ADDR),
-            start_height: app.block_info().height,
-            expiration: cw_utils::Expiration::AtHeight(app.block_info().height + 100),
-            min_voting_period: None,
+#[test]
+fn test_min_duration_same_as_proposal_duration() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let core_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        InstantiateMsg {
             threshold: Threshold::ThresholdQuorum {
                 threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
             },
+            max_voting_period: Duration::Time(10),
+            min_voting_period: Some(Duration::Time(10)),
+            only_members_execute: true,
             allow_revoting: false,
-            total_power: Uint128::new(100),
-            msgs: vec![],
-            status: Status::Open,
-            votes: Votes::zero(),
             deposit_info: None,
-            created: app.block_info().time,
-            last_updated: app.block_info().time,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
         },
-    };
-    assert_eq!(proposals_forward.proposals[0], expected);
+        Some(vec![
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "wale".to_string(),
+                amount: Uint128::new(90),
+            },
+        ]),
+    );
 
-    // Get proposals (3, 5]
-    let proposals_forward: ProposalListResponse = app
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(
-            govmod.clone(),
-            &QueryMsg::ListProposals {
-                start_after: Some(3),
-                limit: Some(2),
-            },
-        )
+        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let mut proposals_backward: ProposalListResponse = app
+    let proposal_modules = gov_state.proposal_modules;
+
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+
+    app.execute_contract(
+        Addr::unchecked("wale"),
+        proposal_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Wale votes yes which under normal curcumstances would cause the
+    // proposal to pass. Because there is a min duration it does not.
+    app.execute_contract(
+        Addr::unchecked("wale"),
+        proposal_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            govmod,
-            &QueryMsg::ReverseProposals {
-                start_before: Some(6),
-                limit: Some(2),
-            },
+            proposal_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
 
-    let expected = ProposalResponse {
-        id: 4,
-        proposal: SingleChoiceProposal {
-            title: "Text proposal 4.".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            proposer: Addr::unchecked(CREATOR_ADDR),
-            start_height: app.block_info().height,
-            expiration: cw_utils::Expiration::AtHeight(app.block_info().height + 100),
-            min_voting_period: None,
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
-            },
-            allow_revoting: false,
-            total_power: Uint128::new(100),
-            msgs: vec![],
-            status: Status::Open,
-            votes: Votes::zero(),
-            deposit_info: None,
-            created: app.block_info().time,
-            last_updated: app.block_info().time,
+    assert_eq!(proposal.proposal.status, Status::Open);
+
+    // ekez can vote no.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        proposal_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::No,
         },
-    };
-    assert_eq!(proposals_forward.proposals[0], expected);
-    assert_eq!(proposals_backward.proposals[1], expected);
+        &[],
+    )
+    .unwrap();
 
-    proposals_backward.proposals.reverse();
-    assert_eq!(proposals_forward.proposals, proposals_backward.proposals);
+    // Let the min voting period pass.
+    app.update_block(|b| b.time = b.time.plus_seconds(10));
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(proposal_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+
+    assert_eq!(proposal.proposal.status, Status::Passed);
 }
 
 #[test]
-fn test_hooks() {
+fn test_timestamp_updated() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
@@ -2316,10 +14180,54 @@ fn test_hooks() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
-    let governance_addr =
-        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: "voter".to_string(),
+                amount: Uint128::new(3),
+            },
+            Cw20Coin {
+                address: "voter2".to_string(),
+                amount: Uint128::new(2),
+            },
+        ]),
+    );
+
     let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
@@ -2331,406 +14239,347 @@ fn test_hooks() {
         )
         .unwrap();
 
-    assert_eq!(governance_modules.len(), 1);
     let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let dao = govmod_config.dao;
-
-    // Expect no hooks
-    let hooks: HooksResponse = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
-        .unwrap();
-    assert_eq!(hooks.hooks.len(), 0);
-
-    let hooks: HooksResponse = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
-        .unwrap();
-    assert_eq!(hooks.hooks.len(), 0);
-
-    let msg = ExecuteMsg::AddProposalHook {
-        address: "some_addr".to_string(),
-    };
-
-    // Expect error as sender is not DAO
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &msg,
-            &[],
-        )
-        .unwrap_err();
-
-    // Expect success as sender is now DAO
-    let _res = app
-        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
-        .unwrap();
-
-    let hooks: HooksResponse = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::ProposalHooks {})
-        .unwrap();
-    assert_eq!(hooks.hooks.len(), 1);
-
-    // Expect error as hook is already set
-    let _err = app
-        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
-        .unwrap_err();
-
-    // Expect error as hook does not exist
-    let _err = app
-        .execute_contract(
-            dao.clone(),
-            govmod_single.clone(),
-            &ExecuteMsg::RemoveProposalHook {
-                address: "not_exist".to_string(),
-            },
-            &[],
-        )
-        .unwrap_err();
-
-    let msg = ExecuteMsg::RemoveProposalHook {
-        address: "some_addr".to_string(),
-    };
-
-    // Expect error as sender is not DAO
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &msg,
-            &[],
-        )
-        .unwrap_err();
-
-    // Expect success
-    let _res = app
-        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
-        .unwrap();
-
-    let msg = ExecuteMsg::AddVoteHook {
-        address: "some_addr".to_string(),
-    };
-
-    // Expect error as sender is not DAO
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &msg,
-            &[],
-        )
-        .unwrap_err();
-
-    // Expect success as sender is now DAO
-    let _res = app
-        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
-        .unwrap();
-
-    let hooks: HooksResponse = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::VoteHooks {})
-        .unwrap();
-    assert_eq!(hooks.hooks.len(), 1);
-
-    // Expect error as hook is already set
-    let _err = app
-        .execute_contract(dao.clone(), govmod_single.clone(), &msg, &[])
-        .unwrap_err();
-
-    // Expect error as hook does not exist
-    let _err = app
-        .execute_contract(
-            dao.clone(),
-            govmod_single.clone(),
-            &ExecuteMsg::RemoveVoteHook {
-                address: "not_exist".to_string(),
-            },
-            &[],
-        )
-        .unwrap_err();
-
-    let msg = ExecuteMsg::RemoveVoteHook {
-        address: "some_addr".to_string(),
-    };
-
-    // Expect error as sender is not DAO
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &msg,
-            &[],
-        )
-        .unwrap_err();
-
-    // Expect success
-    let _res = app.execute_contract(dao, govmod_single, &msg, &[]).unwrap();
-}
-
-#[test]
-fn test_active_threshold_absolute() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(proposal_contract());
+    // Create 2 proposals.
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
-    let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
-        min_voting_period: None,
-        only_members_execute: false,
-        allow_revoting: false,
-        deposit_info: None,
-        close_proposal_on_execution_failure: true,
-    };
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    let governance_addr = instantiate_with_staking_active_threshold(
-        &mut app,
-        govmod_id,
-        instantiate,
-        None,
-        Some(ActiveThreshold::AbsoluteCount {
-            count: Uint128::new(100),
-        }),
-    );
-    let governance_modules: Vec<ProposalModule> = app
+    let created_1: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            governance_addr,
-            &cw_core::msg::QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
-            },
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
+    let current_block = app.block_info();
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+    // Verify created and last updated
+    assert_eq!(created_1.proposal.created, current_block.time);
+    assert_eq!(created_1.proposal.last_updated, current_block.time);
 
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let dao = govmod_config.dao;
-    let voting_module: Addr = app
-        .wrap()
-        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
-        .unwrap();
-    let staking_contract: Addr = app
+    let created_2: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            voting_module.clone(),
-            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
         )
         .unwrap();
-    let token_contract: Addr = app
+
+    // Verify created and last updated
+    assert_eq!(created_2.proposal.created, current_block.time);
+    assert_eq!(created_2.proposal.last_updated, current_block.time);
+
+    // Update block
+    let timestamp = Timestamp::from_seconds(300_000_000);
+    app.update_block(|block| block.time = timestamp);
+
+    // Vote on proposal
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Expect that last_updated changed because of status change
+    let updated: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::TokenContract {},
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
 
-    // Try and create a proposal, will fail as inactive
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
+    assert_eq!(updated.proposal.last_updated, app.block_info().time);
+    assert_eq!(updated.proposal.status, Status::Passed);
+
+    // Update block
+    let timestamp = Timestamp::from_seconds(500_000_000);
+    app.update_block(|block| block.time = timestamp);
+    let latest_time = app.block_info().time;
+
+    // Execute proposal
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Status should have changed to 'Executed'
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
             govmod_single.clone(),
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
-            },
-            &[],
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
-        .unwrap_err();
-
-    // Stake enough tokens
-    let msg = cw20::Cw20ExecuteMsg::Send {
-        contract: staking_contract.to_string(),
-        amount: Uint128::new(100),
-        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
-    };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), token_contract, &msg, &[])
         .unwrap();
-    app.update_block(next_block);
 
-    // Try and create a proposal, will now succeed as enough tokens are staked
-    let _res = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
+    assert_eq!(updated.proposal.last_updated, latest_time);
+    assert_eq!(updated.proposal.status, Status::Executed);
+
+    let timestamp = Timestamp::from_seconds(700_000_000);
+    app.update_block(|block| block.time = timestamp);
+    let latest_time = app.block_info().time;
+
+    // Vote no on second proposal
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: Vote::No,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Status should have changed to 'Rejected'
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
             govmod_single.clone(),
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
-            },
-            &[],
+            &QueryMsg::Proposal { proposal_id: 2 },
         )
         .unwrap();
 
-    // Unstake some tokens to make it inactive again
-    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
-        amount: Uint128::new(50),
-    };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
+    assert_eq!(updated.proposal.last_updated, latest_time);
+    assert_eq!(updated.proposal.status, Status::Rejected);
+
+    let timestamp = Timestamp::from_seconds(900_000_000);
+    app.update_block(|block| block.time = timestamp);
+    let latest_time = app.block_info().time;
+
+    // Close second proposal
+    app.execute_contract(
+        Addr::unchecked("voter"),
+        govmod_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 2 },
+        &[],
+    )
+    .unwrap();
+
+    // Status should have changed to 'Closed'
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 2 })
         .unwrap();
-    app.update_block(next_block);
 
-    // Try and create a proposal, will fail as no longer active
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single,
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
-            },
-            &[],
-        )
-        .unwrap_err();
+    assert_eq!(updated.proposal.last_updated, latest_time);
+    assert_eq!(updated.proposal.status, Status::Closed);
 }
 
 #[test]
-fn test_active_threshold_percent() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(proposal_contract());
+fn test_migrate_mock() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let current_block = &env.block;
+    let max_voting_period = cw_utils::Duration::Height(6);
 
     let threshold = Threshold::AbsolutePercentage {
         percentage: PercentageThreshold::Majority {},
     };
-    let max_voting_period = cw_utils::Duration::Height(6);
-    let instantiate = InstantiateMsg {
-        threshold,
+
+    // Write to storage in old data format
+    let v1_map: Map<u64, V1Proposal> = Map::new("proposals");
+    let v1_proposal = V1Proposal {
+        title: "A simple text proposal".to_string(),
+        description: "This is a simple text proposal".to_string(),
+        proposer: Addr::unchecked(CREATOR_ADDR),
+        start_height: env.block.height,
+        expiration: max_voting_period.after(current_block),
+        min_voting_period: None,
+        threshold: threshold.clone(),
+        allow_revoting: false,
+        total_power: Uint128::new(100_000_000),
+        msgs: vec![],
+        status: Status::Open,
+        votes: Votes::zero(),
+        deposit_info: None,
+    };
+    v1_map.save(&mut deps.storage, 0, &v1_proposal).unwrap();
+
+    let v1_item: Item<V1Config> = Item::new("config");
+    let v1_config = V1Config {
+        threshold: threshold.clone(),
         max_voting_period,
         min_voting_period: None,
-        only_members_execute: false,
+        only_members_execute: true,
         allow_revoting: false,
+        dao: Addr::unchecked("simple happy desert"),
         deposit_info: None,
+    };
+    v1_item.save(&mut deps.storage, &v1_config).unwrap();
+
+    let msg = MigrateMsg::FromV1 {
         close_proposal_on_execution_failure: true,
     };
+    migrate(deps.as_mut(), env.clone(), msg).unwrap();
+
+    // Verify migration.
+    let new_map: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
+    let proposals: Vec<(u64, SingleChoiceProposal)> = new_map
+        .range(&deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()
+        .unwrap();
+
+    let migrated_proposal = &proposals[0];
+    assert_eq!(migrated_proposal.0, 0);
+    assert_eq!(migrated_proposal.1.created, Timestamp::from_seconds(0));
+    assert_eq!(migrated_proposal.1.last_updated, env.block.time);
+    // The contracts that governed proposals before the migration are
+    // not known.
+    assert_eq!(migrated_proposal.1.voting_module_code_id, None);
+    assert_eq!(migrated_proposal.1.module_version, None);
+
+    let new_item: Item<Config> = Item::new("config_v2");
+    let migrated_config = new_item.load(&deps.storage).unwrap();
+    assert_eq!(
+        migrated_config,
+        Config {
+            threshold,
+            max_voting_period,
+            min_voting_period: None,
+            only_members_execute: true,
+            allow_revoting: false,
+            dao: Addr::unchecked("simple happy desert"),
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: VotingPeriodLimit::default(),
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        }
+    );
+}
 
-    // 20% needed to be active, 20% of 100000000 is 20000000
-    let governance_addr = instantiate_with_staking_active_threshold(
-        &mut app,
-        govmod_id,
-        instantiate,
-        None,
-        Some(ActiveThreshold::Percentage {
-            percent: Decimal::percent(20),
+#[test]
+fn test_return_deposit_to_dao_on_proposal_failure() {
+    let (mut app, core_addr) = do_test_votes_cw20_balances(
+        vec![TestSingleChoiceVote {
+            voter: "ekez".to_string(),
+            position: Vote::No,
+            weight: Uint128::new(10),
+            should_execute: ShouldExecute::Yes,
+        }],
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
+        },
+        Status::Open,
+        Some(Uint128::new(100)),
+        Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            refund_failed_proposals: false,
         }),
     );
-    let governance_modules: Vec<ProposalModule> = app
+
+    let core_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(
-            governance_addr,
-            &cw_core::msg::QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
-            },
-        )
+        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
+    let proposal_modules = core_state.proposal_modules;
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
 
-    let govmod_config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let dao = govmod_config.dao;
-    let voting_module: Addr = app
-        .wrap()
-        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
-        .unwrap();
-    let staking_contract: Addr = app
+    // Make the proposal expire. It has now failed.
+    app.update_block(|block| block.height += 10);
+
+    // Close the proposal, this should work as the proposal is now
+    // open and expired.
+    app.execute_contract(
+        Addr::unchecked("keze"),
+        proposal_single.clone(),
+        &ExecuteMsg::Close { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    // Check that a refund was issued.
+    let proposal_config: Config = app
         .wrap()
-        .query_wasm_smart(
-            voting_module.clone(),
-            &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
-        )
+        .query_wasm_smart(proposal_single, &QueryMsg::Config {})
         .unwrap();
-    let token_contract: Addr = app
+    let CheckedDepositInfo { token, .. } = proposal_config.deposit_info.unwrap();
+    let balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            voting_module,
-            &cw_core_interface::voting::Query::TokenContract {},
-        )
-        .unwrap();
-
-    // Try and create a proposal, will fail as inactive
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
-            },
-            &[],
-        )
-        .unwrap_err();
-
-    // Stake enough tokens
-    let msg = cw20::Cw20ExecuteMsg::Send {
-        contract: staking_contract.to_string(),
-        amount: Uint128::new(20000000),
-        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
-    };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), token_contract, &msg, &[])
-        .unwrap();
-    app.update_block(next_block);
-
-    // Try and create a proposal, will now succeed as enough tokens are staked
-    let _res = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: core_addr.into_string(),
             },
-            &[],
         )
         .unwrap();
 
-    // Unstake some tokens to make it inactive again
-    let msg = cw20_stake::msg::ExecuteMsg::Unstake {
-        amount: Uint128::new(1000),
-    };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), staking_contract, &msg, &[])
-        .unwrap();
-    app.update_block(next_block);
-
-    // Try and create a proposal, will fail as no longer active
-    let _err = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single,
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
-            },
-            &[],
-        )
-        .unwrap_err();
+    // Deposit should now belong to the DAO.
+    assert_eq!(balance.balance, Uint128::new(1));
 }
 
 #[test]
-fn test_active_threshold_none() {
+fn test_close_failed_proposal() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
@@ -2746,6 +14595,36 @@ fn test_active_threshold_none() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
     let governance_addr =
@@ -2794,1174 +14673,2551 @@ fn test_active_threshold_none() {
         amount: Uint128::new(2000),
         msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
     };
-    app.execute_contract(Addr::unchecked(CREATOR_ADDR), token_contract, &msg, &[])
-        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
     app.update_block(next_block);
 
-    // Try and create a proposal, will succeed as no threshold
-    let _res = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single,
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
-            },
-            &[],
-        )
-        .unwrap();
-
-    // Now try with balance voting to test when IsActive is not implemented
-    // on the contract
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
-    let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
-        min_voting_period: None,
-        only_members_execute: false,
-        allow_revoting: false,
-        deposit_info: None,
-        close_proposal_on_execution_failure: true,
+    let msg = cw20::Cw20ExecuteMsg::Burn {
+        amount: Uint128::new(2000),
     };
+    let binary_msg = to_binary(&msg).unwrap();
 
-    let governance_addr =
-        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
-    let governance_modules: Vec<ProposalModule> = app
+    // Overburn tokens
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple burn tokens proposal".to_string(),
+            description: "Burning more tokens, than dao treasury have".to_string(),
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: token_contract.to_string(),
+                msg: binary_msg.clone(),
+                funds: vec![],
+            }
+            .into()],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Vote on proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let timestamp = Timestamp::from_seconds(300_000_000);
+    app.update_block(|block| block.time = timestamp);
+
+    // Execute proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let failed: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            governance_addr,
-            &cw_core::msg::QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
+    assert_eq!(failed.proposal.last_updated, app.block_info().time);
+
+    // With disabled feature
+    // Disable feature first
+    {
+        let original: Config = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "Disable closing failed proposals".to_string(),
+                description: "We want to re-execute failed proposals".to_string(),
+                msgs: vec![WasmMsg::Execute {
+                    contract_addr: govmod_single.to_string(),
+                    msg: to_binary(&ExecuteMsg::UpdateConfig {
+                        threshold: original.threshold,
+                        max_voting_period: original.max_voting_period,
+                        min_voting_period: original.min_voting_period,
+                        only_members_execute: original.only_members_execute,
+                        allow_revoting: original.allow_revoting,
+                        dao: original.dao.to_string(),
+                        deposit_info: None,
+                        close_proposal_on_execution_failure: false,
+                        exclude_dao_voting_power: false,
+                        execution_callback: None,
+                        require_second: None,
+                        record_config_history: false,
+                        quorum_reached_hooks: false,
+                        require_power_at_resolution: false,
+                        execution_retry_cooldown: None,
+                        post_execution_cooldown: None,
+                        quorum_extension: None,
+                        native_propose_deposit: None,
+                        snapshot_offset: 0,
+                        pre_execution_hook: None,
+                        required_description_sections: vec![],
+                        voting_period_limit: None,
+                        register_deposit_token: false,
+                        total_power_override: None,
+                        record_vote_history: false,
+                        execute_on_pass: false,
+                        reveal_period: None,
+                        hook_allowlist: None,
+                        revote_settle_period: None,
+                        deposit_escalation: None,
+                        forfeited_deposit_policy: None,
+                        reject_failing_execution: false,
+                        non_voter_default: None,
+                        max_proposal_bytes: None,
+                        config_change_delay: None,
+                        close_reward: None,
+                        cache_voting_power: false,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }
+                .into()],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
             },
+            &[],
         )
         .unwrap();
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    // Try and create a proposal, will succeed as IsActive is not implemented
-    let _res = app
-        .execute_contract(
+        // Vote on proposal
+        app.execute_contract(
             Addr::unchecked(CREATOR_ADDR),
-            govmod_single,
-            &crate::msg::ExecuteMsg::Propose {
-                title: "A simple text proposal".to_string(),
-                description: "This is a simple text proposal".to_string(),
-                msgs: vec![],
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 2,
+                vote: Vote::Yes,
             },
             &[],
         )
         .unwrap();
-}
-
-/// Simple test for revoting.
-#[test]
-fn test_revoting() {
-    let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: true,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
-        },
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(90),
-            },
-            Cw20Coin {
-                address: "slarbibfast".to_string(),
-                amount: Uint128::new(10),
-            },
-        ]),
-    );
 
-    let core_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        // Execute proposal
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 2 },
+            &[],
+        )
         .unwrap();
-    let proposal_module = core_state
-        .proposal_modules
-        .into_iter()
-        .next()
-        .unwrap()
-        .address;
+    }
 
-    // The supreme galatic floob rules over many DAOs with benevolance
-    // and grace. The people of floob have become complacent in the
-    // goodness of the floob.
+    // Overburn tokens (again), this time without reverting
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Propose {
-            title: "Supreme galactic floob.".to_string(),
-            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
-            msgs: vec![],
+            title: "A simple burn tokens proposal".to_string(),
+            description: "Burning more tokens, than dao treasury have".to_string(),
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: token_contract.to_string(),
+                msg: binary_msg,
+                funds: vec![],
+            }
+            .into()],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
     .unwrap();
 
-    // The people initially jump at the chance to recognize the supreme
-    // galactic floob!
+    // Vote on proposal
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Vote {
-            proposal_id: 1,
+            proposal_id: 3,
             vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
 
-    // The wise slarbibfast does not agree. After some digging they
-    // discover that the floob has been bugging the hotel rooms of
-    // political rivals.
+    // Execute proposal
     app.execute_contract(
-        Addr::unchecked("slarbibfast"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
-        },
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 3 },
         &[],
     )
-    .unwrap();
+    .expect_err("Should be sub overflow");
 
-    // Time passes.
-    app.update_block(|b| b.height += 5);
+    // Status should still be passed
+    let updated: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 3 })
+        .unwrap();
 
-    // Word spreads.
-    app.update_block(|b| b.height += 4);
+    // not reverted
+    assert_eq!(updated.proposal.status, Status::Passed);
+}
 
-    // At the last moment the people realize their mistake.
+#[test]
+fn test_execution_retry_cooldown() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: false,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: Some(Duration::Height(5)),
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate.clone(), None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // The DAO has no funds so this will fail until it does.
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "Pay ekez".to_string(),
+            description: "Send ekez some ujuno".to_string(),
+            msgs: vec![BankMsg::Send {
+                to_address: "ekez".to_string(),
+                amount: vec![Coin::new(10, "ujuno")],
+            }
+            .into()],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
-            vote: Vote::No,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
 
+    let execute = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+    };
+
+    // The failed attempt is recorded and the proposal stays passed.
+    execute(&mut app).unwrap();
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_module.clone(),
+            govmod_single.clone(),
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Open);
-    assert_eq!(
-        proposal.proposal.votes,
-        Votes {
-            yes: Uint128::zero(),
-            no: Uint128::new(100),
-            abstain: Uint128::zero()
-        }
-    );
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    let attempt = proposal.proposal.last_execution_attempt.unwrap();
+    assert_eq!(attempt.height, app.block_info().height);
+
+    // The DAO may only reject failing proposals if
+    // `reject_failing_execution` is set.
+    let err: ContractError = app
+        .execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::RejectFailingProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::RejectFailingExecutionDisabled {}
+    ));
+
+    // Removing the cooldown from the config does not change the
+    // cooldown of the existing proposal.
+    let msg = instantiate;
+    app.execute_contract(
+        governance_addr.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: msg.threshold,
+            max_voting_period: msg.max_voting_period,
+            min_voting_period: msg.min_voting_period,
+            only_members_execute: msg.only_members_execute,
+            allow_revoting: msg.allow_revoting,
+            dao: governance_addr.to_string(),
+            deposit_info: msg.deposit_info,
+            close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: msg.exclude_dao_voting_power,
+            execution_callback: msg.execution_callback,
+            require_second: msg.require_second,
+            record_config_history: msg.record_config_history,
+            quorum_reached_hooks: msg.quorum_reached_hooks,
+            require_power_at_resolution: msg.require_power_at_resolution,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: msg.post_execution_cooldown,
+            quorum_extension: msg.quorum_extension,
+            native_propose_deposit: msg.native_propose_deposit,
+            snapshot_offset: msg.snapshot_offset,
+            pre_execution_hook: msg.pre_execution_hook,
+            required_description_sections: msg.required_description_sections,
+            voting_period_limit: msg.voting_period_limit,
+            register_deposit_token: msg.register_deposit_token,
+            total_power_override: msg.total_power_override,
+            record_vote_history: msg.record_vote_history,
+            execute_on_pass: msg.execute_on_pass,
+            reveal_period: msg.reveal_period,
+            hook_allowlist: msg.hook_allowlist,
+            revote_settle_period: msg.revote_settle_period,
+            deposit_escalation: msg.deposit_escalation,
+            forfeited_deposit_policy: msg.forfeited_deposit_policy,
+            reject_failing_execution: msg.reject_failing_execution,
+            non_voter_default: msg.non_voter_default,
+            max_proposal_bytes: msg.max_proposal_bytes,
+            config_change_delay: msg.config_change_delay,
+            close_reward: msg.close_reward,
+            cache_voting_power: msg.cache_voting_power,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Retrying immediately is blocked.
+    let err: ContractError = execute(&mut app).unwrap_err().downcast().unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ExecutionCooldown {
+            retry_at: Expiration::AtHeight(height)
+        } if height == attempt.height + 5
+    ));
+
+    app.update_block(|block| block.height += 4);
+    let err: ContractError = execute(&mut app).unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::ExecutionCooldown { .. }));
+
+    // Once the cooldown has passed the proposal may be retried.
+    app.update_block(next_block);
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: governance_addr.to_string(),
+        amount: vec![Coin::new(10, "ujuno")],
+    }))
+    .unwrap();
+    execute(&mut app).unwrap();
 
-    // As the clock strikes midnight on the last day of the proposal,
-    // revoting has saved the day!
-    app.update_block(|b| b.height += 1);
     let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Rejected);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    let balance = app.wrap().query_balance("ekez", "ujuno").unwrap();
+    assert_eq!(balance.amount, Uint128::new(10));
 }
 
-/// Tests that revoting is stored at a per-proposal level. Proposals
-/// created while revoting is enabled should not have it disabled if a
-/// config change turns if off.
 #[test]
-fn test_allow_revoting_config_changes() {
+fn test_post_execution_cooldown() {
     let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: true,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: Some(Duration::Height(3)),
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
         Some(vec![
             Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(90),
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(100),
             },
             Cw20Coin {
-                address: "slarbibfast".to_string(),
-                amount: Uint128::new(10),
+                address: "ekez".to_string(),
+                amount: Uint128::new(50),
             },
         ]),
     );
-
-    let core_state: cw_core::query::DumpStateResponse = app
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let proposal_module = core_state
+    let govmod_single = gov_state
         .proposal_modules
         .into_iter()
         .next()
         .unwrap()
         .address;
 
-    // Create a proposal. This proposal should allow revoting.
+    let propose = |app: &mut App, proposer: &str| {
+        app.execute_contract(
+            Addr::unchecked(proposer),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+    };
+
+    // Creating a proposal does not start a cooldown.
+    propose(&mut app, CREATOR_ADDR).unwrap();
+    propose(&mut app, CREATOR_ADDR).unwrap();
+
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "Supreme galactic floob.".to_string(),
-            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
-            msgs: vec![],
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    let executed_at = app.block_info().height;
 
-    // Disable revoting.
+    // The proposer is blocked from proposing until the cooldown
+    // ends.
+    let err: ContractError = propose(&mut app, CREATOR_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ProposeCooldown {
+            until: Expiration::AtHeight(height)
+        } if height == executed_at + 3
+    ));
+
+    // Other members are unaffected.
+    propose(&mut app, "ekez").unwrap();
+
+    app.update_block(|block| block.height += 2);
+    let err: ContractError = propose(&mut app, CREATOR_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ProposeCooldown { .. }));
+
+    app.update_block(next_block);
+    propose(&mut app, CREATOR_ADDR).unwrap();
+
+    // Proposals created before the cooldown started are unaffected.
     app.execute_contract(
-        core_addr.clone(),
-        proposal_module.clone(),
-        &ExecuteMsg::UpdateConfig {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            dao: core_addr.to_string(),
-            close_proposal_on_execution_failure: true,
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Vote {
+            proposal_id: 2,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
-
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_module.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
         )
         .unwrap();
-    // The first created proposal should still allow revoting.
-    assert!(proposal.proposal.allow_revoting);
+    assert_eq!(proposal.proposal.status, Status::Passed);
+
+    // The cooldown applies to the proposal's original proposer even
+    // once its authorship has been transferred.
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::TransferProposalAuthorship {
+            proposal_id: 2,
+            new_proposer: "ekez".to_string(),
         },
         &[],
     )
     .unwrap();
-
     app.execute_contract(
         Addr::unchecked("ekez"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 2 },
         &[],
     )
     .unwrap();
+    let err: ContractError = propose(&mut app, CREATOR_ADDR)
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ProposeCooldown { .. }));
+    propose(&mut app, "ekez").unwrap();
+}
 
-    // New proposals should not allow revoting.
+#[test]
+fn test_executable_by_address() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: true,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(50),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    // Proposal 1 passes, 2 stays open, 3 is executed, and 4 passes.
+    for id in 1..=4 {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        if id != 2 {
+            app.execute_contract(
+                Addr::unchecked(CREATOR_ADDR),
+                govmod_single.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id: id,
+                    vote: Vote::Yes,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+    }
     app.execute_contract(
         Addr::unchecked("ekez"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "Supreme galactic floob.".to_string(),
-            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
-            msgs: vec![],
-        },
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 3 },
         &[],
     )
     .unwrap();
 
+    let executable = |app: &App, address: &str, start_after: Option<u64>, limit: Option<u64>| {
+        let res: FilteredProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ExecutableByAddress {
+                    address: address.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap();
+        (
+            res.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+            res.last_examined,
+        )
+    };
+
+    assert_eq!(
+        executable(&app, CREATOR_ADDR, None, None),
+        (vec![1, 4], Some(4))
+    );
+    assert_eq!(executable(&app, "ekez", None, None), (vec![1, 4], Some(4)));
+    assert_eq!(executable(&app, "ekez", None, Some(1)), (vec![1], Some(1)));
+    // The limit bounds the proposals examined, so pages may be empty
+    // while executable proposals remain.
+    assert_eq!(
+        executable(&app, "ekez", Some(1), Some(2)),
+        (vec![], Some(3))
+    );
+    assert_eq!(
+        executable(&app, "ekez", Some(3), Some(2)),
+        (vec![4], Some(4))
+    );
+    assert_eq!(executable(&app, "ekez", Some(4), None), (vec![], None));
+    // Only members may execute proposals.
+    assert_eq!(executable(&app, "stranger", None, None), (vec![], None));
+
+    // Everything the query lists can be executed by that address.
     app.execute_contract(
-        Addr::unchecked("slarbibfast"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 2,
-            vote: Vote::No,
-        },
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
         &[],
     )
     .unwrap();
+    assert_eq!(executable(&app, "ekez", None, None), (vec![4], Some(4)));
+}
 
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked("slarbibfast"),
-            proposal_module,
-            &ExecuteMsg::Vote {
-                proposal_id: 2,
-                vote: Vote::Yes,
+#[test]
+fn test_quorum_extension() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: false,
+        // Proposals stay open until they expire so that every vote
+        // below is cast on an open proposal.
+        allow_revoting: true,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: Some(QuorumExtension {
+            window: Duration::Height(3),
+            extension: Duration::Height(5),
+            max_extensions: 2,
+        }),
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let balances = [("a", 30), ("b", 30), ("c", 20), ("d", 10), ("e", 10)];
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(
+            balances
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount: Uint128::new(*amount),
+                })
+                .collect(),
+        ),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("a"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
             },
             &[],
         )
-        .unwrap_err()
-        .downcast()
         .unwrap();
+    };
+    let vote = |app: &mut App, proposal_id: u64, voter: &str, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap()
+    };
+    let query_proposal = |app: &App, proposal_id: u64| {
+        let res: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        res.proposal
+    };
 
-    assert!(matches!(err, ContractError::AlreadyVoted {}))
+    let start = app.block_info().height;
+    propose(&mut app);
+    propose(&mut app);
+
+    // Proposal 2 reaches quorum well before expiring and is not
+    // extended, even when later votes are cast close to expiration.
+    vote(&mut app, 2, "a", Vote::No);
+    vote(&mut app, 2, "b", Vote::Yes);
+    app.update_block(|block| block.height += 8);
+    vote(&mut app, 2, "d", Vote::No);
+    let proposal = query_proposal(&app, 2);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 10));
+    assert_eq!(proposal.extensions, 0);
+
+    // Votes close to expiration that do not reach quorum do not
+    // extend voting.
+    vote(&mut app, 1, "a", Vote::No);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 10));
+
+    // Proposal 1 first reaches quorum two blocks before it expires.
+    let res = vote(&mut app, 1, "b", Vote::Yes);
+    assert!(res.events.iter().any(|e| e.ty == "wasm-voting_extended"));
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.status, Status::Open);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 15));
+    assert_eq!(proposal.extensions, 1);
+
+    // Votes outside of the window of the new expiration do not
+    // extend it.
+    app.update_block(|block| block.height += 1);
+    vote(&mut app, 1, "c", Vote::Abstain);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 15));
+
+    // Once extended, votes close to the new expiration extend it
+    // again.
+    app.update_block(|block| block.height += 3);
+    vote(&mut app, 1, "d", Vote::No);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 20));
+    assert_eq!(proposal.extensions, 2);
+
+    // The number of extensions is capped.
+    app.update_block(|block| block.height += 5);
+    vote(&mut app, 1, "e", Vote::Yes);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.expiration, Expiration::AtHeight(start + 20));
+    assert_eq!(proposal.extensions, 2);
+
+    app.update_block(|block| block.height += 2);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.status, Status::Open);
+    app.update_block(next_block);
+    let proposal = query_proposal(&app, 1);
+    assert_eq!(proposal.status, Status::Rejected);
+
+    // The extension must use the same units as proposal
+    // expirations.
+    let info = MessageInfo {
+        sender: Addr::unchecked(CREATOR_ADDR),
+        funds: vec![],
+    };
+    let msg = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: Some(QuorumExtension {
+            window: Duration::Time(60),
+            extension: Duration::Height(5),
+            max_extensions: 1,
+        }),
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let err = crate::contract::instantiate(
+        mock_dependencies().as_mut(),
+        mock_env(),
+        info.clone(),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::QuorumExtensionUnitsConflict {}
+    ));
+
+    // Thresholds without a quorum are never extended, so an
+    // extension may not be configured with them.
+    let err = crate::contract::instantiate(
+        mock_dependencies().as_mut(),
+        mock_env(),
+        info,
+        InstantiateMsg {
+            quorum_extension: Some(QuorumExtension {
+                window: Duration::Height(2),
+                extension: Duration::Height(5),
+                max_extensions: 1,
+            }),
+            ..msg
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::QuorumExtensionWithoutQuorum {}
+    ));
 }
 
-/// Tests that we error if a revote casts the same vote as the
-/// previous vote.
 #[test]
-fn test_revoting_same_vote_twice() {
+fn test_threshold_override() {
     let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: true,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
         Some(vec![
             Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(90),
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(60),
             },
             Cw20Coin {
-                address: "slarbibfast".to_string(),
-                amount: Uint128::new(10),
+                address: "ekez".to_string(),
+                amount: Uint128::new(40),
             },
         ]),
     );
-
-    let core_state: cw_core::query::DumpStateResponse = app
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let proposal_module = core_state
+    let govmod_single = gov_state
         .proposal_modules
         .into_iter()
         .next()
         .unwrap()
         .address;
 
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "Supreme galactic floob.".to_string(),
-            description: "Recognize the supreme galactic floob as our DAO leader.".to_string(),
-            msgs: vec![],
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
-
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked("ekez"),
-            proposal_module.clone(),
-            &ExecuteMsg::Vote {
-                proposal_id: 1,
-                vote: Vote::Yes,
-            },
-            &[],
-        )
-        .unwrap_err()
-        .downcast()
-        .unwrap();
-
-    // Can't cast the same vote twice.
-    assert!(matches!(err, ContractError::AlreadyCast {}));
-
-    // Casting a different vote is fine. You can do that as many times
-    // as you'd like.
-    for _ in 1..5 {
+    let propose = |app: &mut App, threshold_override: Option<Threshold>| {
         app.execute_contract(
-            Addr::unchecked("ekez"),
-            proposal_module.clone(),
-            &ExecuteMsg::Vote {
-                proposal_id: 1,
-                vote: Vote::No,
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "Amend the constitution".to_string(),
+                description: "This is a constitutional change".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override,
+                metadata: None,
             },
             &[],
         )
-        .unwrap();
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64| {
         app.execute_contract(
-            Addr::unchecked("ekez"),
-            proposal_module.clone(),
+            Addr::unchecked(voter),
+            govmod_single.clone(),
             &ExecuteMsg::Vote {
-                proposal_id: 1,
+                proposal_id,
                 vote: Vote::Yes,
             },
             &[],
         )
         .unwrap();
-    }
+    };
+    let query_proposal = |app: &App, proposal_id: u64| {
+        let res: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Proposal { proposal_id })
+            .unwrap();
+        res.proposal
+    };
+
+    // Overrides may not weaken the module's threshold.
+    let err: ContractError = propose(
+        &mut app,
+        Some(Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(50)),
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ThresholdOverrideNotStricter {}
+    ));
+
+    // Or change its kind, as different kinds are not comparable.
+    let err: ContractError = propose(
+        &mut app,
+        Some(Threshold::AbsoluteCount {
+            threshold: Uint128::new(100),
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ThresholdOverrideNotStricter {}
+    ));
+
+    // And must be valid thresholds.
+    let err: ContractError = propose(
+        &mut app,
+        Some(Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(101)),
+        }),
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::ThresholdError(ThresholdError::UnreachableThreshold {})
+    ));
+
+    let supermajority = Threshold::AbsolutePercentage {
+        percentage: PercentageThreshold::Percent(Decimal::percent(67)),
+    };
+    propose(&mut app, None).unwrap();
+    propose(&mut app, Some(supermajority.clone())).unwrap();
+
+    let proposal = query_proposal(&app, 2);
+    assert_eq!(proposal.threshold, supermajority);
+
+    // A majority passes the first proposal but not the second.
+    vote(&mut app, CREATOR_ADDR, 1);
+    vote(&mut app, CREATOR_ADDR, 2);
+    assert_eq!(query_proposal(&app, 1).status, Status::Passed);
+    assert_eq!(query_proposal(&app, 2).status, Status::Open);
+
+    // A supermajority passes the second.
+    vote(&mut app, "ekez", 2);
+    assert_eq!(query_proposal(&app, 2).status, Status::Passed);
 }
 
-/// Tests a simple three of five multisig configuration.
+/// Non-voters are counted as the module's `non_voter_default` when
+/// checking the threshold of an expired proposal.
 #[test]
-fn test_three_of_five_multisig() {
-    let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_cw4_groups_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::AbsoluteCount {
-                threshold: Uint128::new(3),
+fn test_non_voter_default() {
+    let outcome = |non_voter_default: Option<Vote>| -> Status {
+        let mut app = App::default();
+        let govmod_id = app.store_code(proposal_contract());
+        let instantiate = InstantiateMsg {
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
             },
-            max_voting_period: Duration::Height(10),
+            max_voting_period: Duration::Height(6),
             min_voting_period: None,
-            only_members_execute: true,
+            only_members_execute: false,
             allow_revoting: false,
             deposit_info: None,
             close_proposal_on_execution_failure: true,
-        },
-        Some(vec![
-            Cw20Coin {
-                address: "one".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "two".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "three".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "four".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "five".to_string(),
-                amount: Uint128::new(1),
-            },
-        ]),
-    );
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        };
+        let governance_addr = instantiate_with_cw20_balances_governance(
+            &mut app,
+            govmod_id,
+            instantiate,
+            Some(vec![
+                Cw20Coin {
+                    address: "ekez".to_string(),
+                    amount: Uint128::new(30),
+                },
+                Cw20Coin {
+                    address: "keze".to_string(),
+                    amount: Uint128::new(10),
+                },
+                Cw20Coin {
+                    address: "zeke".to_string(),
+                    amount: Uint128::new(60),
+                },
+            ]),
+        );
+        let gov_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+            .unwrap();
+        let govmod_single = gov_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address;
 
-    let core_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
         .unwrap();
-    let proposal_module = core_state
-        .proposal_modules
-        .into_iter()
-        .next()
-        .unwrap()
-        .address;
+        for (voter, vote) in [("ekez", Vote::Yes), ("keze", Vote::No)] {
+            app.execute_contract(
+                Addr::unchecked(voter),
+                govmod_single.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote,
+                },
+                &[],
+            )
+            .unwrap();
+        }
 
-    app.execute_contract(
-        Addr::unchecked("one"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "Propose a thing.".to_string(),
-            description: "Do the thing.".to_string(),
-            msgs: vec![],
-        },
-        &[],
-    )
-    .unwrap();
+        // The non-voter's 60% of the voting power is uncast when
+        // the proposal expires.
+        app.update_block(|block| block.height += 10);
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+            .unwrap();
+        proposal.proposal.status
+    };
 
-    app.execute_contract(
-        Addr::unchecked("one"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
-    app.execute_contract(
-        Addr::unchecked("two"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
+    // 30 of the 40 yes and no votes cast are yes.
+    assert_eq!(outcome(None), Status::Passed);
+    // 30 of 100 once the non-voter's 60 are counted as no.
+    assert_eq!(outcome(Some(Vote::No)), Status::Rejected);
+    // Abstaining non-voters are left out of the threshold.
+    assert_eq!(outcome(Some(Vote::Abstain)), Status::Passed);
+}
 
-    // Make sure it doesn't pass early.
-    let proposal: ProposalResponse = app
+#[test]
+fn test_max_proposal_bytes() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: Some(1),
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate.clone(), None);
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_module.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
         )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Open);
-
-    app.execute_contract(
-        Addr::unchecked("three"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    let proposal: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            proposal_module.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+    let propose = |app: &mut App| -> Result<(), ContractError> {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
         )
-        .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Passed);
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    };
+    let set_max = |app: &mut App, max_proposal_bytes: Option<u64>| -> Result<(), ContractError> {
+        let msg = instantiate.clone();
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::UpdateConfig {
+                threshold: msg.threshold,
+                max_voting_period: msg.max_voting_period,
+                min_voting_period: msg.min_voting_period,
+                only_members_execute: msg.only_members_execute,
+                allow_revoting: msg.allow_revoting,
+                dao: governance_addr.to_string(),
+                deposit_info: msg.deposit_info,
+                close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+                exclude_dao_voting_power: msg.exclude_dao_voting_power,
+                execution_callback: msg.execution_callback,
+                require_second: msg.require_second,
+                record_config_history: msg.record_config_history,
+                quorum_reached_hooks: msg.quorum_reached_hooks,
+                require_power_at_resolution: msg.require_power_at_resolution,
+                execution_retry_cooldown: msg.execution_retry_cooldown,
+                post_execution_cooldown: msg.post_execution_cooldown,
+                quorum_extension: msg.quorum_extension,
+                native_propose_deposit: msg.native_propose_deposit,
+                snapshot_offset: msg.snapshot_offset,
+                pre_execution_hook: msg.pre_execution_hook,
+                required_description_sections: msg.required_description_sections,
+                voting_period_limit: msg.voting_period_limit,
+                register_deposit_token: msg.register_deposit_token,
+                total_power_override: msg.total_power_override,
+                record_vote_history: msg.record_vote_history,
+                execute_on_pass: msg.execute_on_pass,
+                reveal_period: msg.reveal_period,
+                hook_allowlist: msg.hook_allowlist,
+                revote_settle_period: msg.revote_settle_period,
+                deposit_escalation: msg.deposit_escalation,
+                forfeited_deposit_policy: msg.forfeited_deposit_policy,
+                reject_failing_execution: msg.reject_failing_execution,
+                non_voter_default: msg.non_voter_default,
+                max_proposal_bytes,
+                config_change_delay: msg.config_change_delay,
+                close_reward: None,
+                cache_voting_power: false,
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    };
 
-    app.execute_contract(
-        Addr::unchecked("four"),
-        proposal_module.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+    // The limit must be non-zero and may not raise the module's
+    // hard limit.
+    let err = set_max(&mut app, Some(0)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidMaxProposalBytes { .. }));
+    let err = set_max(&mut app, Some(MAX_PROPOSAL_SIZE + 1)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidMaxProposalBytes { .. }));
+
+    // Find the size of the proposal by proposing it with the
+    // instantiated limit of one byte, which it can not meet.
+    let size = match propose(&mut app).unwrap_err() {
+        ContractError::ProposalTooLarge { size, max } => {
+            assert_eq!(max, 1);
+            size
+        }
+        err => panic!("unexpected error: {}", err),
+    };
 
-    let proposal: ProposalResponse = app
+    // One byte under the proposal's size rejects it.
+    set_max(&mut app, Some(size - 1)).unwrap();
+    let err = propose(&mut app).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::ProposalTooLarge { size: s, max } if s == size && max == size - 1
+    ));
+
+    // A limit of exactly the proposal's size accepts it.
+    set_max(&mut app, Some(size)).unwrap();
+    propose(&mut app).unwrap();
+
+    // Removing the limit leaves only the module's hard limit.
+    set_max(&mut app, None).unwrap();
+    propose(&mut app).unwrap();
+    let count: u64 = app
         .wrap()
-        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .query_wasm_smart(govmod_single, &QueryMsg::ProposalCount {})
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(count, 2);
 }
 
-/// Tests proposal rejection with three of five multisig style voting.
 #[test]
-fn test_three_of_five_multisig_reject() {
+fn test_config_change_delay() {
     let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_cw4_groups_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::AbsoluteCount {
-                threshold: Uint128::new(3),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+    let govmod_id = app.store_code(proposal_contract());
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
-        Some(vec![
-            Cw20Coin {
-                address: "one".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "two".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "three".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "four".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "five".to_string(),
-                amount: Uint128::new(1),
-            },
-        ]),
-    );
-
-    let core_state: cw_core::query::DumpStateResponse = app
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: Some(Duration::Height(5)),
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate.clone(), None);
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
         .unwrap();
-    let proposal_module = core_state
+    let govmod_single = gov_state
         .proposal_modules
         .into_iter()
         .next()
         .unwrap()
         .address;
 
-    app.execute_contract(
-        Addr::unchecked("one"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "Propose a thing.".to_string(),
-            description: "Do the thing.".to_string(),
-            msgs: vec![],
-        },
-        &[],
-    )
-    .unwrap();
+    let update_config = |app: &mut App,
+                         allow_revoting: bool,
+                         config_change_delay: Option<Duration>|
+     -> Result<(), ContractError> {
+        let msg = instantiate.clone();
+        app.execute_contract(
+            governance_addr.clone(),
+            govmod_single.clone(),
+            &ExecuteMsg::UpdateConfig {
+                threshold: msg.threshold,
+                max_voting_period: msg.max_voting_period,
+                min_voting_period: msg.min_voting_period,
+                only_members_execute: msg.only_members_execute,
+                allow_revoting,
+                dao: governance_addr.to_string(),
+                deposit_info: msg.deposit_info,
+                close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+                exclude_dao_voting_power: msg.exclude_dao_voting_power,
+                execution_callback: msg.execution_callback,
+                require_second: msg.require_second,
+                record_config_history: msg.record_config_history,
+                quorum_reached_hooks: msg.quorum_reached_hooks,
+                require_power_at_resolution: msg.require_power_at_resolution,
+                execution_retry_cooldown: msg.execution_retry_cooldown,
+                post_execution_cooldown: msg.post_execution_cooldown,
+                quorum_extension: msg.quorum_extension,
+                native_propose_deposit: msg.native_propose_deposit,
+                snapshot_offset: msg.snapshot_offset,
+                pre_execution_hook: msg.pre_execution_hook,
+                required_description_sections: msg.required_description_sections,
+                voting_period_limit: msg.voting_period_limit,
+                register_deposit_token: msg.register_deposit_token,
+                total_power_override: msg.total_power_override,
+                record_vote_history: msg.record_vote_history,
+                execute_on_pass: msg.execute_on_pass,
+                reveal_period: msg.reveal_period,
+                hook_allowlist: msg.hook_allowlist,
+                revote_settle_period: msg.revote_settle_period,
+                deposit_escalation: msg.deposit_escalation,
+                forfeited_deposit_policy: msg.forfeited_deposit_policy,
+                reject_failing_execution: msg.reject_failing_execution,
+                non_voter_default: msg.non_voter_default,
+                max_proposal_bytes: msg.max_proposal_bytes,
+                config_change_delay,
+                close_reward: None,
+                cache_voting_power: false,
+            },
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|e| e.downcast().unwrap())
+    };
+    let config = |app: &App| -> Config {
+        app.wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+            .unwrap()
+    };
+    let pending = |app: &App| -> Option<PendingConfig> {
+        app.wrap()
+            .query_wasm_smart(govmod_single.clone(), &QueryMsg::PendingConfig {})
+            .unwrap()
+    };
+    let propose = |app: &mut App| -> SingleChoiceProposal {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposals: ProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ReverseProposals {
+                    start_before: None,
+                    limit: Some(1),
+                },
+            )
+            .unwrap();
+        proposals.proposals.into_iter().next().unwrap().proposal
+    };
+    assert_eq!(pending(&app), None);
 
-    app.execute_contract(
-        Addr::unchecked("one"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
-    app.execute_contract(
-        Addr::unchecked("two"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
-        },
-        &[],
+    // The delay may be at most a year.
+    let err = update_config(
+        &mut app,
+        false,
+        Some(Duration::Height(MAX_CONFIG_CHANGE_DELAY.height + 1)),
     )
-    .unwrap();
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ConfigChangeDelayTooLong {}));
+    assert_eq!(pending(&app), None);
+
+    // The update is scheduled rather than applied. Removing the delay
+    // is itself delayed.
+    let height = app.block_info().height;
+    update_config(&mut app, true, None).unwrap();
+    assert!(!config(&app).allow_revoting);
+    let scheduled = pending(&app).unwrap();
+    assert_eq!(scheduled.effective, Expiration::AtHeight(height + 5));
+    assert!(scheduled.config.allow_revoting);
+    assert_eq!(scheduled.config.config_change_delay, None);
+    assert!(!propose(&mut app).allow_revoting);
+
+    app.update_block(|block| block.height += 4);
+    assert!(!config(&app).allow_revoting);
+    assert!(pending(&app).is_some());
+    assert!(!propose(&mut app).allow_revoting);
+
+    // Once the delay passes the update takes effect, and is applied
+    // by the next message executed.
+    app.update_block(|block| block.height += 1);
+    assert!(config(&app).allow_revoting);
+    assert_eq!(pending(&app), None);
+    assert!(propose(&mut app).allow_revoting);
+    assert_eq!(config(&app), scheduled.config);
+
+    // Without a delay, updates apply immediately.
+    update_config(&mut app, false, None).unwrap();
+    assert!(!config(&app).allow_revoting);
+    assert_eq!(pending(&app), None);
+}
 
-    app.execute_contract(
-        Addr::unchecked("three"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
-        },
-        &[],
-    )
-    .unwrap();
+#[test]
+fn test_proposal_metadata() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
-    app.execute_contract(
-        Addr::unchecked("four"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
-        &[],
-    )
-    .unwrap();
-
-    // Still one vote outstanding but the module ought to have
-    // rejected it already as that one vote can not make the proposal
-    // pass.
-    let proposal: ProposalResponse = app
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            amount: Uint128::new(100),
+        }]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(
-            proposal_module.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
-        )
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Rejected);
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    app.execute_contract(
-        Addr::unchecked("four"),
-        proposal_module.clone(),
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+    let propose = |app: &mut App, metadata: Option<String>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "Rename the DAO".to_string(),
+                description: "A new name".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata,
+            },
+            &[],
+        )
+    };
 
+    // Metadata is stored as provided and returned with the proposal.
+    let metadata = r#"{"image":"ipfs://banner.png","links":["https://daodao.zone"]}"#;
+    propose(&mut app, Some(metadata.to_string())).unwrap();
+    propose(&mut app, None).unwrap();
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(proposal.proposal.metadata, Some(metadata.to_string()));
     let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Closed);
+    assert_eq!(proposal.proposal.metadata, None);
+
+    // Metadata may be at most MAX_PROPOSAL_METADATA_SIZE bytes.
+    let max = MAX_PROPOSAL_METADATA_SIZE as usize;
+    propose(&mut app, Some("a".repeat(max))).unwrap();
+    let err: ContractError = propose(&mut app, Some("a".repeat(max + 1)))
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(
+        err,
+        ContractError::MetadataTooLarge { size, max } if size == max + 1
+    ));
 }
 
-/// Tests that we fail to instantiate when using multisig style voting
-/// power and `VotingModuleToken {}`.
 #[test]
-#[should_panic]
-fn test_voting_module_token_with_multisig_style_voting() {
+fn test_voter_influence() {
     let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    instantiate_with_cw4_groups_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::AbsoluteCount {
-                threshold: Uint128::new(3),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: Some(DepositInfo {
-                token: DepositToken::VotingModuleToken {},
-                deposit: Uint128::new(1),
-                refund_failed_proposals: true,
-            }),
-            close_proposal_on_execution_failure: true,
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
         Some(vec![
             Cw20Coin {
-                address: "one".to_string(),
-                amount: Uint128::new(1),
+                address: "whale".to_string(),
+                amount: Uint128::new(20),
             },
             Cw20Coin {
-                address: "two".to_string(),
-                amount: Uint128::new(1),
+                address: "ekez".to_string(),
+                amount: Uint128::new(45),
             },
             Cw20Coin {
-                address: "three".to_string(),
-                amount: Uint128::new(1),
+                address: "keze".to_string(),
+                amount: Uint128::new(35),
             },
         ]),
     );
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+
+    let propose = |app: &mut App| {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+    };
+    let vote = |app: &mut App, voter: &str, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked(voter),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+    };
+    let influence_page = |app: &App,
+                          address: &str,
+                          start_after: Option<u64>,
+                          limit: Option<u64>|
+     -> VoterInfluenceResponse {
+        app.wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::VoterInfluence {
+                    address: address.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    };
+    let influence = |app: &App, address: &str| influence_page(app, address, None, None);
+
+    // Proposal 1 will fail with 45 yes votes, but passes if the whale
+    // votes yes too.
+    propose(&mut app);
+    vote(&mut app, "ekez", 1, Vote::Yes);
+    // Proposal 2 will fail with 35 no votes, and the whale's 20 can
+    // not bring yes above half of the total power.
+    propose(&mut app);
+    vote(&mut app, "keze", 2, Vote::No);
+    // Proposal 3 has already passed.
+    propose(&mut app);
+    vote(&mut app, "ekez", 3, Vote::Yes);
+    vote(&mut app, "keze", 3, Vote::Yes);
+
+    assert_eq!(
+        influence(&app, "whale"),
+        VoterInfluenceResponse {
+            proposals: vec![
+                ProposalInfluence {
+                    proposal_id: 1,
+                    would_change_outcome: true,
+                },
+                ProposalInfluence {
+                    proposal_id: 2,
+                    would_change_outcome: false,
+                },
+            ],
+            last_examined: Some(3),
+        }
+    );
+    // Proposals are paginated by ID with the limit bounding the
+    // proposals examined, including those that can not be voted on.
+    assert_eq!(
+        influence_page(&app, "whale", None, Some(1)),
+        VoterInfluenceResponse {
+            proposals: vec![ProposalInfluence {
+                proposal_id: 1,
+                would_change_outcome: true,
+            }],
+            last_examined: Some(1),
+        }
+    );
+    assert_eq!(
+        influence_page(&app, "whale", Some(1), Some(1)),
+        VoterInfluenceResponse {
+            proposals: vec![ProposalInfluence {
+                proposal_id: 2,
+                would_change_outcome: false,
+            }],
+            last_examined: Some(2),
+        }
+    );
+    assert_eq!(
+        influence_page(&app, "whale", Some(2), None),
+        VoterInfluenceResponse {
+            proposals: vec![],
+            last_examined: Some(3),
+        }
+    );
+    assert_eq!(
+        influence_page(&app, "whale", Some(3), None),
+        VoterInfluenceResponse {
+            proposals: vec![],
+            last_examined: None,
+        }
+    );
+
+    // Once the whale has voted they may not vote again as revoting is
+    // not allowed.
+    vote(&mut app, "whale", 1, Vote::No);
+    assert_eq!(
+        influence(&app, "whale"),
+        VoterInfluenceResponse {
+            proposals: vec![ProposalInfluence {
+                proposal_id: 2,
+                would_change_outcome: false,
+            }],
+            last_examined: Some(3),
+        }
+    );
+
+    // Addresses without voting power may not vote on anything.
+    assert_eq!(
+        influence(&app, "nobody"),
+        VoterInfluenceResponse {
+            proposals: vec![],
+            last_examined: Some(3),
+        }
+    );
 }
 
-/// Tests revoting with multisig style absolute count thresholds.
 #[test]
-fn test_three_of_five_multisig_revoting() {
+fn test_deposit_escalation() {
     let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_cw4_groups_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::AbsoluteCount {
-                threshold: Uint128::new(3),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: true,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(10),
+            refund_failed_proposals: false,
+        }),
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: Some(DepositEscalation {
+            multipliers: vec![2, 4],
+        }),
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
         Some(vec![
             Cw20Coin {
-                address: "one".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "two".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "three".to_string(),
-                amount: Uint128::new(1),
-            },
-            Cw20Coin {
-                address: "four".to_string(),
-                amount: Uint128::new(1),
+                address: "ekez".to_string(),
+                amount: Uint128::new(200),
             },
             Cw20Coin {
-                address: "five".to_string(),
-                amount: Uint128::new(1),
+                address: "keze".to_string(),
+                amount: Uint128::new(400),
             },
         ]),
     );
-
-    let core_state: cw_core::query::DumpStateResponse = app
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let proposal_module = core_state
+    let govmod_single = gov_state
         .proposal_modules
         .into_iter()
         .next()
         .unwrap()
         .address;
-
-    app.execute_contract(
-        Addr::unchecked("one"),
-        proposal_module.clone(),
-        &ExecuteMsg::Propose {
-            title: "Propose a thing.".to_string(),
-            description: "Do the thing.".to_string(),
-            msgs: vec![],
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked("one"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
-    app.execute_contract(
-        Addr::unchecked("two"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked("three"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked("four"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Make sure it doesn't pass early.
-    let proposal: ProposalResponse = app
+    let config: Config = app
         .wrap()
-        .query_wasm_smart(
-            proposal_module.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .unwrap();
+    let token = config.deposit_info.unwrap().token;
+
+    let multiplier = |app: &App| -> u64 {
+        app.wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::DepositMultiplier {
+                    address: "ekez".to_string(),
+                },
+            )
+            .unwrap()
+    };
+    let balance = |app: &App| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: "ekez".to_string(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+    // Proposes as ekez, approving `deposit` tokens, and returns the
+    // deposit taken.
+    let propose = |app: &mut App, deposit: u128| -> Uint128 {
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            token.clone(),
+            &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+                spender: govmod_single.to_string(),
+                amount: Uint128::new(deposit),
+                expires: None,
+            },
+            &[],
         )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Open);
-
-    // Four changes their mind.
-    app.execute_contract(
-        Addr::unchecked("four"),
-        proposal_module.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
-        &[],
-    )
-    .unwrap();
+        let before = balance(app);
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        before - balance(app)
+    };
+    let vote = |app: &mut App, proposal_id: u64, vote: Vote| {
+        app.execute_contract(
+            Addr::unchecked("keze"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote { proposal_id, vote },
+            &[],
+        )
+        .unwrap();
+    };
 
-    app.update_block(|b| b.height += 10);
+    assert_eq!(multiplier(&app), 1);
+    assert_eq!(propose(&mut app, 10), Uint128::new(10));
+    vote(&mut app, 1, Vote::No);
 
+    // The deposit rises with each consecutive rejection.
+    assert_eq!(multiplier(&app), 2);
+    assert_eq!(propose(&mut app, 20), Uint128::new(20));
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_module.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
         )
         .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(
+        proposal.proposal.deposit_info.unwrap().deposit,
+        Uint128::new(20)
+    );
+    vote(&mut app, 2, Vote::No);
 
+    assert_eq!(multiplier(&app), 4);
+    // The deposit must be approved in full.
     app.execute_contract(
-        Addr::unchecked("four"),
-        proposal_module.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
+        Addr::unchecked("ekez"),
+        govmod_single.clone(),
+        &ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
         &[],
     )
-    .unwrap();
-
-    let proposal: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(proposal_module, &QueryMsg::Proposal { proposal_id: 1 })
-        .unwrap();
-    assert_eq!(proposal.proposal.status, Status::Executed);
-}
-
-/// Tests that absolute count style thresholds work with token style
-/// voting.
-#[test]
-fn test_absolute_count_threshold_non_multisig() {
-    do_votes_staked_balances(
-        vec![
-            TestSingleChoiceVote {
-                voter: "one".to_string(),
-                position: Vote::Yes,
-                weight: Uint128::new(10),
-                should_execute: ShouldExecute::Yes,
-            },
-            TestSingleChoiceVote {
-                voter: "two".to_string(),
-                position: Vote::No,
-                weight: Uint128::new(200),
-                should_execute: ShouldExecute::Yes,
-            },
-            TestSingleChoiceVote {
-                voter: "three".to_string(),
-                position: Vote::Yes,
-                weight: Uint128::new(1),
-                should_execute: ShouldExecute::Yes,
-            },
-        ],
-        Threshold::AbsoluteCount {
-            threshold: Uint128::new(11),
-        },
-        Status::Passed,
-        None,
-    );
-}
-
-/// Tests that we do not overflow when faced with really high token /
-/// vote supply.
-#[test]
-fn test_large_absolute_count_threshold() {
-    do_votes_staked_balances(
-        vec![
-            // Instant rejection after this.
-            TestSingleChoiceVote {
-                voter: "two".to_string(),
-                position: Vote::No,
-                weight: Uint128::new(1),
-                should_execute: ShouldExecute::Yes,
-            },
-            TestSingleChoiceVote {
-                voter: "one".to_string(),
-                position: Vote::Yes,
-                weight: Uint128::new(u128::MAX - 1),
-                should_execute: ShouldExecute::No,
-            },
-        ],
-        Threshold::AbsoluteCount {
-            threshold: Uint128::new(u128::MAX),
-        },
-        Status::Rejected,
-        None,
-    );
-
-    do_votes_staked_balances(
-        vec![
-            TestSingleChoiceVote {
-                voter: "one".to_string(),
-                position: Vote::Yes,
-                weight: Uint128::new(u128::MAX - 1),
-                should_execute: ShouldExecute::Yes,
-            },
-            TestSingleChoiceVote {
-                voter: "two".to_string(),
-                position: Vote::No,
-                weight: Uint128::new(1),
-                should_execute: ShouldExecute::Yes,
-            },
-        ],
-        Threshold::AbsoluteCount {
-            threshold: Uint128::new(u128::MAX),
-        },
-        Status::Rejected,
-        None,
-    );
+    .unwrap_err();
+    assert_eq!(propose(&mut app, 40), Uint128::new(40));
+
+    // Open proposals do not affect the multiplier, which stays at its
+    // last step once reached.
+    assert_eq!(multiplier(&app), 4);
+    vote(&mut app, 3, Vote::No);
+    assert_eq!(multiplier(&app), 4);
+
+    // A passed proposal resets the escalation.
+    assert_eq!(propose(&mut app, 40), Uint128::new(40));
+    vote(&mut app, 4, Vote::Yes);
+    assert_eq!(multiplier(&app), 1);
+    assert_eq!(propose(&mut app, 10), Uint128::new(10));
 }
 
 #[test]
-fn test_migrate_from_compatible() {
+fn test_execution_callback() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
+    let callback_id = app.store_code(execution_callback_contract());
+
+    let callback = app
+        .instantiate_contract(
+            callback_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &false,
+            &[],
+            "callback",
+            None,
+        )
+        .unwrap();
+    let failing_callback = app
+        .instantiate_contract(
+            callback_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &true,
+            &[],
+            "failing callback",
+            None,
+        )
+        .unwrap();
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: Some(callback.to_string()),
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
-
     let governance_addr =
         instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
-    let governance_modules: Vec<ProposalModule> = app
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(
             governance_addr.clone(),
-            &cw_core::msg::QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
-            },
+            &cw_core::msg::QueryMsg::DumpState {},
         )
         .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    let config: Config = app
-        .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+    let set_item = |key: &str| -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: governance_addr.to_string(),
+            msg: to_binary(&cw_core::msg::ExecuteMsg::SetItem {
+                key: key.to_string(),
+                addr: "item".to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into()
+    };
+    let propose_vote_execute = |app: &mut App, proposal_id: u64, msgs: Vec<CosmosMsg>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
         .unwrap();
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap();
+    };
 
-    app.execute(
-        governance_addr,
-        CosmosMsg::Wasm(WasmMsg::Migrate {
-            contract_addr: govmod_single.to_string(),
-            new_code_id: govmod_id,
-            msg: to_binary(&MigrateMsg::FromCompatible {}).unwrap(),
-        }),
-    )
-    .unwrap();
-
-    let new_config: Config = app
+    // The callback is sent the proposal's messages after they are
+    // executed.
+    propose_vote_execute(&mut app, 1, vec![set_item("one")]);
+    let last: Option<ExecutionCallbackMsg> = app
         .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Config {})
+        .query_wasm_smart(callback.clone(), &Empty {})
         .unwrap();
-
-    assert_eq!(config, new_config);
-}
-
-#[test]
-fn test_proposal_count_initialized_to_zero() {
-    let mut app = App::default();
-    let proposal_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        proposal_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: None,
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
-        },
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(10),
-            },
-            Cw20Coin {
-                address: "innactive".to_string(),
-                amount: Uint128::new(90),
-            },
-        ]),
+    assert_eq!(
+        last,
+        Some(ExecutionCallbackMsg::ProposalExecuted {
+            proposal_id: 1,
+            msgs: vec![set_item("one")],
+        })
     );
 
-    let gov_state: cw_core::query::DumpStateResponse = app
+    // Proposals without messages also trigger the callback.
+    propose_vote_execute(&mut app, 2, vec![]);
+    let last: Option<ExecutionCallbackMsg> =
+        app.wrap().query_wasm_smart(callback, &Empty {}).unwrap();
+    assert_eq!(
+        last,
+        Some(ExecutionCallbackMsg::ProposalExecuted {
+            proposal_id: 2,
+            msgs: vec![],
+        })
+    );
+
+    // A failing callback does not revert the proposal's execution.
+    let config: Config = app
         .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
         .unwrap();
-    let proposal_modules = gov_state.proposal_modules;
-
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+    app.execute_contract(
+        governance_addr.clone(),
+        govmod_single.clone(),
+        &ExecuteMsg::UpdateConfig {
+            threshold: config.threshold,
+            max_voting_period: config.max_voting_period,
+            min_voting_period: config.min_voting_period,
+            only_members_execute: config.only_members_execute,
+            allow_revoting: config.allow_revoting,
+            dao: config.dao.to_string(),
+            deposit_info: None,
+            close_proposal_on_execution_failure: config.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: config.exclude_dao_voting_power,
+            execution_callback: Some(failing_callback.to_string()),
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+        &[],
+    )
+    .unwrap();
 
-    let proposal_count: u64 = app
+    propose_vote_execute(&mut app, 3, vec![set_item("three")]);
+    let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(proposal_single, &QueryMsg::ProposalCount {})
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 3 })
         .unwrap();
-    assert_eq!(proposal_count, 0);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    let item: cw_core::query::GetItemResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr,
+            &cw_core::msg::QueryMsg::GetItem {
+                key: "three".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(item.item, Some("item".to_string()));
 }
 
 #[test]
-fn test_no_early_pass_with_min_duration() {
+fn test_pre_execution_hook() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        govmod_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: Some(Duration::Height(2)),
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
-        },
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(10),
-            },
-            Cw20Coin {
-                address: "wale".to_string(),
-                amount: Uint128::new(90),
-            },
-        ]),
-    );
+    let hook_id = app.store_code(pre_execution_hook_contract());
 
+    let hook = app
+        .instantiate_contract(
+            hook_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &true,
+            &[],
+            "hook",
+            None,
+        )
+        .unwrap();
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: Some(hook.to_string()),
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
     let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
         .unwrap();
-    let proposal_modules = gov_state.proposal_modules;
-
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
     app.execute_contract(
-        Addr::unchecked("wale"),
-        proposal_single.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
+            title: "A simple proposal".to_string(),
+            description: "This is a simple proposal".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
     .unwrap();
-
-    // Wale votes yes which under normal curcumstances would cause the
-    // proposal to pass. Because there is a min duration it does not.
     app.execute_contract(
-        Addr::unchecked("wale"),
-        proposal_single.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
@@ -3970,152 +17226,167 @@ fn test_no_early_pass_with_min_duration() {
     )
     .unwrap();
 
+    // The hook vetoes execution and the proposal remains passed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::ExecutionVetoedByHook {}));
     let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            proposal_single.clone(),
+            govmod_single.clone(),
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    let executable = |app: &App| {
+        let res: FilteredProposalListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::ExecutableByAddress {
+                    address: CREATOR_ADDR.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        res.proposals.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    assert_eq!(executable(&app), Vec::<u64>::new());
 
-    assert_eq!(proposal.proposal.status, Status::Open);
-
-    // Let the min voting period pass.
-    app.update_block(|b| b.height += 2);
-
+    // Once the hook stops vetoing the proposal may be executed.
+    app.execute_contract(Addr::unchecked(CREATOR_ADDR), hook, &false, &[])
+        .unwrap();
+    assert_eq!(executable(&app), vec![1]);
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
     let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(proposal_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-
-    assert_eq!(proposal.proposal.status, Status::Passed);
-}
-
-#[test]
-#[should_panic(
-    expected = "min_voting_period and max_voting_period must have the same units (height or time)"
-)]
-fn test_min_duration_units_missmatch() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(proposal_contract());
-    instantiate_with_staked_balances_governance(
-        &mut app,
-        govmod_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: Some(Duration::Time(2)),
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
-        },
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(10),
-            },
-            Cw20Coin {
-                address: "wale".to_string(),
-                amount: Uint128::new(90),
-            },
-        ]),
-    );
+    assert_eq!(proposal.proposal.status, Status::Executed);
 }
 
 #[test]
-#[should_panic(expected = "Min voting period must be less than or equal to max voting period")]
-fn test_min_duration_larger_than_proposal_duration() {
+fn test_proposal_messages_execute_as_dao() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
-    instantiate_with_staked_balances_governance(
-        &mut app,
-        govmod_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Height(10),
-            min_voting_period: Some(Duration::Height(11)),
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
-        },
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(10),
-            },
-            Cw20Coin {
-                address: "wale".to_string(),
-                amount: Uint128::new(90),
-            },
-        ]),
-    );
-}
 
-#[test]
-fn test_min_duration_same_as_proposal_duration() {
-    let mut app = App::default();
-    let govmod_id = app.store_code(proposal_contract());
-    let core_addr = instantiate_with_staked_balances_governance(
-        &mut app,
-        govmod_id,
-        InstantiateMsg {
-            threshold: Threshold::ThresholdQuorum {
-                threshold: PercentageThreshold::Majority {},
-                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
-            },
-            max_voting_period: Duration::Time(10),
-            min_voting_period: Some(Duration::Time(10)),
-            only_members_execute: true,
-            allow_revoting: false,
-            deposit_info: None,
-            close_proposal_on_execution_failure: true,
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
         },
-        Some(vec![
-            Cw20Coin {
-                address: "ekez".to_string(),
-                amount: Uint128::new(10),
-            },
-            Cw20Coin {
-                address: "wale".to_string(),
-                amount: Uint128::new(90),
-            },
-        ]),
-    );
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
+        )
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    let gov_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
-        .unwrap();
-    let proposal_modules = gov_state.proposal_modules;
+    // The DAO only accepts config updates from itself.
+    let update_config = cw_core::msg::ExecuteMsg::UpdateConfig {
+        config: cw_core::state::Config {
+            name: "Renamed DAO".to_string(),
+            ..gov_state.config
+        },
+    };
 
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
+    // The proposal module holds no authority over the DAO.
+    let err: cw_core::ContractError = app
+        .execute_contract(
+            govmod_single.clone(),
+            governance_addr.clone(),
+            &update_config,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, cw_core::ContractError::Unauthorized {}));
 
     app.execute_contract(
-        Addr::unchecked("wale"),
-        proposal_single.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            msgs: vec![],
+            title: "Rename the DAO".to_string(),
+            description: "Only the DAO may do this".to_string(),
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: governance_addr.to_string(),
+                msg: to_binary(&update_config).unwrap(),
+                funds: vec![],
+            }
+            .into()],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
     .unwrap();
-
-    // Wale votes yes which under normal curcumstances would cause the
-    // proposal to pass. Because there is a min duration it does not.
     app.execute_contract(
-        Addr::unchecked("wale"),
-        proposal_single.clone(),
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
@@ -4123,386 +17394,558 @@ fn test_min_duration_same_as_proposal_duration() {
         &[],
     )
     .unwrap();
-
-    let proposal: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            proposal_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
-        )
-        .unwrap();
-
-    assert_eq!(proposal.proposal.status, Status::Open);
-
-    // ekez can vote no.
     app.execute_contract(
-        Addr::unchecked("ekez"),
-        proposal_single.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::No,
-        },
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
         &[],
     )
     .unwrap();
 
-    // Let the min voting period pass.
-    app.update_block(|b| b.time = b.time.plus_seconds(10));
-
+    // The message was executed with the DAO as its sender.
     let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(proposal_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
         .unwrap();
-
-    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    let config: cw_core::state::Config = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.name, "Renamed DAO");
 }
 
 #[test]
-fn test_timestamp_updated() {
+fn test_execution_error_recorded() {
     let mut app = App::default();
     let govmod_id = app.store_code(proposal_contract());
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: cw_utils::Duration::Height(6),
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
-    let governance_addr = instantiate_with_cw20_balances_governance(
-        &mut app,
-        govmod_id,
-        instantiate,
-        Some(vec![
-            Cw20Coin {
-                address: "voter".to_string(),
-                amount: Uint128::new(3),
-            },
-            Cw20Coin {
-                address: "voter2".to_string(),
-                amount: Uint128::new(2),
-            },
-        ]),
-    );
-
-    let governance_modules: Vec<ProposalModule> = app
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
+        .wrap()
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+        .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+    let token_contract: Addr = app
         .wrap()
         .query_wasm_smart(
-            governance_addr,
-            &cw_core::msg::QueryMsg::ProposalModules {
-                start_after: None,
-                limit: None,
-            },
+            gov_state.voting_module,
+            &cw_core_interface::voting::Query::TokenContract {},
         )
         .unwrap();
 
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
-
-    // Create 2 proposals.
+    // The DAO has no tokens so burning any will revert.
     app.execute_contract(
-        Addr::unchecked("voter"),
+        Addr::unchecked(CREATOR_ADDR),
         govmod_single.clone(),
         &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            msgs: vec![],
+            title: "A simple burn tokens proposal".to_string(),
+            description: "Burning more tokens, than dao treasury have".to_string(),
+            msgs: vec![WasmMsg::Execute {
+                contract_addr: token_contract.to_string(),
+                msg: to_binary(&cw20::Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(2000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
     .unwrap();
-
     app.execute_contract(
-        Addr::unchecked("voter"),
+        Addr::unchecked(CREATOR_ADDR),
         govmod_single.clone(),
-        &ExecuteMsg::Propose {
-            title: "A simple text proposal".to_string(),
-            description: "This is a simple text proposal".to_string(),
-            msgs: vec![],
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
 
-    let created_1: ProposalResponse = app
+    let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    let current_block = app.block_info();
-
-    // Verify created and last updated
-    assert_eq!(created_1.proposal.created, current_block.time);
-    assert_eq!(created_1.proposal.last_updated, current_block.time);
-
-    let created_2: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 2 },
-        )
-        .unwrap();
-
-    // Verify created and last updated
-    assert_eq!(created_2.proposal.created, current_block.time);
-    assert_eq!(created_2.proposal.last_updated, current_block.time);
-
-    // Update block
-    let timestamp = Timestamp::from_seconds(300_000_000);
-    app.update_block(|block| block.time = timestamp);
+    assert_eq!(proposal.proposal.execution_error, None);
 
-    // Vote on proposal
     app.execute_contract(
-        Addr::unchecked("voter"),
+        Addr::unchecked(CREATOR_ADDR),
         govmod_single.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 1,
-            vote: Vote::Yes,
-        },
+        &ExecuteMsg::Execute { proposal_id: 1 },
         &[],
     )
     .unwrap();
 
-    // Expect that last_updated changed because of status change
-    let updated: ProposalResponse = app
+    let failed: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
+    match failed.proposal.execution_error {
+        Some(ExecutionError::Reverted { error }) => assert!(!error.is_empty()),
+        other => panic!("expected a revert, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_execution_funds() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
         )
         .unwrap();
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    assert_eq!(updated.proposal.last_updated, app.block_info().time);
-    assert_eq!(updated.proposal.status, Status::Passed);
-
-    // Update block
-    let timestamp = Timestamp::from_seconds(500_000_000);
-    app.update_block(|block| block.time = timestamp);
-    let latest_time = app.block_info().time;
+    let ujuno = |amount: u128| Coin {
+        denom: "ujuno".to_string(),
+        amount: Uint128::new(amount),
+    };
+    let send = |amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: "recipient".to_string(),
+            amount: vec![ujuno(amount)],
+        }
+        .into()
+    };
+    let propose = |app: &mut App, msgs: Vec<CosmosMsg>, execution_funds: Vec<Coin>| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A sponsored proposal".to_string(),
+                description: "This is a sponsored proposal".to_string(),
+                msgs,
+                expiration_override: None,
+                execution_funds,
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+    };
+    let execute = |app: &mut App, proposal_id: u64, funds: &[Coin]| {
+        app.execute_contract(
+            Addr::unchecked("sponsor"),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            funds,
+        )
+    };
+    let balance = |app: &App, address: &str| {
+        app.wrap()
+            .query_balance(address.to_string(), "ujuno")
+            .unwrap()
+            .amount
+    };
+    let has_sponsor = |app: &App, proposal_id: u64| {
+        app.wrap()
+            .query_wasm_raw(
+                govmod_single.clone(),
+                EXECUTION_SPONSORS.key(proposal_id).to_vec(),
+            )
+            .unwrap()
+            .is_some()
+    };
 
-    // Execute proposal
-    app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+    // Execution funds must be well formed and only set on proposals
+    // with messages.
+    let err: ContractError = propose(&mut app, vec![send(100)], vec![ujuno(50), ujuno(50)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExecutionFunds {}));
+    let err: ContractError = propose(&mut app, vec![send(100)], vec![ujuno(0)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExecutionFunds {}));
+    let err: ContractError = propose(&mut app, vec![], vec![ujuno(100)])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::InvalidExecutionFunds {}));
 
-    // Status should have changed to 'Executed'
-    let updated: ProposalResponse = app
+    // The DAO holds no ujuno so these proposals rely on a sponsor.
+    propose(&mut app, vec![send(100)], vec![ujuno(100)]).unwrap();
+    propose(&mut app, vec![send(300)], vec![ujuno(100)]).unwrap();
+    for proposal_id in [1, 2] {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+    let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
             &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
+    assert_eq!(proposal.proposal.execution_funds, vec![ujuno(100)]);
 
-    assert_eq!(updated.proposal.last_updated, latest_time);
-    assert_eq!(updated.proposal.status, Status::Executed);
-
-    let timestamp = Timestamp::from_seconds(700_000_000);
-    app.update_block(|block| block.time = timestamp);
-    let latest_time = app.block_info().time;
-
-    // Vote no on second proposal
-    app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Vote {
-            proposal_id: 2,
-            vote: Vote::No,
-        },
-        &[],
-    )
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: "sponsor".to_string(),
+        amount: vec![ujuno(200)],
+    }))
     .unwrap();
 
-    // Status should have changed to 'Rejected'
-    let updated: ProposalResponse = app
+    // The executor must send exactly the proposal's execution funds.
+    for funds in [vec![], vec![ujuno(50)], vec![ujuno(150)]] {
+        let err: ContractError = execute(&mut app, 1, &funds)
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(err, ContractError::ExecutionFundsMismatch {}));
+    }
+
+    execute(&mut app, 1, &[ujuno(100)]).unwrap();
+    let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
             govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 2 },
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-
-    assert_eq!(updated.proposal.last_updated, latest_time);
-    assert_eq!(updated.proposal.status, Status::Rejected);
-
-    let timestamp = Timestamp::from_seconds(900_000_000);
-    app.update_block(|block| block.time = timestamp);
-    let latest_time = app.block_info().time;
-
-    // Close second proposal
-    app.execute_contract(
-        Addr::unchecked("voter"),
-        govmod_single.clone(),
-        &ExecuteMsg::Close { proposal_id: 2 },
-        &[],
-    )
-    .unwrap();
-
-    // Status should have changed to 'Closed'
-    let updated: ProposalResponse = app
+    assert_eq!(proposal.proposal.status, Status::Executed);
+    assert_eq!(balance(&app, "recipient"), Uint128::new(100));
+    assert_eq!(balance(&app, "sponsor"), Uint128::new(100));
+    assert_eq!(balance(&app, governance_addr.as_str()), Uint128::zero());
+    // The sponsor is only recorded while execution may fail.
+    assert!(!has_sponsor(&app, 1));
+
+    // The funds are returned to the sponsor if execution fails.
+    execute(&mut app, 2, &[ujuno(100)]).unwrap();
+    let proposal: ProposalResponse = app
         .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 2 })
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 2 },
+        )
         .unwrap();
-
-    assert_eq!(updated.proposal.last_updated, latest_time);
-    assert_eq!(updated.proposal.status, Status::Closed);
+    assert_eq!(proposal.proposal.status, Status::ExecutionFailed);
+    assert_eq!(balance(&app, "recipient"), Uint128::new(100));
+    assert_eq!(balance(&app, "sponsor"), Uint128::new(100));
+    assert_eq!(balance(&app, govmod_single.as_str()), Uint128::zero());
+    assert!(!has_sponsor(&app, 2));
 }
 
 #[test]
-fn test_migrate_mock() {
-    let mut deps = mock_dependencies();
-    let env = mock_env();
-    let current_block = &env.block;
-    let max_voting_period = cw_utils::Duration::Height(6);
-
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-
-    // Write to storage in old data format
-    let v1_map: Map<u64, V1Proposal> = Map::new("proposals");
-    let v1_proposal = V1Proposal {
-        title: "A simple text proposal".to_string(),
-        description: "This is a simple text proposal".to_string(),
-        proposer: Addr::unchecked(CREATOR_ADDR),
-        start_height: env.block.height,
-        expiration: max_voting_period.after(current_block),
-        min_voting_period: None,
-        threshold: threshold.clone(),
-        allow_revoting: false,
-        total_power: Uint128::new(100_000_000),
-        msgs: vec![],
-        status: Status::Open,
-        votes: Votes::zero(),
-        deposit_info: None,
-    };
-    v1_map.save(&mut deps.storage, 0, &v1_proposal).unwrap();
-
-    let v1_item: Item<V1Config> = Item::new("config");
-    let v1_config = V1Config {
-        threshold: threshold.clone(),
-        max_voting_period,
-        min_voting_period: None,
-        only_members_execute: true,
-        allow_revoting: false,
-        dao: Addr::unchecked("simple happy desert"),
-        deposit_info: None,
-    };
-    v1_item.save(&mut deps.storage, &v1_config).unwrap();
+fn test_require_power_at_resolution() {
+    for require_power_at_resolution in [false, true] {
+        let mut app = App::default();
+        let govmod_id = app.store_code(proposal_contract());
 
-    let msg = MigrateMsg::FromV1 {
-        close_proposal_on_execution_failure: true,
-    };
-    migrate(deps.as_mut(), env.clone(), msg).unwrap();
-
-    // Verify migration.
-    let new_map: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
-    let proposals: Vec<(u64, SingleChoiceProposal)> = new_map
-        .range(&deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()
-        .unwrap();
-
-    let migrated_proposal = &proposals[0];
-    assert_eq!(migrated_proposal.0, 0);
-    assert_eq!(migrated_proposal.1.created, Timestamp::from_seconds(0));
-    assert_eq!(migrated_proposal.1.last_updated, env.block.time);
-
-    let new_item: Item<Config> = Item::new("config_v2");
-    let migrated_config = new_item.load(&deps.storage).unwrap();
-    assert_eq!(
-        migrated_config,
-        Config {
-            threshold,
-            max_voting_period,
+        let instantiate = InstantiateMsg {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(6),
             min_voting_period: None,
-            only_members_execute: true,
+            only_members_execute: false,
             allow_revoting: false,
-            dao: Addr::unchecked("simple happy desert"),
             deposit_info: None,
             close_proposal_on_execution_failure: true,
-        }
-    );
-}
-
-#[test]
-fn test_return_deposit_to_dao_on_proposal_failure() {
-    let (mut app, core_addr) = do_test_votes_cw20_balances(
-        vec![TestSingleChoiceVote {
-            voter: "ekez".to_string(),
-            position: Vote::No,
-            weight: Uint128::new(10),
-            should_execute: ShouldExecute::Yes,
-        }],
-        Threshold::AbsolutePercentage {
-            percentage: PercentageThreshold::Percent(Decimal::percent(90)),
-        },
-        Status::Open,
-        Some(Uint128::new(100)),
-        Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            refund_failed_proposals: false,
-        }),
-    );
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: None,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        };
+        let core_addr = instantiate_with_staked_balances_governance(
+            &mut app,
+            govmod_id,
+            instantiate,
+            Some(vec![
+                Cw20Coin {
+                    address: "ekez".to_string(),
+                    amount: Uint128::new(60),
+                },
+                Cw20Coin {
+                    address: "keze".to_string(),
+                    amount: Uint128::new(40),
+                },
+            ]),
+        );
+        let gov_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(core_addr, &cw_core::msg::QueryMsg::DumpState {})
+            .unwrap();
+        let govmod_single = gov_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address;
+        let staking_contract: Addr = app
+            .wrap()
+            .query_wasm_smart(
+                gov_state.voting_module,
+                &cw20_staked_balance_voting::msg::QueryMsg::StakingContract {},
+            )
+            .unwrap();
 
-    let core_state: cw_core::query::DumpStateResponse = app
-        .wrap()
-        .query_wasm_smart(core_addr.clone(), &cw_core::msg::QueryMsg::DumpState {})
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
         .unwrap();
-    let proposal_modules = core_state.proposal_modules;
-
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
-
-    // Make the proposal expire. It has now failed.
-    app.update_block(|block| block.height += 10);
-
-    // Close the proposal, this should work as the proposal is now
-    // open and expired.
-    app.execute_contract(
-        Addr::unchecked("keze"),
-        proposal_single.clone(),
-        &ExecuteMsg::Close { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
-
-    // Check that a refund was issued.
-    let proposal_config: Config = app
-        .wrap()
-        .query_wasm_smart(proposal_single, &QueryMsg::Config {})
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
         .unwrap();
-    let CheckedDepositInfo { token, .. } = proposal_config.deposit_info.unwrap();
-    let balance: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            token,
-            &cw20::Cw20QueryMsg::Balance {
-                address: core_addr.into_string(),
+
+        // Having passed the proposal, ekez unstakes everything.
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            staking_contract,
+            &cw20_stake::msg::ExecuteMsg::Unstake {
+                amount: Uint128::new(60),
             },
+            &[],
         )
         .unwrap();
+        app.update_block(next_block);
 
-    // Deposit should now belong to the DAO.
-    assert_eq!(balance.balance, Uint128::new(1));
+        app.execute_contract(
+            Addr::unchecked("keze"),
+            govmod_single.clone(),
+            &ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::Proposal { proposal_id: 1 },
+            )
+            .unwrap();
+        if !require_power_at_resolution {
+            assert_eq!(proposal.proposal.status, Status::Executed);
+            assert_eq!(proposal.proposal.votes.yes, Uint128::new(60));
+            continue;
+        }
+
+        // ekez's vote no longer counts so the proposal is open again
+        // and may not be executed.
+        assert_eq!(proposal.proposal.status, Status::Open);
+        assert_eq!(proposal.proposal.votes, Votes::zero());
+        let vote: VoteResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::GetVote {
+                    proposal_id: 1,
+                    voter: "ekez".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(vote.vote, None);
+
+        // Having no voting power, ekez may not vote again with their
+        // power at the proposal's snapshot.
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked("ekez"),
+                govmod_single.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote: Vote::Yes,
+                },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(err, ContractError::NotRegistered {}));
+
+        // Without further votes the proposal is rejected.
+        app.update_block(|block| block.height += 6);
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked("keze"),
+                govmod_single.clone(),
+                &ExecuteMsg::Execute { proposal_id: 1 },
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert!(matches!(err, ContractError::NotPassed {}));
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+            .unwrap();
+        assert_eq!(proposal.proposal.status, Status::Rejected);
+    }
 }
 
 #[test]
-fn test_close_failed_proposal() {
+fn test_no_double_refund_on_execute_fail_and_close() {
     let mut app = App::default();
-    let govmod_id = app.store_code(proposal_contract());
+    let proposal_module_id = app.store_code(proposal_contract());
 
     let threshold = Threshold::AbsolutePercentage {
         percentage: PercentageThreshold::Majority {},
@@ -4514,16 +17957,63 @@ fn test_close_failed_proposal() {
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
-        deposit_info: None,
+        deposit_info: Some(DepositInfo {
+            token: DepositToken::VotingModuleToken {},
+            deposit: Uint128::new(1),
+            // Important to set to true here as we want to be sure
+            // that we don't get a second refund on close. Refunds on
+            // close only happen if this is true.
+            refund_failed_proposals: true,
+        }),
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
-    let governance_addr =
-        instantiate_with_staking_active_threshold(&mut app, govmod_id, instantiate, None, None);
-    let governance_modules: Vec<ProposalModule> = app
+    let core_addr = instantiate_with_staking_active_threshold(
+        &mut app,
+        proposal_module_id,
+        instantiate,
+        Some(vec![Cw20Coin {
+            address: CREATOR_ADDR.to_string(),
+            // One token for sending to the DAO treasury, one token
+            // for staking, one token for paying the proposal deposit.
+            amount: Uint128::new(3),
+        }]),
+        None,
+    );
+    let proposal_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            governance_addr,
+            core_addr,
             &cw_core::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
@@ -4531,14 +18021,14 @@ fn test_close_failed_proposal() {
         )
         .unwrap();
 
-    assert_eq!(governance_modules.len(), 1);
-    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+    assert_eq!(proposal_modules.len(), 1);
+    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
 
-    let govmod_config: Config = app
+    let proposal_config: Config = app
         .wrap()
-        .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
+        .query_wasm_smart(proposal_single.clone(), &QueryMsg::Config {})
         .unwrap();
-    let dao = govmod_config.dao;
+    let dao = proposal_config.dao;
     let voting_module: Addr = app
         .wrap()
         .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
@@ -4558,10 +18048,10 @@ fn test_close_failed_proposal() {
         )
         .unwrap();
 
-    // Stake some tokens so we can propose
+    // Stake a token so we can propose.
     let msg = cw20::Cw20ExecuteMsg::Send {
         contract: staking_contract.to_string(),
-        amount: Uint128::new(2000),
+        amount: Uint128::new(1),
         msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
     };
     app.execute_contract(
@@ -4573,24 +18063,56 @@ fn test_close_failed_proposal() {
     .unwrap();
     app.update_block(next_block);
 
+    // Send some tokens to the proposal module so it has the ability
+    // to double refund if the code is buggy.
+    let msg = cw20::Cw20ExecuteMsg::Transfer {
+        recipient: proposal_single.to_string(),
+        amount: Uint128::new(1),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        token_contract.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
+
     let msg = cw20::Cw20ExecuteMsg::Burn {
         amount: Uint128::new(2000),
     };
     let binary_msg = to_binary(&msg).unwrap();
 
-    // Overburn tokens
+    // Increase allowance to pay the proposal deposit.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        govmod_single.clone(),
+        token_contract.clone(),
+        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
+            spender: proposal_single.to_string(),
+            amount: Uint128::new(1),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // proposal to overburn tokens
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_single.clone(),
         &ExecuteMsg::Propose {
             title: "A simple burn tokens proposal".to_string(),
             description: "Burning more tokens, than dao treasury have".to_string(),
             msgs: vec![WasmMsg::Execute {
                 contract_addr: token_contract.to_string(),
-                msg: binary_msg.clone(),
+                msg: binary_msg,
                 funds: vec![],
             }
             .into()],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
@@ -4599,7 +18121,7 @@ fn test_close_failed_proposal() {
     // Vote on proposal
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        govmod_single.clone(),
+        proposal_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
@@ -4611,191 +18133,853 @@ fn test_close_failed_proposal() {
     let timestamp = Timestamp::from_seconds(300_000_000);
     app.update_block(|block| block.time = timestamp);
 
-    // Execute proposal
-    app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        govmod_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
-        &[],
+    // Execute proposal
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        proposal_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+
+    let failed: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            proposal_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
+    assert_eq!(failed.proposal.last_updated, app.block_info().time);
+
+    // Check that our deposit has been refunded.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token_contract.to_string(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(balance.balance, Uint128::new(1));
+
+    // Close the proposal - this should fail as it was executed.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_single,
+            &ExecuteMsg::Close { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+
+    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+
+    // Check that our deposit was not refunded a second time on close.
+    let balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token_contract.to_string(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(balance.balance, Uint128::new(1));
+}
+
+#[test]
+pub fn test_migrate_update_version() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+    assert_eq!(version.contract, CONTRACT_NAME);
+}
+
+#[test]
+fn test_migrate_config_stored_by_v2() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.2.0").unwrap();
+
+    // A config as stored by v0.2.0, before every field after
+    // `close_proposal_on_execution_failure` was added.
+    let stored = br#"{
+        "threshold": {"absolute_percentage": {"percentage": {"majority": {}}}},
+        "max_voting_period": {"height": 6},
+        "min_voting_period": null,
+        "only_members_execute": false,
+        "allow_revoting": true,
+        "dao": "dao",
+        "deposit_info": null,
+        "close_proposal_on_execution_failure": true
+    }"#;
+    deps.storage.set(b"config_v2", stored);
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(6),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: true,
+            dao: Addr::unchecked("dao"),
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit: VotingPeriodLimit::default(),
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        }
+    );
+
+    // The module may be queried after the migration.
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    assert_eq!(from_slice::<Config>(&res).unwrap(), config);
+}
+
+#[test]
+fn test_migrate_initializes_deposit_accounting() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.2.0").unwrap();
+    let stored = br#"{
+        "threshold": {"absolute_percentage": {"percentage": {"majority": {}}}},
+        "max_voting_period": {"height": 6},
+        "min_voting_period": null,
+        "only_members_execute": false,
+        "allow_revoting": false,
+        "dao": "dao",
+        "deposit_info": {"token": "token", "deposit": "10", "refund_failed_proposals": true},
+        "close_proposal_on_execution_failure": true
+    }"#;
+    deps.storage.set(b"config_v2", stored);
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::DepositAccounting {}).unwrap();
+    let res: DepositAccountingResponse = from_slice(&res).unwrap();
+    assert_eq!(
+        res.deposits,
+        vec![TokenDepositAccounting {
+            token: Addr::unchecked("token"),
+            collected: Uint128::zero(),
+            refunded: Uint128::zero(),
+            forfeited: Uint128::zero(),
+        }]
+    );
+}
+
+#[test]
+fn test_migrate_applies_pending_config() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.2.0").unwrap();
+    let config = br#"{
+        "threshold": {"absolute_percentage": {"percentage": {"majority": {}}}},
+        "max_voting_period": {"height": 6},
+        "min_voting_period": null,
+        "only_members_execute": false,
+        "allow_revoting": false,
+        "dao": "dao",
+        "deposit_info": null,
+        "close_proposal_on_execution_failure": true
+    }"#;
+    deps.storage.set(b"config_v2", config);
+    // A pending update that took effect before the migration and is
+    // missing the fields added since it was stored.
+    let pending = format!(
+        r#"{{
+            "config": {{
+                "threshold": {{"absolute_percentage": {{"percentage": {{"majority": {{}}}}}}}},
+                "max_voting_period": {{"height": 6}},
+                "min_voting_period": null,
+                "only_members_execute": false,
+                "allow_revoting": true,
+                "dao": "dao",
+                "deposit_info": null,
+                "close_proposal_on_execution_failure": true
+            }},
+            "effective": {{"at_height": {}}}
+        }}"#,
+        env.block.height
+    );
+    deps.storage.set(b"pending_config", pending.as_bytes());
+
+    migrate(deps.as_mut(), env.clone(), MigrateMsg::FromCompatible {}).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert!(config.allow_revoting);
+    assert_eq!(config.config_change_delay, None);
+    let res = query(deps.as_ref(), env, QueryMsg::PendingConfig {}).unwrap();
+    let pending: Option<PendingConfig> = from_slice(&res).unwrap();
+    assert_eq!(pending, None);
+}
+
+#[test]
+fn test_migrate_consolidate_duplicate_ballots() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    let proposal = |votes: Votes| SingleChoiceProposal {
+        title: "A simple text proposal".to_string(),
+        description: "This is a simple text proposal".to_string(),
+        proposer: Addr::unchecked(CREATOR_ADDR),
+        start_height: env.block.height,
+        expiration: Duration::Height(6).after(&env.block),
+        min_voting_period: None,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        allow_revoting: true,
+        total_power: Uint128::new(100),
+        msgs: vec![],
+        status: Status::Open,
+        votes,
+        deposit_info: None,
+        created: env.block.time,
+        last_updated: env.block.time,
+        execution_error: None,
+        seconding_deadline: None,
+        execution_funds: vec![],
+        quorum_reached: false,
+        voting_module: None,
+        require_power_at_resolution: false,
+        related_proposals: vec![],
+        last_execution_attempt: None,
+        extensions: 0,
+        native_deposit: None,
+        snapshot_height: None,
+        close_proposal_on_execution_failure: None,
+        reveal_period: None,
+        revote_settle_period: None,
+        settlement: None,
+        metadata: None,
+        vote_count: None,
+        voting_module_code_id: None,
+        module_version: None,
+        non_voter_default: None,
+        resolved_at: None,
+        execution_retry_cooldown: None,
+        original_proposer: None,
+    };
+    let cast = |storage: &mut dyn cosmwasm_std::Storage,
+                proposal_id: u64,
+                voter: &str,
+                vote: Vote,
+                power: u128,
+                height: Option<u64>| {
+        let ballot = Ballot {
+            power: Uint128::new(power),
+            vote,
+            height,
+        };
+        BALLOTS
+            .save(storage, (proposal_id, Addr::unchecked(voter)), &ballot)
+            .unwrap();
+        BALLOTS_BY_POSITION
+            .save(
+                storage,
+                (proposal_id, vote.to_string(), Addr::unchecked(voter)),
+                &ballot.power,
+            )
+            .unwrap();
+    };
+
+    // Proposal 1 has a more recent duplicate ballot from an upper
+    // case address and both have been counted. Proposal 2 has
+    // duplicates without heights or a lowercase ballot and proposal 3
+    // is clean.
+    PROPOSALS
+        .save(
+            &mut deps.storage,
+            1,
+            &proposal(Votes {
+                yes: Uint128::new(10),
+                no: Uint128::new(10),
+                abstain: Uint128::new(5),
+            }),
+        )
+        .unwrap();
+    cast(&mut deps.storage, 1, "ekez", Vote::Yes, 10, Some(7));
+    cast(&mut deps.storage, 1, "EKEZ", Vote::No, 10, Some(9));
+    cast(&mut deps.storage, 1, "keze", Vote::Abstain, 5, Some(7));
+    PROPOSALS
+        .save(
+            &mut deps.storage,
+            2,
+            &proposal(Votes {
+                yes: Uint128::new(10),
+                no: Uint128::new(10),
+                abstain: Uint128::zero(),
+            }),
+        )
+        .unwrap();
+    cast(&mut deps.storage, 2, "EKEZ", Vote::Yes, 10, None);
+    cast(&mut deps.storage, 2, "Ekez", Vote::No, 10, None);
+    PROPOSALS
+        .save(
+            &mut deps.storage,
+            3,
+            &proposal(Votes::with_yes(Uint128::new(10))),
+        )
+        .unwrap();
+    cast(&mut deps.storage, 3, "ekez", Vote::Yes, 10, Some(7));
+
+    let res = migrate(
+        deps.as_mut(),
+        env,
+        MigrateMsg::ConsolidateDuplicateBallots {},
+    )
+    .unwrap();
+    let attribute = |key: &str| {
+        res.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+            .unwrap()
+            .value
+            .clone()
+    };
+    assert_eq!(attribute("proposal_ids"), "1,2");
+    assert_eq!(attribute("removed_ballots"), "2");
+
+    let ballots = |proposal_id: u64| {
+        BALLOTS
+            .prefix(proposal_id)
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(Addr, Ballot)>>>()
+            .unwrap()
+    };
+
+    // The most recent ballot is kept under the lowercase address.
+    assert_eq!(
+        ballots(1),
+        vec![
+            (
+                Addr::unchecked("ekez"),
+                Ballot {
+                    power: Uint128::new(10),
+                    vote: Vote::No,
+                    height: Some(9),
+                }
+            ),
+            (
+                Addr::unchecked("keze"),
+                Ballot {
+                    power: Uint128::new(5),
+                    vote: Vote::Abstain,
+                    height: Some(7),
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        PROPOSALS.load(&deps.storage, 1).unwrap().votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(10),
+            abstain: Uint128::new(5),
+        }
+    );
+    assert!(!BALLOTS_BY_POSITION.has(
+        &deps.storage,
+        (1, Vote::Yes.to_string(), Addr::unchecked("ekez"))
+    ));
+    assert!(!BALLOTS_BY_POSITION.has(
+        &deps.storage,
+        (1, Vote::No.to_string(), Addr::unchecked("EKEZ"))
+    ));
+
+    // Without heights the last ballot ordered by address is kept.
+    assert_eq!(
+        ballots(2),
+        vec![(
+            Addr::unchecked("ekez"),
+            Ballot {
+                power: Uint128::new(10),
+                vote: Vote::No,
+                height: None,
+            }
+        )]
+    );
+    assert_eq!(
+        PROPOSALS.load(&deps.storage, 2).unwrap().votes,
+        Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(10),
+            abstain: Uint128::zero(),
+        }
+    );
+    assert_eq!(
+        BALLOTS_BY_POSITION
+            .load(
+                &deps.storage,
+                (2, Vote::No.to_string(), Addr::unchecked("ekez"))
+            )
+            .unwrap(),
+        Uint128::new(10)
+    );
+
+    // Clean proposals are untouched.
+    assert_eq!(ballots(3).len(), 1);
+    assert_eq!(
+        PROPOSALS.load(&deps.storage, 3).unwrap().votes,
+        Votes::with_yes(Uint128::new(10))
+    );
+}
+
+#[test]
+fn test_load_proposal_stored_before_new_variants() {
+    let mut deps = mock_dependencies();
+
+    // A proposal as stored before `ThresholdMode`, the `Pending` and
+    // `Revealing` statuses, `SimpleQuorumMajority`, and every
+    // `#[serde(default)]` proposal field were added.
+    let stored = br#"{
+        "title": "A simple text proposal",
+        "description": "This is a simple text proposal",
+        "proposer": "creator",
+        "start_height": 12345,
+        "min_voting_period": null,
+        "expiration": {"at_height": 12351},
+        "threshold": {"threshold_quorum": {"threshold": {"majority": {}}, "quorum": {"percent": "0.3"}}},
+        "total_power": "100",
+        "msgs": [],
+        "status": "execution_failed",
+        "votes": {"yes": "60", "no": "10", "abstain": "0"},
+        "allow_revoting": false,
+        "deposit_info": null,
+        "created": "1571797419879305533",
+        "last_updated": "1571797419879305533"
+    }"#;
+    deps.storage.set(&PROPOSALS.key(1), stored);
+
+    let proposal = PROPOSALS.load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        proposal,
+        SingleChoiceProposal {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            proposer: Addr::unchecked("creator"),
+            start_height: 12345,
+            min_voting_period: None,
+            expiration: Expiration::AtHeight(12351),
+            threshold: Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            total_power: Uint128::new(100),
+            msgs: vec![],
+            status: Status::ExecutionFailed,
+            votes: Votes {
+                yes: Uint128::new(60),
+                no: Uint128::new(10),
+                abstain: Uint128::zero(),
+            },
+            allow_revoting: false,
+            deposit_info: None,
+            created: Timestamp::from_nanos(1571797419879305533),
+            last_updated: Timestamp::from_nanos(1571797419879305533),
+            execution_error: None,
+            seconding_deadline: None,
+            execution_funds: vec![],
+            quorum_reached: false,
+            voting_module: None,
+            require_power_at_resolution: false,
+            related_proposals: vec![],
+            last_execution_attempt: None,
+            extensions: 0,
+            native_deposit: None,
+            snapshot_height: None,
+            close_proposal_on_execution_failure: None,
+            reveal_period: None,
+            revote_settle_period: None,
+            settlement: None,
+            metadata: None,
+            vote_count: None,
+            voting_module_code_id: None,
+            module_version: None,
+            non_voter_default: None,
+            resolved_at: None,
+            execution_retry_cooldown: None,
+            original_proposer: None,
+        }
+    );
+
+    // The proposal may also be queried.
+    let res: ProposalResponse = from_slice(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap(),
     )
     .unwrap();
+    assert_eq!(res.proposal, proposal);
+}
 
-    let failed: ProposalResponse = app
+#[test]
+fn test_native_propose_deposit() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
+
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: Some(Coin::new(10, "ujuno")),
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_cw20_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate.clone(),
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(50),
+            },
+        ]),
+    );
+    let gov_state: cw_core::query::DumpStateResponse = app
         .wrap()
         .query_wasm_smart(
-            govmod_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::DumpState {},
         )
         .unwrap();
-    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
-    assert_eq!(failed.proposal.last_updated, app.block_info().time);
+    let govmod_single = gov_state
+        .proposal_modules
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
 
-    // With disabled feature
-    // Disable feature first
-    {
-        let original: Config = app
-            .wrap()
-            .query_wasm_smart(govmod_single.clone(), &QueryMsg::Config {})
-            .unwrap();
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: CREATOR_ADDR.to_string(),
+        amount: vec![Coin::new(20, "ujuno"), Coin::new(10, "uatom")],
+    }))
+    .unwrap();
 
+    let propose = |app: &mut App, funds: &[Coin]| {
         app.execute_contract(
             Addr::unchecked(CREATOR_ADDR),
             govmod_single.clone(),
             &ExecuteMsg::Propose {
-                title: "Disable closing failed proposals".to_string(),
-                description: "We want to re-execute failed proposals".to_string(),
-                msgs: vec![WasmMsg::Execute {
-                    contract_addr: govmod_single.to_string(),
-                    msg: to_binary(&ExecuteMsg::UpdateConfig {
-                        threshold: original.threshold,
-                        max_voting_period: original.max_voting_period,
-                        min_voting_period: original.min_voting_period,
-                        only_members_execute: original.only_members_execute,
-                        allow_revoting: original.allow_revoting,
-                        dao: original.dao.to_string(),
-                        deposit_info: None,
-                        close_proposal_on_execution_failure: false,
-                    })
-                    .unwrap(),
-                    funds: vec![],
-                }
-                .into()],
+                title: "A simple text proposal".to_string(),
+                description: "This is a simple text proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
             },
-            &[],
+            funds,
         )
-        .unwrap();
+    };
+    let balance = |app: &App, address: &str| {
+        app.wrap()
+            .query_balance(address.to_string(), "ujuno")
+            .unwrap()
+            .amount
+            .u128()
+    };
 
-        // Vote on proposal
-        app.execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            govmod_single.clone(),
-            &ExecuteMsg::Vote {
-                proposal_id: 2,
-                vote: Vote::Yes,
-            },
-            &[],
-        )
-        .unwrap();
+    // Exactly the deposit must be sent.
+    for funds in [
+        vec![],
+        vec![Coin::new(5, "ujuno")],
+        vec![Coin::new(10, "uatom")],
+        vec![Coin::new(10, "uatom"), Coin::new(10, "ujuno")],
+    ] {
+        let err: ContractError = propose(&mut app, &funds).unwrap_err().downcast().unwrap();
+        assert!(matches!(
+            err,
+            ContractError::NativeDepositMismatch { expected } if expected == Coin::new(10, "ujuno")
+        ));
+    }
 
-        // Execute proposal
-        app.execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
+    propose(&mut app, &[Coin::new(10, "ujuno")]).unwrap();
+    propose(&mut app, &[Coin::new(10, "ujuno")]).unwrap();
+    assert_eq!(balance(&app, CREATOR_ADDR), 0);
+    assert_eq!(balance(&app, govmod_single.as_str()), 20);
+
+    // Native deposits are owed until they are returned or forfeited.
+    let owed = |app: &App| -> u128 {
+        let solvency: DepositSolvencyResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::DepositSolvency {
+                    asset: DepositAsset::Native {
+                        denom: "ujuno".to_string(),
+                    },
+                },
+            )
+            .unwrap();
+        assert!(solvency.solvent);
+        solvency.owed.u128()
+    };
+    assert_eq!(owed(&app), 20);
+
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
             govmod_single.clone(),
-            &ExecuteMsg::Execute { proposal_id: 2 },
-            &[],
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
-    }
+    assert_eq!(
+        proposal.proposal.native_deposit,
+        Some(Coin::new(10, "ujuno"))
+    );
 
-    // Overburn tokens (again), this time without reverting
+    // The deposit is returned when the proposal is executed.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
         govmod_single.clone(),
-        &ExecuteMsg::Propose {
-            title: "A simple burn tokens proposal".to_string(),
-            description: "Burning more tokens, than dao treasury have".to_string(),
-            msgs: vec![WasmMsg::Execute {
-                contract_addr: token_contract.to_string(),
-                msg: binary_msg,
-                funds: vec![],
-            }
-            .into()],
+        &ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
         },
         &[],
     )
     .unwrap();
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &ExecuteMsg::Execute { proposal_id: 1 },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(balance(&app, CREATOR_ADDR), 10);
 
-    // Vote on proposal
+    // And sent to the DAO when the proposal is closed.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
         govmod_single.clone(),
         &ExecuteMsg::Vote {
-            proposal_id: 3,
-            vote: Vote::Yes,
+            proposal_id: 2,
+            vote: Vote::No,
         },
         &[],
     )
     .unwrap();
-
-    // Execute proposal
     app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
+        Addr::unchecked("ekez"),
         govmod_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 3 },
+        &ExecuteMsg::Close { proposal_id: 2 },
         &[],
     )
-    .expect_err("Should be sub overflow");
+    .unwrap();
+    assert_eq!(balance(&app, CREATOR_ADDR), 10);
+    assert_eq!(balance(&app, governance_addr.as_str()), 10);
+    assert_eq!(balance(&app, govmod_single.as_str()), 0);
+    assert_eq!(owed(&app), 0);
 
-    // Status should still be passed
-    let updated: ProposalResponse = app
-        .wrap()
-        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 3 })
+    // A native deposit may not be configured alongside a cw20 one.
+    let err: ContractError = app
+        .execute_contract(
+            governance_addr.clone(),
+            govmod_single,
+            &ExecuteMsg::UpdateConfig {
+                threshold: instantiate.threshold,
+                max_voting_period: instantiate.max_voting_period,
+                min_voting_period: None,
+                only_members_execute: false,
+                allow_revoting: false,
+                dao: governance_addr.to_string(),
+                deposit_info: Some(DepositInfo {
+                    token: DepositToken::VotingModuleToken {},
+                    deposit: Uint128::new(1),
+                    refund_failed_proposals: false,
+                }),
+                close_proposal_on_execution_failure: true,
+                exclude_dao_voting_power: false,
+                execution_callback: None,
+                require_second: None,
+                record_config_history: false,
+                quorum_reached_hooks: false,
+                require_power_at_resolution: false,
+                execution_retry_cooldown: None,
+                post_execution_cooldown: None,
+                quorum_extension: None,
+                native_propose_deposit: Some(Coin::new(10, "ujuno")),
+                snapshot_offset: 0,
+                pre_execution_hook: None,
+                required_description_sections: vec![],
+                voting_period_limit: None,
+                register_deposit_token: false,
+                total_power_override: None,
+                record_vote_history: false,
+                execute_on_pass: false,
+                reveal_period: None,
+                hook_allowlist: None,
+                revote_settle_period: None,
+                deposit_escalation: None,
+                forfeited_deposit_policy: None,
+                reject_failing_execution: false,
+                non_voter_default: None,
+                max_proposal_bytes: None,
+                config_change_delay: None,
+                close_reward: None,
+                cache_voting_power: false,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
         .unwrap();
-
-    // not reverted
-    assert_eq!(updated.proposal.status, Status::Passed);
+    assert!(matches!(err, ContractError::DepositConflict {}));
 }
 
 #[test]
-fn test_no_double_refund_on_execute_fail_and_close() {
+fn test_snapshot_offset() {
     let mut app = App::default();
-    let proposal_module_id = app.store_code(proposal_contract());
+    let govmod_id = app.store_code(proposal_contract());
 
-    let threshold = Threshold::AbsolutePercentage {
-        percentage: PercentageThreshold::Majority {},
-    };
-    let max_voting_period = cw_utils::Duration::Height(6);
     let instantiate = InstantiateMsg {
-        threshold,
-        max_voting_period,
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
         min_voting_period: None,
         only_members_execute: false,
         allow_revoting: false,
-        deposit_info: Some(DepositInfo {
-            token: DepositToken::VotingModuleToken {},
-            deposit: Uint128::new(1),
-            // Important to set to true here as we want to be sure
-            // that we don't get a second refund on close. Refunds on
-            // close only happen if this is true.
-            refund_failed_proposals: true,
-        }),
+        deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 5,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
-
-    let core_addr = instantiate_with_staking_active_threshold(
+    let governance_addr = instantiate_with_staked_balances_governance(
         &mut app,
-        proposal_module_id,
+        govmod_id,
         instantiate,
         Some(vec![Cw20Coin {
             address: CREATOR_ADDR.to_string(),
-            // One token for sending to the DAO treasury, one token
-            // for staking, one token for paying the proposal deposit.
-            amount: Uint128::new(3),
+            amount: Uint128::new(100),
         }]),
-        None,
     );
-    let proposal_modules: Vec<ProposalModule> = app
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            core_addr,
+            governance_addr.clone(),
             &cw_core::msg::QueryMsg::ProposalModules {
                 start_after: None,
                 limit: None,
             },
         )
         .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
-    assert_eq!(proposal_modules.len(), 1);
-    let proposal_single = proposal_modules.into_iter().next().unwrap().address;
-
-    let proposal_config: Config = app
-        .wrap()
-        .query_wasm_smart(proposal_single.clone(), &QueryMsg::Config {})
-        .unwrap();
-    let dao = proposal_config.dao;
     let voting_module: Addr = app
         .wrap()
-        .query_wasm_smart(dao, &cw_core::msg::QueryMsg::VotingModule {})
+        .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::VotingModule {})
         .unwrap();
     let staking_contract: Addr = app
         .wrap()
@@ -4812,75 +18996,102 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         )
         .unwrap();
 
-    // Stake a token so we can propose.
-    let msg = cw20::Cw20ExecuteMsg::Send {
-        contract: staking_contract.to_string(),
-        amount: Uint128::new(1),
-        msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
-    };
+    // Unstake and claim half of the creator's tokens and give them
+    // to ekez.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        token_contract.clone(),
-        &msg,
+        staking_contract.clone(),
+        &cw20_stake::msg::ExecuteMsg::Unstake {
+            amount: Uint128::new(50),
+        },
         &[],
     )
     .unwrap();
-    app.update_block(next_block);
-
-    // Send some tokens to the proposal module so it has the ability
-    // to double refund if the code is buggy.
-    let msg = cw20::Cw20ExecuteMsg::Transfer {
-        recipient: proposal_single.to_string(),
-        amount: Uint128::new(1),
-    };
+    app.update_block(|block| block.height += 6);
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        token_contract.clone(),
-        &msg,
+        staking_contract.clone(),
+        &cw20_stake::msg::ExecuteMsg::Claim {},
         &[],
     )
     .unwrap();
-
-    let msg = cw20::Cw20ExecuteMsg::Burn {
-        amount: Uint128::new(2000),
-    };
-    let binary_msg = to_binary(&msg).unwrap();
-
-    // Increase allowance to pay the proposal deposit.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
         token_contract.clone(),
-        &cw20_base::msg::ExecuteMsg::IncreaseAllowance {
-            spender: proposal_single.to_string(),
-            amount: Uint128::new(1),
-            expires: None,
+        &cw20::Cw20ExecuteMsg::Transfer {
+            recipient: "ekez".to_string(),
+            amount: Uint128::new(50),
         },
         &[],
     )
     .unwrap();
 
-    // proposal to overburn tokens
+    // ekez stakes their tokens one block before the proposal is
+    // created.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        token_contract,
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: staking_contract.to_string(),
+            amount: Uint128::new(50),
+            msg: to_binary(&cw20_stake::msg::ReceiveMsg::Stake {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        proposal_single.clone(),
+        govmod_single.clone(),
         &ExecuteMsg::Propose {
-            title: "A simple burn tokens proposal".to_string(),
-            description: "Burning more tokens, than dao treasury have".to_string(),
-            msgs: vec![WasmMsg::Execute {
-                contract_addr: token_contract.to_string(),
-                msg: binary_msg,
-                funds: vec![],
-            }
-            .into()],
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
     .unwrap();
 
-    // Vote on proposal
+    // Voting power is snapshotted five blocks before the proposal
+    // was created, before ekez staked.
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap();
+    let height = app.block_info().height;
+    assert_eq!(proposal.proposal.start_height, height);
+    assert_eq!(proposal.proposal.snapshot_height, Some(height - 5));
+    assert_eq!(proposal.proposal.total_power, Uint128::new(50));
+
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::No,
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::NotRegistered {}));
+
+    // The creator's remaining stake is all of the power on the
+    // proposal.
     app.execute_contract(
         Addr::unchecked(CREATOR_ADDR),
-        proposal_single.clone(),
+        govmod_single.clone(),
         &ExecuteMsg::Vote {
             proposal_id: 1,
             vote: Vote::Yes,
@@ -4888,76 +19099,659 @@ fn test_no_double_refund_on_execute_fail_and_close() {
         &[],
     )
     .unwrap();
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+        .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Passed);
+    assert_eq!(proposal.proposal.votes.yes, Uint128::new(50));
+}
 
-    let timestamp = Timestamp::from_seconds(300_000_000);
-    app.update_block(|block| block.time = timestamp);
-
-    // Execute proposal
-    app.execute_contract(
-        Addr::unchecked(CREATOR_ADDR),
-        proposal_single.clone(),
-        &ExecuteMsg::Execute { proposal_id: 1 },
-        &[],
-    )
-    .unwrap();
+#[test]
+fn test_required_description_sections() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
-    let failed: ProposalResponse = app
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec!["Summary".to_string(), "Motivation".to_string()],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr =
+        instantiate_with_cw20_balances_governance(&mut app, govmod_id, instantiate, None);
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            proposal_single.clone(),
-            &QueryMsg::Proposal { proposal_id: 1 },
+            governance_addr,
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
         )
         .unwrap();
-    assert_eq!(failed.proposal.status, Status::ExecutionFailed);
-    assert_eq!(failed.proposal.last_updated, app.block_info().time);
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
-    // Check that our deposit has been refunded.
-    let balance: cw20::BalanceResponse = app
+    let propose = |app: &mut App, description: &str| {
+        app.execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: description.to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+    };
+
+    // Headers are matched case-insensitively, with or without
+    // markdown heading markers.
+    propose(
+        &mut app,
+        "# summary\nDo the thing.\n\nMOTIVATION:\nThe thing is good.",
+    )
+    .unwrap();
+    let proposal: ProposalResponse = app
         .wrap()
         .query_wasm_smart(
-            token_contract.to_string(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: CREATOR_ADDR.to_string(),
-            },
+            govmod_single.clone(),
+            &QueryMsg::Proposal { proposal_id: 1 },
         )
         .unwrap();
+    assert_eq!(proposal.proposal.status, Status::Open);
 
-    assert_eq!(balance.balance, Uint128::new(1));
+    // Mentioning a section in the body is not a header.
+    let err: ContractError = propose(
+        &mut app,
+        "## Summary\nThe motivation is that the thing is good.",
+    )
+    .unwrap_err()
+    .downcast()
+    .unwrap();
+    match err {
+        ContractError::MissingDescriptionSection { section } => {
+            assert_eq!(section, "Motivation")
+        }
+        _ => panic!("unexpected error: {:?}", err),
+    }
 
-    // Close the proposal - this should fail as it was executed.
-    let err: ContractError = app
-        .execute_contract(
-            Addr::unchecked(CREATOR_ADDR),
-            proposal_single,
-            &ExecuteMsg::Close { proposal_id: 1 },
-            &[],
-        )
+    let err: ContractError = propose(&mut app, "Do the thing.")
         .unwrap_err()
         .downcast()
         .unwrap();
+    match err {
+        ContractError::MissingDescriptionSection { section } => assert_eq!(section, "Summary"),
+        _ => panic!("unexpected error: {:?}", err),
+    }
+}
 
-    assert!(matches!(err, ContractError::WrongCloseStatus {}));
+#[test]
+fn test_effective_voting_power() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(proposal_contract());
 
-    // Check that our deposit was not refunded a second time on close.
-    let balance: cw20::BalanceResponse = app
+    let instantiate = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let instantiate_height = app.block_info().height;
+    let governance_addr = instantiate_with_staked_balances_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(100),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(50),
+            },
+        ]),
+    );
+    let governance_modules: Vec<ProposalModule> = app
         .wrap()
         .query_wasm_smart(
-            token_contract.to_string(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: CREATOR_ADDR.to_string(),
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
             },
         )
         .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
 
-    assert_eq!(balance.balance, Uint128::new(1));
+    let effective_power = |app: &App, address: &str, height: Option<u64>| {
+        let power: cw_core_interface::voting::VotingPowerAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                govmod_single.clone(),
+                &QueryMsg::EffectiveVotingPower {
+                    address: address.to_string(),
+                    height,
+                },
+            )
+            .unwrap();
+        power
+    };
+
+    let power = effective_power(&app, CREATOR_ADDR, None);
+    assert_eq!(power.power, Uint128::new(100));
+    assert_eq!(power.height, app.block_info().height);
+    assert_eq!(effective_power(&app, "ekez", None).power, Uint128::new(50));
+    assert_eq!(effective_power(&app, "keze", None).power, Uint128::zero());
+
+    // Without delegation the effective power is the power reported
+    // by the DAO's voting module, including at past heights.
+    for address in [CREATOR_ADDR, "ekez", "keze"].iter() {
+        for height in [Some(instantiate_height), None].iter() {
+            let expected: cw_core_interface::voting::VotingPowerAtHeightResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    governance_addr.clone(),
+                    &cw_core::msg::QueryMsg::VotingPowerAtHeight {
+                        address: address.to_string(),
+                        height: *height,
+                    },
+                )
+                .unwrap();
+            assert_eq!(effective_power(&app, address, *height), expected);
+        }
+    }
 }
 
 #[test]
-pub fn test_migrate_update_version() {
+fn test_voting_period_limit() {
+    let instantiate = |max_voting_period: Duration,
+                       voting_period_limit: Option<VotingPeriodLimit>| {
+        InstantiateMsg {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period,
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            deposit_info: None,
+            close_proposal_on_execution_failure: true,
+            exclude_dao_voting_power: false,
+            execution_callback: None,
+            require_second: None,
+            record_config_history: false,
+            quorum_reached_hooks: false,
+            require_power_at_resolution: false,
+            execution_retry_cooldown: None,
+            post_execution_cooldown: None,
+            quorum_extension: None,
+            native_propose_deposit: None,
+            snapshot_offset: 0,
+            pre_execution_hook: None,
+            required_description_sections: vec![],
+            voting_period_limit,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            id_prefix: None,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        }
+    };
+    let info = MessageInfo {
+        sender: Addr::unchecked(CREATOR_ADDR),
+        funds: vec![],
+    };
+    let default_limit = VotingPeriodLimit::default();
+
+    // Voting periods up to and including the default limit are
+    // allowed.
     let mut deps = mock_dependencies();
-    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
-    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
-    let version = cw2::get_contract_version(&deps.storage).unwrap();
-    assert_eq!(version.version, CONTRACT_VERSION);
-    assert_eq!(version.contract, CONTRACT_NAME);
+    crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(Duration::Time(default_limit.time), None),
+    )
+    .unwrap();
+    crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(Duration::Height(default_limit.height), None),
+    )
+    .unwrap();
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(Duration::Time(default_limit.time + 1), None),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodTooLong {}));
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(Duration::Height(default_limit.height + 1), None),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodTooLong {}));
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(Duration::Time(u64::MAX), None),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodTooLong {}));
+
+    // The limit may be raised up to, but not beyond, the largest
+    // allowed limit.
+    crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(
+            Duration::Time(MAX_VOTING_PERIOD_LIMIT.time),
+            Some(MAX_VOTING_PERIOD_LIMIT),
+        ),
+    )
+    .unwrap();
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(
+            Duration::Time(1),
+            Some(VotingPeriodLimit {
+                time: MAX_VOTING_PERIOD_LIMIT.time + 1,
+                height: MAX_VOTING_PERIOD_LIMIT.height,
+            }),
+        ),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodLimitTooLong {}));
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(
+            Duration::Height(1),
+            Some(VotingPeriodLimit {
+                time: MAX_VOTING_PERIOD_LIMIT.time,
+                height: MAX_VOTING_PERIOD_LIMIT.height + 1,
+            }),
+        ),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodLimitTooLong {}));
+
+    // Governance may adjust the limit when updating the config, and
+    // the new max voting period is checked against the new limit.
+    let update_config = |max_voting_period: Duration,
+                         voting_period_limit: Option<VotingPeriodLimit>|
+     -> ExecuteMsg {
+        let msg = instantiate(max_voting_period, voting_period_limit);
+        ExecuteMsg::UpdateConfig {
+            threshold: msg.threshold,
+            max_voting_period: msg.max_voting_period,
+            min_voting_period: msg.min_voting_period,
+            only_members_execute: msg.only_members_execute,
+            allow_revoting: msg.allow_revoting,
+            dao: CREATOR_ADDR.to_string(),
+            deposit_info: msg.deposit_info,
+            close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: msg.exclude_dao_voting_power,
+            execution_callback: msg.execution_callback,
+            require_second: msg.require_second,
+            record_config_history: msg.record_config_history,
+            quorum_reached_hooks: msg.quorum_reached_hooks,
+            require_power_at_resolution: msg.require_power_at_resolution,
+            execution_retry_cooldown: msg.execution_retry_cooldown,
+            post_execution_cooldown: msg.post_execution_cooldown,
+            quorum_extension: msg.quorum_extension,
+            native_propose_deposit: msg.native_propose_deposit,
+            snapshot_offset: msg.snapshot_offset,
+            pre_execution_hook: msg.pre_execution_hook,
+            required_description_sections: msg.required_description_sections,
+            voting_period_limit: msg.voting_period_limit,
+            register_deposit_token: false,
+            total_power_override: None,
+            record_vote_history: false,
+            execute_on_pass: false,
+            reveal_period: None,
+            hook_allowlist: None,
+            revote_settle_period: None,
+            deposit_escalation: None,
+            forfeited_deposit_policy: None,
+            reject_failing_execution: false,
+            non_voter_default: None,
+            max_proposal_bytes: None,
+            config_change_delay: None,
+            close_reward: None,
+            cache_voting_power: false,
+        }
+    };
+    let limit = VotingPeriodLimit {
+        time: 100,
+        height: 10,
+    };
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        update_config(Duration::Height(10), Some(limit)),
+    )
+    .unwrap();
+    let config: Config =
+        from_slice(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.voting_period_limit, limit);
+    let err = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        update_config(Duration::Height(11), Some(limit)),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodTooLong {}));
+    let err = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        update_config(
+            Duration::Time(MAX_VOTING_PERIOD_LIMIT.time + 1),
+            Some(MAX_VOTING_PERIOD_LIMIT),
+        ),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::VotingPeriodTooLong {}));
+
+    // Without a limit the default is used.
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        update_config(Duration::Height(11), None),
+    )
+    .unwrap();
+    let config: Config =
+        from_slice(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.voting_period_limit, VotingPeriodLimit::default());
+}
+
+#[test]
+fn test_total_power_override() {
+    let instantiate = |total_power_override: Option<Uint128>| InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+
+    // Creates a proposal that a holder of 60 of the voting module's
+    // 100 tokens votes yes on and returns it.
+    let vote_yes = |total_power_override: Option<Uint128>| {
+        let mut app = App::default();
+        let govmod_id = app.store_code(proposal_contract());
+        let governance_addr = instantiate_with_cw20_balances_governance(
+            &mut app,
+            govmod_id,
+            instantiate(total_power_override),
+            Some(vec![
+                Cw20Coin {
+                    address: "ekez".to_string(),
+                    amount: Uint128::new(60),
+                },
+                Cw20Coin {
+                    address: "keze".to_string(),
+                    amount: Uint128::new(40),
+                },
+            ]),
+        );
+        let gov_state: cw_core::query::DumpStateResponse = app
+            .wrap()
+            .query_wasm_smart(governance_addr, &cw_core::msg::QueryMsg::DumpState {})
+            .unwrap();
+        let govmod_single = gov_state
+            .proposal_modules
+            .into_iter()
+            .next()
+            .unwrap()
+            .address;
+
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Propose {
+                title: "A simple proposal".to_string(),
+                description: "This is a simple proposal".to_string(),
+                msgs: vec![],
+                expiration_override: None,
+                execution_funds: vec![],
+                related_proposals: vec![],
+                threshold_override: None,
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("ekez"),
+            govmod_single.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: Vote::Yes,
+            },
+            &[],
+        )
+        .unwrap();
+        let proposal: ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(govmod_single, &QueryMsg::Proposal { proposal_id: 1 })
+            .unwrap();
+        proposal.proposal
+    };
+
+    // Measured against the voting module's total the vote reaches
+    // quorum and passes.
+    let proposal = vote_yes(None);
+    assert_eq!(proposal.total_power, Uint128::new(100));
+    assert_eq!(proposal.status, Status::Passed);
+
+    // Measured against a larger fixed supply it does not reach
+    // quorum. The voter's own power is unchanged.
+    let proposal = vote_yes(Some(Uint128::new(200)));
+    assert_eq!(proposal.total_power, Uint128::new(200));
+    assert_eq!(proposal.votes.yes, Uint128::new(60));
+    assert_eq!(proposal.status, Status::Open);
+
+    // A zero override is rejected at instantiation and when updating
+    // the config.
+    let info = MessageInfo {
+        sender: Addr::unchecked(CREATOR_ADDR),
+        funds: vec![],
+    };
+    let mut deps = mock_dependencies();
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        instantiate(Some(Uint128::zero())),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ZeroTotalPowerOverride {}));
+
+    let msg = instantiate(Some(Uint128::new(1)));
+    crate::contract::instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    let err = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateConfig {
+            threshold: msg.threshold,
+            max_voting_period: msg.max_voting_period,
+            min_voting_period: msg.min_voting_period,
+            only_members_execute: msg.only_members_execute,
+            allow_revoting: msg.allow_revoting,
+            dao: CREATOR_ADDR.to_string(),
+            deposit_info: msg.deposit_info,
+            close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
+            exclude_dao_voting_power: msg.exclude_dao_voting_power,
+            execution_callback: msg.execution_callback,
+            require_second: msg.require_second,
+            record_config_history: msg.record_config_history,
+            quorum_reached_hooks: msg.quorum_reached_hooks,
+            require_power_at_resolution: msg.require_power_at_resolution,
+            execution_retry_cooldown: msg.execution_retry_cooldown,
+            post_execution_cooldown: msg.post_execution_cooldown,
+            quorum_extension: msg.quorum_extension,
+            native_propose_deposit: msg.native_propose_deposit,
+            snapshot_offset: msg.snapshot_offset,
+            pre_execution_hook: msg.pre_execution_hook,
+            required_description_sections: msg.required_description_sections,
+            voting_period_limit: msg.voting_period_limit,
+            register_deposit_token: msg.register_deposit_token,
+            total_power_override: Some(Uint128::zero()),
+            record_vote_history: msg.record_vote_history,
+            execute_on_pass: msg.execute_on_pass,
+            reveal_period: msg.reveal_period,
+            hook_allowlist: msg.hook_allowlist,
+            revote_settle_period: msg.revote_settle_period,
+            deposit_escalation: msg.deposit_escalation,
+            forfeited_deposit_policy: msg.forfeited_deposit_policy,
+            reject_failing_execution: msg.reject_failing_execution,
+            non_voter_default: msg.non_voter_default,
+            max_proposal_bytes: msg.max_proposal_bytes,
+            config_change_delay: msg.config_change_delay,
+            close_reward: None,
+            cache_voting_power: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ZeroTotalPowerOverride {}));
 }
@@ -119,6 +119,7 @@ pub fn execute(
     match msg {
         ExecuteMsg::StakeChangeHook(msg) => execute_stake_changed(deps, env, info, msg),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Restake {} => execute_restake(deps, env, info),
         ExecuteMsg::Fund {} => execute_fund_native(deps, env, info),
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::UpdateRewardDuration { new_duration } => {
@@ -243,14 +244,7 @@ pub fn execute_claim(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response<Empty>, ContractError> {
-    update_rewards(&mut deps, &env, &info.sender)?;
-    let rewards = PENDING_REWARDS
-        .load(deps.storage, info.sender.clone())
-        .map_err(|_| NoRewardsClaimable {})?;
-    if rewards == Uint128::zero() {
-        return Err(ContractError::NoRewardsClaimable {});
-    }
-    PENDING_REWARDS.save(deps.storage, info.sender.clone(), &Uint128::zero())?;
+    let rewards = take_pending_rewards(&mut deps, &env, &info.sender)?;
     let config = CONFIG.load(deps.storage)?;
     let transfer_msg = get_transfer_msg(info.sender, rewards, config.reward_token)?;
     Ok(Response::new()
@@ -259,6 +253,58 @@ pub fn execute_claim(
         .add_attribute("amount", rewards))
 }
 
+pub fn execute_restake(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<Empty>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking_config: cw20_stake::state::Config = deps.querier.query_wasm_smart(
+        &config.staking_contract,
+        &cw20_stake::msg::QueryMsg::GetConfig {},
+    )?;
+    if config.reward_token != Cw20(staking_config.token_address.clone()) {
+        return Err(ContractError::RewardTokenNotStaked {});
+    }
+
+    let rewards = take_pending_rewards(&mut deps, &env, &info.sender)?;
+    // Staking triggers the staking contract's stake changed hook
+    // which updates the sender's rewards for their new balance.
+    let stake_msg = WasmMsg::Execute {
+        contract_addr: staking_config.token_address.into_string(),
+        msg: to_binary(&cw20::Cw20ExecuteMsg::Send {
+            contract: config.staking_contract.into_string(),
+            amount: rewards,
+            msg: to_binary(&cw20_stake::msg::ReceiveMsg::StakeFor {
+                address: info.sender.to_string(),
+            })?,
+        })?,
+        funds: vec![],
+    };
+    Ok(Response::new()
+        .add_message(stake_msg)
+        .add_attribute("action", "restake")
+        .add_attribute("amount", rewards))
+}
+
+/// Updates `addr`'s rewards and zeroes them, returning the amount
+/// that was pending.
+fn take_pending_rewards(
+    deps: &mut DepsMut,
+    env: &Env,
+    addr: &Addr,
+) -> Result<Uint128, ContractError> {
+    update_rewards(deps, env, addr)?;
+    let rewards = PENDING_REWARDS
+        .load(deps.storage, addr.clone())
+        .map_err(|_| NoRewardsClaimable {})?;
+    if rewards == Uint128::zero() {
+        return Err(ContractError::NoRewardsClaimable {});
+    }
+    PENDING_REWARDS.save(deps.storage, addr.clone(), &Uint128::zero())?;
+    Ok(rewards)
+}
+
 pub fn get_transfer_msg(recipient: Addr, amount: Uint128, denom: Denom) -> StdResult<CosmosMsg> {
     match denom {
         Denom::Native(denom) => Ok(BankMsg::Send {
@@ -1241,6 +1287,117 @@ mod tests {
         stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR3, 1);
     }
 
+    #[test]
+    fn test_restake() {
+        let mut app = mock_app();
+        let admin = Addr::unchecked(OWNER);
+        app.borrow_mut().update_block(|b| b.height = 0);
+        let cw20_addr = instantiate_cw20(
+            &mut app,
+            vec![
+                Cw20Coin {
+                    address: ADDR1.to_string(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: ADDR2.to_string(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: OWNER.to_string(),
+                    amount: Uint128::new(500000000),
+                },
+            ],
+        );
+        app.update_block(next_block);
+        let staking_addr = instantiate_staking(&mut app, cw20_addr.clone(), None);
+        app.update_block(next_block);
+        stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR1, 100);
+        stake_tokens(&mut app, &staking_addr, &cw20_addr, ADDR2, 100);
+
+        // Rewards are paid in the staked token.
+        let reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr.clone(),
+            Denom::Cw20(cw20_addr.clone()),
+            admin.clone(),
+            Addr::unchecked(MANAGER),
+        );
+        app.borrow_mut().update_block(|b| b.height = 1000);
+        fund_rewards_cw20(&mut app, &admin, cw20_addr.clone(), &reward_addr, 100000000);
+
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 500);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 500);
+
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            reward_addr.clone(),
+            &ExecuteMsg::Restake {},
+            &[],
+        )
+        .unwrap();
+
+        let staked: cw20_stake::msg::StakedBalanceAtHeightResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &staking_addr,
+                &cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+                    address: ADDR1.to_string(),
+                    height: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(staked.balance, Uint128::new(600));
+        assert_eq!(get_balance_cw20(&app, &cw20_addr, ADDR1), Uint128::zero());
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 0);
+
+        // Restaked rewards earn rewards.
+        app.borrow_mut().update_block(next_block);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR1, 857);
+        assert_pending_rewards(&mut app, &reward_addr, ADDR2, 642);
+
+        // Nothing can be restaked twice.
+        app.execute_contract(
+            Addr::unchecked(ADDR1),
+            reward_addr.clone(),
+            &ExecuteMsg::Restake {},
+            &[],
+        )
+        .unwrap();
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(ADDR1),
+                reward_addr,
+                &ExecuteMsg::Restake {},
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::NoRewardsClaimable {});
+
+        // Rewards in other tokens can not be restaked.
+        let native_reward_addr = setup_reward_contract(
+            &mut app,
+            staking_addr,
+            Denom::Native("utest".to_string()),
+            admin,
+            Addr::unchecked(MANAGER),
+        );
+        let err: ContractError = app
+            .execute_contract(
+                Addr::unchecked(ADDR1),
+                native_reward_addr,
+                &ExecuteMsg::Restake {},
+                &[],
+            )
+            .unwrap_err()
+            .downcast()
+            .unwrap();
+        assert_eq!(err, ContractError::RewardTokenNotStaked {});
+    }
+
     #[test]
     fn update_rewards() {
         let mut app = mock_app();
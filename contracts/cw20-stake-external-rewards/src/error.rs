@@ -21,4 +21,6 @@ pub enum ContractError {
     RewardRateLessThenOnePerBlock {},
     #[error("Reward duration can not be zero")]
     ZeroRewardDuration {},
+    #[error("Only rewards in the staked token can be restaked")]
+    RewardTokenNotStaked {},
 }
@@ -24,11 +24,21 @@ pub struct MigrateMsg {}
 pub enum ExecuteMsg {
     StakeChangeHook(StakeChangedHookMsg),
     Claim {},
+    /// Claims the sender's pending rewards and stakes them in the
+    /// staking contract on their behalf. Only possible if the reward
+    /// token is the staked token.
+    Restake {},
     Receive(Cw20ReceiveMsg),
     Fund {},
-    UpdateRewardDuration { new_duration: u64 },
-    UpdateOwner { new_owner: Option<String> },
-    UpdateManager { new_manager: Option<String> },
+    UpdateRewardDuration {
+        new_duration: u64,
+    },
+    UpdateOwner {
+        new_owner: Option<String>,
+    },
+    UpdateManager {
+        new_manager: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -10,7 +10,7 @@ use cw20::{
 use cw20_stake::msg::{
     ClaimsResponse, ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, QueryMsg,
     StakedBalanceAtHeightResponse, StakedValueResponse, TotalStakedAtHeightResponse,
-    TotalValueResponse,
+    TotalValueResponse, UnstakingInfoResponse,
 };
 use cw20_stake::state::Config;
 
@@ -35,6 +35,7 @@ fn main() {
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
     export_schema(&schema_for!(ListStakersResponse), &out_dir);
+    export_schema(&schema_for!(UnstakingInfoResponse), &out_dir);
 
     // Need to rename so it matches the TS pattern
     export_schema_with_title(&schema_for!(Config), &out_dir, "GetConfigResponse");
@@ -3,7 +3,7 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, to_vec, Addr, Binary, Deps, DepsMut, Empty, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128,
+    MessageInfo, Order, Response, StdError, StdResult, Uint128,
 };
 
 use cw20::Cw20ReceiveMsg;
@@ -12,10 +12,11 @@ use crate::hooks::{stake_hook_msgs, unstake_hook_msgs};
 use crate::msg::{
     ExecuteMsg, GetHooksResponse, InstantiateMsg, ListStakersResponse, MigrateMsg, QueryMsg,
     ReceiveMsg, StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
-    TotalStakedAtHeightResponse, TotalValueResponse,
+    TotalStakedAtHeightResponse, TotalValueResponse, UnstakingInfoResponse,
 };
 use crate::state::{
-    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, STAKED_BALANCES, STAKED_TOTAL,
+    Config, BALANCE, CLAIMS, CONFIG, HOOKS, MAX_CLAIMS, PENDING_CLAIMS, STAKED_BALANCES,
+    STAKED_TOTAL,
 };
 use crate::ContractError;
 use cw2::set_contract_version;
@@ -29,7 +30,8 @@ pub use cw20_base::contract::{
     query_token_info,
 };
 pub use cw20_base::enumerable::{query_all_accounts, query_all_allowances};
-use cw_controllers::ClaimsResponse;
+use cw_controllers::{Claim, ClaimsResponse};
+use cw_storage_plus::Map;
 use cw_utils::Duration;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cw20-stake";
@@ -85,6 +87,7 @@ pub fn instantiate(
     // `unwrap_or_default` carries on.
     STAKED_TOTAL.save(deps.storage, &Uint128::zero(), env.block.height)?;
     BALANCE.save(deps.storage, &Uint128::zero())?;
+    PENDING_CLAIMS.save(deps.storage, &0)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -102,6 +105,7 @@ pub fn execute(
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::Unstake { amount } => execute_unstake(deps, env, info, amount),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::ClaimUpTo { limit } => execute_claim_up_to(deps, env, info, limit),
         ExecuteMsg::UpdateConfig {
             owner,
             manager,
@@ -176,6 +180,10 @@ pub fn execute_receive(
     let sender = deps.api.addr_validate(&wrapper.sender)?;
     match msg {
         ReceiveMsg::Stake {} => execute_stake(deps, env, sender, wrapper.amount),
+        ReceiveMsg::StakeFor { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            execute_stake(deps, env, address, wrapper.amount)
+        }
         ReceiveMsg::Fund {} => execute_fund(deps, env, &sender, wrapper.amount),
     }
 }
@@ -289,6 +297,7 @@ pub fn execute_unstake(
                 amount_to_claim,
                 duration.after(&env.block),
             )?;
+            PENDING_CLAIMS.update(deps.storage, |pending| -> StdResult<_> { Ok(pending + 1) })?;
             Ok(Response::new()
                 .add_attribute("action", "unstake")
                 .add_submessages(hook_msgs)
@@ -301,13 +310,55 @@ pub fn execute_unstake(
 
 pub fn execute_claim(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    claim(deps, env, info, None)
+}
+
+pub fn execute_claim_up_to(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    // `Claims::claim_tokens` releases matured claims in the order
+    // they were created until the next one would exceed its cap, so
+    // capping the release at the sum of the first `limit` matured
+    // claims releases exactly those claims and keeps the rest queued.
+    let cap = CLAIMS
+        .query_claims(deps.as_ref(), &info.sender)?
+        .claims
+        .into_iter()
+        .filter(|claim| claim.release_at.is_expired(&env.block))
+        .take(limit as usize)
+        .try_fold(Uint128::zero(), |cap, claim| cap.checked_add(claim.amount))
+        .map_err(StdError::overflow)?;
+    claim(deps, env, info, Some(cap))
+}
+
+fn claim(
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
+    cap: Option<Uint128>,
 ) -> Result<Response, ContractError> {
-    let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &_env.block, None)?;
+    let outstanding = CLAIMS
+        .query_claims(deps.as_ref(), &info.sender)?
+        .claims
+        .len();
+    let release = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, cap)?;
     if release.is_zero() {
         return Err(ContractError::NothingToClaim {});
     }
+    let released = outstanding
+        - CLAIMS
+            .query_claims(deps.as_ref(), &info.sender)?
+            .claims
+            .len();
+    PENDING_CLAIMS.update(deps.storage, |pending| -> StdResult<_> {
+        Ok(pending.saturating_sub(released as u64))
+    })?;
     let config = CONFIG.load(deps.storage)?;
     let cw_send_msg = cw20::Cw20ExecuteMsg::Transfer {
         recipient: info.sender.to_string(),
@@ -391,6 +442,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ListStakers { start_after, limit } => {
             query_list_stakers(deps, start_after, limit)
         }
+        QueryMsg::UnstakingInfo {} => to_binary(&query_unstaking_info(deps)?),
     }
 }
 
@@ -459,6 +511,14 @@ pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
     CLAIMS.query_claims(deps, &deps.api.addr_validate(&address)?)
 }
 
+pub fn query_unstaking_info(deps: Deps) -> StdResult<UnstakingInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(UnstakingInfoResponse {
+        unstaking_duration: config.unstaking_duration,
+        pending_claims: PENDING_CLAIMS.load(deps.storage)?,
+    })
+}
+
 pub fn query_hooks(deps: Deps) -> StdResult<GetHooksResponse> {
     Ok(GetHooksResponse {
         hooks: HOOKS.query_hooks(deps)?.hooks,
@@ -507,6 +567,15 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
         pub unstaking_duration: Option<Duration>,
     }
 
+    // Count the claims created before pending claims were counted.
+    let claims: Map<&Addr, Vec<Claim>> = Map::new("claims");
+    let pending = claims
+        .range(deps.storage, None, None, Order::Ascending)
+        .try_fold(0u64, |pending, item| {
+            item.map(|(_, claims)| pending + claims.len() as u64)
+        })?;
+    PENDING_CLAIMS.save(deps.storage, &pending)?;
+
     match msg {
         MigrateMsg::FromBeta { manager } => {
             let data = deps
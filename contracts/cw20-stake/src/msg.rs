@@ -25,6 +25,13 @@ pub enum ExecuteMsg {
         amount: Uint128,
     },
     Claim {},
+    /// Claims at most `limit` matured claims, oldest first, leaving
+    /// the rest to be claimed later. Useful for addresses with many
+    /// outstanding claims where claiming all of them at once would
+    /// run out of gas.
+    ClaimUpTo {
+        limit: u32,
+    },
     UpdateConfig {
         owner: Option<String>,
         manager: Option<String>,
@@ -42,6 +49,11 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ReceiveMsg {
     Stake {},
+    /// Stakes the sent tokens on behalf of `address`. Used by reward
+    /// contracts to restake rewards for their recipient.
+    StakeFor {
+        address: String,
+    },
     Fund {},
 }
 
@@ -68,6 +80,10 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets the contract's unstaking duration and the number of
+    /// claims that have not yet been claimed. Returns
+    /// `UnstakingInfoResponse`.
+    UnstakingInfo {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -115,6 +131,17 @@ pub struct ListStakersResponse {
     pub stakers: Vec<StakerBalanceResponse>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UnstakingInfoResponse {
+    /// How long unstaked tokens take to become claimable. None if
+    /// unstaking is instant.
+    pub unstaking_duration: Option<Duration>,
+    /// The number of claims across all addresses that have not yet
+    /// been claimed, whether or not they have matured.
+    pub pending_claims: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct StakerBalanceResponse {
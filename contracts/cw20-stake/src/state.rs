@@ -36,6 +36,11 @@ pub const MAX_CLAIMS: u64 = 100;
 
 pub const CLAIMS: Claims = Claims::new("claims");
 
+/// The number of claims across all addresses that have not yet been
+/// claimed. Claims created by earlier versions of the contract are
+/// counted when it is migrated.
+pub const PENDING_CLAIMS: Item<u64> = Item::new("pending_claims");
+
 pub const BALANCE: Item<Uint128> = Item::new("balance");
 
 // Hooks to contracts that will receive staking and unstaking messages
@@ -4,9 +4,9 @@ use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
     ExecuteMsg, ListStakersResponse, MigrateMsg, QueryMsg, ReceiveMsg,
     StakedBalanceAtHeightResponse, StakedValueResponse, StakerBalanceResponse,
-    TotalStakedAtHeightResponse, TotalValueResponse,
+    TotalStakedAtHeightResponse, TotalValueResponse, UnstakingInfoResponse,
 };
-use crate::state::{Config, MAX_CLAIMS};
+use crate::state::{Config, CLAIMS, MAX_CLAIMS, PENDING_CLAIMS};
 use crate::ContractError;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{from_slice, to_binary, Addr, Empty, MessageInfo, Storage, Uint128};
@@ -128,6 +128,11 @@ fn query_config<T: Into<String>>(app: &App, contract_addr: T) -> Config {
     app.wrap().query_wasm_smart(contract_addr, &msg).unwrap()
 }
 
+fn query_unstaking_info<T: Into<String>>(app: &App, contract_addr: T) -> UnstakingInfoResponse {
+    let msg = QueryMsg::UnstakingInfo {};
+    app.wrap().query_wasm_smart(contract_addr, &msg).unwrap()
+}
+
 fn query_total_staked<T: Into<String>>(app: &App, contract_addr: T) -> Uint128 {
     let msg = QueryMsg::TotalStakedAtHeight { height: None };
     let result: TotalStakedAtHeightResponse =
@@ -680,6 +685,87 @@ fn test_unstaking_with_claims() {
     assert_eq!(get_balance(&app, &cw20_addr, ADDR1), Uint128::from(70u128));
 }
 
+#[test]
+fn test_claim_up_to() {
+    let mut app = mock_app();
+    let amount1 = Uint128::from(1000u128);
+    let unstaking_blocks = 10u64;
+    let initial_balances = vec![Cw20Coin {
+        address: ADDR1.to_string(),
+        amount: amount1,
+    }];
+    let (staking_addr, cw20_addr) = setup_test_case(
+        &mut app,
+        initial_balances,
+        Some(Duration::Height(unstaking_blocks)),
+    );
+
+    let info = mock_info(ADDR1, &[]);
+    stake_tokens(&mut app, &staking_addr, &cw20_addr, info.clone(), amount1).unwrap();
+    app.update_block(next_block);
+
+    // Create 25 claims of 1, 2, ..., 25 tokens that will mature and
+    // five more that have not matured when claimed.
+    for amount in 1..=25u128 {
+        unstake_tokens(&mut app, &staking_addr, info.clone(), Uint128::new(amount)).unwrap();
+    }
+    app.update_block(|b| b.height += unstaking_blocks);
+    for _ in 0..5 {
+        unstake_tokens(&mut app, &staking_addr, info.clone(), Uint128::new(1)).unwrap();
+    }
+    assert_eq!(query_claims(&app, &staking_addr, ADDR1).len(), 30);
+
+    let claim_up_to = |app: &mut App, limit: u32| {
+        app.execute_contract(
+            info.sender.clone(),
+            staking_addr.clone(),
+            &ExecuteMsg::ClaimUpTo { limit },
+            &[],
+        )
+    };
+
+    // The oldest matured claims are claimed first.
+    claim_up_to(&mut app, 10).unwrap();
+    assert_eq!(
+        get_balance(&app, &cw20_addr, ADDR1),
+        Uint128::new((1..=10).sum())
+    );
+    let claims = query_claims(&app, &staking_addr, ADDR1);
+    assert_eq!(claims.len(), 20);
+    assert_eq!(claims[0].amount, Uint128::new(11));
+
+    claim_up_to(&mut app, 10).unwrap();
+    assert_eq!(
+        get_balance(&app, &cw20_addr, ADDR1),
+        Uint128::new((1..=20).sum())
+    );
+    assert_eq!(query_claims(&app, &staking_addr, ADDR1).len(), 10);
+
+    // Only five matured claims remain.
+    claim_up_to(&mut app, 10).unwrap();
+    assert_eq!(
+        get_balance(&app, &cw20_addr, ADDR1),
+        Uint128::new((1..=25).sum())
+    );
+    let claims = query_claims(&app, &staking_addr, ADDR1);
+    assert_eq!(claims.len(), 5);
+    assert!(claims
+        .iter()
+        .all(|claim| claim.release_at == AtHeight(app.block_info().height + unstaking_blocks)));
+
+    let err: ContractError = claim_up_to(&mut app, 10).unwrap_err().downcast().unwrap();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // The remaining claims can be claimed once they mature.
+    app.update_block(|b| b.height += unstaking_blocks);
+    claim_up_to(&mut app, 10).unwrap();
+    assert!(query_claims(&app, &staking_addr, ADDR1).is_empty());
+    assert_eq!(
+        get_balance(&app, &cw20_addr, ADDR1),
+        Uint128::new((1..=25).sum::<u128>() + 5)
+    );
+}
+
 #[test]
 fn multiple_address_staking() {
     let amount1 = Uint128::from(100u128);
@@ -1005,6 +1091,111 @@ fn test_simple_unstaking_with_duration() {
     assert_eq!(get_balance(&app, &cw20_addr, ADDR2), Uint128::from(100u128));
 }
 
+#[test]
+fn test_unstaking_info() {
+    let mut app = mock_app();
+    let amount1 = Uint128::from(100u128);
+    let initial_balances = vec![
+        Cw20Coin {
+            address: ADDR1.to_string(),
+            amount: amount1,
+        },
+        Cw20Coin {
+            address: ADDR2.to_string(),
+            amount: amount1,
+        },
+    ];
+    let (staking_addr, cw20_addr) =
+        setup_test_case(&mut app, initial_balances, Some(Duration::Height(2)));
+
+    let info = query_unstaking_info(&app, &staking_addr);
+    assert_eq!(
+        info.unstaking_duration,
+        query_config(&app, &staking_addr).unstaking_duration
+    );
+    assert_eq!(
+        info,
+        UnstakingInfoResponse {
+            unstaking_duration: Some(Duration::Height(2)),
+            pending_claims: 0,
+        }
+    );
+
+    let info = mock_info(ADDR1, &[]);
+    stake_tokens(&mut app, &staking_addr, &cw20_addr, info, amount1).unwrap();
+    let info = mock_info(ADDR2, &[]);
+    stake_tokens(&mut app, &staking_addr, &cw20_addr, info, amount1).unwrap();
+    app.update_block(next_block);
+
+    // Each unstake creates a claim.
+    let info = mock_info(ADDR1, &[]);
+    unstake_tokens(&mut app, &staking_addr, info.clone(), Uint128::new(50)).unwrap();
+    unstake_tokens(&mut app, &staking_addr, info, Uint128::new(50)).unwrap();
+    let info = mock_info(ADDR2, &[]);
+    unstake_tokens(&mut app, &staking_addr, info, Uint128::new(100)).unwrap();
+    assert_eq!(query_unstaking_info(&app, &staking_addr).pending_claims, 3);
+
+    // Claims are pending until they are claimed, even once matured.
+    app.update_block(next_block);
+    app.update_block(next_block);
+    assert_eq!(query_unstaking_info(&app, &staking_addr).pending_claims, 3);
+
+    let info = mock_info(ADDR1, &[]);
+    claim_tokens(&mut app, &staking_addr, info).unwrap();
+    assert_eq!(query_unstaking_info(&app, &staking_addr).pending_claims, 1);
+
+    // Updating the unstaking duration is reflected in the query.
+    let info = mock_info("owner", &[]);
+    update_config(
+        &mut app,
+        &staking_addr,
+        info,
+        Some(Addr::unchecked("owner")),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        query_unstaking_info(&app, &staking_addr),
+        UnstakingInfoResponse {
+            unstaking_duration: None,
+            pending_claims: 1,
+        }
+    );
+}
+
+#[test]
+fn test_migrate_counts_pending_claims() {
+    let mut deps = mock_dependencies();
+    CLAIMS
+        .create_claim(
+            &mut deps.storage,
+            &Addr::unchecked(ADDR1),
+            Uint128::new(1),
+            AtHeight(10),
+        )
+        .unwrap();
+    CLAIMS
+        .create_claim(
+            &mut deps.storage,
+            &Addr::unchecked(ADDR1),
+            Uint128::new(2),
+            AtHeight(20),
+        )
+        .unwrap();
+    CLAIMS
+        .create_claim(
+            &mut deps.storage,
+            &Addr::unchecked(ADDR2),
+            Uint128::new(3),
+            AtHeight(10),
+        )
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+    assert_eq!(PENDING_CLAIMS.load(&deps.storage).unwrap(), 3);
+}
+
 #[test]
 fn test_double_unstake_at_height() {
     let mut app = App::default();
@@ -4,10 +4,12 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 use cosmwasm_std::Addr;
 use cw20_staked_balance_voting::msg::{
-    ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ActiveStatusResponse, ActiveThresholdResponse, CirculatingPowerResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, QueryMsg,
 };
 use cw_core_interface::voting::{
-    InfoResponse, IsActiveResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    InfoResponse, IsActiveResponse, MembersAbovePowerResponse, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
 };
 
 fn main() {
@@ -24,8 +26,11 @@ fn main() {
     export_schema(&schema_for!(InfoResponse), &out_dir);
     export_schema(&schema_for!(TotalPowerAtHeightResponse), &out_dir);
     export_schema(&schema_for!(VotingPowerAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(MembersAbovePowerResponse), &out_dir);
     export_schema(&schema_for!(ActiveThresholdResponse), &out_dir);
     export_schema(&schema_for!(IsActiveResponse), &out_dir);
+    export_schema(&schema_for!(ActiveStatusResponse), &out_dir);
+    export_schema(&schema_for!(CirculatingPowerResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query resonses to the correct name
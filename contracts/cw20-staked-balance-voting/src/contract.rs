@@ -6,14 +6,14 @@ use cosmwasm_std::{
 };
 use cw2::set_contract_version;
 use cw20::{Cw20Coin, TokenInfoResponse};
-use cw_core_interface::voting::IsActiveResponse;
+use cw_core_interface::voting::{IsActiveResponse, MemberPower, MembersAbovePowerResponse};
 use cw_utils::parse_reply_instantiate_data;
 use std::convert::TryInto;
 
 use crate::error::ContractError;
 use crate::msg::{
-    ActiveThreshold, ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    StakingInfo, TokenInfo,
+    ActiveStatusResponse, ActiveThreshold, ActiveThresholdResponse, CirculatingPowerResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo, TokenInfo,
 };
 use crate::state::{
     ACTIVE_THRESHOLD, DAO, STAKING_CONTRACT, STAKING_CONTRACT_CODE_ID,
@@ -26,6 +26,9 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const INSTANTIATE_TOKEN_REPLY_ID: u64 = 0;
 const INSTANTIATE_STAKING_REPLY_ID: u64 = 1;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 // We multiply by this when calculating needed power for being active
 // when using active threshold with percent
 const PRECISION_FACTOR: u128 = 10u128.pow(9);
@@ -237,6 +240,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::IsActive {} => query_is_active(deps),
         QueryMsg::ActiveThreshold {} => query_active_threshold(deps),
+        QueryMsg::ActiveStatus {} => to_binary(&get_active_status(deps)?),
+        QueryMsg::MembersAbovePower {
+            min_power,
+            start_after,
+            limit,
+        } => query_members_above_power(deps, min_power, start_after, limit),
+        QueryMsg::CirculatingPower { exclude } => query_circulating_power(deps, exclude),
     }
 }
 
@@ -287,6 +297,73 @@ pub fn query_total_power_at_height(
     })
 }
 
+pub fn query_circulating_power(deps: Deps, exclude: Vec<String>) -> StdResult<Binary> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let mut exclude = exclude
+        .into_iter()
+        .map(|address| deps.api.addr_validate(&address))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    exclude.sort();
+    exclude.dedup();
+
+    let total: cw20_stake::msg::TotalStakedAtHeightResponse = deps.querier.query_wasm_smart(
+        staking_contract.clone(),
+        &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height: None },
+    )?;
+    let mut power = total.total;
+    for address in exclude {
+        let res: cw20_stake::msg::StakedBalanceAtHeightResponse = deps.querier.query_wasm_smart(
+            staking_contract.clone(),
+            &cw20_stake::msg::QueryMsg::StakedBalanceAtHeight {
+                address: address.into_string(),
+                height: Some(total.height),
+            },
+        )?;
+        power = power.checked_sub(res.balance)?;
+    }
+
+    to_binary(&CirculatingPowerResponse {
+        power,
+        height: total.height,
+    })
+}
+
+pub fn query_members_above_power(
+    deps: Deps,
+    min_power: Uint128,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    // Balances are owned by the staking contract. The limit bounds
+    // the stakers examined rather than those returned so that a
+    // single page of stakers is fetched from it.
+    let page: cw20_stake::msg::ListStakersResponse = deps.querier.query_wasm_smart(
+        staking_contract,
+        &cw20_stake::msg::QueryMsg::ListStakers {
+            start_after,
+            limit: Some(limit),
+        },
+    )?;
+    let last_examined = page.stakers.last().map(|staker| staker.address.clone());
+    let members = page
+        .stakers
+        .into_iter()
+        .filter(|staker| !staker.balance.is_zero() && staker.balance >= min_power)
+        .map(|staker| MemberPower {
+            address: staker.address,
+            power: staker.balance,
+        })
+        .collect();
+
+    to_binary(&MembersAbovePowerResponse {
+        members,
+        last_examined,
+    })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cw_core_interface::voting::InfoResponse { info })
@@ -298,41 +375,71 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 pub fn query_is_active(deps: Deps) -> StdResult<Binary> {
+    // Without a threshold the DAO is always active so there is no
+    // need to query the staking contract.
+    let active = match ACTIVE_THRESHOLD.may_load(deps.storage)? {
+        Some(_) => get_active_status(deps)?.active,
+        None => true,
+    };
+    to_binary(&IsActiveResponse { active })
+}
+
+/// Computes whether the DAO is active along with the staked and
+/// required amounts that determine it.
+pub fn get_active_status(deps: Deps) -> StdResult<ActiveStatusResponse> {
+    let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
+    let actual_power: cw20_stake::msg::TotalStakedAtHeightResponse =
+        deps.querier.query_wasm_smart(
+            staking_contract,
+            &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height: None },
+        )?;
+    let staked = actual_power.total;
+
     let threshold = ACTIVE_THRESHOLD.may_load(deps.storage)?;
-    if let Some(threshold) = threshold {
-        let token_contract = TOKEN.load(deps.storage)?;
-        let staking_contract = STAKING_CONTRACT.load(deps.storage)?;
-        let actual_power: cw20_stake::msg::TotalStakedAtHeightResponse =
-            deps.querier.query_wasm_smart(
-                staking_contract,
-                &cw20_stake::msg::QueryMsg::TotalStakedAtHeight { height: None },
-            )?;
-        match threshold {
-            ActiveThreshold::AbsoluteCount { count } => to_binary(&IsActiveResponse {
-                active: actual_power.total >= count,
-            }),
-            ActiveThreshold::Percentage { percent } => {
-                let total_potential_power: TokenInfoResponse = deps
-                    .querier
-                    .query_wasm_smart(token_contract, &cw20_base::msg::QueryMsg::TokenInfo {})?;
-                let total_power = total_potential_power
-                    .total_supply
-                    .full_mul(PRECISION_FACTOR);
-                let applied = total_power.multiply_ratio(
-                    percent.atomics(),
-                    Uint256::from(10u64).pow(percent.decimal_places()),
-                );
-                let rounded = (applied + Uint256::from(PRECISION_FACTOR) - Uint256::from(1u128))
-                    / Uint256::from(PRECISION_FACTOR);
-                let count: Uint128 = rounded.try_into().unwrap();
-                to_binary(&IsActiveResponse {
-                    active: actual_power.total >= count,
-                })
-            }
+    let required = match &threshold {
+        None => None,
+        Some(ActiveThreshold::AbsoluteCount { count }) => Some(*count),
+        Some(ActiveThreshold::Percentage { percent }) => {
+            let token_contract = TOKEN.load(deps.storage)?;
+            let total_potential_power: TokenInfoResponse = deps
+                .querier
+                .query_wasm_smart(token_contract, &cw20_base::msg::QueryMsg::TokenInfo {})?;
+            let total_power = total_potential_power
+                .total_supply
+                .full_mul(PRECISION_FACTOR);
+            let applied = total_power.multiply_ratio(
+                percent.atomics(),
+                Uint256::from(10u64).pow(percent.decimal_places()),
+            );
+            let rounded = (applied + Uint256::from(PRECISION_FACTOR) - Uint256::from(1u128))
+                / Uint256::from(PRECISION_FACTOR);
+            let count: Uint128 = rounded.try_into().unwrap();
+            Some(count)
         }
-    } else {
-        to_binary(&IsActiveResponse { active: true })
-    }
+    };
+
+    let (active, to_activate, to_deactivate) = match required {
+        None => (true, None, None),
+        Some(required) if staked >= required => {
+            // Nothing can be unstaked below a requirement of zero.
+            let to_deactivate = if required.is_zero() {
+                None
+            } else {
+                Some(staked - required + Uint128::new(1))
+            };
+            (true, None, to_deactivate)
+        }
+        Some(required) => (false, Some(required - staked), None),
+    };
+
+    Ok(ActiveStatusResponse {
+        active,
+        active_threshold: threshold,
+        staked,
+        required,
+        to_activate,
+        to_deactivate,
+    })
 }
 
 pub fn query_active_threshold(deps: Deps) -> StdResult<Binary> {
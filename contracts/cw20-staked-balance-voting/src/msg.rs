@@ -71,6 +71,27 @@ pub enum QueryMsg {
     StakingContract {},
     Dao {},
     ActiveThreshold {},
+    /// Returns `ActiveStatusResponse`.
+    ActiveStatus {},
+    /// Lists the stakers with at least `min_power` staked in the
+    /// staking contract, ordered by address. Stakers with nothing
+    /// staked are never listed. At most `limit` stakers are
+    /// examined, so a page may hold fewer members than `limit` while
+    /// more remain. Continue from the response's `last_examined`.
+    /// Returns `cw_core_interface::voting::MembersAbovePowerResponse`.
+    MembersAbovePower {
+        min_power: Uint128,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the total voting power at the current height less the
+    /// voting power of the addresses in `exclude`, for example the
+    /// DAO's treasury or vesting contracts that never vote. Each
+    /// address is only excluded once. Returns
+    /// `CirculatingPowerResponse`.
+    CirculatingPower {
+        exclude: Vec<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -79,5 +100,34 @@ pub struct ActiveThresholdResponse {
     pub active_threshold: Option<ActiveThreshold>,
 }
 
+/// Whether the DAO is active and how far it is from changing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ActiveStatusResponse {
+    /// Whether the DAO is active. The same as the `IsActive` query.
+    pub active: bool,
+    /// The configured active threshold. None if the DAO is always
+    /// active.
+    pub active_threshold: Option<ActiveThreshold>,
+    /// The number of tokens currently staked.
+    pub staked: Uint128,
+    /// The number of tokens that must be staked for the DAO to be
+    /// active. None if there is no active threshold.
+    pub required: Option<Uint128>,
+    /// If the DAO is inactive, the number of additional tokens that
+    /// must be staked for it to become active.
+    pub to_activate: Option<Uint128>,
+    /// If the DAO is active and may become inactive, the number of
+    /// tokens that unstaking would make it inactive.
+    pub to_deactivate: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct CirculatingPowerResponse {
+    /// The total voting power less that of the excluded addresses.
+    pub power: Uint128,
+    /// The height the power was computed at.
+    pub height: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct MigrateMsg {}
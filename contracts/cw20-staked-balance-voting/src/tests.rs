@@ -4,14 +4,17 @@ use cosmwasm_std::{
 };
 use cw2::ContractVersion;
 use cw20::{BalanceResponse, Cw20Coin, MinterResponse, TokenInfoResponse};
-use cw_core_interface::voting::{InfoResponse, IsActiveResponse, VotingPowerAtHeightResponse};
+use cw_core_interface::voting::{
+    InfoResponse, IsActiveResponse, MemberPower, MembersAbovePowerResponse,
+    VotingPowerAtHeightResponse,
+};
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
     msg::{
-        ActiveThreshold, ActiveThresholdResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-        StakingInfo,
+        ActiveStatusResponse, ActiveThreshold, ActiveThresholdResponse, CirculatingPowerResponse,
+        ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StakingInfo,
     },
 };
 
@@ -892,6 +895,212 @@ fn test_different_heights() {
     );
 }
 
+#[test]
+fn test_members_above_power() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_id = app.store_code(staking_contract());
+
+    // More stakers than fit in one page of the staking contract's
+    // `ListStakers` query. Staker `i` stakes `i + 1` tokens.
+    let stakers: Vec<String> = (0..40).map(|i| format!("staker{:02}", i)).collect();
+    let initial_balances = stakers
+        .iter()
+        .enumerate()
+        .map(|(i, address)| Cw20Coin {
+            address: address.clone(),
+            amount: Uint128::new(i as u128 + 1),
+        })
+        .collect();
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances,
+                marketing: None,
+                staking_code_id: staking_id,
+                unstaking_duration: None,
+                initial_dao_balance: None,
+            },
+            active_threshold: None,
+        },
+    );
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+    for (i, staker) in stakers.iter().enumerate() {
+        stake_tokens(
+            &mut app,
+            staking_addr.clone(),
+            token_addr.clone(),
+            staker,
+            i as u128 + 1,
+        );
+    }
+
+    let members_above_power = |min_power: u128, start_after: Option<&str>, limit: Option<u32>| {
+        let res: MembersAbovePowerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::MembersAbovePower {
+                    min_power: Uint128::new(min_power),
+                    start_after: start_after.map(|addr| addr.to_string()),
+                    limit,
+                },
+            )
+            .unwrap();
+        (res.members, res.last_examined)
+    };
+    let members = |range: std::ops::Range<usize>| -> Vec<MemberPower> {
+        range
+            .map(|i| MemberPower {
+                address: stakers[i].clone(),
+                power: Uint128::new(i as u128 + 1),
+            })
+            .collect()
+    };
+    let last = |i: usize| Some(stakers[i].clone());
+
+    // Ten stakers are examined by default, and at most one page of
+    // the staking contract's stakers.
+    assert_eq!(
+        members_above_power(0, None, None),
+        (members(0..10), last(9))
+    );
+    assert_eq!(
+        members_above_power(36, None, None),
+        (members(0..0), last(9))
+    );
+    assert_eq!(
+        members_above_power(36, None, Some(100)),
+        (members(0..0), last(29))
+    );
+
+    // Qualifying stakers past the first page are found by continuing
+    // from the last staker examined.
+    assert_eq!(
+        members_above_power(36, Some("staker29"), Some(30)),
+        (members(35..40), last(39))
+    );
+    assert_eq!(
+        members_above_power(36, Some("staker35"), Some(2)),
+        (members(36..38), last(37))
+    );
+    assert_eq!(
+        members_above_power(41, Some("staker29"), Some(30)),
+        (members(0..0), last(39))
+    );
+    assert_eq!(
+        members_above_power(0, Some("staker39"), None),
+        (members(0..0), None)
+    );
+}
+
+#[test]
+fn test_circulating_power() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_id = app.store_code(staking_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: CREATOR_ADDR.to_string(),
+                        amount: Uint128::new(10),
+                    },
+                    Cw20Coin {
+                        address: "vesting".to_string(),
+                        amount: Uint128::new(30),
+                    },
+                ],
+                marketing: None,
+                staking_code_id: staking_id,
+                unstaking_duration: None,
+                initial_dao_balance: Some(Uint128::new(60)),
+            },
+            active_threshold: None,
+        },
+    );
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+
+    // Everyone stakes, including the treasury and vesting contract.
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        CREATOR_ADDR,
+        10,
+    );
+    stake_tokens(
+        &mut app,
+        staking_addr.clone(),
+        token_addr.clone(),
+        "vesting",
+        30,
+    );
+    stake_tokens(&mut app, staking_addr, token_addr, DAO_ADDR, 60);
+    app.update_block(next_block);
+
+    let circulating_power = |exclude: Vec<&str>| {
+        let res: CirculatingPowerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::CirculatingPower {
+                    exclude: exclude.into_iter().map(|a| a.to_string()).collect(),
+                },
+            )
+            .unwrap();
+        assert_eq!(res.height, app.block_info().height);
+        res.power
+    };
+
+    assert_eq!(circulating_power(vec![]), Uint128::new(100));
+    assert_eq!(circulating_power(vec![DAO_ADDR]), Uint128::new(40));
+    assert_eq!(
+        circulating_power(vec![DAO_ADDR, "vesting"]),
+        Uint128::new(10)
+    );
+
+    // Addresses are only excluded once, and excluding an address
+    // with nothing staked changes nothing.
+    assert_eq!(
+        circulating_power(vec![DAO_ADDR, DAO_ADDR, "nobody"]),
+        Uint128::new(40)
+    );
+}
+
 #[test]
 fn test_active_threshold_absolute_count() {
     let mut app = App::default();
@@ -1192,6 +1401,116 @@ fn test_update_active_threshold() {
     );
 }
 
+#[test]
+fn test_active_status() {
+    let mut app = App::default();
+    let cw20_id = app.store_code(cw20_contract());
+    let voting_id = app.store_code(staked_balance_voting_contract());
+    let staking_contract_id = app.store_code(staking_contract());
+
+    let voting_addr = instantiate_voting(
+        &mut app,
+        voting_id,
+        InstantiateMsg {
+            token_info: crate::msg::TokenInfo::New {
+                code_id: cw20_id,
+                label: "DAO DAO voting".to_string(),
+                name: "DAO DAO".to_string(),
+                symbol: "DAO".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: CREATOR_ADDR.to_string(),
+                    amount: Uint128::from(200u64),
+                }],
+                marketing: None,
+                unstaking_duration: None,
+                staking_code_id: staking_contract_id,
+                initial_dao_balance: Some(Uint128::from(100u64)),
+            },
+            active_threshold: None,
+        },
+    );
+
+    let token_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::TokenContract {})
+        .unwrap();
+    let staking_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::StakingContract {})
+        .unwrap();
+
+    let active_status = |app: &App| -> ActiveStatusResponse {
+        app.wrap()
+            .query_wasm_smart(voting_addr.clone(), &QueryMsg::ActiveStatus {})
+            .unwrap()
+    };
+    let update_threshold = |app: &mut App, new_threshold: Option<ActiveThreshold>| {
+        app.execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            voting_addr.clone(),
+            &ExecuteMsg::UpdateActiveThreshold { new_threshold },
+            &[],
+        )
+        .unwrap();
+    };
+
+    // Always active without a threshold.
+    assert_eq!(
+        active_status(&app),
+        ActiveStatusResponse {
+            active: true,
+            active_threshold: None,
+            staked: Uint128::zero(),
+            required: None,
+            to_activate: None,
+            to_deactivate: None,
+        }
+    );
+
+    let threshold = ActiveThreshold::AbsoluteCount {
+        count: Uint128::new(100),
+    };
+    update_threshold(&mut app, Some(threshold.clone()));
+    stake_tokens(&mut app, staking_addr, token_addr, CREATOR_ADDR, 60);
+    app.update_block(next_block);
+
+    assert_eq!(
+        active_status(&app),
+        ActiveStatusResponse {
+            active: false,
+            active_threshold: Some(threshold),
+            staked: Uint128::new(60),
+            required: Some(Uint128::new(100)),
+            to_activate: Some(Uint128::new(40)),
+            to_deactivate: None,
+        }
+    );
+
+    // 10% of the 300 token supply must be staked.
+    let threshold = ActiveThreshold::Percentage {
+        percent: Decimal::percent(10),
+    };
+    update_threshold(&mut app, Some(threshold.clone()));
+
+    assert_eq!(
+        active_status(&app),
+        ActiveStatusResponse {
+            active: true,
+            active_threshold: Some(threshold),
+            staked: Uint128::new(60),
+            required: Some(Uint128::new(30)),
+            to_activate: None,
+            to_deactivate: Some(Uint128::new(31)),
+        }
+    );
+    let is_active: IsActiveResponse = app
+        .wrap()
+        .query_wasm_smart(voting_addr, &QueryMsg::IsActive {})
+        .unwrap();
+    assert!(is_active.active);
+}
+
 #[test]
 #[should_panic(expected = "Active threshold percentage must be greater than 0 and less than 1")]
 fn test_active_threshold_percentage_gt_100() {
@@ -4,9 +4,10 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 use cosmwasm_std::Addr;
 use cw4::MemberDiff;
-use cw4_voting::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use cw4_voting::msg::{ExecuteMsg, InstantiateMsg, MembersAtHeightResponse, MigrateMsg, QueryMsg};
 use cw_core_interface::voting::{
-    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    InfoResponse, MembersAbovePowerResponse, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
 };
 
 fn main() {
@@ -25,6 +26,8 @@ fn main() {
     export_schema(&schema_for!(InfoResponse), &out_dir);
     export_schema(&schema_for!(TotalPowerAtHeightResponse), &out_dir);
     export_schema(&schema_for!(VotingPowerAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(MembersAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(MembersAbovePowerResponse), &out_dir);
 
     // Auto TS code generation expects the query return type as QueryNameResponse
     // Here we map query resonses to the correct name
@@ -1,14 +1,17 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
-    SubMsg, Uint128, WasmMsg,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
 use cw_utils::parse_reply_instantiate_data;
 
+use cw_core_interface::voting::{MemberPower, MembersAbovePowerResponse};
+
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MembersAtHeightResponse, MigrateMsg, QueryMsg};
 use crate::state::{DAO_ADDRESS, GROUP_CONTRACT, TOTAL_WEIGHT, USER_WEIGHTS};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cw4-voting";
@@ -16,6 +19,9 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_GROUP_REPLY_ID: u64 = 0;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -154,6 +160,16 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Info {} => query_info(deps),
         QueryMsg::GroupContract {} => to_binary(&GROUP_CONTRACT.load(deps.storage)?),
         QueryMsg::Dao {} => to_binary(&DAO_ADDRESS.load(deps.storage)?),
+        QueryMsg::MembersAtHeight {
+            height,
+            start_after,
+            limit,
+        } => query_members_at_height(deps, env, height, start_after, limit),
+        QueryMsg::MembersAbovePower {
+            min_power,
+            start_after,
+            limit,
+        } => query_members_above_power(deps, min_power, start_after, limit),
     }
 }
 
@@ -180,6 +196,96 @@ pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) ->
     to_binary(&cw_core_interface::voting::TotalPowerAtHeightResponse { power, height })
 }
 
+pub fn query_members_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let height = height.unwrap_or(env.block.height);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let mut last = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    // Every weight change is written to the snapshot changelog, keyed
+    // by (address, height), so the changelog holds every address that
+    // has ever been a member, including ones that have since been
+    // removed. Walk it one address at a time and keep the ones with
+    // a weight at the requested height. The limit bounds the
+    // addresses examined rather than those returned, so that removed
+    // members can not make the query cost unbounded gas.
+    let mut members = vec![];
+    let mut last_examined = None;
+    for _ in 0..limit {
+        let min = last.as_ref().map(|addr| Bound::exclusive((addr, u64::MAX)));
+        let next = USER_WEIGHTS
+            .changelog()
+            .keys(deps.storage, min, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        let (addr, _) = match next {
+            Some(key) => key,
+            None => break,
+        };
+        if let Some(weight) = USER_WEIGHTS.may_load_at_height(deps.storage, &addr, height)? {
+            members.push(cw4::Member {
+                addr: addr.to_string(),
+                // Weights are set from cw4 member weights which are u64s.
+                weight: weight.u128() as u64,
+            });
+        }
+        last_examined = Some(addr.to_string());
+        last = Some(addr);
+    }
+
+    to_binary(&MembersAtHeightResponse {
+        members,
+        height,
+        last_examined,
+    })
+}
+
+pub fn query_members_above_power(
+    deps: Deps,
+    min_power: Uint128,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    // The limit bounds the addresses examined rather than those
+    // returned, so that addresses below the minimum can not make the
+    // query cost unbounded gas.
+    let examined = USER_WEIGHTS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+    let last_examined = examined.last().map(|(address, _)| address.to_string());
+    let members = examined
+        .into_iter()
+        .filter(|(_, power)| !power.is_zero() && *power >= min_power)
+        .map(|(address, power)| MemberPower {
+            address: address.into_string(),
+            power,
+        })
+        .collect();
+
+    to_binary(&MembersAbovePowerResponse {
+        members,
+        last_examined,
+    })
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_binary(&cw_core_interface::voting::InfoResponse { info })
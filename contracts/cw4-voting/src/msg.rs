@@ -1,3 +1,4 @@
+use cosmwasm_std::Uint128;
 use cw_core_macros::voting_query;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -20,6 +21,40 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     GroupContract {},
     Dao {},
+    /// Lists the members of the group and their weights as of
+    /// `height`, ordered by address. Defaults to the current height
+    /// if `height` is None. Like `VotingPowerAtHeight`, membership
+    /// changes made at `height` are not reflected until the
+    /// following block. At most `limit` addresses that have ever
+    /// been members are examined, so a page may hold fewer members
+    /// than `limit` while more remain. Continue from the response's
+    /// `last_examined`. Returns `MembersAtHeightResponse`.
+    MembersAtHeight {
+        height: Option<u64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Lists the members of the group with a weight of at least
+    /// `min_power`, ordered by address. Members with no weight are
+    /// never listed. At most `limit` addresses are examined, so a
+    /// page may hold fewer members than `limit` while more remain.
+    /// Continue from the response's `last_examined`. Returns
+    /// `cw_core_interface::voting::MembersAbovePowerResponse`.
+    MembersAbovePower {
+        min_power: Uint128,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MembersAtHeightResponse {
+    pub members: Vec<cw4::Member>,
+    pub height: u64,
+    /// The last address examined, or None if there were no addresses
+    /// left to examine. Pass this as `start_after` to continue
+    /// scanning.
+    pub last_examined: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -4,13 +4,14 @@ use cosmwasm_std::{
 };
 use cw2::ContractVersion;
 use cw_core_interface::voting::{
-    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+    InfoResponse, MemberPower, MembersAbovePowerResponse, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
 };
 use cw_multi_test::{next_block, App, Contract, ContractWrapper, Executor};
 
 use crate::{
     contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION},
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ExecuteMsg, InstantiateMsg, MembersAtHeightResponse, MigrateMsg, QueryMsg},
     ContractError,
 };
 
@@ -479,6 +480,223 @@ fn test_power_at_height() {
     assert_eq!(total_voting_power.height, app.block_info().height - 1);
 }
 
+#[test]
+fn test_members_at_height() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    let instantiate_height = app.block_info().height;
+    app.update_block(next_block);
+
+    let cw4_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::GroupContract {})
+        .unwrap();
+
+    // Remove ADDR2 and give ADDR4 some weight.
+    let msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+        remove: vec![ADDR2.to_string()],
+        add: vec![cw4::Member {
+            addr: ADDR4.to_string(),
+            weight: 5,
+        }],
+    };
+    app.execute_contract(Addr::unchecked(DAO_ADDR), cw4_addr, &msg, &[])
+        .unwrap();
+    let update_height = app.block_info().height;
+    app.update_block(next_block);
+
+    let member = |addr: &str, weight: u64| cw4::Member {
+        addr: addr.to_string(),
+        weight,
+    };
+
+    // Current membership reflects the update.
+    let current: MembersAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MembersAtHeight {
+                height: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        current,
+        MembersAtHeightResponse {
+            members: vec![member(ADDR1, 1), member(ADDR3, 1), member(ADDR4, 5)],
+            height: app.block_info().height,
+            last_examined: Some(ADDR4.to_string()),
+        }
+    );
+
+    // Changes are not visible until the block after they are made,
+    // so membership at the update height is the original set.
+    let historical: MembersAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MembersAtHeight {
+                height: Some(update_height),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        historical,
+        MembersAtHeightResponse {
+            members: vec![member(ADDR1, 1), member(ADDR2, 1), member(ADDR3, 1)],
+            height: update_height,
+            last_examined: Some(ADDR4.to_string()),
+        }
+    );
+
+    // No members before the contract was instantiated.
+    let before: MembersAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MembersAtHeight {
+                height: Some(instantiate_height),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(before.members.is_empty());
+
+    // The limit bounds the addresses examined, including removed
+    // members, so pages may be empty while members remain.
+    let page: MembersAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MembersAtHeight {
+                height: None,
+                start_after: Some(ADDR1.to_string()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert!(page.members.is_empty());
+    assert_eq!(page.last_examined, Some(ADDR2.to_string()));
+
+    let page: MembersAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr.clone(),
+            &QueryMsg::MembersAtHeight {
+                height: None,
+                start_after: page.last_examined,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(page.members, vec![member(ADDR3, 1)]);
+    assert_eq!(page.last_examined, Some(ADDR3.to_string()));
+
+    let page: MembersAtHeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            voting_addr,
+            &QueryMsg::MembersAtHeight {
+                height: None,
+                start_after: Some(ADDR4.to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(page.members.is_empty());
+    assert_eq!(page.last_examined, None);
+}
+
+#[test]
+fn test_members_above_power() {
+    let mut app = App::default();
+    let voting_addr = setup_test_case(&mut app);
+    app.update_block(next_block);
+
+    let cw4_addr: Addr = app
+        .wrap()
+        .query_wasm_smart(voting_addr.clone(), &QueryMsg::GroupContract {})
+        .unwrap();
+    let msg = cw4_group::msg::ExecuteMsg::UpdateMembers {
+        remove: vec![],
+        add: vec![
+            cw4::Member {
+                addr: ADDR2.to_string(),
+                weight: 5,
+            },
+            cw4::Member {
+                addr: ADDR4.to_string(),
+                weight: 3,
+            },
+        ],
+    };
+    app.execute_contract(Addr::unchecked(DAO_ADDR), cw4_addr, &msg, &[])
+        .unwrap();
+    app.update_block(next_block);
+
+    let members_above_power = |min_power: u128, start_after: Option<&str>, limit: Option<u32>| {
+        let res: MembersAbovePowerResponse = app
+            .wrap()
+            .query_wasm_smart(
+                voting_addr.clone(),
+                &QueryMsg::MembersAbovePower {
+                    min_power: Uint128::new(min_power),
+                    start_after: start_after.map(|addr| addr.to_string()),
+                    limit,
+                },
+            )
+            .unwrap();
+        (res.members, res.last_examined)
+    };
+    let member = |address: &str, power: u128| MemberPower {
+        address: address.to_string(),
+        power: Uint128::new(power),
+    };
+    let last = |address: &str| Some(address.to_string());
+
+    assert_eq!(
+        members_above_power(2, None, None),
+        (vec![member(ADDR2, 5), member(ADDR4, 3)], last(ADDR4))
+    );
+    assert_eq!(
+        members_above_power(5, None, None),
+        (vec![member(ADDR2, 5)], last(ADDR4))
+    );
+    assert_eq!(members_above_power(6, None, None), (vec![], last(ADDR4)));
+
+    // Members without weight are never listed.
+    assert_eq!(
+        members_above_power(0, None, None),
+        (
+            vec![
+                member(ADDR1, 1),
+                member(ADDR2, 5),
+                member(ADDR3, 1),
+                member(ADDR4, 3),
+            ],
+            last(ADDR4)
+        )
+    );
+
+    // The limit bounds the members examined, so pages may be empty
+    // while members above the minimum remain.
+    assert_eq!(members_above_power(2, None, Some(1)), (vec![], last(ADDR1)));
+    assert_eq!(
+        members_above_power(2, Some(ADDR1), Some(1)),
+        (vec![member(ADDR2, 5)], last(ADDR2))
+    );
+    assert_eq!(
+        members_above_power(2, Some(ADDR2), Some(2)),
+        (vec![member(ADDR4, 3)], last(ADDR4))
+    );
+    assert_eq!(members_above_power(2, Some(ADDR4), None), (vec![], None));
+}
+
 #[test]
 fn test_migrate() {
     let mut app = App::default();
@@ -6,12 +6,13 @@ use crate::msg::{
     TotalStakedAtHeightResponse,
 };
 use crate::state::{
-    Config, CONFIG, HOOKS, MAX_CLAIMS, NFT_CLAIMS, STAKED_NFTS_PER_OWNER, TOTAL_STAKED_NFTS,
+    Config, CONFIG, HOOKS, MAX_CLAIMS, NFT_CLAIMS, NFT_CLAIM_RECIPIENTS, STAKED_NFTS_PER_OWNER,
+    TOKEN_WEIGHTS, TOTAL_STAKED_NFTS, TOTAL_STAKED_POWER,
 };
 use crate::ContractError;
 use cosmwasm_std::{
     entry_point, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
-    Response, StdError, StdResult, Uint128, WasmMsg,
+    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw721::Cw721ReceiveMsg;
@@ -50,7 +51,16 @@ pub fn instantiate(
         unstaking_duration: msg.unstaking_duration,
     };
     CONFIG.save(deps.storage, &config)?;
+    for weight in msg.token_weights.unwrap_or_default() {
+        if weight.weight.is_zero() {
+            return Err(ContractError::ZeroWeight {
+                token_id: weight.token_id,
+            });
+        }
+        TOKEN_WEIGHTS.save(deps.storage, &weight.token_id, &weight.weight)?;
+    }
     TOTAL_STAKED_NFTS.save(deps.storage, &Uint128::zero(), env.block.height)?;
+    TOTAL_STAKED_POWER.save(deps.storage, &Uint128::zero(), env.block.height)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default()
@@ -74,7 +84,10 @@ pub fn execute(
 ) -> Result<Response<Empty>, ContractError> {
     match msg {
         ExecuteMsg::ReceiveNft(msg) => execute_stake(deps, env, info, msg),
-        ExecuteMsg::Unstake { token_ids } => execute_unstake(deps, env, info, token_ids),
+        ExecuteMsg::Unstake {
+            token_ids,
+            recipient,
+        } => execute_unstake(deps, env, info, token_ids, recipient),
         ExecuteMsg::ClaimNfts {} => execute_claim_nfts(deps, env, info),
         ExecuteMsg::UpdateConfig {
             owner,
@@ -124,6 +137,18 @@ pub fn execute_stake(
         },
     )?;
 
+    let weight = token_weight(deps.storage, &wrapper.token_id)?;
+    TOTAL_STAKED_POWER.update(
+        deps.storage,
+        env.block.height,
+        |total_power| -> StdResult<_> {
+            total_power
+                .unwrap()
+                .checked_add(weight)
+                .map_err(StdError::overflow)
+        },
+    )?;
+
     let hook_msgs = stake_hook_msgs(deps.storage, sender.clone(), wrapper.token_id.clone())?;
     Ok(Response::default()
         .add_submessages(hook_msgs)
@@ -137,12 +162,16 @@ pub fn execute_unstake(
     env: Env,
     info: MessageInfo,
     token_ids: Vec<String>,
+    recipient: Option<String>,
 ) -> Result<Response, ContractError> {
     if token_ids.is_empty() {
         return Err(ContractError::ZeroUnstake {});
     }
 
     let config = CONFIG.load(deps.storage)?;
+    let recipient = recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?;
 
     let resulting_collection = STAKED_NFTS_PER_OWNER.update(
         deps.storage,
@@ -196,16 +225,29 @@ pub fn execute_unstake(
         },
     )?;
 
+    let weight = total_weight(deps.storage, token_ids.iter())?;
+    TOTAL_STAKED_POWER.update(
+        deps.storage,
+        env.block.height,
+        |total_power| -> StdResult<_> {
+            total_power
+                .unwrap()
+                .checked_sub(weight)
+                .map_err(StdError::overflow)
+        },
+    )?;
+
     let hook_msgs = unstake_hook_msgs(deps.storage, info.sender.clone(), token_ids.clone())?;
     match config.unstaking_duration {
         None => {
+            let recipient = recipient.unwrap_or_else(|| info.sender.clone());
             let return_messages = token_ids
                 .into_iter()
                 .map(|token_id| -> StdResult<WasmMsg> {
                     Ok(cosmwasm_std::WasmMsg::Execute {
                         contract_addr: config.nft_address.to_string(),
                         msg: to_binary(&cw721::Cw721ExecuteMsg::TransferNft {
-                            recipient: info.sender.to_string(),
+                            recipient: recipient.to_string(),
                             token_id,
                         })?,
                         funds: vec![],
@@ -218,6 +260,7 @@ pub fn execute_unstake(
                 .add_submessages(hook_msgs)
                 .add_attribute("action", "unstake")
                 .add_attribute("from", info.sender)
+                .add_attribute("recipient", recipient)
                 .add_attribute("claim_duration", "None"))
         }
 
@@ -229,6 +272,14 @@ pub fn execute_unstake(
                 return Err(ContractError::TooManyClaims {});
             }
 
+            // The staker claims the NFTs, which are sent to the
+            // recipient.
+            if let Some(recipient) = &recipient {
+                for token_id in token_ids.iter() {
+                    NFT_CLAIM_RECIPIENTS.save(deps.storage, token_id, recipient)?;
+                }
+            }
+
             // Out of gas here is fine - just try again with fewer
             // tokens.
             NFT_CLAIMS.create_nft_claims(
@@ -241,7 +292,8 @@ pub fn execute_unstake(
             Ok(Response::default()
                 .add_attribute("action", "unstake")
                 .add_submessages(hook_msgs)
-                .add_attribute("from", info.sender)
+                .add_attribute("from", info.sender.clone())
+                .add_attribute("recipient", recipient.unwrap_or(info.sender))
                 .add_attribute("claim_duration", format!("{}", duration)))
         }
     }
@@ -262,10 +314,14 @@ pub fn execute_claim_nfts(
     let msgs = nfts
         .into_iter()
         .map(|nft| -> StdResult<CosmosMsg> {
+            let recipient = NFT_CLAIM_RECIPIENTS
+                .may_load(deps.storage, &nft)?
+                .unwrap_or_else(|| info.sender.clone());
+            NFT_CLAIM_RECIPIENTS.remove(deps.storage, &nft);
             Ok(WasmMsg::Execute {
                 contract_addr: config.nft_address.to_string(),
                 msg: to_binary(&cw721::Cw721ExecuteMsg::TransferNft {
-                    recipient: info.sender.to_string(),
+                    recipient: recipient.to_string(),
                     token_id: nft,
                 })?,
                 funds: vec![],
@@ -419,7 +475,7 @@ pub fn query_voting_power_at_height(
     let collection = STAKED_NFTS_PER_OWNER
         .may_load_at_height(deps.storage, address, height)?
         .unwrap_or_default();
-    let power = Uint128::new(collection.len() as u128);
+    let power = total_weight(deps.storage, collection.iter())?;
 
     to_binary(&cw_core_interface::voting::VotingPowerAtHeightResponse { power, height })
 }
@@ -442,9 +498,16 @@ pub fn query_total_staked_at_height(
 
 pub fn query_total_power_at_height(deps: Deps, env: Env, height: Option<u64>) -> StdResult<Binary> {
     let height = height.unwrap_or(env.block.height);
-    let power = TOTAL_STAKED_NFTS
-        .may_load_at_height(deps.storage, height)?
-        .unwrap_or_default();
+    // Contracts migrated from versions without token weights have no
+    // record of total power before the migration. Those contracts
+    // have no token weights, so their power is the number of staked
+    // NFTs.
+    let power = match TOTAL_STAKED_POWER.may_load_at_height(deps.storage, height)? {
+        Some(power) => power,
+        None => TOTAL_STAKED_NFTS
+            .may_load_at_height(deps.storage, height)?
+            .unwrap_or_default(),
+    };
     to_binary(&cw_core_interface::voting::TotalPowerAtHeightResponse { power, height })
 }
 
@@ -527,8 +590,36 @@ pub fn query_staked_nfts(
     to_binary(&res)
 }
 
+/// Gets the voting power granted by staking the NFT with ID
+/// `token_id`.
+fn token_weight(storage: &dyn Storage, token_id: &str) -> StdResult<Uint128> {
+    Ok(TOKEN_WEIGHTS
+        .may_load(storage, token_id)?
+        .unwrap_or_else(|| Uint128::new(1)))
+}
+
+/// Gets the voting power granted by staking all of `token_ids`.
+fn total_weight<'a>(
+    storage: &dyn Storage,
+    token_ids: impl Iterator<Item = &'a String>,
+) -> StdResult<Uint128> {
+    token_ids.try_fold(Uint128::zero(), |total, token_id| {
+        total
+            .checked_add(token_weight(storage, token_id)?)
+            .map_err(StdError::overflow)
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Start tracking total voting power if this contract predates
+    // token weights.
+    if TOTAL_STAKED_POWER.may_load(deps.storage)?.is_none() {
+        let total_staked = TOTAL_STAKED_NFTS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        TOTAL_STAKED_POWER.save(deps.storage, &total_staked, env.block.height)?;
+    }
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
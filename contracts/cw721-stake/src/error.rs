@@ -32,4 +32,7 @@ pub enum ContractError {
 
     #[error("Can't unstake zero NFTs.")]
     ZeroUnstake {},
+
+    #[error("NFT ({token_id}) may not have a voting weight of zero")]
+    ZeroWeight { token_id: String },
 }
@@ -29,6 +29,17 @@ pub struct InstantiateMsg {
     pub manager: Option<String>,
     pub nft_address: String,
     pub unstaking_duration: Option<Duration>,
+    // Voting power granted by staking specific NFTs. NFTs not listed
+    // here grant one unit of voting power. These may not be changed
+    // after instantiation.
+    pub token_weights: Option<Vec<TokenWeight>>,
+}
+
+/// The voting power granted by staking the NFT with ID `token_id`.
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
+pub struct TokenWeight {
+    pub token_id: String,
+    pub weight: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
@@ -40,6 +51,11 @@ pub enum ExecuteMsg {
     /// length.
     Unstake {
         token_ids: Vec<String>,
+        /// The address the NFTs are sent to when they are returned,
+        /// either immediately or when claimed if there is an
+        /// unstaking duration. Defaults to the sender. Voting power is
+        /// removed from the sender either way.
+        recipient: Option<String>,
     },
     ClaimNfts {},
     UpdateConfig {
@@ -1,7 +1,7 @@
 use cosmwasm_std::{Addr, Uint128};
 use cw721_controllers::NftClaims;
 use cw_controllers::Hooks;
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 use cw_utils::Duration;
 use indexmap::set::IndexSet;
 use schemars::JsonSchema;
@@ -36,9 +36,29 @@ pub const TOTAL_STAKED_NFTS: SnapshotItem<Uint128> = SnapshotItem::new(
     Strategy::EveryBlock,
 );
 
+/// The voting power granted by staking a given NFT. NFTs without an
+/// entry have a weight of one. Written only at instantiation so that
+/// voting power at past heights may be computed from the NFTs staked
+/// at those heights.
+pub const TOKEN_WEIGHTS: Map<&str, Uint128> = Map::new("token_weights");
+
+/// The total voting power of the NFTs staked with this contract at a
+/// given height. This only differs from `TOTAL_STAKED_NFTS` if token
+/// weights were set at instantiation.
+pub const TOTAL_STAKED_POWER: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_staked_power",
+    "total_staked_power__checkpoints",
+    "total_staked_power__changelog",
+    Strategy::EveryBlock,
+);
+
 /// The maximum number of claims that may be outstanding.
 pub const MAX_CLAIMS: u64 = 100;
 pub const NFT_CLAIMS: NftClaims = NftClaims::new("nft_claims");
+/// The address an NFT with an outstanding claim is sent to when it
+/// is claimed, if it was unstaked to an address other than the
+/// staker's.
+pub const NFT_CLAIM_RECIPIENTS: Map<&str, Addr> = Map::new("nft_claim_recipients");
 
 // Hooks to contracts that will receive staking and unstaking messages
 pub const HOOKS: Hooks = Hooks::new("hooks");
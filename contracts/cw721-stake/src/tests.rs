@@ -1,7 +1,7 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
-    ExecuteMsg, MigrateMsg, Owner, QueryMsg, StakedBalanceAtHeightResponse,
-    TotalStakedAtHeightResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, Owner, QueryMsg, StakedBalanceAtHeightResponse,
+    TokenWeight, TotalStakedAtHeightResponse,
 };
 use crate::state::{Config, MAX_CLAIMS};
 use crate::ContractError;
@@ -79,6 +79,7 @@ fn instantiate_staking(app: &mut App, cw721: Addr, unstaking_duration: Option<Du
         manager: Some("manager".to_string()),
         nft_address: cw721.to_string(),
         unstaking_duration,
+        token_weights: None,
     };
     app.instantiate_contract(
         staking_code_id,
@@ -219,7 +220,10 @@ fn unstake_tokens(
     info: MessageInfo,
     token_ids: Vec<String>,
 ) -> AnyResult<AppResponse> {
-    let msg = ExecuteMsg::Unstake { token_ids };
+    let msg = ExecuteMsg::Unstake {
+        token_ids,
+        recipient: None,
+    };
     app.execute_contract(info.sender, staking_addr.clone(), &msg, &[])
 }
 
@@ -386,6 +390,7 @@ fn test_instantiate_with_instantiator_owner() {
             manager: Some("manager".to_string()),
             nft_address: cw721_addr.to_string(),
             unstaking_duration: None,
+            token_weights: None,
         };
         app.instantiate_contract(
             staking_code_id,
@@ -1192,6 +1197,253 @@ fn test_unstake_that_which_you_do_not_own() {
     assert_eq!(total_staked, Uint128::zero());
 }
 
+#[test]
+fn test_unstake_to_recipient() {
+    let mut app = mock_app();
+    let (staking_addr, cw721_addr) = setup_test_case(&mut app, None);
+
+    let info = mock_info(ADDR1, &[]);
+    mint_nft(
+        &mut app,
+        &cw721_addr,
+        NFT_ID1.to_string(),
+        ADDR1.to_string(),
+        info.clone(),
+    )
+    .unwrap();
+    stake_nft(
+        &mut app,
+        &staking_addr,
+        &cw721_addr,
+        NFT_ID1.to_string(),
+        info,
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Unstake to ADDR3. The NFT is sent to ADDR3 and the voting power
+    // is removed from ADDR1.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        staking_addr.clone(),
+        &ExecuteMsg::Unstake {
+            token_ids: vec![NFT_ID1.to_string()],
+            recipient: Some(ADDR3.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(get_nft_balance(&app, &cw721_addr, ADDR3), Uint128::new(1));
+    assert_eq!(get_nft_balance(&app, &cw721_addr, ADDR1), Uint128::zero());
+    assert_eq!(
+        query_voting_power(&app, &staking_addr, ADDR1, None),
+        Uint128::zero()
+    );
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::zero()
+    );
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::zero());
+}
+
+#[test]
+fn test_unstake_to_recipient_with_duration() {
+    let mut app = mock_app();
+    let (staking_addr, cw721_addr) = setup_test_case(&mut app, Some(Duration::Height(1)));
+
+    let info = mock_info(ADDR1, &[]);
+    mint_nft(
+        &mut app,
+        &cw721_addr,
+        NFT_ID1.to_string(),
+        ADDR1.to_string(),
+        info.clone(),
+    )
+    .unwrap();
+    mint_nft(
+        &mut app,
+        &cw721_addr,
+        NFT_ID2.to_string(),
+        ADDR1.to_string(),
+        info.clone(),
+    )
+    .unwrap();
+    stake_nft(
+        &mut app,
+        &staking_addr,
+        &cw721_addr,
+        NFT_ID1.to_string(),
+        info.clone(),
+    )
+    .unwrap();
+    stake_nft(
+        &mut app,
+        &staking_addr,
+        &cw721_addr,
+        NFT_ID2.to_string(),
+        info.clone(),
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // Unstake one NFT to ADDR3 and the other to ADDR1.
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        staking_addr.clone(),
+        &ExecuteMsg::Unstake {
+            token_ids: vec![NFT_ID1.to_string()],
+            recipient: Some(ADDR3.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    unstake_tokens(&mut app, &staking_addr, info, vec![NFT_ID2.to_string()]).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        query_voting_power(&app, &staking_addr, ADDR1, None),
+        Uint128::zero()
+    );
+
+    // The claims belong to the staker.
+    assert_eq!(query_nft_claims(&app, &staking_addr, ADDR1).len(), 2);
+    assert!(query_nft_claims(&app, &staking_addr, ADDR3).is_empty());
+
+    let info = mock_info(ADDR1, &[]);
+    claim_nfts(&mut app, &staking_addr, info).unwrap();
+    assert_eq!(get_nft_balance(&app, &cw721_addr, ADDR3), Uint128::new(1));
+    assert_eq!(get_nft_balance(&app, &cw721_addr, ADDR1), Uint128::new(1));
+}
+
+#[test]
+fn test_token_weights() {
+    let mut app = mock_app();
+    let cw721_addr = instantiate_cw721(&mut app);
+    let staking_code_id = app.store_code(contract_staking());
+
+    // Zero weights are not allowed.
+    let err: ContractError = app
+        .instantiate_contract(
+            staking_code_id,
+            Addr::unchecked(ADDR1),
+            &InstantiateMsg {
+                owner: None,
+                manager: None,
+                nft_address: cw721_addr.to_string(),
+                unstaking_duration: None,
+                token_weights: Some(vec![TokenWeight {
+                    token_id: NFT_ID1.to_string(),
+                    weight: Uint128::zero(),
+                }]),
+            },
+            &[],
+            "staking",
+            None,
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        ContractError::ZeroWeight {
+            token_id: NFT_ID1.to_string()
+        }
+    );
+
+    let staking_addr = app
+        .instantiate_contract(
+            staking_code_id,
+            Addr::unchecked(ADDR1),
+            &InstantiateMsg {
+                owner: None,
+                manager: None,
+                nft_address: cw721_addr.to_string(),
+                unstaking_duration: None,
+                token_weights: Some(vec![
+                    TokenWeight {
+                        token_id: NFT_ID1.to_string(),
+                        weight: Uint128::new(5),
+                    },
+                    TokenWeight {
+                        token_id: NFT_ID2.to_string(),
+                        weight: Uint128::new(2),
+                    },
+                ]),
+            },
+            &[],
+            "staking",
+            None,
+        )
+        .unwrap();
+    app.update_block(next_block);
+
+    let info = mock_info(ADDR1, &[]);
+    // NFT_ID3 has no configured weight.
+    for (token_id, owner) in [(NFT_ID1, ADDR1), (NFT_ID2, ADDR2), (NFT_ID3, ADDR1)] {
+        mint_nft(
+            &mut app,
+            &cw721_addr,
+            token_id.to_string(),
+            owner.to_string(),
+            info.clone(),
+        )
+        .unwrap();
+        stake_nft(
+            &mut app,
+            &staking_addr,
+            &cw721_addr,
+            token_id.to_string(),
+            mock_info(owner, &[]),
+        )
+        .unwrap();
+    }
+    app.update_block(next_block);
+    let staked_height = app.block_info().height;
+
+    assert_eq!(
+        query_voting_power(&app, &staking_addr, ADDR1, None),
+        Uint128::new(6)
+    );
+    assert_eq!(
+        query_voting_power(&app, &staking_addr, ADDR2, None),
+        Uint128::new(2)
+    );
+    assert_eq!(
+        query_total_power_at_height(&app, &staking_addr, None),
+        Uint128::new(8)
+    );
+    // Staked balances still count NFTs.
+    assert_eq!(
+        query_staked_balance(&app, &staking_addr, ADDR1),
+        Uint128::new(2)
+    );
+    assert_eq!(query_total_staked(&app, &staking_addr), Uint128::new(3));
+
+    unstake_tokens(&mut app, &staking_addr, info, vec![NFT_ID1.to_string()]).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        query_voting_power(&app, &staking_addr, ADDR1, None),
+        Uint128::new(1)
+    );
+    assert_eq!(
+        query_total_power_at_height(&app, &staking_addr, None),
+        Uint128::new(3)
+    );
+
+    // Historical power is unaffected.
+    assert_eq!(
+        query_voting_power(&app, &staking_addr, ADDR1, Some(staked_height)),
+        Uint128::new(6)
+    );
+    assert_eq!(
+        query_total_power_at_height(&app, &staking_addr, Some(staked_height)),
+        Uint128::new(8)
+    );
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -7,7 +7,9 @@ use vote_hooks::VoteHookMsg;
 
 use crate::error::ContractError;
 use crate::msg::{CountResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG, PROPOSAL_COUNTER, STATUS_CHANGED_COUNTER, VOTE_COUNTER};
+use crate::state::{
+    Config, CONFIG, PROPOSAL_COUNTER, QUORUM_REACHED_COUNTER, STATUS_CHANGED_COUNTER, VOTE_COUNTER,
+};
 
 const CONTRACT_NAME: &str = "crates.io:proposal-hooks-counter";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -28,6 +30,7 @@ pub fn instantiate(
     PROPOSAL_COUNTER.save(deps.storage, &0)?;
     VOTE_COUNTER.save(deps.storage, &0)?;
     STATUS_CHANGED_COUNTER.save(deps.storage, &0)?;
+    QUORUM_REACHED_COUNTER.save(deps.storage, &0)?;
     Ok(Response::new().add_attribute("action", "instantiate"))
 }
 
@@ -68,6 +71,11 @@ pub fn execute_proposal_hook(
             count += 1;
             STATUS_CHANGED_COUNTER.save(deps.storage, &count)?;
         }
+        ProposalHookMsg::QuorumReached { .. } => {
+            let mut count = QUORUM_REACHED_COUNTER.load(deps.storage)?;
+            count += 1;
+            QUORUM_REACHED_COUNTER.save(deps.storage, &count)?;
+        }
     }
 
     Ok(Response::new().add_attribute("action", "proposal_hook"))
@@ -102,5 +110,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::StatusChangedCounter {} => to_binary(&CountResponse {
             count: STATUS_CHANGED_COUNTER.load(deps.storage)?,
         }),
+        QueryMsg::QuorumReachedCounter {} => to_binary(&CountResponse {
+            count: QUORUM_REACHED_COUNTER.load(deps.storage)?,
+        }),
     }
 }
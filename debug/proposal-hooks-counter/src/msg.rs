@@ -21,6 +21,7 @@ pub enum QueryMsg {
     VoteCounter {},
     ProposalCounter {},
     StatusChangedCounter {},
+    QuorumReachedCounter {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -11,3 +11,4 @@ pub const CONFIG: Item<Config> = Item::new("config");
 pub const VOTE_COUNTER: Item<u64> = Item::new("vote_counter");
 pub const PROPOSAL_COUNTER: Item<u64> = Item::new("proposal_counter");
 pub const STATUS_CHANGED_COUNTER: Item<u64> = Item::new("stauts_changed_counter");
+pub const QUORUM_REACHED_COUNTER: Item<u64> = Item::new("quorum_reached_counter");
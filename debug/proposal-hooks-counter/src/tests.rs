@@ -1,11 +1,11 @@
-use cosmwasm_std::{to_binary, Addr, Empty, Uint128};
+use cosmwasm_std::{to_binary, Addr, Decimal, Empty, Uint128};
 use cw20::Cw20Coin;
 use cw_core::state::ProposalModule;
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use indexable_hooks::HooksResponse;
 
 use voting::{
-    threshold::{PercentageThreshold, Threshold},
+    threshold::{PercentageThreshold, Threshold, ThresholdMode},
     voting::Vote,
 };
 
@@ -122,6 +122,7 @@ fn instantiate_with_default_governance(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     instantiate_governance(app, governance_id, governance_instantiate)
@@ -145,6 +146,36 @@ fn test_counters() {
         allow_revoting: false,
         deposit_info: None,
         close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: false,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
     };
 
     let governance_addr =
@@ -201,6 +232,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
             address: counters.to_string(),
+            override_allowlist: false,
         },
         &[],
     )
@@ -210,6 +242,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
             address: counters.to_string(),
+            override_allowlist: false,
         },
         &[],
     )
@@ -248,6 +281,11 @@ fn test_counters() {
             title: "A simple text proposal".to_string(),
             description: "This is a simple text proposal".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
@@ -306,6 +344,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
             address: failing_counters.to_string(),
+            override_allowlist: false,
         },
         &[],
     )
@@ -315,6 +354,7 @@ fn test_counters() {
         govmod_single.clone(),
         &cw_proposal_single::msg::ExecuteMsg::AddVoteHook {
             address: failing_counters.to_string(),
+            override_allowlist: false,
         },
         &[],
     )
@@ -346,6 +386,11 @@ fn test_counters() {
             title: "A simple text proposal 2nd".to_string(),
             description: "This is a simple text proposal 2nd".to_string(),
             msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
         },
         &[],
     )
@@ -459,3 +504,154 @@ fn test_counters() {
         .unwrap();
     assert_eq!(hooks.hooks.len(), 1);
 }
+
+#[test]
+fn test_quorum_reached_counter() {
+    let mut app = App::default();
+    let govmod_id = app.store_code(single_govmod_contract());
+    let counters_id = app.store_code(counters_contract());
+
+    let instantiate = cw_proposal_single::msg::InstantiateMsg {
+        threshold: Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(50)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
+        },
+        max_voting_period: cw_utils::Duration::Height(6),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        deposit_info: None,
+        close_proposal_on_execution_failure: true,
+        exclude_dao_voting_power: false,
+        execution_callback: None,
+        require_second: None,
+        record_config_history: false,
+        quorum_reached_hooks: true,
+        require_power_at_resolution: false,
+        execution_retry_cooldown: None,
+        post_execution_cooldown: None,
+        quorum_extension: None,
+        native_propose_deposit: None,
+        snapshot_offset: 0,
+        pre_execution_hook: None,
+        required_description_sections: vec![],
+        voting_period_limit: None,
+        register_deposit_token: false,
+        total_power_override: None,
+        record_vote_history: false,
+        execute_on_pass: false,
+        reveal_period: None,
+        hook_allowlist: None,
+        revote_settle_period: None,
+        deposit_escalation: None,
+        forfeited_deposit_policy: None,
+        reject_failing_execution: false,
+        id_prefix: None,
+        non_voter_default: None,
+        max_proposal_bytes: None,
+        config_change_delay: None,
+        close_reward: None,
+        cache_voting_power: false,
+    };
+    let governance_addr = instantiate_with_default_governance(
+        &mut app,
+        govmod_id,
+        instantiate,
+        Some(vec![
+            Cw20Coin {
+                address: CREATOR_ADDR.to_string(),
+                amount: Uint128::new(10),
+            },
+            Cw20Coin {
+                address: "ekez".to_string(),
+                amount: Uint128::new(10),
+            },
+        ]),
+    );
+    let governance_modules: Vec<ProposalModule> = app
+        .wrap()
+        .query_wasm_smart(
+            governance_addr.clone(),
+            &cw_core::msg::QueryMsg::ProposalModules {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let govmod_single = governance_modules.into_iter().next().unwrap().address;
+
+    let counters: Addr = app
+        .instantiate_contract(
+            counters_id,
+            Addr::unchecked(CREATOR_ADDR),
+            &InstantiateMsg {
+                should_error: false,
+            },
+            &[],
+            "counters",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        governance_addr,
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::AddProposalHook {
+            address: counters.to_string(),
+            override_allowlist: false,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Propose {
+            title: "A simple text proposal".to_string(),
+            description: "This is a simple text proposal".to_string(),
+            msgs: vec![],
+            expiration_override: None,
+            execution_funds: vec![],
+            related_proposals: vec![],
+            threshold_override: None,
+            metadata: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Half of the voting power voting reaches quorum.
+    app.execute_contract(
+        Addr::unchecked(CREATOR_ADDR),
+        govmod_single.clone(),
+        &cw_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    let resp: CountResponse = app
+        .wrap()
+        .query_wasm_smart(counters.clone(), &QueryMsg::QuorumReachedCounter {})
+        .unwrap();
+    assert_eq!(resp.count, 1);
+
+    // Quorum is only reached once.
+    app.execute_contract(
+        Addr::unchecked("ekez"),
+        govmod_single,
+        &cw_proposal_single::msg::ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: Vote::Yes,
+        },
+        &[],
+    )
+    .unwrap();
+    let resp: CountResponse = app
+        .wrap()
+        .query_wasm_smart(counters, &QueryMsg::QuorumReachedCounter {})
+        .unwrap();
+    assert_eq!(resp.count, 1);
+}
@@ -2,6 +2,7 @@ use cosmwasm_std::{CosmosMsg, Empty};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+pub mod migration;
 pub mod voting;
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
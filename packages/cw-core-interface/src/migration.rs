@@ -0,0 +1,40 @@
+use cw2::ContractVersion;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A `MigrateMsg` variant accepted by a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SupportedMigration {
+    /// The name of the variant as it appears in JSON, for example
+    /// `from_v1`.
+    pub msg: String,
+    /// The cw2 versions of the contract that may be migrated from
+    /// with this variant.
+    pub from_versions: Vec<String>,
+}
+
+/// The `MigrateMsg` variants shared by DAO DAO contracts: `FromV1`,
+/// which migrates from DAO DAO v1, and `FromCompatible`, which
+/// migrates from `version`, the contract's current version. Contracts
+/// add any variants of their own, and must keep the list up to date
+/// with their `MigrateMsg` and `migrate`.
+pub fn supported_migrations(version: &str) -> Vec<SupportedMigration> {
+    vec![
+        SupportedMigration {
+            msg: "from_v1".to_string(),
+            from_versions: vec!["0.1.0".to_string()],
+        },
+        SupportedMigration {
+            msg: "from_compatible".to_string(),
+            from_versions: vec![version.to_string()],
+        },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrationInfoResponse {
+    /// The contract's current cw2 version info.
+    pub info: ContractVersion,
+    /// The `MigrateMsg` variants the contract's code accepts.
+    pub supported_migrations: Vec<SupportedMigration>,
+}
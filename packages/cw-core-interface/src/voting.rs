@@ -33,6 +33,24 @@ pub struct IsActiveResponse {
     pub active: bool,
 }
 
+/// Response to a voting module's `MembersAbovePower` query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MembersAbovePowerResponse {
+    /// Members with at least the requested voting power among the
+    /// addresses examined, ordered by address.
+    pub members: Vec<MemberPower>,
+    /// The last address examined, or None if there were no addresses
+    /// left to examine. Pass this as `start_after` to continue
+    /// scanning.
+    pub last_examined: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MemberPower {
+    pub address: String,
+    pub power: Uint128,
+}
+
 mod tests {
     /// Make sure the enum has all of the fields we expect. This will
     /// fail to compile if not.
@@ -2,12 +2,23 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use cosmwasm_std::{Addr, CustomQuery, Deps, StdError, StdResult, Storage, SubMsg};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, BlockInfo, CustomQuery, Deps, StdError, StdResult, Storage, SubMsg};
+use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct HooksResponse {
     pub hooks: Vec<String>,
+    /// When each hook was registered, in the same order as `hooks`.
+    #[serde(default)]
+    pub registrations: Vec<HookRegistration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct HookRegistration {
+    pub address: String,
+    /// The block height at which the hook was registered. Zero for
+    /// hooks registered before registration heights were recorded.
+    pub height: u64,
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -22,32 +33,69 @@ pub enum HookError {
     HookNotRegistered {},
 }
 
+/// Namespace for the heights hooks were registered at, keyed by the
+/// storage key of their `Hooks` and their address. Hooks without an
+/// entry were registered before heights were recorded.
+const REGISTRATIONS_NAMESPACE: &str = "hook_registrations";
+
 // store all hook addresses in one item. We cannot have many of them before the contract becomes unusable anyway.
-pub struct Hooks<'a>(Item<'a, Vec<Addr>>);
+pub struct Hooks<'a> {
+    hooks: Item<'a, Vec<Addr>>,
+    storage_key: &'a str,
+}
 
 impl<'a> Hooks<'a> {
     pub const fn new(storage_key: &'a str) -> Self {
-        Hooks(Item::new(storage_key))
+        Hooks {
+            hooks: Item::new(storage_key),
+            storage_key,
+        }
     }
 
-    pub fn add_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.may_load(storage)?.unwrap_or_default();
+    fn registrations(&self) -> Map<'a, (&'a str, Addr), u64> {
+        Map::new(REGISTRATIONS_NAMESPACE)
+    }
+
+    pub fn add_hook(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        addr: Addr,
+    ) -> Result<(), HookError> {
+        let mut hooks = self.hooks.may_load(storage)?.unwrap_or_default();
         if !hooks.iter().any(|h| h == &addr) {
-            hooks.push(addr);
+            hooks.push(addr.clone());
         } else {
             return Err(HookError::HookAlreadyRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        self.registrations()
+            .save(storage, (self.storage_key, addr), &block.height)?;
+        Ok(self.hooks.save(storage, &hooks)?)
+    }
+
+    /// Records a registration height of zero for hooks registered
+    /// before heights were recorded. Called when migrating contracts
+    /// that store hooks from versions that did not record heights.
+    pub fn backfill_registrations(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        for hook in self.hooks.may_load(storage)?.unwrap_or_default() {
+            let key = (self.storage_key, hook);
+            if !self.registrations().has(storage, key.clone()) {
+                self.registrations().save(storage, key, &0)?;
+            }
+        }
+        Ok(())
     }
 
     pub fn remove_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.load(storage)?;
+        let mut hooks = self.hooks.load(storage)?;
         if let Some(p) = hooks.iter().position(|x| x == &addr) {
             hooks.remove(p);
         } else {
             return Err(HookError::HookNotRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        self.registrations()
+            .remove(storage, (self.storage_key, addr));
+        Ok(self.hooks.save(storage, &hooks)?)
     }
 
     pub fn remove_hook_by_index(
@@ -55,9 +103,11 @@ impl<'a> Hooks<'a> {
         storage: &mut dyn Storage,
         index: u64,
     ) -> Result<Addr, HookError> {
-        let mut hooks = self.0.load(storage)?;
+        let mut hooks = self.hooks.load(storage)?;
         let hook = hooks.remove(index as usize);
-        self.0.save(storage, &hooks)?;
+        self.registrations()
+            .remove(storage, (self.storage_key, hook.clone()));
+        self.hooks.save(storage, &hooks)?;
         Ok(hook)
     }
 
@@ -66,7 +116,7 @@ impl<'a> Hooks<'a> {
         storage: &dyn Storage,
         prep: F,
     ) -> StdResult<Vec<SubMsg>> {
-        self.0
+        self.hooks
             .may_load(storage)?
             .unwrap_or_default()
             .into_iter()
@@ -75,8 +125,24 @@ impl<'a> Hooks<'a> {
     }
 
     pub fn query_hooks<Q: CustomQuery>(&self, deps: Deps<Q>) -> StdResult<HooksResponse> {
-        let hooks = self.0.may_load(deps.storage)?.unwrap_or_default();
+        let hooks = self.hooks.may_load(deps.storage)?.unwrap_or_default();
+        let registrations = hooks
+            .iter()
+            .map(|hook| {
+                let height = self
+                    .registrations()
+                    .may_load(deps.storage, (self.storage_key, hook.clone()))?
+                    .unwrap_or_default();
+                Ok(HookRegistration {
+                    address: hook.to_string(),
+                    height,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
         let hooks = hooks.into_iter().map(String::from).collect();
-        Ok(HooksResponse { hooks })
+        Ok(HooksResponse {
+            hooks,
+            registrations,
+        })
     }
 }
@@ -15,6 +15,10 @@ pub enum ProposalHookMsg {
         old_status: String,
         new_status: String,
     },
+    /// Sent the first time a proposal's votes reach its quorum.
+    QuorumReached {
+        id: u64,
+    },
 }
 
 // This is just a helper to properly serialize the above message
@@ -78,3 +82,28 @@ pub fn proposal_status_changed_hooks(
         Ok(tmp)
     })
 }
+
+/// Prepares quorum reached hook messages. These messages reply on
+/// error and have even reply IDs.
+/// IDs are set to even numbers to then be interleaved with the vote hooks.
+pub fn quorum_reached_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    id: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_binary(&ProposalHookExecuteMsg::ProposalHook(
+        ProposalHookMsg::QuorumReached { id },
+    ))?;
+    let mut index: u64 = 0;
+    hooks.prepare_hooks(storage, |a| {
+        let execute = WasmMsg::Execute {
+            contract_addr: a.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let masked_index = mask_proposal_hook_index(index);
+        let tmp = SubMsg::reply_on_error(execute, masked_index);
+        index += 1;
+        Ok(tmp)
+    })
+}
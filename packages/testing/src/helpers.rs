@@ -71,6 +71,7 @@ pub fn instantiate_with_cw20_balances_governance(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     app.instantiate_contract(
@@ -153,6 +154,7 @@ pub fn instantiate_with_staked_balances_governance(
             msg: governance_instantiate,
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let core_addr = app
@@ -262,6 +264,7 @@ pub fn instantiate_with_staking_active_threshold(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     app.instantiate_contract(
@@ -331,6 +334,7 @@ pub fn instantiate_with_cw4_groups_governance(
             label: "DAO DAO governance module".to_string(),
         }],
         initial_items: None,
+        initial_cw20_transfers: None,
     };
 
     let addr = app
@@ -1,7 +1,7 @@
 use cosmwasm_std::{Decimal, Uint128};
 use rand::{prelude::SliceRandom, Rng};
 use voting::status::Status;
-use voting::threshold::{PercentageThreshold, Threshold};
+use voting::threshold::{PercentageThreshold, Threshold, ThresholdMode};
 use voting::voting::Vote;
 
 /// If a test vote should execute. Used for fuzzing and checking that
@@ -191,6 +191,7 @@ where
             Threshold::ThresholdQuorum {
                 threshold: PercentageThreshold::Percent(Decimal::percent(100)),
                 quorum: PercentageThreshold::Percent(Decimal::percent(i)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
             },
             Status::Rejected,
             None,
@@ -387,6 +388,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Percent(Decimal::percent(10)),
             quorum: PercentageThreshold::Majority {},
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Passed,
         None,
@@ -415,6 +417,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Percent(Decimal::percent(50)),
             quorum: PercentageThreshold::Majority {},
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Passed,
         None,
@@ -438,6 +441,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Majority {},
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Rejected,
         None,
@@ -458,6 +462,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Open,
         Some(Uint128::new(100)),
@@ -472,6 +477,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         // As the threshold is 50% and 59% of voters have voted no
         // this is unable to pass.
@@ -494,6 +500,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Passed,
         Some(Uint128::new(100)),
@@ -523,6 +530,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Passed,
         Some(Uint128::new(100)),
@@ -537,6 +545,7 @@ where
         Threshold::ThresholdQuorum {
             threshold: PercentageThreshold::Majority {},
             quorum: PercentageThreshold::Percent(Decimal::percent(60)),
+            mode: ThresholdMode::MajorityOfNonAbstain {},
         },
         Status::Rejected,
         Some(Uint128::new(100)),
@@ -78,6 +78,9 @@ impl DepositInfo {
     }
 }
 
+/// Gets the message taking a proposal's deposit from `sender`. Zero
+/// deposits are not transferred as some cw20 tokens reject transfers
+/// of zero tokens, which would prevent proposals from being created.
 pub fn get_deposit_msg(
     info: &Option<CheckedDepositInfo>,
     contract: &Addr,
@@ -105,6 +108,10 @@ pub fn get_deposit_msg(
     }
 }
 
+/// Gets the message returning a proposal's deposit to `receiver`. As
+/// with `get_deposit_msg` zero deposits are not transferred, so that
+/// a token rejecting zero transfers can not block closing or
+/// executing a proposal.
 pub fn get_return_deposit_msg(
     deposit_info: &CheckedDepositInfo,
     receiver: &Addr,
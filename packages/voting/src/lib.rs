@@ -2,6 +2,7 @@ pub mod deposit;
 pub mod error;
 pub mod proposal;
 pub mod reply;
+pub mod reward;
 pub mod status;
 pub mod threshold;
 pub mod voting;
@@ -1,9 +1,11 @@
 /// Masks for reply id
 const FAILED_PROPOSAL_EXECUTION_MASK: u64 = 0b00;
 const FAILED_PROPOSAL_HOOK_MASK: u64 = 0b01;
+const FAILED_EXECUTION_CALLBACK_MASK: u64 = 0b10;
 const FAILED_VOTE_HOOK_MASK: u64 = 0b11;
+const FAILED_DEPOSIT_REFUND_MASK: u64 = 0b100;
 
-const BITS_RESERVED_FOR_REPLY_TYPE: u8 = 2;
+const BITS_RESERVED_FOR_REPLY_TYPE: u8 = 3;
 const REPLY_TYPE_MASK: u64 = (1 << BITS_RESERVED_FOR_REPLY_TYPE) - 1;
 
 /// Since we can only pass `id`, and we need to perform different actions in reply,
@@ -14,6 +16,8 @@ pub enum TaggedReplyId {
     FailedProposalExecution(u64),
     FailedProposalHook(u64),
     FailedVoteHook(u64),
+    FailedExecutionCallback(u64),
+    FailedDepositRefund(u64),
 }
 
 impl TaggedReplyId {
@@ -30,6 +34,10 @@ impl TaggedReplyId {
             }
             FAILED_PROPOSAL_HOOK_MASK => Ok(TaggedReplyId::FailedProposalHook(id_after_shift)),
             FAILED_VOTE_HOOK_MASK => Ok(TaggedReplyId::FailedVoteHook(id_after_shift)),
+            FAILED_EXECUTION_CALLBACK_MASK => {
+                Ok(TaggedReplyId::FailedExecutionCallback(id_after_shift))
+            }
+            FAILED_DEPOSIT_REFUND_MASK => Ok(TaggedReplyId::FailedDepositRefund(id_after_shift)),
             _ => Err(error::TagError::UnknownReplyId { id }),
         }
     }
@@ -48,6 +56,14 @@ pub fn mask_vote_hook_index(index: u64) -> u64 {
     FAILED_VOTE_HOOK_MASK | (index << BITS_RESERVED_FOR_REPLY_TYPE)
 }
 
+pub fn mask_execution_callback_proposal_id(proposal_id: u64) -> u64 {
+    FAILED_EXECUTION_CALLBACK_MASK | (proposal_id << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
+pub fn mask_deposit_refund_proposal_id(proposal_id: u64) -> u64 {
+    FAILED_DEPOSIT_REFUND_MASK | (proposal_id << BITS_RESERVED_FOR_REPLY_TYPE)
+}
+
 pub mod error {
     use thiserror::Error;
 
@@ -64,14 +80,18 @@ mod test {
 
     #[test]
     fn test_tagged_reply_id() {
-        // max u62, change this if new reply types added
-        let proposal_id = 4611686018427387903;
+        // max u61, change this if new reply types added
+        let proposal_id = 2305843009213693951;
         let proposal_hook_idx = 1234;
         let vote_hook_idx = 4321;
+        let callback_proposal_id = 5678;
+        let refund_proposal_id = 8765;
 
         let m_proposal_id = mask_proposal_execution_proposal_id(proposal_id);
         let m_proposal_hook_idx = mask_proposal_hook_index(proposal_hook_idx);
         let m_vote_hook_idx = mask_vote_hook_index(vote_hook_idx);
+        let m_callback_proposal_id = mask_execution_callback_proposal_id(callback_proposal_id);
+        let m_refund_proposal_id = mask_deposit_refund_proposal_id(refund_proposal_id);
 
         assert_eq!(
             TaggedReplyId::new(m_proposal_id).unwrap(),
@@ -85,5 +105,13 @@ mod test {
             TaggedReplyId::new(m_vote_hook_idx).unwrap(),
             TaggedReplyId::FailedVoteHook(vote_hook_idx)
         );
+        assert_eq!(
+            TaggedReplyId::new(m_callback_proposal_id).unwrap(),
+            TaggedReplyId::FailedExecutionCallback(callback_proposal_id)
+        );
+        assert_eq!(
+            TaggedReplyId::new(m_refund_proposal_id).unwrap(),
+            TaggedReplyId::FailedDepositRefund(refund_proposal_id)
+        );
     }
 }
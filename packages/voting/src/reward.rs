@@ -0,0 +1,227 @@
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, StdResult, Uint128, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::deposit::CheckedDepositInfo;
+
+/// Where a proposal module takes rewards it pays out from, for
+/// example rewards to an address that closes a rejected proposal or
+/// relays its execution. Features that pay rewards should be
+/// configured with a `RewardSource` and pay them with
+/// `get_reward_msgs` so that DAOs configure reward sourcing the same
+/// way for each of them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardSource {
+    /// Pay rewards out of the proposal's deposit, in the deposit's
+    /// token. Rewards are capped at the size of the deposit and
+    /// nothing is paid for proposals without a deposit.
+    Deposit {},
+    /// Pay rewards from a pool of this cw20 token held by the
+    /// proposal module.
+    Cw20 { address: String },
+    /// Pay rewards from a pool of this native denom held by the
+    /// proposal module.
+    Native { denom: String },
+}
+
+/// Counterpart to the `RewardSource` enum which has been processed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckedRewardSource {
+    Deposit {},
+    Cw20 { address: Addr },
+    Native { denom: String },
+}
+
+impl RewardSource {
+    /// Converts a reward source into a checked reward source.
+    pub fn into_checked(self, deps: Deps) -> StdResult<CheckedRewardSource> {
+        Ok(match self {
+            RewardSource::Deposit {} => CheckedRewardSource::Deposit {},
+            RewardSource::Cw20 { address } => {
+                let address = deps.api.addr_validate(&address)?;
+                // Make an info query as a smoke test that we are
+                // indeed working with a token here.
+                let _info: cw20::TokenInfoResponse = deps
+                    .querier
+                    .query_wasm_smart(address.clone(), &cw20::Cw20QueryMsg::TokenInfo {})?;
+                CheckedRewardSource::Cw20 { address }
+            }
+            RewardSource::Native { denom } => CheckedRewardSource::Native { denom },
+        })
+    }
+}
+
+/// The messages paying a reward.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RewardPayment {
+    pub msgs: Vec<CosmosMsg>,
+    /// The amount of the proposal's deposit spent on the reward. The
+    /// caller must refund or forfeit only what remains of the
+    /// deposit.
+    pub from_deposit: Uint128,
+}
+
+/// Gets the messages paying `amount` to `recipient` from `source`.
+/// `deposit_info` is the deposit of the proposal the reward is being
+/// paid for and is only used if the source is `Deposit`. Zero
+/// rewards are paid with no messages.
+pub fn get_reward_msgs(
+    source: &CheckedRewardSource,
+    deposit_info: &Option<CheckedDepositInfo>,
+    amount: Uint128,
+    recipient: &Addr,
+) -> StdResult<RewardPayment> {
+    let payment = match (source, deposit_info) {
+        (CheckedRewardSource::Deposit {}, Some(deposit_info)) => {
+            let amount = std::cmp::min(amount, deposit_info.deposit);
+            RewardPayment {
+                msgs: get_cw20_transfer_msg(&deposit_info.token, recipient, amount)?,
+                from_deposit: amount,
+            }
+        }
+        (CheckedRewardSource::Deposit {}, None) => RewardPayment::default(),
+        (CheckedRewardSource::Cw20 { address }, _) => RewardPayment {
+            msgs: get_cw20_transfer_msg(address, recipient, amount)?,
+            from_deposit: Uint128::zero(),
+        },
+        (CheckedRewardSource::Native { denom }, _) => RewardPayment {
+            msgs: if amount.is_zero() {
+                vec![]
+            } else {
+                vec![BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![Coin {
+                        denom: denom.clone(),
+                        amount,
+                    }],
+                }
+                .into()]
+            },
+            from_deposit: Uint128::zero(),
+        },
+    };
+    Ok(payment)
+}
+
+fn get_cw20_transfer_msg(
+    token: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    if amount.is_zero() {
+        return Ok(vec![]);
+    }
+    let transfer_msg = WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        funds: vec![],
+        msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+    };
+    Ok(vec![transfer_msg.into()])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn deposit(amount: u128) -> Option<CheckedDepositInfo> {
+        Some(CheckedDepositInfo {
+            token: Addr::unchecked("deposit_token"),
+            deposit: Uint128::new(amount),
+            refund_failed_proposals: false,
+        })
+    }
+
+    fn cw20_transfer(token: &str, amount: u128) -> CosmosMsg {
+        WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            funds: vec![],
+            msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "keeper".to_string(),
+                amount: Uint128::new(amount),
+            })
+            .unwrap(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_deposit_reward() {
+        let keeper = Addr::unchecked("keeper");
+        let source = CheckedRewardSource::Deposit {};
+
+        let payment = get_reward_msgs(&source, &deposit(10), Uint128::new(4), &keeper).unwrap();
+        assert_eq!(
+            payment,
+            RewardPayment {
+                msgs: vec![cw20_transfer("deposit_token", 4)],
+                from_deposit: Uint128::new(4),
+            }
+        );
+
+        // Rewards are capped at the size of the deposit.
+        let payment = get_reward_msgs(&source, &deposit(10), Uint128::new(11), &keeper).unwrap();
+        assert_eq!(
+            payment,
+            RewardPayment {
+                msgs: vec![cw20_transfer("deposit_token", 10)],
+                from_deposit: Uint128::new(10),
+            }
+        );
+
+        // Nothing is paid without a deposit.
+        let payment = get_reward_msgs(&source, &None, Uint128::new(4), &keeper).unwrap();
+        assert_eq!(payment, RewardPayment::default());
+        let payment = get_reward_msgs(&source, &deposit(0), Uint128::new(4), &keeper).unwrap();
+        assert_eq!(payment, RewardPayment::default());
+    }
+
+    #[test]
+    fn test_cw20_reward() {
+        let keeper = Addr::unchecked("keeper");
+        let source = CheckedRewardSource::Cw20 {
+            address: Addr::unchecked("reward_pool"),
+        };
+
+        // The deposit is left untouched.
+        let payment = get_reward_msgs(&source, &deposit(10), Uint128::new(40), &keeper).unwrap();
+        assert_eq!(
+            payment,
+            RewardPayment {
+                msgs: vec![cw20_transfer("reward_pool", 40)],
+                from_deposit: Uint128::zero(),
+            }
+        );
+
+        let payment = get_reward_msgs(&source, &None, Uint128::zero(), &keeper).unwrap();
+        assert_eq!(payment, RewardPayment::default());
+    }
+
+    #[test]
+    fn test_native_reward() {
+        let keeper = Addr::unchecked("keeper");
+        let source = CheckedRewardSource::Native {
+            denom: "ujuno".to_string(),
+        };
+
+        let payment = get_reward_msgs(&source, &deposit(10), Uint128::new(40), &keeper).unwrap();
+        assert_eq!(
+            payment,
+            RewardPayment {
+                msgs: vec![BankMsg::Send {
+                    to_address: "keeper".to_string(),
+                    amount: vec![Coin::new(40, "ujuno")],
+                }
+                .into()],
+                from_deposit: Uint128::zero(),
+            }
+        );
+
+        let payment = get_reward_msgs(&source, &None, Uint128::zero(), &keeper).unwrap();
+        assert_eq!(payment, RewardPayment::default());
+    }
+}
@@ -1,6 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// The status of a proposal. Statuses are stored in proposals, so
+/// variants may be added but existing ones must not be renamed or
+/// removed, else proposals created before the change can not be
+/// loaded.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Copy)]
 #[serde(rename_all = "snake_case")]
 #[repr(u8)]
@@ -18,6 +22,13 @@ pub enum Status {
     Closed,
     // The proposal has failed during execution
     ExecutionFailed,
+    /// The proposal is waiting to be seconded before it opens for
+    /// voting.
+    Pending,
+    /// Voting on the proposal has ended and the votes committed to
+    /// during it are being revealed. The proposal's outcome is known
+    /// once revealing ends.
+    Revealing,
 }
 
 impl std::fmt::Display for Status {
@@ -29,6 +40,42 @@ impl std::fmt::Display for Status {
             Status::Executed => write!(f, "executed"),
             Status::Closed => write!(f, "closed"),
             Status::ExecutionFailed => write!(f, "execution_failed"),
+            Status::Pending => write!(f, "pending"),
+            Status::Revealing => write!(f, "revealing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_status_serialization_is_stable() {
+        // The serialized form of every status, as stored in
+        // proposals. Changing any of these breaks loading proposals
+        // created before the change.
+        let cases = [
+            (Status::Open, "open"),
+            (Status::Rejected, "rejected"),
+            (Status::Passed, "passed"),
+            (Status::Executed, "executed"),
+            (Status::Closed, "closed"),
+            (Status::ExecutionFailed, "execution_failed"),
+            (Status::Pending, "pending"),
+            (Status::Revealing, "revealing"),
+        ];
+        for (status, serialized) in cases {
+            let quoted = format!("\"{}\"", serialized);
+            assert_eq!(
+                String::from_utf8(cosmwasm_std::to_vec(&status).unwrap()).unwrap(),
+                quoted
+            );
+            assert_eq!(
+                cosmwasm_std::from_slice::<Status>(quoted.as_bytes()).unwrap(),
+                status
+            );
+            assert_eq!(status.to_string(), serialized);
         }
     }
 }
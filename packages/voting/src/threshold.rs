@@ -4,13 +4,19 @@ use serde::{Deserialize, Serialize};
 
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum ThresholdError {
     #[error("Required threshold cannot be zero")]
     ZeroThreshold {},
 
     #[error("Not possible to reach required (passing) threshold")]
     UnreachableThreshold {},
+
+    #[error("Not possible to reach required quorum, quorum may not be greater than 100%")]
+    UnreachableQuorum {},
+
+    #[error("Absolute count threshold cannot be zero")]
+    ZeroAbsoluteCount {},
 }
 
 /// A percentage of voting power that must vote yes for a proposal to
@@ -46,7 +52,43 @@ pub enum PercentageThreshold {
     Percent(Decimal),
 }
 
+/// How votes are counted against the passing threshold of a
+/// `ThresholdQuorum` once quorum has been reached. In every mode all
+/// votes cast, including abstain votes, count towards quorum.
+///
+/// Below `threshold` refers to the configured `PercentageThreshold`,
+/// with `>=` becoming `>` when it is `Majority {}`.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdMode {
+    /// Abstain votes are ignored when computing the threshold. A
+    /// proposal passes if `yes >= threshold * (yes + no)`. This is
+    /// the default, and the behavior of `ThresholdQuorum` before
+    /// modes were introduced.
+    MajorityOfNonAbstain {},
+    /// Every vote cast is counted when computing the threshold, so
+    /// abstaining counts against the proposal. A proposal passes if
+    /// `yes >= threshold * (yes + no + abstain)`.
+    MajorityOfParticipation {},
+    /// The threshold is measured against the total voting power at
+    /// proposal creation, so abstaining and not voting both count
+    /// against the proposal. A proposal passes if `yes >= threshold *
+    /// total_power`.
+    AbsoluteMajority {},
+}
+
+impl Default for ThresholdMode {
+    fn default() -> Self {
+        ThresholdMode::MajorityOfNonAbstain {}
+    }
+}
+
 /// The ways a proposal may reach its passing / failing threshold.
+///
+/// Thresholds are stored in proposals, so their serialized form must
+/// remain loadable across migrations. Variants may be added, but
+/// existing variants must not be renamed or removed, and fields added
+/// to an existing variant must be `#[serde(default)]`.
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum Threshold {
@@ -63,11 +105,29 @@ pub enum Threshold {
     ThresholdQuorum {
         threshold: PercentageThreshold,
         quorum: PercentageThreshold,
+        /// How votes are counted against `threshold`. Thresholds
+        /// stored before this field existed deserialize to
+        /// `MajorityOfNonAbstain`, which matches their previous
+        /// behavior.
+        #[serde(default)]
+        mode: ThresholdMode,
     },
 
     /// An absolute number of votes needed for something to cross the
     /// threshold. Useful for multisig style voting.
     AbsoluteCount { threshold: Uint128 },
+
+    /// Declares a `quorum` of the total weight that must vote, after
+    /// which the proposal passes if there are more yes votes than no
+    /// votes. Abstain votes count towards quorum and are otherwise
+    /// ignored.
+    ///
+    /// Before expiration a proposal passes once quorum is met and the
+    /// outstanding voting power voting no could not bring the no
+    /// votes level with the yes votes. It is rejected early once the
+    /// outstanding voting power voting yes could not bring the yes
+    /// votes above the no votes.
+    SimpleQuorumMajority { quorum: PercentageThreshold },
 }
 
 /// Asserts that the 0.0 < percent <= 1.0
@@ -91,7 +151,7 @@ pub fn validate_quorum(quorum: &PercentageThreshold) -> Result<(), ThresholdErro
         PercentageThreshold::Majority {} => Ok(()),
         PercentageThreshold::Percent(quorum) => {
             if *quorum > Decimal::one() {
-                Err(ThresholdError::UnreachableThreshold {})
+                Err(ThresholdError::UnreachableQuorum {})
             } else {
                 Ok(())
             }
@@ -99,7 +159,80 @@ pub fn validate_quorum(quorum: &PercentageThreshold) -> Result<(), ThresholdErro
     }
 }
 
+impl PercentageThreshold {
+    /// Returns true if every vote count that meets this percentage
+    /// also meets `other`.
+    pub fn is_at_least_as_strict_as(&self, other: &PercentageThreshold) -> bool {
+        let half = Decimal::percent(50);
+        match (self, other) {
+            (PercentageThreshold::Majority {}, PercentageThreshold::Majority {}) => true,
+            // `> 50%` meets `>= percent` only if percent is at most
+            // 50%.
+            (PercentageThreshold::Majority {}, PercentageThreshold::Percent(percent)) => {
+                *percent <= half
+            }
+            // `>= percent` meets `> 50%` only if percent is over 50%.
+            (PercentageThreshold::Percent(percent), PercentageThreshold::Majority {}) => {
+                *percent > half
+            }
+            (PercentageThreshold::Percent(percent), PercentageThreshold::Percent(other)) => {
+                percent >= other
+            }
+        }
+    }
+}
+
+impl ThresholdMode {
+    /// Orders modes by how many votes they count against the
+    /// threshold. Yes votes make up the smallest share of the votes
+    /// counted in `AbsoluteMajority`, so it is the strictest.
+    fn strictness(&self) -> u8 {
+        match self {
+            ThresholdMode::MajorityOfNonAbstain {} => 0,
+            ThresholdMode::MajorityOfParticipation {} => 1,
+            ThresholdMode::AbsoluteMajority {} => 2,
+        }
+    }
+}
+
 impl Threshold {
+    /// Returns true if a proposal that passes with this threshold
+    /// would also pass with `other`. Thresholds of different kinds
+    /// are not comparable, so this is false for them.
+    pub fn is_at_least_as_strict_as(&self, other: &Threshold) -> bool {
+        match (self, other) {
+            (
+                Threshold::AbsolutePercentage { percentage },
+                Threshold::AbsolutePercentage { percentage: other },
+            ) => percentage.is_at_least_as_strict_as(other),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold,
+                    quorum,
+                    mode,
+                },
+                Threshold::ThresholdQuorum {
+                    threshold: other_threshold,
+                    quorum: other_quorum,
+                    mode: other_mode,
+                },
+            ) => {
+                threshold.is_at_least_as_strict_as(other_threshold)
+                    && quorum.is_at_least_as_strict_as(other_quorum)
+                    && mode.strictness() >= other_mode.strictness()
+            }
+            (
+                Threshold::AbsoluteCount { threshold },
+                Threshold::AbsoluteCount { threshold: other },
+            ) => threshold >= other,
+            (
+                Threshold::SimpleQuorumMajority { quorum },
+                Threshold::SimpleQuorumMajority { quorum: other },
+            ) => quorum.is_at_least_as_strict_as(other),
+            _ => false,
+        }
+    }
+
     /// returns error if this is an unreachable value,
     /// given a total weight of all members in the group
     pub fn validate(&self) -> Result<(), ThresholdError> {
@@ -107,17 +240,264 @@ impl Threshold {
             Threshold::AbsolutePercentage {
                 percentage: percentage_needed,
             } => validate_percentage(percentage_needed),
-            Threshold::ThresholdQuorum { threshold, quorum } => {
+            Threshold::ThresholdQuorum {
+                threshold, quorum, ..
+            } => {
                 validate_percentage(threshold)?;
                 validate_quorum(quorum)
             }
             Threshold::AbsoluteCount { threshold } => {
                 if threshold.is_zero() {
-                    Err(ThresholdError::ZeroThreshold {})
+                    Err(ThresholdError::ZeroAbsoluteCount {})
                 } else {
                     Ok(())
                 }
             }
+            Threshold::SimpleQuorumMajority { quorum } => validate_quorum(quorum),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_threshold_mode_defaults_to_non_abstain() {
+        // Thresholds serialized before `mode` was added must keep
+        // their meaning.
+        let threshold: Threshold = cosmwasm_std::from_slice(
+            br#"{"threshold_quorum":{"threshold":{"majority":{}},"quorum":{"percent":"0.1"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            threshold,
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            }
+        );
+    }
+
+    #[test]
+    fn test_threshold_serialization_is_stable() {
+        // The serialized form of every threshold, as stored in
+        // proposals. Changing any of these breaks loading proposals
+        // created before the change.
+        let cases = [
+            (
+                Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Majority {},
+                },
+                r#"{"absolute_percentage":{"percentage":{"majority":{}}}}"#,
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Percent(Decimal::percent(50)),
+                    quorum: PercentageThreshold::Percent(Decimal::percent(10)),
+                    mode: ThresholdMode::MajorityOfNonAbstain {},
+                },
+                r#"{"threshold_quorum":{"threshold":{"percent":"0.5"},"quorum":{"percent":"0.1"},"mode":{"majority_of_non_abstain":{}}}}"#,
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Majority {},
+                    mode: ThresholdMode::MajorityOfParticipation {},
+                },
+                r#"{"threshold_quorum":{"threshold":{"majority":{}},"quorum":{"majority":{}},"mode":{"majority_of_participation":{}}}}"#,
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Majority {},
+                    mode: ThresholdMode::AbsoluteMajority {},
+                },
+                r#"{"threshold_quorum":{"threshold":{"majority":{}},"quorum":{"majority":{}},"mode":{"absolute_majority":{}}}}"#,
+            ),
+            (
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::new(10),
+                },
+                r#"{"absolute_count":{"threshold":"10"}}"#,
+            ),
+            (
+                Threshold::SimpleQuorumMajority {
+                    quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+                },
+                r#"{"simple_quorum_majority":{"quorum":{"percent":"0.2"}}}"#,
+            ),
+        ];
+        for (threshold, serialized) in cases {
+            assert_eq!(
+                String::from_utf8(cosmwasm_std::to_vec(&threshold).unwrap()).unwrap(),
+                serialized
+            );
+            assert_eq!(
+                cosmwasm_std::from_slice::<Threshold>(serialized.as_bytes()).unwrap(),
+                threshold
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_threshold() {
+        let valid = [
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(100)),
+            },
+            // Zero quorums allow plurality-style voting.
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Percent(Decimal::percent(50)),
+                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+                mode: ThresholdMode::MajorityOfNonAbstain {},
+            },
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(100)),
+                mode: ThresholdMode::AbsoluteMajority {},
+            },
+            Threshold::AbsoluteCount {
+                threshold: Uint128::new(1),
+            },
+            Threshold::SimpleQuorumMajority {
+                quorum: PercentageThreshold::Percent(Decimal::percent(0)),
+            },
+            Threshold::SimpleQuorumMajority {
+                quorum: PercentageThreshold::Majority {},
+            },
+        ];
+        for threshold in valid {
+            assert_eq!(threshold.validate(), Ok(()));
+        }
+
+        let invalid = [
+            (
+                Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Percent(Decimal::percent(0)),
+                },
+                ThresholdError::ZeroThreshold {},
+            ),
+            (
+                Threshold::AbsolutePercentage {
+                    percentage: PercentageThreshold::Percent(Decimal::percent(101)),
+                },
+                ThresholdError::UnreachableThreshold {},
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Percent(Decimal::percent(0)),
+                    quorum: PercentageThreshold::Majority {},
+                    mode: ThresholdMode::MajorityOfNonAbstain {},
+                },
+                ThresholdError::ZeroThreshold {},
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Percent(Decimal::percent(101)),
+                    quorum: PercentageThreshold::Majority {},
+                    mode: ThresholdMode::MajorityOfNonAbstain {},
+                },
+                ThresholdError::UnreachableThreshold {},
+            ),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Percent(Decimal::percent(101)),
+                    mode: ThresholdMode::MajorityOfParticipation {},
+                },
+                ThresholdError::UnreachableQuorum {},
+            ),
+            (
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::zero(),
+                },
+                ThresholdError::ZeroAbsoluteCount {},
+            ),
+            (
+                Threshold::SimpleQuorumMajority {
+                    quorum: PercentageThreshold::Percent(Decimal::percent(101)),
+                },
+                ThresholdError::UnreachableQuorum {},
+            ),
+        ];
+        for (threshold, err) in invalid {
+            assert_eq!(threshold.validate(), Err(err));
+        }
+    }
+
+    #[test]
+    fn test_threshold_strictness() {
+        let majority = PercentageThreshold::Majority {};
+        let percent = |p| PercentageThreshold::Percent(Decimal::percent(p));
+
+        assert!(majority.is_at_least_as_strict_as(&majority));
+        assert!(majority.is_at_least_as_strict_as(&percent(50)));
+        assert!(!majority.is_at_least_as_strict_as(&percent(51)));
+        assert!(!percent(50).is_at_least_as_strict_as(&majority));
+        assert!(percent(51).is_at_least_as_strict_as(&majority));
+        assert!(percent(67).is_at_least_as_strict_as(&percent(67)));
+        assert!(!percent(66).is_at_least_as_strict_as(&percent(67)));
+
+        let quorum = |threshold, quorum, mode| Threshold::ThresholdQuorum {
+            threshold,
+            quorum,
+            mode,
+        };
+        let default = quorum(
+            majority,
+            percent(20),
+            ThresholdMode::MajorityOfNonAbstain {},
+        );
+        assert!(default.is_at_least_as_strict_as(&default));
+        assert!(
+            quorum(percent(67), percent(40), ThresholdMode::AbsoluteMajority {})
+                .is_at_least_as_strict_as(&default)
+        );
+        // Every part of the threshold must be at least as strict.
+        assert!(
+            !quorum(percent(67), percent(10), ThresholdMode::AbsoluteMajority {})
+                .is_at_least_as_strict_as(&default)
+        );
+        assert!(
+            !quorum(percent(50), percent(40), ThresholdMode::AbsoluteMajority {})
+                .is_at_least_as_strict_as(&default)
+        );
+        assert!(!quorum(
+            majority,
+            percent(20),
+            ThresholdMode::MajorityOfNonAbstain {}
+        )
+        .is_at_least_as_strict_as(&quorum(
+            majority,
+            percent(20),
+            ThresholdMode::MajorityOfParticipation {}
+        )));
+
+        let count = |threshold| Threshold::AbsoluteCount {
+            threshold: Uint128::new(threshold),
+        };
+        assert!(count(3).is_at_least_as_strict_as(&count(2)));
+        assert!(!count(1).is_at_least_as_strict_as(&count(2)));
+
+        let simple = |quorum| Threshold::SimpleQuorumMajority { quorum };
+        assert!(simple(percent(30)).is_at_least_as_strict_as(&simple(percent(20))));
+        assert!(!simple(percent(10)).is_at_least_as_strict_as(&simple(percent(20))));
+
+        // Different kinds of thresholds are not comparable.
+        assert!(!Threshold::AbsolutePercentage {
+            percentage: percent(100)
         }
+        .is_at_least_as_strict_as(&default));
+        assert!(!simple(percent(20)).is_at_least_as_strict_as(&default));
+        assert!(
+            !count(u128::MAX).is_at_least_as_strict_as(&Threshold::AbsolutePercentage {
+                percentage: majority
+            })
+        );
     }
 }
@@ -55,7 +55,12 @@ impl MultipleChoiceVotes {
         self.vote_weights.iter().sum()
     }
 
+    /// Adds a vote, failing if either its option's weight or the
+    /// total weight would overflow.
     pub fn add_vote(&mut self, vote: MultipleChoiceVote, weight: Uint128) -> StdResult<()> {
+        self.total()
+            .checked_add(weight)
+            .map_err(StdError::overflow)?;
         self.vote_weights[vote.option_id as usize] = self.vote_weights[vote.option_id as usize]
             .checked_add(weight)
             .map_err(StdError::overflow)?;
@@ -194,13 +199,20 @@ impl Votes {
         }
     }
 
-    /// Adds a vote to the votes.
-    pub fn add_vote(&mut self, vote: Vote, power: Uint128) {
-        match vote {
-            Vote::Yes => self.yes += power,
-            Vote::No => self.no += power,
-            Vote::Abstain => self.abstain += power,
-        }
+    /// Adds a vote to the votes. Fails if the votes for that
+    /// position or the total number of votes would overflow, which
+    /// keeps `total` from overflowing.
+    pub fn add_vote(&mut self, vote: Vote, power: Uint128) -> StdResult<()> {
+        self.total()
+            .checked_add(power)
+            .map_err(StdError::overflow)?;
+        let count = match vote {
+            Vote::Yes => &mut self.yes,
+            Vote::No => &mut self.no,
+            Vote::Abstain => &mut self.abstain,
+        };
+        *count = count.checked_add(power).map_err(StdError::overflow)?;
+        Ok(())
     }
 
     /// Removes a vote from the votes. The vote being removed must
@@ -220,7 +232,8 @@ impl Votes {
     /// is a `Uint128`. As it is not possible to vote twice we know
     /// that the sum of votes must be <= 2^128 and can safely return a
     /// `Uint128` from this function. A missbehaving voting power
-    /// module may break this invariant.
+    /// module could break this invariant, so `add_vote` refuses votes
+    /// that would.
     pub fn total(&self) -> Uint128 {
         self.yes + self.no + self.abstain
     }
@@ -292,9 +305,9 @@ mod test {
     #[test]
     fn count_votes() {
         let mut votes = Votes::with_yes(Uint128::new(5));
-        votes.add_vote(Vote::No, Uint128::new(10));
-        votes.add_vote(Vote::Yes, Uint128::new(30));
-        votes.add_vote(Vote::Abstain, Uint128::new(40));
+        votes.add_vote(Vote::No, Uint128::new(10)).unwrap();
+        votes.add_vote(Vote::Yes, Uint128::new(30)).unwrap();
+        votes.add_vote(Vote::Abstain, Uint128::new(40)).unwrap();
 
         assert_eq!(votes.total(), Uint128::new(5 + 10 + 30 + 40));
         assert_eq!(votes.yes, Uint128::new(35));
@@ -302,6 +315,23 @@ mod test {
         assert_eq!(votes.abstain, Uint128::new(40));
     }
 
+    #[test]
+    fn add_vote_overflow() {
+        let mut votes = Votes::with_yes(Uint128::new(u128::MAX - 1));
+        votes.add_vote(Vote::Yes, Uint128::new(2)).unwrap_err();
+        // The total may not overflow either.
+        votes.add_vote(Vote::No, Uint128::new(2)).unwrap_err();
+        votes.add_vote(Vote::No, Uint128::new(1)).unwrap();
+        assert_eq!(votes.total(), Uint128::MAX);
+        assert_eq!(votes.no, Uint128::new(1));
+
+        let mut votes = MultipleChoiceVotes::zero(2);
+        let option = |option_id| MultipleChoiceVote { option_id };
+        votes.add_vote(option(0), Uint128::MAX).unwrap();
+        votes.add_vote(option(1), Uint128::new(1)).unwrap_err();
+        assert_eq!(votes.total(), Uint128::MAX);
+    }
+
     #[test]
     fn vote_comparisons() {
         assert!(!compare_vote_count(